@@ -0,0 +1,25 @@
+//! Supplies the security.txt fields embedded in the program binary.
+//!
+//! Each field is read from the build environment when set, so deployments can point auditors at their own contacts
+//! without editing the source, and falls back to the project's defaults otherwise.
+const SECURITY_TXT_FIELDS: &[(&str, &str)] = &[
+    ("SECURITY_TXT_NAME", "Miming Spoke"),
+    ("SECURITY_TXT_PROJECT_URL", "https://github.com/Miming-DAO/miming-spoke-solana"),
+    (
+        "SECURITY_TXT_CONTACTS",
+        "link:https://github.com/Miming-DAO/miming-spoke-solana/security/advisories/new",
+    ),
+    (
+        "SECURITY_TXT_POLICY",
+        "https://github.com/Miming-DAO/miming-spoke-solana/blob/main/SECURITY.md",
+    ),
+    ("SECURITY_TXT_SOURCE_CODE", "https://github.com/Miming-DAO/miming-spoke-solana"),
+];
+
+fn main() {
+    for (name, default) in SECURITY_TXT_FIELDS {
+        println!("cargo:rerun-if-env-changed={name}");
+        let value = std::env::var(name).unwrap_or_else(|_| default.to_string());
+        println!("cargo:rustc-env={name}={value}");
+    }
+}
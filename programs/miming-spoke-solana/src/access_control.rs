@@ -0,0 +1,73 @@
+//! # Access Control Module
+//!
+//! This module defines the roles that authorize instructions across the multisig, vault, and staking modules, and the
+//! helpers their account contexts check them with, so each context states who may call it in one `constraint` instead of
+//! an ad-hoc check in its handler.
+//!
+//! ## Roles
+//!
+//! - **Admin:** The governing multisig, recorded as the program configuration's `authority`. It acts through approved
+//!   proposals rather than by signing directly.
+//! - **Guardian:** The key recorded as the configuration's `guardian`, which can halt the program.
+//! - **Operator:** The key recorded as the configuration's `operator`, which publishes staking reports. While no operator is
+//!   configured, reports stay permissionless; the time-triggered cranks are permissionless either way.
+//! - **Member:** A signer of the multisig council, which can sign proposals and close resolved governance accounts.
+//! - **Relayer:** The key recorded as the configuration's `relayer`, which relays ledger entries to the hub and may close
+//!   them once they leave the retention window.
+//!
+//! The operator and relayer are appointed through approved `ConfigureRole` staking proposals, executed by
+//! [`ProgramConfigInstructions::configure_role`](crate::program_config::ProgramConfigInstructions::configure_role).
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! #[account(
+//!     seeds = [PROGRAM_CONFIG_SEED],
+//!     bump = program_config.bump,
+//!     constraint = has_role(&program_config, Role::Guardian, guardian.key) @ ProgramConfigErrorCode::UnauthorizedGuardian
+//! )]
+//! pub program_config: Account<'info, ProgramConfigAccount>,
+//! ```
+use anchor_lang::prelude::*;
+use crate::{multisig::MultisigAccount, program_config::ProgramConfigAccount};
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Admin,
+    Guardian,
+    Operator,
+    Member,
+    Relayer,
+}
+
+impl Role {
+    /// Returns `true` if the role is held by a key the program configuration records and `ConfigureRole` proposals appoint.
+    pub fn is_appointed(&self) -> bool {
+        matches!(self, Role::Operator | Role::Relayer)
+    }
+}
+
+/// Returns `true` if `key` holds `role` in the program configuration.
+///
+/// Unset roles are held by nobody, and `Member` is held by the multisig council instead; check it with [`is_member`].
+pub fn has_role(program_config: &ProgramConfigAccount, role: Role, key: &Pubkey) -> bool {
+    let holder = match role {
+        Role::Admin => program_config.authority,
+        Role::Guardian => program_config.guardian,
+        Role::Operator => program_config.operator,
+        Role::Relayer => program_config.relayer,
+        Role::Member => return false,
+    };
+
+    holder != Pubkey::default() && holder == *key
+}
+
+/// Returns `true` if `key` is one of the multisig's signers, or if the multisig has no signers yet.
+pub fn is_member(multisig: &MultisigAccount, key: &Pubkey) -> bool {
+    multisig.signers.is_empty() || multisig.signers.iter().any(|signer| signer.pubkey == *key)
+}
+
+/// Returns `true` if `key` may publish a staking report: anyone while no operator is configured, only the operator once one is.
+pub fn can_operate(program_config: &ProgramConfigAccount, key: &Pubkey) -> bool {
+    program_config.operator == Pubkey::default() || has_role(program_config, Role::Operator, key)
+}
@@ -0,0 +1,195 @@
+//! # Bubblegum Module
+//!
+//! This module mints deposit receipts as Metaplex Bubblegum compressed NFTs, leaves of a merkle tree the program manages,
+//! so high-volume deposits get verifiable receipts for a fraction of the cost of a mint, metadata, and edition account.
+//!
+//! ## Features
+//!
+//! - **Program-Managed Tree:** Receipt trees are created through Bubblegum's `create_tree` with the deposit receipt
+//!   authority PDA as their private creator, so only this program can mint into them.
+//! - **Tree Checks:** Governance can only register a tree whose Bubblegum configuration delegates minting to the receipt
+//!   authority PDA.
+//! - **Compressed Mints:** Receipts are minted through Bubblegum's `mint_v1`, signed by the receipt authority PDA, with the
+//!   same name, symbol, and URI as receipt NFTs; indexers serve them through the DAS API.
+//!
+//! ## Main Data Structures
+//!
+//! - [`BubblegumAccounts`]: The accounts `create_tree` and `mint_v1` are invoked with.
+//! - [`ReceiptMetadata`]: The name, symbol, and URI of a compressed receipt.
+//!
+//! ## Constants
+//!
+//! - `BUBBLEGUM_PROGRAM_ID`: The Bubblegum program, which owns tree configurations and mints compressed NFTs.
+//! - `SPL_ACCOUNT_COMPRESSION_ID`: The account compression program owning merkle trees.
+//! - `SPL_NOOP_ID`: The no-op program Bubblegum logs leaves through, for indexers.
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+use crate::states::errors::BubblegumErrorCode;
+
+/// The Metaplex Bubblegum program on Solana.
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// The SPL account compression program, which owns concurrent merkle trees.
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// The SPL no-op program, which Bubblegum logs new leaves through.
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Anchor discriminator of Bubblegum's `TreeConfig` account.
+const TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+/// Anchor discriminator of Bubblegum's `create_tree` instruction.
+const CREATE_TREE_DISCRIMINATOR: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+/// Anchor discriminator of Bubblegum's `mint_v1` instruction.
+const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+/// Tag of the `NonFungible` token standard.
+const TOKEN_STANDARD_NON_FUNGIBLE: u8 = 0;
+/// Tag of the `Original` token program version.
+const TOKEN_PROGRAM_VERSION_ORIGINAL: u8 = 0;
+
+/// The accounts Bubblegum's `create_tree` and `mint_v1` are invoked with.
+pub struct BubblegumAccounts<'a, 'info> {
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub tree_authority: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+}
+
+/// The metadata of a compressed receipt.
+pub struct ReceiptMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// Returns the address of the Bubblegum tree configuration of `merkle_tree`.
+pub fn find_tree_config_address(merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &BUBBLEGUM_PROGRAM_ID).0
+}
+
+/// Checks that `tree_config` is the Bubblegum configuration of `merkle_tree`, and returns its tree delegate, the authority
+/// allowed to mint into the tree, which follows the discriminator and the tree creator.
+pub fn load_tree_delegate(tree_config: &AccountInfo, merkle_tree: &Pubkey) -> Result<Pubkey> {
+    require_keys_eq!(*tree_config.owner, BUBBLEGUM_PROGRAM_ID, BubblegumErrorCode::InvalidReceiptTree);
+    require_keys_eq!(tree_config.key(), find_tree_config_address(merkle_tree), BubblegumErrorCode::InvalidReceiptTree);
+
+    let data = tree_config.try_borrow_data()?;
+    require!(data.starts_with(&TREE_CONFIG_DISCRIMINATOR), BubblegumErrorCode::InvalidReceiptTree);
+
+    let mut fields = data.get(8 + 32..).ok_or(BubblegumErrorCode::InvalidReceiptTree)?;
+    let tree_delegate = Pubkey::deserialize(&mut fields).map_err(|_| BubblegumErrorCode::InvalidReceiptTree)?;
+
+    Ok(tree_delegate)
+}
+
+/// Creates a private Bubblegum tree in `merkle_tree`, an account the compression program already owns, with the tree
+/// authority as its creator.
+pub fn create_tree(
+    accounts: BubblegumAccounts,
+    max_depth: u32,
+    max_buffer_size: u32,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = CREATE_TREE_DISCRIMINATOR.to_vec();
+    max_depth.serialize(&mut data)?;
+    max_buffer_size.serialize(&mut data)?;
+    Some(false).serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.tree_config.key(), false),
+            AccountMeta::new(accounts.merkle_tree.key(), false),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.tree_authority.key(), true),
+            AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+            AccountMeta::new_readonly(accounts.compression_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.tree_config.clone(),
+            accounts.merkle_tree.clone(),
+            accounts.payer.clone(),
+            accounts.tree_authority.clone(),
+            accounts.log_wrapper.clone(),
+            accounts.compression_program.clone(),
+            accounts.system_program.clone(),
+            accounts.bubblegum_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Mints a compressed, immutable, royalty-free NFT with `metadata` to `leaf_owner`, signed by the tree authority.
+pub fn mint_v1<'info>(
+    accounts: BubblegumAccounts<'_, 'info>,
+    leaf_owner: &AccountInfo<'info>,
+    metadata: ReceiptMetadata,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+    metadata.name.serialize(&mut data)?;
+    metadata.symbol.serialize(&mut data)?;
+    metadata.uri.serialize(&mut data)?;
+    // Seller fee, primary sale, mutability, and edition nonce.
+    0u16.serialize(&mut data)?;
+    false.serialize(&mut data)?;
+    false.serialize(&mut data)?;
+    None::<u8>.serialize(&mut data)?;
+    Some(TOKEN_STANDARD_NON_FUNGIBLE).serialize(&mut data)?;
+    // No collection and no uses.
+    data.push(0);
+    data.push(0);
+    data.push(TOKEN_PROGRAM_VERSION_ORIGINAL);
+    // No creators.
+    0u32.serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.tree_config.key(), false),
+            AccountMeta::new_readonly(leaf_owner.key(), false),
+            AccountMeta::new_readonly(leaf_owner.key(), false),
+            AccountMeta::new(accounts.merkle_tree.key(), false),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.tree_authority.key(), true),
+            AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+            AccountMeta::new_readonly(accounts.compression_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.tree_config.clone(),
+            leaf_owner.clone(),
+            accounts.merkle_tree.clone(),
+            accounts.payer.clone(),
+            accounts.tree_authority.clone(),
+            accounts.log_wrapper.clone(),
+            accounts.compression_program.clone(),
+            accounts.system_program.clone(),
+            accounts.bubblegum_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
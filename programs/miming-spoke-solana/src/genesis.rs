@@ -0,0 +1,321 @@
+//! # Genesis Module
+//!
+//! This module implements the one-shot bootstrap of a deployment, creating every singleton the modules need in a single
+//! atomic instruction instead of a sequence of transactions that anyone could front-run.
+//!
+//! ## Features
+//!
+//! - **Atomic Bootstrap:** The multisig, the program configuration, the hub registry, the vault's identifiers, and the
+//!   staking configuration, pool, activity window, reward vault, and proposal identifier are created together, or not at
+//!   all.
+//! - **Upgrade Authority Only:** Genesis must be signed by the program's upgrade authority, read from its program data
+//!   account, so no one else can seat a council or pick the reward mint before the deployer does.
+//! - **Genesis Parameters:** The initial council, its threshold, the teleport fee, the guardian, and the rent recipient are
+//!   supplied up front, so the deployment is governed from its first slot.
+//!
+//! ## Main Data Structures
+//!
+//! - [`GenesisParams`]: The initial council and program configuration values.
+//!
+//! ## Instructions
+//!
+//! - [`GenesisInstructions::initialize_program`]: Creates every singleton from the genesis parameters.
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    hub_registry::HubRegistryAccount,
+    messaging::MessagingTransport,
+    multisig::{MultisigAccount, MultisigMode, MultisigSigners, MAX_MULTISIG_NAME_LEN, MAX_SIGNERS, MAX_THRESHOLD},
+    program::MimingSpokeSolana,
+    program_config::{ProgramConfigAccount, MAX_TELEPORT_FEE},
+    staking::{StakingActivityAccount, StakingConfigAccount, StakingPoolAccount},
+    states::{
+        audit::Audited,
+        errors::{GenesisErrorCode, MultisigErrorCode, StakingErrorCode},
+        events::{EventModule, ProgramInitializedEvent, StateMutationEvent},
+        pda::{
+            HUB_REGISTRY_SEED, LEDGER_IDENTIFIER_SEED, MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_IDENTIFIER_SEED, STAKING_ACTIVITY_SEED,
+            STAKING_AUTHORITY_SEED, STAKING_CONFIG_SEED, STAKING_POOL_SEED, STAKING_PROPOSAL_IDENTIFIER_SEED,
+            TRANSFER_PROPOSAL_IDENTIFIER_SEED,
+        },
+        versioning::VersionedAccount,
+    },
+    wormhole::WORMHOLE_CORE_BRIDGE_ID,
+    IdentifierAccount,
+};
+
+/// The values a deployment starts with.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GenesisParams {
+    pub signers: Vec<MultisigSigners>,
+    pub threshold: u8,
+    pub teleport_fee: u64,
+    pub guardian: Pubkey,
+    pub rent_recipient: Pubkey,
+}
+
+impl GenesisParams {
+    /// Checks the council against the same limits as a multisig proposal, and the teleport fee against its governed bound.
+    ///
+    /// The council must have at least one signer, no signer twice, and a threshold it can reach, since genesis seats it
+    /// without a proposal that could be corrected afterwards.
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.signers.is_empty(), GenesisErrorCode::EmptyCouncil);
+        require!(self.threshold > 0, GenesisErrorCode::InvalidThreshold);
+        require!(self.threshold as usize <= self.signers.len(), GenesisErrorCode::InvalidThreshold);
+        require!(self.threshold <= MAX_THRESHOLD, MultisigErrorCode::ThresholdLimitReached);
+        require!(self.signers.len() <= MAX_SIGNERS, MultisigErrorCode::SignerLimitReached);
+        require!(
+            self.signers
+                .iter()
+                .enumerate()
+                .all(|(index, signer)| self.signers[..index].iter().all(|other| other.pubkey != signer.pubkey)),
+            GenesisErrorCode::DuplicateSigner
+        );
+        require!(
+            self.signers.iter().all(|signer| signer.name.len() <= MAX_MULTISIG_NAME_LEN),
+            MultisigErrorCode::NameTooLong
+        );
+        require!(self.teleport_fee <= MAX_TELEPORT_FEE, StakingErrorCode::InvalidConfigValue);
+
+        Ok(())
+    }
+}
+
+/// The accounts created at genesis. The singletons are boxed to keep the accounts' deserialization within the stack
+/// frame limit.
+#[derive(Accounts)]
+#[instruction(params: GenesisParams)]
+pub struct ProgramInitialization<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ GenesisErrorCode::InvalidProgramData)]
+    pub program: Program<'info, MimingSpokeSolana>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(signer.key()) @ GenesisErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(init, payer = payer, space = 8 + IdentifierAccount::INIT_SPACE, seeds = [PROPOSAL_IDENTIFIER_SEED], bump)]
+    pub proposal_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MultisigAccount::len_for(params.signers.len()),
+        seeds = [MULTISIG_SEED],
+        bump
+    )]
+    pub multisig: Box<Account<'info, MultisigAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProgramConfigAccount::INIT_SPACE,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfigAccount>>,
+
+    #[account(init, payer = payer, space = HubRegistryAccount::SPACE, seeds = [HUB_REGISTRY_SEED], bump)]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
+
+    #[account(init, payer = payer, space = 8 + IdentifierAccount::INIT_SPACE, seeds = [LEDGER_IDENTIFIER_SEED], bump)]
+    pub ledger_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    #[account(init, payer = payer, space = 8 + IdentifierAccount::INIT_SPACE, seeds = [TRANSFER_PROPOSAL_IDENTIFIER_SEED], bump)]
+    pub transfer_proposal_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingConfigAccount::INIT_SPACE,
+        seeds = [STAKING_CONFIG_SEED],
+        bump
+    )]
+    pub staking_config: Box<Account<'info, StakingConfigAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingPoolAccount::INIT_SPACE,
+        seeds = [STAKING_POOL_SEED],
+        bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingActivityAccount::INIT_SPACE,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    #[account(init, payer = payer, space = 8 + IdentifierAccount::INIT_SPACE, seeds = [STAKING_PROPOSAL_IDENTIFIER_SEED], bump)]
+    pub staking_proposal_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub struct GenesisInstructions;
+
+impl GenesisInstructions {
+    /// Creates every singleton of a new deployment from the genesis parameters.
+    ///
+    /// This function performs the following actions:
+    /// - Checks, through the account constraints, that the signer is the program's upgrade authority.
+    /// - Validates the council and the teleport fee.
+    /// - Creates the multisig with the given signers and threshold in `Unanimous` mode, and its proposal identifier.
+    /// - Creates the program configuration governed by the multisig, with both modules enabled, the given teleport fee,
+    ///   guardian, and rent recipient.
+    /// - Creates the hub registry without a hub or routes, for governance to configure.
+    /// - Creates the vault's ledger and transfer proposal identifiers.
+    /// - Creates the default staking configuration with the given reward mint, the staking pool statistics starting at the
+    ///   current epoch, the staking activity window, the reward vault, and the staking proposal identifier.
+    /// - Emits a `ProgramInitializedEvent`.
+    ///
+    /// Every account is created with `init`, so genesis runs at most once and fails if any singleton already exists.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the upgrade authority, the program data, and the singletons to create.
+    /// * `params` - The initial council and program configuration values.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the signer is not the upgrade authority, the council exceeds the multisig limits, or the
+    /// teleport fee exceeds `MAX_TELEPORT_FEE`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the deployment is initialized, otherwise returns an error.
+    pub fn initialize_program(ctx: Context<ProgramInitialization>, params: GenesisParams) -> Result<()> {
+        params.validate()?;
+
+        let signer_key = ctx.accounts.signer.key();
+        let clock = Clock::get()?;
+
+        ctx.accounts.proposal_identifier.initialize(ctx.bumps.proposal_identifier);
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.name = String::from("System");
+        multisig.threshold = params.threshold;
+        multisig.signers = params.signers.clone();
+        multisig.mode = MultisigMode::Unanimous;
+        multisig.min_approval_weight = 0;
+        multisig.version = MultisigAccount::VERSION;
+        multisig.bump = ctx.bumps.multisig;
+        multisig.record_creation(signer_key)?;
+
+        ctx.accounts.program_config.set_inner(ProgramConfigAccount {
+            authority: ctx.accounts.multisig.key(),
+            vault_enabled: true,
+            staking_enabled: true,
+            teleport_paused: false,
+            teleport_fee: params.teleport_fee,
+            guardian: params.guardian,
+            global_paused: false,
+            version: ProgramConfigAccount::VERSION,
+            rent_recipient: params.rent_recipient,
+            bump: ctx.bumps.program_config,
+            cpi_guard_enabled: false,
+            cpi_allowed_callers: Vec::new(),
+            operator: Pubkey::default(),
+            relayer: Pubkey::default(),
+            created_at: 0,
+            updated_at: 0,
+            last_actor: Pubkey::default(),
+            hub_chain: 0,
+            hub_emitter: [0; 32],
+            messaging_transport: MessagingTransport::Wormhole,
+            messaging_endpoint: WORMHOLE_CORE_BRIDGE_ID,
+            teleport_fee_usd_cents: 0,
+            sol_usd_feed_id: [0; 32],
+            oracle_max_staleness: 0,
+            oracle_max_confidence_bps: 0,
+            switchboard_sol_usd_feed: Pubkey::default(),
+            oracle_max_divergence_bps: 0,
+            deposit_receipt_threshold: 0,
+            deposit_receipt_uri_prefix: String::new(),
+            squads_multisig: Pubkey::default(),
+            squads_vault_index: 0,
+            squads_vault_authority: Pubkey::default(),
+            realms_governance: Pubkey::default(),
+            realms_realm: Pubkey::default(),
+            deposit_receipt_tree: Pubkey::default(),
+            relayer_fee_bps: 0,
+        });
+        ctx.accounts.program_config.record_creation(signer_key)?;
+
+        ctx.accounts.hub_registry.set_inner(HubRegistryAccount {
+            hub_chain: 0,
+            hub_emitter: [0; 32],
+            routes: Vec::new(),
+            version: HubRegistryAccount::VERSION,
+            bump: ctx.bumps.hub_registry,
+        });
+
+        ctx.accounts.ledger_identifier.initialize(ctx.bumps.ledger_identifier);
+        ctx.accounts.transfer_proposal_identifier.initialize(ctx.bumps.transfer_proposal_identifier);
+
+        ctx.accounts.staking_config.set_inner(StakingConfigAccount {
+            reward_mint: ctx.accounts.reward_mint.key(),
+            bump: ctx.bumps.staking_config,
+            ..Default::default()
+        });
+        ctx.accounts.staking_config.record_creation(signer_key)?;
+
+        let mut staking_pool = ctx.accounts.staking_pool.load_init()?;
+        staking_pool.epoch = clock.epoch;
+        staking_pool.version = StakingPoolAccount::VERSION;
+        staking_pool.bump = ctx.bumps.staking_pool;
+        ctx.accounts.staking_activity.load_init()?;
+        ctx.accounts.staking_proposal_identifier.initialize(ctx.bumps.staking_proposal_identifier);
+
+        emit!(ProgramInitializedEvent {
+            upgrade_authority: signer_key,
+            multisig: ctx.accounts.multisig.key(),
+            signers: params.signers.iter().map(|signer| signer.pubkey).collect(),
+            threshold: params.threshold,
+            teleport_fee: params.teleport_fee,
+            guardian: params.guardian,
+            rent_recipient: params.rent_recipient,
+            reward_mint: ctx.accounts.reward_mint.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Genesis, "initialize_program", signer_key)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! # Hub Registry Module
+//!
+//! This module implements the governance-managed routing table of the spoke: the hub it answers to and the chains its
+//! teleports may be bound for, so teleport and release instructions read their routing from one account instead of
+//! assuming it.
+//!
+//! ## Features
+//!
+//! - **Hub Identity:** The registry stores the hub's Wormhole chain id and emitter address, the only source releases are
+//!   completed from; governance sets them through `ConfigureHubRegistry` or `ConfigureHubEmitter` proposals.
+//! - **Supported Routes:** Each destination chain teleports may be posted to is listed with its own limit; a teleport to
+//!   an unlisted chain, or above its chain's limit, is rejected before any message is posted.
+//! - **Created at Genesis:** The registry lives at the fixed `HUB_REGISTRY_SEED` address and starts without a hub and
+//!   without routes, so nothing is routed until governance configures it. Deployments bootstrapped before the registry
+//!   existed get it from their first hub configuration proposal.
+//!
+//! ## Main Data Structures
+//!
+//! - [`HubRoute`]: A supported destination chain and the largest teleport posted to it.
+//! - [`HubRegistryAccount`]: Stores the hub's chain and emitter and the supported routes.
+//!
+//! ## Constants
+//!
+//! - `MAX_HUB_ROUTES`: Maximum number of destination chains the registry lists.
+use anchor_lang::prelude::*;
+use crate::{
+    states::{
+        errors::{HubRegistryErrorCode, StakingErrorCode, WormholeErrorCode},
+        versioning::VersionedAccount,
+    },
+    wormhole::SOLANA_CHAIN_ID,
+};
+
+/// Maximum number of destination chains the registry lists.
+pub const MAX_HUB_ROUTES: usize = 8;
+
+/// A destination chain teleports may be posted to.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HubRoute {
+    /// The destination's Wormhole chain id.
+    pub chain: u16,
+    /// The largest amount, in lamports, a single teleport bound for the chain may carry.
+    pub max_teleport_amount: u64,
+}
+
+/// Checks that the hub is a chain other than Solana with a non-zero emitter, and that every route is bound for a distinct
+/// chain other than Solana with a non-zero limit.
+pub fn validate_hub_routing(hub_chain: u16, hub_emitter: &[u8; 32], routes: &[HubRoute]) -> Result<()> {
+    require!(
+        hub_chain != 0 && hub_chain != SOLANA_CHAIN_ID && *hub_emitter != [0u8; 32],
+        StakingErrorCode::InvalidConfigValue
+    );
+    require!(routes.len() <= MAX_HUB_ROUTES, StakingErrorCode::InvalidConfigValue);
+    for (index, route) in routes.iter().enumerate() {
+        require!(
+            route.chain != 0
+                && route.chain != SOLANA_CHAIN_ID
+                && route.max_teleport_amount > 0
+                && routes[..index].iter().all(|other| other.chain != route.chain),
+            StakingErrorCode::InvalidConfigValue
+        );
+    }
+
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct HubRegistryAccount {
+    pub hub_chain: u16,
+    pub hub_emitter: [u8; 32],
+    #[max_len(MAX_HUB_ROUTES)]
+    pub routes: Vec<HubRoute>,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl HubRegistryAccount {
+    /// Stamps the version and canonical `bump` of a registry created by the hub configuration proposal being executed.
+    pub fn initialize_if_needed(&mut self, bump: u8) {
+        if self.version == 0 {
+            self.upgrade(bump);
+        }
+    }
+
+    /// Checks that a hub is configured and that it sent a message from `source_chain` by `sender`.
+    pub fn check_emitter(&self, source_chain: u16, sender: &[u8; 32]) -> Result<()> {
+        require!(self.hub_chain != 0, WormholeErrorCode::HubEmitterNotConfigured);
+        require!(
+            source_chain == self.hub_chain && *sender == self.hub_emitter,
+            WormholeErrorCode::UnknownEmitter
+        );
+
+        Ok(())
+    }
+
+    /// Checks that `chain` is a supported destination and that `amount` is within its limit.
+    pub fn check_route(&self, chain: u16, amount: u64) -> Result<()> {
+        let route = self
+            .routes
+            .iter()
+            .find(|route| route.chain == chain)
+            .ok_or(HubRegistryErrorCode::UnsupportedDestinationChain)?;
+        require!(amount <= route.max_teleport_amount, HubRegistryErrorCode::TeleportAboveRouteLimit);
+
+        Ok(())
+    }
+}
+
+impl VersionedAccount for HubRegistryAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + HubRegistryAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
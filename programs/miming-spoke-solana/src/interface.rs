@@ -0,0 +1,271 @@
+//! # Composing with the Spoke
+//!
+//! This module, enabled with the `cpi` feature, gives other programs and off-chain clients typed builders for the
+//! spoke's entry points, so they never hand-roll instruction data or derive its PDAs themselves.
+//!
+//! ## Calling the Spoke from Another Anchor Program
+//!
+//! Depend on the crate with the `cpi` feature, which also disables its entrypoint:
+//!
+//! ```toml
+//! miming-spoke-solana = { version = "0.1.0", features = ["cpi"] }
+//! ```
+//!
+//! Anchor then generates a function per instruction in [`crate::cpi`], taking a `CpiContext` over the matching
+//! account struct in [`crate::cpi::accounts`]. Views return their value through the CPI's return data:
+//!
+//! ```ignore
+//! let voting_power = miming_spoke_solana::cpi::staking_get_voting_power(
+//!     CpiContext::new(spoke_program.to_account_info(), StakingGetVotingPower { staking_config })
+//!         .with_remaining_accounts(registries),
+//!     staker,
+//! )?
+//! .get();
+//! ```
+//!
+//! ## Building Instructions
+//!
+//! The builders below return a complete [`Instruction`], with every PDA derived from [`crate::states::pda`], ready to be
+//! sent by a client or passed to `invoke` / `invoke_signed`. Builders whose accounts depend on a counter, such as the
+//! id of the next ledger entry or proposal, take it as an argument; read it from the matching `IdentifierAccount`.
+//! Builders for instructions that create accounts take a `payer` funding their rent, which may be the signer itself.
+//!
+//! - [`vault_teleport`]: Deposits SOL into the vault and records a ledger entry.
+//! - [`multisig_create_proposal`]: Creates a multisig proposal.
+//! - [`staking_create_proposal`]: Creates a staking proposal.
+//! - [`staking_get_voting_power`]: Reads a staker's voting power across their positions.
+//! - [`staking_get_position`]: Reads a live view of a stake position.
+//! - [`staking_get_stake_info`]: Reads a compact view of a stake position.
+//! - [`multisig_get_state`]: Reads the multisig's configuration.
+//! - [`vault_get_status`]: Reads the vault's balance, switches, and counters.
+//! - [`program_config_get_settings`]: Reads the program configuration.
+//! - [`staking_get_config`]: Reads the staking configuration.
+use anchor_lang::{
+    prelude::*,
+    solana_program::instruction::Instruction,
+    InstructionData,
+};
+
+use crate::{
+    multisig::{MultisigMode, MultisigSigners},
+    staking::StakingProposalAction,
+    states::pda::{
+        find_multisig_address, find_program_config_address, find_proposal_address, find_proposal_identifier_address,
+        find_staking_proposal_address, find_staking_proposal_identifier_address,
+    },
+};
+#[cfg(feature = "vault")]
+use crate::states::pda::{
+    find_ledger_address, find_ledger_identifier_address, find_transfer_proposal_identifier_address, find_vault_address,
+};
+#[cfg(any(feature = "vault", feature = "staking"))]
+use crate::states::pda::{find_staking_config_address, find_staking_registry_address};
+
+#[cfg(feature = "vault")]
+/// Builds a `vault_teleport` instruction depositing `amount` lamports plus the teleport fee from `signer`, bound for
+/// `destination` on `destination_chain`, or for a destination picked when posting if `destination_chain` is zero.
+///
+/// `ledger_id` is the current id of the ledger identifier, which numbers the entry the deposit is recorded in.
+/// Passing `fee_discount_position` lets the signer's stake position at that index discount the teleport fee.
+/// `sol_usd_price_update` and `sol_usd_fallback_feed` are the Pyth SOL/USD price update and the Switchboard pull feed
+/// backing it, one of which is required while the fee is set in USD.
+#[allow(clippy::too_many_arguments)]
+pub fn vault_teleport(
+    signer: Pubkey,
+    payer: Pubkey,
+    ledger_id: u64,
+    amount: u64,
+    destination_chain: u16,
+    destination: [u8; 32],
+    fee_discount_position: Option<u64>,
+    sol_usd_price_update: Option<Pubkey>,
+    sol_usd_fallback_feed: Option<Pubkey>,
+) -> Instruction {
+    let (staking_config, staking_registry) = match fee_discount_position {
+        Some(position_index) => (
+            Some(find_staking_config_address().0),
+            Some(find_staking_registry_address(&signer, position_index).0),
+        ),
+        None => (None, None),
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::VaultTeleport {
+            signer,
+            payer,
+            vault: find_vault_address().0,
+            ledger_identifier: find_ledger_identifier_address().0,
+            ledger: find_ledger_address(ledger_id).0,
+            program_config: find_program_config_address().0,
+            staking_config,
+            staking_registry,
+            sol_usd_price_update,
+            sol_usd_fallback_feed,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::VaultTeleport { amount, destination_chain, destination }.data(),
+    }
+}
+
+/// Builds a `multisig_create_proposal` instruction proposing a new multisig configuration.
+///
+/// `proposal_id` is the current id of the proposal identifier, which numbers the new proposal.
+#[allow(clippy::too_many_arguments)]
+pub fn multisig_create_proposal(
+    signer: Pubkey,
+    payer: Pubkey,
+    proposal_id: u64,
+    name: String,
+    threshold: u8,
+    signers: Vec<MultisigSigners>,
+    mode: MultisigMode,
+    min_approval_weight: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::MultisigCreateProposal {
+            signer,
+            payer,
+            current_multisig: find_multisig_address().0,
+            proposal_identifier: find_proposal_identifier_address().0,
+            proposal: find_proposal_address(proposal_id).0,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::MultisigCreateProposal {
+            name,
+            threshold,
+            signers,
+            mode,
+            min_approval_weight,
+        }
+        .data(),
+    }
+}
+
+/// Builds a `staking_create_proposal` instruction proposing `action`.
+///
+/// `proposal_id` is the current id of the staking proposal identifier, which numbers the new proposal.
+pub fn staking_create_proposal(
+    signer: Pubkey,
+    payer: Pubkey,
+    proposal_id: u64,
+    action: StakingProposalAction,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::StakingCreateProposal {
+            signer,
+            payer,
+            current_multisig: find_multisig_address().0,
+            staking_proposal_identifier: find_staking_proposal_identifier_address().0,
+            staking_proposal: find_staking_proposal_address(proposal_id).0,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::StakingCreateProposal { action }.data(),
+    }
+}
+
+#[cfg(feature = "staking")]
+/// Builds a `staking_get_voting_power` view summing the voting power of `staker`'s positions at `position_indexes`.
+pub fn staking_get_voting_power(staker: Pubkey, position_indexes: &[u64]) -> Instruction {
+    let mut accounts = crate::accounts::StakingGetVotingPower {
+        staking_config: find_staking_config_address().0,
+    }
+    .to_account_metas(None);
+    accounts.extend(position_indexes.iter().map(|position_index| {
+        AccountMeta::new_readonly(find_staking_registry_address(&staker, *position_index).0, false)
+    }));
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: crate::instruction::StakingGetVotingPower { staker }.data(),
+    }
+}
+
+#[cfg(feature = "staking")]
+/// Builds a `staking_get_position` view of `staker`'s position at `position_index`.
+pub fn staking_get_position(staker: Pubkey, position_index: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::StakingGetPosition {
+            staking_config: find_staking_config_address().0,
+            staking_registry: find_staking_registry_address(&staker, position_index).0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::StakingGetPosition { staker, position_index }.data(),
+    }
+}
+
+#[cfg(feature = "staking")]
+/// Builds a `staking_get_stake_info` view of `staker`'s position at `position_index`.
+pub fn staking_get_stake_info(staker: Pubkey, position_index: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::StakingGetStakeInfo {
+            staking_config: find_staking_config_address().0,
+            staking_registry: find_staking_registry_address(&staker, position_index).0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::StakingGetStakeInfo { staker, position_index }.data(),
+    }
+}
+
+#[cfg(feature = "multisig")]
+/// Builds a `multisig_get_state` view of the multisig's configuration.
+pub fn multisig_get_state() -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::MultisigGetState {
+            multisig: find_multisig_address().0,
+            proposal_identifier: find_proposal_identifier_address().0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::MultisigGetState {}.data(),
+    }
+}
+
+#[cfg(feature = "vault")]
+/// Builds a `vault_get_status` view of the vault's balance, switches, and counters.
+pub fn vault_get_status() -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::VaultGetStatus {
+            vault: find_vault_address().0,
+            program_config: find_program_config_address().0,
+            ledger_identifier: find_ledger_identifier_address().0,
+            transfer_proposal_identifier: find_transfer_proposal_identifier_address().0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::VaultGetStatus {}.data(),
+    }
+}
+
+/// Builds a `program_config_get_settings` view of the program configuration.
+pub fn program_config_get_settings() -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::ProgramConfigGetSettings {
+            program_config: find_program_config_address().0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::ProgramConfigGetSettings {}.data(),
+    }
+}
+
+#[cfg(feature = "staking")]
+/// Builds a `staking_get_config` view of the staking configuration.
+pub fn staking_get_config() -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: crate::accounts::StakingGetConfig {
+            staking_config: find_staking_config_address().0,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::StakingGetConfig {}.data(),
+    }
+}
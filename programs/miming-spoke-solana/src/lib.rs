@@ -4,31 +4,141 @@
 //!
 //! ## Modules
 //!
+//! - **bubblegum**: Creates Metaplex Bubblegum trees and mints compressed NFTs into them, for compressed deposit receipts.
+//! - **genesis**: Bootstraps a deployment's singletons in one instruction signed by the upgrade authority.
+//! - **hub_registry**: Holds the governed hub chain, hub emitter, and supported teleport routes with their limits.
 //! - **multisig**: Provides multisignature account creation, proposal management, and approval workflows.
+//! - **program_config**: Holds the multisig-governed module switches, teleport fee, teleport pause switch, emergency stop, and CPI guard.
+//! - **nonce**: Per-wallet replay protection nonces checked by signing, execution, and thaw instructions.
+//! - **oracle**: Reads Pyth SOL/USD prices, backed by a Switchboard feed, with staleness, confidence, and divergence checks,
+//!   to convert USD-denominated fees.
+//! - **realms**: Verifies SPL Governance (Realms) governances and the CPI of their proposals, which governance can register
+//!   to act for the council.
+//! - **registry**: Lists the program's singleton PDAs with type tags, so indexers discover a deployment's state.
+//! - **squads**: Verifies Squads multisigs and the CPI of their vaults, which governance can register to approve vault
+//!   transfer executions.
 //! - **vault**: Enables secure token storage, teleportation, and multisig-governed transfer proposals from vaults.
 //! - **staking**: Supports staking account freezing and thawing operations.
 //! - **states**: Contains shared state definitions and account structures.
+//! - **light_client**: Records the hub's state roots and verifies merkle proofs of inbound claims against them.
+//! - **messaging**: Abstracts the cross-chain transport behind an adapter chosen by the program configuration.
+//! - **wormhole**: Encodes outbound teleports and posts them to the hub chain through the Wormhole core bridge, and decodes
+//!   the hub's releases from the VAAs it verified.
+//! - **interface**: Typed instruction builders for programs and clients composing with the spoke, enabled by the `cpi` feature.
 //!
 //! ## Program Features
 //!
+//! - **Genesis**
+//!   - Create the multisig with its initial council, the program configuration, the vault's identifiers, and the staking
+//!     configuration atomically, restricted to the program's upgrade authority so the bootstrap cannot be front-run.
+//!
 //! - **Multisig Account Management**
 //!   - Initialize multisig accounts with customizable signers and thresholds.
 //!   - Create, sign, and approve proposals for multisig actions.
+//!   - Create and sign a proposal in one instruction, for multisig and vault transfer proposals alike.
+//!   - Optionally approve proposals by staking voting weight instead of unanimity.
+//!
+//! - **Program Configuration**
+//!   - Create a program-wide configuration at genesis, governed by the multisig.
+//!   - Enable or disable the vault and staking modules, pause teleports, and set the teleport fee through multisig-approved proposals.
+//!   - Halt the whole program instantly through a guardian's emergency stop, lifted only by a multisig-approved proposal.
+//!   - Switch the cross-chain messaging transport and its endpoint program through multisig-approved proposals.
+//!   - Set the teleport fee in USD cents, converted at each teleport from a Pyth SOL/USD feed with a Switchboard fallback.
+//!   - Set the teleport amount from which depositors can mint receipt NFTs, and the receipts' metadata URI prefix.
+//!   - Register a Squads vault as an approving authority for vault transfer executions.
+//!   - Register a Realms governance whose executed proposals approve configuration changes and vault transfers directly.
+//!   - Register the Bubblegum tree compressed deposit receipts are minted into.
 //!
 //! - **Vault Operations**
 //!   - Teleport tokens from vaults.
+//!   - Post recorded teleports to the hub chain as Wormhole messages, storing each message's sequence on its ledger entry.
+//!   - Complete releases from the hub chain from Wormhole VAAs, once per hub sequence, releasing SOL or minting MIMING.
+//!   - Mint MIMING on arrival of teleports from the hub chain, recorded as `Mint` ledger entries and in the bridged supply.
+//!   - Mint a Metaplex receipt NFT, carrying the ledger id and a receipt hash, for teleports above the receipt threshold.
+//!   - Mint the same receipt as a Bubblegum compressed NFT into a program-delegated tree, at a fraction of the cost.
 //!   - Create, sign, and execute transfer proposals from vaults, governed by multisig approval.
+//!   - Execute transfer proposals from a registered Squads vault, whose Squads approval replaces the council's signatures.
+//!   - Execute transfer proposals from an approved proposal of a registered Realms governance.
+//!   - Attach an SPL Memo naming the proposal id to executed transfers, shown by wallets as the payment reference.
 //!
 //! - **Staking Controls**
 //!   - Freeze and thaw staking accounts for advanced staking management.
+//!   - Lock stakes for a chosen duration, with an explicit early-exit penalty path.
+//!   - Hold several stake positions per wallet, each with its own lockup and mint.
+//!   - Freeze or thaw several positions in a single transaction.
+//!   - Extend lockups in exchange for a higher reward multiplier.
+//!   - Earn tier-boosted rewards on staked amounts and claim them from the reward vault.
+//!   - Earn loyalty boosts for stakes held without interruption past configured milestones.
+//!   - Fund the reward vault on-chain, tracking the reward budget that backs claims.
+//!   - Set the base reward rate from a governed, epoch-laddered reward schedule.
+//!   - Drive epoch snapshots, reward schedule steps, and the unstake queue through permissionless cranks that succeed
+//!     without changes while not due, so keepers can call them on a schedule.
+//!   - Stake tokens in escrow through a program-owned stake vault, with partial unstakes.
+//!   - Let multisig-authorized custodians stake on behalf of beneficiary wallets, which alone can unstake and claim.
+//!   - Stake through a delegate approval on a relayer's transaction, for gasless onboarding.
+//!   - Discount the teleport fee for active stakers according to their live staked amount.
+//!   - Stake MIMING + SOL pairs in a governed ratio at a boosted rate, bootstrapping protocol-owned liquidity.
+//!   - Stake SPL Token and Token-2022 mints, accounting for transfer fees and default-frozen accounts.
+//!   - Stake mints whose freeze authority is held by the program, configured per mint by multisig-approved proposals.
+//!   - Release stuck freeze stakes through multisig-approved emergency thaws.
+//!   - Slash escrow stakes only with referenced evidence and after a dispute window open to the staker's counter-claim.
+//!   - Update the minimum staking amount and unstake cooldown through multisig-approved proposals.
+//!   - Cap the total staked supply so emissions are not diluted past a planned level.
+//!   - Restrict staking to a multisig-governed allowlist of mints.
+//!   - Pause new freezes and stakes during incidents through multisig-approved proposals, while exits keep working.
+//!   - Release stakes without attestations, lockups, or penalties once a pause outlasts its grace period.
+//!   - Require an attestor's ed25519 signature before thawing, confirming off-chain settlement.
+//!   - Unbond in two steps, requesting an unstake and releasing it after the cooldown.
+//!   - Queue escrow unstakes for FIFO payout by a permissionless crank as stake vault liquidity returns.
+//!   - Derive voting power from stake positions, exposed as return data.
+//!   - Read a position's live amount, unclaimed rewards, effective APR, and unlock time as return data.
+//!   - Let other programs query a position's amount, unlock time, multiplier, and slashed flag through CPI.
+//!   - Snapshot total and per-tier stake once per epoch for reward distribution.
+//!   - Report total staked, staker count, and epoch to the hub chain through sequenced outbound reports.
+//!   - Record freezes, thaws, claims, and slashes as sequenced per-staker ledger events for deterministic statements.
+//!   - Track each recent epoch's new stakes, unstakes, and net flow in a rolling on-chain activity window.
+//!   - Attribute stakes to referrers, with per-referrer referral statistics.
+//!   - Close fully released stake positions to reclaim their rent.
+//!   - Migrate stake registries created with a legacy layout after upgrades.
+//!   - Version the staking configuration and migrate it in place when later upgrades append fields.
+//!   - Fund an epoch's SOL staking rewards from the vault through multisig-approved proposals, claimed pro rata against the epoch snapshot.
+//!   - Delegate vault SOL to allowlisted validators through the native stake program, governed by multisig-approved proposals.
+//!
+//! - **Account Versioning**
+//!   - Stamp a layout version on every account and upgrade older layouts in place through per-type `migrate_*` instructions.
+//!
+//! - **Account Closing**
+//!   - Close resolved proposals, old ledger entries, fully claimed SOL claim receipts, and retired allowlist entries, refunding
+//!     governance accounts' rent to the rent recipient configured in the program configuration.
+//!
+//! - **PDA Derivation**
+//!   - Share every PDA seed as a constant in `states::pda`, with `find_*_address` helpers for clients to derive addresses.
+//!   - Cache each account's canonical bump at creation, so later instructions verify its address without searching for it.
+//!
+//! - **Events**
+//!   - Emit a versioned `StateMutationEvent` envelope (module, action, version, actor, slot) from every state-mutating instruction.
+//!
+//! - **Light Client**
+//!   - Record the hub's state roots, submitted by a quorum of the council, and verify merkle proofs of inbound claims
+//!     against them instead of trusting a single attestor's signature.
+//!
+//! - **Replay Protection**
+//!   - Open a per-wallet nonce account and pass an `expected_nonce` when signing, executing, or thawing, so relayed or queued
+//!     transactions cannot be applied twice after a retry.
 //!
 //! - **Identifier Account**
 //!   - Provides a simple on-chain account for unique identifier management, useful for indexing or referencing entities.
+//!   - New subsystems count their accounts in a namespaced counter at `[IDENTIFIER_SEED, namespace]`, created with
+//!     `identifier_initialize` and advanced with `IdentifierAccount::next_id`.
 //!
 //! ## Usage
 //!
 //! Integrate this program into your Solana project to leverage secure multisig workflows, vault-based token management, and advanced staking controls.
 //!
+//! The `multisig`, `vault`, and `staking` cargo features, all enabled by default, register each module's instructions. The
+//! `multisig` feature also carries the program configuration and the staking proposals that govern every module, so the
+//! other two require it.
+//!
 //! ---
 //!
 //! ## License
@@ -56,18 +166,51 @@
 //! SOFTWARE.
 use anchor_lang::prelude::*;
 
+#[cfg(not(feature = "multisig"))]
+compile_error!("the `multisig` feature is required, since the multisig governs every other module");
+
+pub mod access_control;
+pub mod bubblegum;
+pub mod genesis;
+pub mod hub_registry;
+#[cfg(feature = "cpi")]
+pub mod interface;
+pub mod light_client;
+pub mod messaging;
 pub mod multisig;
+pub mod nonce;
+pub mod oracle;
+pub mod program_config;
+pub mod realms;
+pub mod registry;
+pub mod squads;
 pub mod staking;
 pub mod states;
 pub mod vault;
+pub mod wormhole;
 
+use genesis::*;
+use light_client::*;
 use multisig::*;
+use nonce::*;
+use program_config::*;
+use registry::*;
 use staking::*;
 use states::*;
+#[cfg(feature = "vault")]
 use vault::*;
 
 declare_id!("3e2igyWExmDZmJfRpMRwn5mrM838Fam3AMzPYvttxRT8");
 
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: env!("SECURITY_TXT_NAME"),
+    project_url: env!("SECURITY_TXT_PROJECT_URL"),
+    contacts: env!("SECURITY_TXT_CONTACTS"),
+    policy: env!("SECURITY_TXT_POLICY"),
+    source_code: env!("SECURITY_TXT_SOURCE_CODE")
+}
+
 #[program]
 /// This module contains the implementation of the Miming Spoke Solana program.
 ///
@@ -75,16 +218,298 @@ declare_id!("3e2igyWExmDZmJfRpMRwn5mrM838Fam3AMzPYvttxRT8");
 pub mod miming_spoke_solana {
     use super::*;
 
-    /// Initializes a new multisig account.
+    /// Bootstraps a new deployment at genesis.
+    ///
+    /// This function calls the `initialize_program` function from the `genesis::GenesisInstructions` module
+    /// to atomically create the multisig, the program configuration, the vault's identifiers, and the staking
+    /// configuration, signed by the program's upgrade authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramInitialization` instruction.
+    /// * `params` - The initial council and program configuration values.
+    pub fn initialize_program(ctx: Context<ProgramInitialization>, params: GenesisParams) -> Result<()> {
+        genesis::GenesisInstructions::initialize_program(ctx, params)
+    }
+
+    /// Creates the program registry.
+    ///
+    /// This function calls the `initialize` function from the `registry::RegistryInstructions` module
+    /// to create the empty directory of the program's singleton PDAs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RegistryInitialization` instruction.
+    pub fn registry_initialize(ctx: Context<RegistryInitialization>) -> Result<()> {
+        registry::RegistryInstructions::initialize(ctx)
+    }
+
+    /// Records a singleton PDA in the program registry.
+    ///
+    /// This function calls the `register` function from the `registry::RegistryInstructions` module
+    /// to list the account under its type tag for indexers.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RegistryRegister` instruction.
+    /// * `kind` - The type tag of the account.
+    pub fn registry_register(ctx: Context<RegistryRegister>, kind: ProgramRegistryKind) -> Result<()> {
+        registry::RegistryInstructions::register(ctx, kind)
+    }
+
+    /// Records the hub's state root at a new height.
+    ///
+    /// This function calls the `submit_root` function from the `light_client::LightClientInstructions` module
+    /// to record a root submitted by a quorum of the council, keeping the most recent ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `LightClientSubmitRoot` instruction.
+    /// * `height` - The hub height the root was committed at.
+    /// * `root` - The hub's state root at that height.
+    pub fn light_client_submit_root(ctx: Context<LightClientSubmitRoot>, height: u64, root: [u8; 32]) -> Result<()> {
+        light_client::LightClientInstructions::submit_root(ctx, height, root)
+    }
+
+    /// Verifies a merkle proof against a recorded hub state root.
+    ///
+    /// This function calls the `verify_proof` function from the `light_client::LightClientInstructions` module
+    /// to check that a claim is included in the hub's state at the given height.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `LightClientVerifyProof` instruction.
+    /// * `height` - The hub height of the root the proof was built against.
+    /// * `leaf` - The claim's data, hashed as the proof's leaf.
+    /// * `proof` - The sibling hashes from the leaf up to the root.
+    pub fn light_client_verify_proof(
+        ctx: Context<LightClientVerifyProof>,
+        height: u64,
+        leaf: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        light_client::LightClientInstructions::verify_proof(ctx, height, leaf, proof)
+    }
+
+    /// Opens the signer's replay protection nonce account.
+    ///
+    /// This function calls the `initialize` function from the `nonce::NonceInstructions` module
+    /// to create the nonce account checked by instructions given an `expected_nonce`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `NonceInitialization` instruction.
+    pub fn nonce_initialize(ctx: Context<NonceInitialization>) -> Result<()> {
+        nonce::NonceInstructions::initialize(ctx)
+    }
+
+    /// Executes an approved program configuration proposal.
+    ///
+    /// This function calls the `update` function from the `program_config::ProgramConfigInstructions` module
+    /// to change the module switches and teleport settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigUpdate` instruction.
+    pub fn program_config_update(ctx: Context<ProgramConfigUpdate>) -> Result<()> {
+        program_config::ProgramConfigInstructions::update(ctx)
+    }
+
+    /// Halts the program through the guardian's emergency stop.
+    ///
+    /// This function calls the `emergency_pause` function from the `program_config::ProgramConfigInstructions` module
+    /// to reject every user-facing and permissionless handler until the multisig resumes the program.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigEmergencyPause` instruction.
+    pub fn program_config_emergency_pause(ctx: Context<ProgramConfigEmergencyPause>) -> Result<()> {
+        program_config::ProgramConfigInstructions::emergency_pause(ctx)
+    }
+
+    /// Executes an approved proposal lifting the emergency stop.
+    ///
+    /// This function calls the `resume` function from the `program_config::ProgramConfigInstructions` module
+    /// to clear the emergency stop flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigResume` instruction.
+    pub fn program_config_resume(ctx: Context<ProgramConfigResume>) -> Result<()> {
+        program_config::ProgramConfigInstructions::resume(ctx)
+    }
+
+    /// Executes an approved proposal configuring the CPI guard.
+    ///
+    /// This function calls the `configure_cpi_guard` function from the `program_config::ProgramConfigInstructions` module
+    /// to toggle the guard and replace the programs allowed to call the treasury paths through CPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureCpiGuard` instruction.
+    pub fn program_config_configure_cpi_guard(ctx: Context<ProgramConfigConfigureCpiGuard>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_cpi_guard(ctx)
+    }
+
+    /// Executes an approved proposal appointing the operator or relayer.
+    ///
+    /// This function calls the `configure_role` function from the `program_config::ProgramConfigInstructions` module
+    /// to record the role's new holder, or clear it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureRole` instruction.
+    pub fn program_config_configure_role(ctx: Context<ProgramConfigConfigureRole>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_role(ctx)
+    }
+
+    /// Executes an approved proposal setting the hub's Wormhole emitter.
+    ///
+    /// This function calls the `configure_hub_emitter` function from the `program_config::ProgramConfigInstructions` module
+    /// to record the chain and emitter address whose messages inbound releases accept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureHubEmitter` instruction.
+    pub fn program_config_configure_hub_emitter(ctx: Context<ProgramConfigConfigureHubEmitter>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_hub_emitter(ctx)
+    }
+
+    /// Executes an approved proposal switching the cross-chain messaging transport.
+    ///
+    /// This function calls the `configure_messaging` function from the `program_config::ProgramConfigInstructions` module
+    /// to record the transport teleports and releases travel over, and its endpoint program.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureMessaging` instruction.
+    pub fn program_config_configure_messaging(ctx: Context<ProgramConfigConfigureMessaging>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_messaging(ctx)
+    }
+
+    /// Executes an approved proposal setting the teleport fee in USD cents.
+    ///
+    /// This function calls the `configure_usd_fee` function from the `program_config::ProgramConfigInstructions` module
+    /// to record the USD fee and the Pyth SOL/USD feed it is converted with.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureUsdFee` instruction.
+    pub fn program_config_configure_usd_fee(ctx: Context<ProgramConfigConfigureUsdFee>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_usd_fee(ctx)
+    }
+
+    /// Executes an approved proposal setting the Switchboard feed backing the Pyth SOL/USD feed.
+    ///
+    /// This function calls the `configure_oracle_fallback` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the fallback feed and the divergence allowed between the two prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureOracleFallback` instruction.
+    pub fn program_config_configure_oracle_fallback(ctx: Context<ProgramConfigConfigureOracleFallback>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_oracle_fallback(ctx)
+    }
+
+    /// Executes an approved proposal setting the deposit receipt threshold and metadata URI prefix.
+    ///
+    /// This function calls the `configure_deposit_receipts` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the teleport amount from which depositors can mint a receipt NFT.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureDepositReceipts` instruction.
+    pub fn program_config_configure_deposit_receipts(ctx: Context<ProgramConfigConfigureDepositReceipts>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_deposit_receipts(ctx)
+    }
+
+    /// Executes an approved proposal registering the Squads vault that approves vault transfer executions.
+    ///
+    /// This function calls the `configure_squads_authority` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the Squads multisig, its vault index, and the vault address derived from them.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureSquadsAuthority` instruction.
+    pub fn program_config_configure_squads_authority(ctx: Context<ProgramConfigConfigureSquadsAuthority>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_squads_authority(ctx)
+    }
+
+    /// Executes an approved proposal registering the Realms governance that acts for the council.
+    ///
+    /// This function calls the `configure_realms_governance` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the SPL Governance governance account and the realm it belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureRealmsGovernance` instruction.
+    pub fn program_config_configure_realms_governance(
+        ctx: Context<ProgramConfigConfigureRealmsGovernance>,
+    ) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_realms_governance(ctx)
+    }
+
+    /// Executes an approved proposal registering the Bubblegum tree compressed deposit receipts are minted into.
+    ///
+    /// This function calls the `configure_compressed_receipts` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the tree, checked to be delegated to the deposit receipt authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureCompressedReceipts` instruction.
+    pub fn program_config_configure_compressed_receipts(
+        ctx: Context<ProgramConfigConfigureCompressedReceipts>,
+    ) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_compressed_receipts(ctx)
+    }
+
+    /// Executes an approved proposal setting the hub registry's hub and supported teleport routes.
+    ///
+    /// This function calls the `configure_hub_registry` function from the `program_config::ProgramConfigInstructions`
+    /// module to replace the hub's chain and emitter and the routes, each with its teleport limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureHubRegistry` instruction.
+    pub fn program_config_configure_hub_registry(ctx: Context<ProgramConfigConfigureHubRegistry>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_hub_registry(ctx)
+    }
+
+    /// Executes an approved proposal setting the relayer fee.
+    ///
+    /// This function calls the `configure_relayer_fee` function from the `program_config::ProgramConfigInstructions`
+    /// module to record the share of relayed transfers paid to the wallet submitting them.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProgramConfigConfigureRelayerFee` instruction.
+    pub fn program_config_configure_relayer_fee(ctx: Context<ProgramConfigConfigureRelayerFee>) -> Result<()> {
+        program_config::ProgramConfigInstructions::configure_relayer_fee(ctx)
+    }
+
+    /// Migrates the program configuration created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `ProgramConfigAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    pub fn program_config_migrate(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<ProgramConfigAccount>(ctx, seeds, EventModule::ProgramConfig, "program_config_migrate")
+    }
+
+    /// Returns a view of the program configuration.
     ///
-    /// This function calls the `initialize` function from the `multisig::MultisigInstructions` module 
-    /// to perform the initialization.
+    /// This function calls the `get_settings` function from the `program_config::ProgramConfigInstructions` module
+    /// to read the configuration's authorities, module switches, teleport settings, and CPI guard.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `MultisigInitialization` instruction.
-    pub fn multisig_initialize(ctx: Context<MultisigInitialization>) -> Result<()> {
-        multisig::MultisigInstructions::initialize(ctx)
+    /// * `ctx` - The context for the `ProgramConfigGetSettings` instruction.
+    pub fn program_config_get_settings(ctx: Context<ProgramConfigGetSettings>) -> Result<ProgramConfigView> {
+        program_config::ProgramConfigInstructions::get_settings(ctx)
     }
 
     /// Creates a new proposal for a multisig account.
@@ -98,13 +523,18 @@ pub mod miming_spoke_solana {
     /// * `name` - The name of the proposal.
     /// * `threshold` - The number of approvals required for the proposal to be executed.
     /// * `signers` - The list of signers for the proposal.
+    /// * `mode` - The approval mode of the proposed multisig.
+    /// * `min_approval_weight` - The staking voting weight required to approve proposals in stake-weighted mode.
+    #[cfg(feature = "multisig")]
     pub fn multisig_create_proposal(
         ctx: Context<MultisigCreateProposal>,
         name: String,
         threshold: u8,
         signers: Vec<MultisigSigners>,
+        mode: MultisigMode,
+        min_approval_weight: u64,
     ) -> Result<()> {
-        multisig::MultisigInstructions::create_proposal(ctx, name, threshold, signers)
+        multisig::MultisigInstructions::create_proposal(ctx, name, threshold, signers, mode, min_approval_weight)
     }
 
     /// Signs a proposal for a multisig account.
@@ -115,8 +545,45 @@ pub mod miming_spoke_solana {
     /// # Arguments
     ///
     /// * `ctx` - The context for the `MultisigSignProposal` instruction.
-    pub fn multisig_sign_proposal(ctx: Context<MultisigSignProposal>) -> Result<()> {
-        multisig::MultisigInstructions::sign_proposal(ctx)
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_sign_proposal(ctx: Context<MultisigSignProposal>, expected_nonce: Option<u64>) -> Result<()> {
+        multisig::MultisigInstructions::sign_proposal(ctx, expected_nonce)
+    }
+
+    /// Creates a new proposal for a multisig account and signs it.
+    ///
+    /// This function calls the `create_and_sign_proposal` function from the `multisig::MultisigInstructions` module
+    /// to create the proposal and record the proposer's signature in the same transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `MultisigCreateAndSignProposal` instruction.
+    /// * `name` - The name of the proposal.
+    /// * `threshold` - The number of approvals required for the proposal to be executed.
+    /// * `signers` - The list of signers for the proposal.
+    /// * `mode` - The approval mode of the proposed multisig.
+    /// * `min_approval_weight` - The staking voting weight required to approve proposals in stake-weighted mode.
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_create_and_sign_proposal(
+        ctx: Context<MultisigCreateAndSignProposal>,
+        name: String,
+        threshold: u8,
+        signers: Vec<MultisigSigners>,
+        mode: MultisigMode,
+        min_approval_weight: u64,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        multisig::MultisigInstructions::create_and_sign_proposal(
+            ctx,
+            name,
+            threshold,
+            signers,
+            mode,
+            min_approval_weight,
+            expected_nonce,
+        )
     }
 
     /// Approves a proposal for a multisig account.
@@ -127,20 +594,69 @@ pub mod miming_spoke_solana {
     /// # Arguments
     ///
     /// * `ctx` - The context for the `MultisigApproveProposal` instruction.
-    pub fn multisig_approve_proposal(ctx: Context<MultisigApproveProposal>) -> Result<()> {
-        multisig::MultisigInstructions::approve_proposal(ctx)
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    /// * `dry_run` - Whether to only simulate the approval: every check runs and a `MultisigApprovalPreviewEvent` is
+    ///   emitted, then the instruction fails with `DryRunCompleted`.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_approve_proposal(
+        ctx: Context<MultisigApproveProposal>,
+        expected_nonce: Option<u64>,
+        dry_run: bool,
+    ) -> Result<()> {
+        multisig::MultisigInstructions::approve_proposal(ctx, expected_nonce, dry_run)
+    }
+
+    /// Migrates the multisig account created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `MultisigAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_migrate_account(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<MultisigAccount>(ctx, seeds, EventModule::Multisig, "multisig_migrate_account")
+    }
+
+    /// Closes an approved multisig proposal.
+    ///
+    /// This function calls the `close_proposal` function from the `multisig::MultisigInstructions` module
+    /// to refund the proposal's rent to the configured rent recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `MultisigCloseProposal` instruction.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_close_proposal(ctx: Context<MultisigCloseProposal>) -> Result<()> {
+        multisig::MultisigInstructions::close_proposal(ctx)
+    }
+
+    /// Converts a multisig proposal created with the older serialized layout.
+    ///
+    /// This function calls the `migrate_proposal` function from the `multisig::MultisigInstructions` module
+    /// to rewrite the `MultisigProposalAccount` in its zero-copy layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `MultisigMigrateProposal` instruction.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_migrate_proposal(ctx: Context<MultisigMigrateProposal>) -> Result<()> {
+        multisig::MultisigInstructions::migrate_proposal(ctx)
     }
 
-    /// Initializes a new vault account.
+    /// Returns a view of the multisig.
     ///
-    /// This function calls the `initialize` function from the `vault::VaultInitializationInstructions` module 
-    /// to perform the initialization.
+    /// This function calls the `get_state` function from the `multisig::MultisigInstructions` module
+    /// to read the multisig's name, threshold, signers, mode, and next proposal id.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `VaultInitialization` instruction.
-    pub fn vault_initialize(ctx: Context<VaultInitialization>) -> Result<()> {
-        vault::VaultInitializationInstructions::initialize(ctx)
+    /// * `ctx` - The context for the `MultisigGetState` instruction.
+    #[cfg(feature = "multisig")]
+    pub fn multisig_get_state(ctx: Context<MultisigGetState>) -> Result<MultisigStateView> {
+        multisig::MultisigInstructions::get_state(ctx)
     }
 
     /// Teleports tokens from a vault.
@@ -152,8 +668,127 @@ pub mod miming_spoke_solana {
     ///
     /// * `ctx` - The context for the `VaultTeleport` instruction.
     /// * `amount` - The amount of tokens to teleport.
-    pub fn vault_teleport(ctx: Context<VaultTeleport>, amount: u64) -> Result<()> {
-        vault::VaultTeleportInstructions::teleport(ctx, amount)
+    /// * `destination_chain` - The Wormhole chain id the teleport is bound for, or zero to pick it when posting.
+    /// * `destination` - The recipient on the destination chain, as a 32-byte Wormhole address.
+    #[cfg(feature = "vault")]
+    pub fn vault_teleport(
+        ctx: Context<VaultTeleport>,
+        amount: u64,
+        destination_chain: u16,
+        destination: [u8; 32],
+    ) -> Result<()> {
+        vault::VaultTeleportInstructions::teleport(ctx, amount, destination_chain, destination)
+    }
+
+    /// Posts a recorded teleport to the hub chain.
+    ///
+    /// This function calls the `post_teleport` function from the `vault::VaultTeleportInstructions` module
+    /// to post the teleport as a Wormhole message and store its sequence on the ledger entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultPostTeleport` instruction.
+    /// * `destination_chain` - The Wormhole chain id the teleport is bound for, as committed by the teleport if it did.
+    /// * `destination` - The recipient on the destination chain, as a 32-byte Wormhole address.
+    #[cfg(feature = "vault")]
+    pub fn vault_post_teleport(
+        ctx: Context<VaultPostTeleport>,
+        destination_chain: u16,
+        destination: [u8; 32],
+    ) -> Result<()> {
+        vault::VaultTeleportInstructions::post_teleport(ctx, destination_chain, destination)
+    }
+
+    /// Mints a receipt NFT for a teleport at or above the receipt threshold.
+    ///
+    /// This function calls the `mint_deposit_receipt` function from the `vault::VaultTeleportInstructions` module
+    /// to mint a one-of-one Metaplex NFT carrying the ledger id and receipt hash to the depositor.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultMintDepositReceipt` instruction.
+    #[cfg(feature = "vault")]
+    pub fn vault_mint_deposit_receipt(ctx: Context<VaultMintDepositReceipt>) -> Result<()> {
+        vault::VaultTeleportInstructions::mint_deposit_receipt(ctx)
+    }
+
+    /// Creates a Bubblegum tree for compressed deposit receipts.
+    ///
+    /// This function calls the `create_receipt_tree` function from the `vault::VaultTeleportInstructions` module
+    /// to create a private tree whose creator and delegate is the deposit receipt authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultCreateReceiptTree` instruction.
+    /// * `max_depth` - The tree's depth.
+    /// * `max_buffer_size` - The tree's concurrent change buffer size.
+    #[cfg(feature = "vault")]
+    pub fn vault_create_receipt_tree(
+        ctx: Context<VaultCreateReceiptTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        vault::VaultTeleportInstructions::create_receipt_tree(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Mints a compressed receipt NFT for a teleport at or above the receipt threshold.
+    ///
+    /// This function calls the `mint_compressed_deposit_receipt` function from the `vault::VaultTeleportInstructions`
+    /// module to mint a Bubblegum leaf carrying the ledger id and receipt hash to the depositor.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultMintCompressedDepositReceipt` instruction.
+    #[cfg(feature = "vault")]
+    pub fn vault_mint_compressed_deposit_receipt(ctx: Context<VaultMintCompressedDepositReceipt>) -> Result<()> {
+        vault::VaultTeleportInstructions::mint_compressed_deposit_receipt(ctx)
+    }
+
+    /// Completes a release sent by the hub chain.
+    ///
+    /// This function calls the `complete_inbound` function from the `vault::VaultTeleportInstructions` module
+    /// to pay out a release from a VAA verified by the Wormhole core bridge and record it in the ledger.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultCompleteInbound` instruction.
+    /// * `vaa_hash` - The hash of the VAA's body, which the posted VAA's address is derived from.
+    /// * `sequence` - The VAA's sequence, marked as consumed by the inbound receipt.
+    /// * `height` - The hub height of the state root the proof was built against.
+    /// * `proof` - The sibling hashes from the release's leaf up to the root.
+    #[cfg(feature = "vault")]
+    pub fn vault_complete_inbound(
+        ctx: Context<VaultCompleteInbound>,
+        vaa_hash: [u8; 32],
+        sequence: u64,
+        height: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        vault::VaultTeleportInstructions::complete_inbound(ctx, vaa_hash, sequence, height, proof)
+    }
+
+    /// Mints MIMING for a teleport sent from the hub chain.
+    ///
+    /// This function calls the `complete_inbound_mint` function from the `vault::VaultTeleportInstructions` module
+    /// to mint a teleport from a VAA verified by the Wormhole core bridge, record it in the ledger, and add it to the
+    /// bridged supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultCompleteInboundMint` instruction.
+    /// * `vaa_hash` - The hash of the VAA's body, which the posted VAA's address is derived from.
+    /// * `sequence` - The VAA's sequence, marked as consumed by the inbound receipt.
+    /// * `height` - The hub height of the state root the proof was built against.
+    /// * `proof` - The sibling hashes from the teleport's leaf up to the root.
+    #[cfg(feature = "vault")]
+    pub fn vault_complete_inbound_mint(
+        ctx: Context<VaultCompleteInboundMint>,
+        vaa_hash: [u8; 32],
+        sequence: u64,
+        height: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        vault::VaultTeleportInstructions::complete_inbound_mint(ctx, vaa_hash, sequence, height, proof)
     }
 
     /// Creates a new transfer proposal from a vault.
@@ -166,6 +801,7 @@ pub mod miming_spoke_solana {
     /// * `ctx` - The context for the `VaultCreateTransferProposal` instruction.
     /// * `recipient` - The public key of the recipient who will receive the tokens.
     /// * `amount` - The amount of tokens to be transferred in the proposal.
+    #[cfg(feature = "vault")]
     pub fn vault_create_transfer_proposal(
         ctx: Context<VaultCreateTransferProposal>,
         recipient: Pubkey,
@@ -182,8 +818,35 @@ pub mod miming_spoke_solana {
     /// # Arguments
     ///
     /// * `ctx` - The context for the `VaultSignTransferProposal` instruction.
-    pub fn vault_sign_transfer_proposal(ctx: Context<VaultSignTransferProposal>) -> Result<()> {
-        vault::VaultTransferProposalInstructions::sign_transfer_proposal(ctx)
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "vault")]
+    pub fn vault_sign_transfer_proposal(
+        ctx: Context<VaultSignTransferProposal>,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        vault::VaultTransferProposalInstructions::sign_transfer_proposal(ctx, expected_nonce)
+    }
+
+    /// Creates a new transfer proposal from a vault and signs it.
+    ///
+    /// This function calls the `create_and_sign_transfer_proposal` function from the
+    /// `vault::VaultTransferProposalInstructions` module to create a transfer proposal and record the proposer's
+    /// signature in the same transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultCreateAndSignTransferProposal` instruction.
+    /// * `recipient` - The public key of the recipient who will receive the tokens.
+    /// * `amount` - The amount of tokens to be transferred in the proposal.
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "vault")]
+    pub fn vault_create_and_sign_transfer_proposal(
+        ctx: Context<VaultCreateAndSignTransferProposal>,
+        recipient: Pubkey,
+        amount: u64,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        vault::VaultTransferProposalInstructions::create_and_sign_transfer_proposal(ctx, recipient, amount, expected_nonce)
     }
 
     // Executes a transfer proposal from a vault.
@@ -194,63 +857,1160 @@ pub mod miming_spoke_solana {
     /// # Arguments
     ///
     /// * `ctx` - The context for the `VaultExecuteTransferProposal` instruction.
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    /// * `dry_run` - Whether to only simulate the transfer: every check runs and a `VaultTransferPreviewEvent` is
+    ///   emitted, then the instruction fails with `DryRunCompleted`.
+    #[cfg(feature = "vault")]
     pub fn vault_execute_transfer_proposal(
         ctx: Context<VaultExecuteTransferProposal>,
+        expected_nonce: Option<u64>,
+        dry_run: bool,
     ) -> Result<()> {
-        vault::VaultTransferProposalInstructions::execute_transfer_proposal(ctx)
+        vault::VaultTransferProposalInstructions::execute_transfer_proposal(ctx, expected_nonce, dry_run)
     }
 
-    /// Freezes a staking account.
+    /// Closes an approved vault transfer proposal.
     ///
-    /// This function calls the `freeze` function from the `staking::StakingInstructions` module
-    /// to freeze the account.
+    /// This function calls the `close_transfer_proposal` function from the `vault::VaultTransferProposalInstructions` module
+    /// to refund the proposal's rent to the configured rent recipient.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `StakingFreeze` instruction.
-    /// * `reference_number` - The reference number for the freeze operation.
-    pub fn staking_freeze(ctx: Context<StakingFreeze>, reference_number: String) -> Result<()> {
-        staking::StakingInstructions::freeze(ctx, reference_number)
+    /// * `ctx` - The context for the `VaultCloseTransferProposal` instruction.
+    #[cfg(feature = "vault")]
+    pub fn vault_close_transfer_proposal(ctx: Context<VaultCloseTransferProposal>) -> Result<()> {
+        vault::VaultTransferProposalInstructions::close_transfer_proposal(ctx)
     }
 
-    /// Thaws a staking account.
+    /// Closes a vault ledger entry that has fallen out of the retention window.
     ///
-    /// This function calls the `thaw` function from the `staking::StakingInstructions` module
-    /// to thaw the account.
+    /// This function calls the `close_ledger` function from the `vault::VaultLedgerInstructions` module
+    /// to refund the entry's rent to the configured rent recipient.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `StakingThaw` instruction.
-    pub fn staking_thaw(ctx: Context<StakingThaw>) -> Result<()> {
-        staking::StakingInstructions::thaw(ctx)
+    /// * `ctx` - The context for the `VaultCloseLedger` instruction.
+    #[cfg(feature = "vault")]
+    pub fn vault_close_ledger(ctx: Context<VaultCloseLedger>) -> Result<()> {
+        vault::VaultLedgerInstructions::close_ledger(ctx)
     }
-}
 
-#[account]
-/// Stores a unique identifier for account management.
-///
-/// The `IdentifierAccount` struct is an on-chain account that holds a single `u64` identifier.
-/// This account can be used to track or reference unique entities within the program, such as
-/// for indexing, mapping, or associating data with a specific ID.
-///
-/// ## Fields
-///
-/// - `id` - A 64-bit unsigned integer representing the unique identifier.
-///
-/// ## Size
-///
-/// The total size of the account is defined by `IdentifierAccount::LEN`, which includes
-/// the Anchor account discriminator and the size of the `u64` field.
+    /// Migrates a vault ledger entry created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `VaultLedgerAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "vault")]
+    pub fn vault_migrate_ledger(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<VaultLedgerAccount>(ctx, seeds, EventModule::Vault, "vault_migrate_ledger")
+    }
+
+    /// Migrates a vault transfer proposal created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `VaultTransferProposalAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "vault")]
+    pub fn vault_migrate_transfer_proposal(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<VaultTransferProposalAccount>(ctx, seeds, EventModule::Vault, "vault_migrate_transfer_proposal")
+    }
+
+    /// Returns a view of the vault.
+    ///
+    /// This function calls the `get_status` function from the `vault::VaultLedgerInstructions` module
+    /// to read the vault's balance, switches, teleport fee, and next ledger and transfer proposal ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `VaultGetStatus` instruction.
+    #[cfg(feature = "vault")]
+    pub fn vault_get_status(ctx: Context<VaultGetStatus>) -> Result<VaultStatusView> {
+        vault::VaultLedgerInstructions::get_status(ctx)
+    }
+
+    /// Freezes a staking account.
+    ///
+    /// This function calls the `freeze` function from the `staking::StakingInstructions` module
+    /// to freeze the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingFreeze` instruction.
+    /// * `position_index` - The index of the staker's stake position.
+    /// * `reference_number` - The reference number for the freeze operation.
+    /// * `lock_duration` - The number of seconds the stake stays locked.
+    /// * `referrer` - The wallet that referred the staker, if any.
+    #[cfg(feature = "staking")]
+    pub fn staking_freeze(
+        ctx: Context<StakingFreeze>,
+        position_index: u64,
+        reference_number: String,
+        lock_duration: i64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        staking::StakingInstructions::freeze(ctx, position_index, reference_number, lock_duration, referrer)
+    }
+
+    /// Thaws a staking account.
+    ///
+    /// This function calls the `thaw` function from the `staking::StakingInstructions` module
+    /// to thaw the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingThaw` instruction.
+    /// * `early_exit` - Whether to thaw a still-locked stake by forfeiting its accrued rewards.
+    /// * `attestation_expires_at` - The expiry signed by the attestor, if thaws require an attestation.
+    /// * `expected_nonce` - The staker's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "staking")]
+    pub fn staking_thaw(
+        ctx: Context<StakingThaw>,
+        early_exit: bool,
+        attestation_expires_at: Option<i64>,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        staking::StakingInstructions::thaw(ctx, early_exit, attestation_expires_at, expected_nonce)
+    }
+
+    /// Claims accrued staking rewards.
+    ///
+    /// This function calls the `claim_rewards` function from the `staking::StakingInstructions` module
+    /// to transfer the staker's accrued rewards from the reward vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingClaimRewards` instruction.
+    /// * `compound` - Whether to add the rewards to the escrow stake instead of transferring them out.
+    #[cfg(feature = "staking")]
+    pub fn staking_claim_rewards(ctx: Context<StakingClaimRewards>, compound: bool) -> Result<()> {
+        staking::StakingInstructions::claim_rewards(ctx, compound)
+    }
+
+    /// Freezes several stake positions at once.
+    ///
+    /// This function calls the `batch_freeze` function from the `staking::StakingInstructions` module
+    /// to freeze each position's token account, with the positions' accounts passed as remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingBatchFreeze` instruction.
+    /// * `positions` - The position index, reference number, and lock duration of each position.
+    #[cfg(feature = "staking")]
+    pub fn staking_batch_freeze<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakingBatchFreeze<'info>>,
+        positions: Vec<StakingBatchFreezeEntry>,
+    ) -> Result<()> {
+        staking::StakingInstructions::batch_freeze(ctx, positions)
+    }
+
+    /// Thaws several stake positions at once.
+    ///
+    /// This function calls the `batch_thaw` function from the `staking::StakingInstructions` module
+    /// to thaw each position's token account, with the positions' accounts passed as remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingBatchThaw` instruction.
+    /// * `early_exit` - Whether to release still-locked stakes by forfeiting their accrued rewards.
+    #[cfg(feature = "staking")]
+    pub fn staking_batch_thaw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakingBatchThaw<'info>>,
+        early_exit: bool,
+    ) -> Result<()> {
+        staking::StakingInstructions::batch_thaw(ctx, early_exit)
+    }
+
+    /// Migrates a stake position's registry to the current layout.
+    ///
+    /// This function calls the `migrate_registry` function from the `staking::StakingInstructions` module
+    /// to reallocate a legacy registry and backfill its new fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingMigrateRegistry` instruction.
+    /// * `position_index` - The index of the stake position.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_registry(ctx: Context<StakingMigrateRegistry>, position_index: u64) -> Result<()> {
+        staking::StakingInstructions::migrate_registry(ctx, position_index)
+    }
+
+    /// Migrates the staking configuration to the current layout.
+    ///
+    /// This function calls the `migrate_config` function from the `staking::StakingInstructions` module
+    /// to reallocate a configuration created with an older layout and backfill its new fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingMigrateConfig` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_config(ctx: Context<StakingMigrateConfig>) -> Result<()> {
+        staking::StakingInstructions::migrate_config(ctx)
+    }
+
+    /// Migrates a staker's user account created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingUserAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_user(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingUserAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_user")
+    }
+
+    /// Migrates a referrer's statistics account created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingReferrerAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_referrer(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingReferrerAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_referrer")
+    }
+
+    /// Migrates the zero-copy staking pool statistics created with an older layout.
+    ///
+    /// This function calls the `migrate_pool` function from the `staking::StakingInstructions` module
+    /// to stamp the `StakingPoolAccount` in place with its current layout version.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingMigratePool` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_pool(ctx: Context<StakingMigratePool>) -> Result<()> {
+        staking::StakingInstructions::migrate_pool(ctx)
+    }
+
+    /// Migrates an epoch snapshot created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingEpochSnapshotAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_epoch_snapshot(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingEpochSnapshotAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_epoch_snapshot")
+    }
+
+    /// Migrates an outbound staking report created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingReportAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_report(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingReportAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_report")
+    }
+
+    /// Migrates an epoch's SOL reward distribution created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingSolDistributionAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_sol_distribution(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingSolDistributionAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_sol_distribution")
+    }
+
+    /// Migrates a SOL reward claim receipt created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingSolClaimAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_sol_claim(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingSolClaimAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_sol_claim")
+    }
+
+    /// Migrates a mint's unstake queue created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingUnstakeQueueAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_unstake_queue(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingUnstakeQueueAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_unstake_queue")
+    }
+
+    /// Migrates the pair staking SOL vault created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingPairVaultAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_pair_vault(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingPairVaultAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_pair_vault")
+    }
+
+    /// Migrates the reward schedule created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingRewardScheduleAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_reward_schedule(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingRewardScheduleAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_reward_schedule")
+    }
+
+    /// Migrates a mint's staking configuration created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingMintConfigAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_mint_config(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingMintConfigAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_mint_config")
+    }
+
+    /// Migrates a validator allowlist entry created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingValidatorAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_validator(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingValidatorAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_validator")
+    }
+
+    /// Migrates a custodian allowlist entry created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingCustodianAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_custodian(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingCustodianAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_custodian")
+    }
+
+    /// Migrates a vault stake delegation created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingVaultDelegationAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_vault_delegation(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingVaultDelegationAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_vault_delegation")
+    }
+
+    /// Migrates a staking proposal created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingProposalAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "multisig")]
+    pub fn staking_migrate_proposal(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingProposalAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_proposal")
+    }
+
+    /// Migrates a slash dispute created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `StakingSlashDisputeAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    #[cfg(feature = "staking")]
+    pub fn staking_migrate_slash_dispute(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<StakingSlashDisputeAccount>(ctx, seeds, EventModule::Staking, "staking_migrate_slash_dispute")
+    }
+
+    /// Funds the staking reward vault.
+    ///
+    /// This function calls the `fund_rewards` function from the `staking::StakingInstructions` module
+    /// to transfer reward tokens into the reward vault and add them to the reward budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingFundRewards` instruction.
+    /// * `amount` - The amount of reward tokens to fund.
+    #[cfg(feature = "staking")]
+    pub fn staking_fund_rewards(ctx: Context<StakingFundRewards>, amount: u64) -> Result<()> {
+        staking::StakingInstructions::fund_rewards(ctx, amount)
+    }
+
+    /// Returns a staker's voting power.
+    ///
+    /// This function calls the `get_voting_power` function from the `staking::StakingInstructions` module
+    /// to compute the voting power of the stake positions passed as remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingGetVotingPower` instruction.
+    /// * `staker` - The public key of the staker.
+    #[cfg(feature = "staking")]
+    pub fn staking_get_voting_power(ctx: Context<StakingGetVotingPower>, staker: Pubkey) -> Result<u64> {
+        staking::StakingInstructions::get_voting_power(ctx, staker)
+    }
+
+    /// Returns a live view of a stake position.
+    ///
+    /// This function calls the `get_position` function from the `staking::StakingInstructions` module
+    /// to read the position's staked amount, unclaimed rewards, effective APR, and unlock time.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingGetPosition` instruction.
+    /// * `staker` - The public key of the staker.
+    /// * `position_index` - The index of the stake position.
+    #[cfg(feature = "staking")]
+    pub fn staking_get_position(
+        ctx: Context<StakingGetPosition>,
+        staker: Pubkey,
+        position_index: u64,
+    ) -> Result<StakingPositionView> {
+        staking::StakingInstructions::get_position(ctx, staker, position_index)
+    }
+
+    /// Returns a compact view of a stake position for CPI consumers.
+    ///
+    /// This function calls the `get_stake_info` function from the `staking::StakingInstructions` module
+    /// to read the position's staked amount, unlock time, multiplier, and slashed flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingGetStakeInfo` instruction.
+    /// * `staker` - The public key of the staker.
+    /// * `position_index` - The index of the stake position.
+    #[cfg(feature = "staking")]
+    pub fn staking_get_stake_info(
+        ctx: Context<StakingGetStakeInfo>,
+        staker: Pubkey,
+        position_index: u64,
+    ) -> Result<StakingStakeInfo> {
+        staking::StakingInstructions::get_stake_info(ctx, staker, position_index)
+    }
+
+    /// Returns a view of the staking configuration.
+    ///
+    /// This function calls the `get_config` function from the `staking::StakingInstructions` module
+    /// to read the configuration's amounts, rates, durations, and pause state.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingGetConfig` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_get_config(ctx: Context<StakingGetConfig>) -> Result<StakingConfigView> {
+        staking::StakingInstructions::get_config(ctx)
+    }
+
+    /// Records the staking totals for the current epoch.
+    ///
+    /// This function calls the `snapshot_epoch` function from the `staking::StakingInstructions` module
+    /// to copy the pool's total and per-tier stake into the epoch's snapshot account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingSnapshotEpoch` instruction.
+    /// * `epoch` - The current epoch.
+    #[cfg(feature = "staking")]
+    pub fn staking_snapshot_epoch(ctx: Context<StakingSnapshotEpoch>, epoch: u64) -> Result<()> {
+        staking::StakingInstructions::snapshot_epoch(ctx, epoch)
+    }
+
+    /// Reports staking totals to the hub.
+    ///
+    /// This function calls the `report` function from the `staking::StakingInstructions` module
+    /// to record the pool's total staked, staker count, and epoch into the next sequenced report account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingReport` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_report(ctx: Context<StakingReport>) -> Result<()> {
+        staking::StakingInstructions::report(ctx)
+    }
+
+    /// Applies the reward schedule.
+    ///
+    /// This function calls the `apply_reward_schedule` function from the `staking::StakingInstructions` module
+    /// to move the base reward rate to the reward schedule step in force for the current epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingApplyRewardSchedule` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_apply_reward_schedule(ctx: Context<StakingApplyRewardSchedule>) -> Result<()> {
+        staking::StakingInstructions::apply_reward_schedule(ctx)
+    }
+
+    /// Claims SOL staking rewards.
+    ///
+    /// This function calls the `claim_sol_rewards` function from the `staking::StakingInstructions` module
+    /// to transfer a stake position's pro-rata share of an epoch's SOL reward distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingClaimSolRewards` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_claim_sol_rewards(ctx: Context<StakingClaimSolRewards>) -> Result<()> {
+        staking::StakingInstructions::claim_sol_rewards(ctx)
+    }
+
+    /// Requests to unstake a stake position.
+    ///
+    /// This function calls the `request_unstake` function from the `staking::StakingInstructions` module
+    /// to start the unstake cooldown.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingRequestUnstake` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_request_unstake(ctx: Context<StakingRequestUnstake>) -> Result<()> {
+        staking::StakingInstructions::request_unstake(ctx)
+    }
+
+    /// Extends the lock of a stake position.
+    ///
+    /// This function calls the `extend_lock` function from the `staking::StakingInstructions` module
+    /// to push out the unlock time in exchange for a higher reward multiplier.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingExtendLock` instruction.
+    /// * `new_unlock_ts` - The Unix timestamp at which the stake will unlock.
+    #[cfg(feature = "staking")]
+    pub fn staking_extend_lock(ctx: Context<StakingExtendLock>, new_unlock_ts: i64) -> Result<()> {
+        staking::StakingInstructions::extend_lock(ctx, new_unlock_ts)
+    }
+
+    /// Closes a fully released stake position.
+    ///
+    /// This function calls the `close_position` function from the `staking::StakingInstructions` module
+    /// to close the position's registry and return its rent to the staker.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingClosePosition` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_close_position(ctx: Context<StakingClosePosition>) -> Result<()> {
+        staking::StakingInstructions::close_position(ctx)
+    }
+
+    /// Closes a SOL reward claim receipt once its distribution is fully claimed.
+    ///
+    /// This function calls the `close_sol_claim` function from the `staking::StakingInstructions` module
+    /// to close the receipt and return its rent to the staker.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingCloseSolClaim` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_close_sol_claim(ctx: Context<StakingCloseSolClaim>) -> Result<()> {
+        staking::StakingInstructions::close_sol_claim(ctx)
+    }
+
+    /// Closes an approved staking proposal.
+    ///
+    /// This function calls the `close_proposal` function from the `staking::StakingProposalInstructions` module
+    /// to refund the proposal's rent to the configured rent recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingCloseProposal` instruction.
+    #[cfg(feature = "multisig")]
+    pub fn staking_close_proposal(ctx: Context<StakingCloseProposal>) -> Result<()> {
+        staking::StakingProposalInstructions::close_proposal(ctx)
+    }
+
+    /// Closes a retired validator allowlist entry.
+    ///
+    /// This function calls the `close_validator` function from the `staking::StakingProposalInstructions` module
+    /// to refund the entry's rent to the configured rent recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingCloseValidator` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_close_validator(ctx: Context<StakingCloseValidator>) -> Result<()> {
+        staking::StakingProposalInstructions::close_validator(ctx)
+    }
+
+    /// Closes a retired custodian allowlist entry.
+    ///
+    /// This function calls the `close_custodian` function from the `staking::StakingProposalInstructions` module
+    /// to refund the entry's rent to the configured rent recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingCloseCustodian` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_close_custodian(ctx: Context<StakingCloseCustodian>) -> Result<()> {
+        staking::StakingProposalInstructions::close_custodian(ctx)
+    }
+
+    /// Stakes tokens in escrow.
+    ///
+    /// This function calls the `stake` function from the `staking::StakingEscrowInstructions` module
+    /// to transfer tokens into the program-owned stake vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingStake` instruction.
+    /// * `position_index` - The index of the staker's stake position.
+    /// * `reference_number` - The reference number for the stake.
+    /// * `amount` - The amount of tokens to stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked.
+    /// * `referrer` - The wallet that referred the staker, if any.
+    #[cfg(feature = "staking")]
+    pub fn staking_stake(
+        ctx: Context<StakingStake>,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        staking::StakingEscrowInstructions::stake(ctx, position_index, reference_number, amount, lock_duration, referrer)
+    }
+
+    /// Stakes tokens in escrow on behalf of a beneficiary wallet.
+    ///
+    /// This function calls the `stake_for` function from the `staking::StakingEscrowInstructions` module
+    /// to transfer an authorized custodian's tokens into the stake vault as the beneficiary's stake.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingStakeFor` instruction.
+    /// * `beneficiary` - The wallet the stake is made for.
+    /// * `position_index` - The index of the beneficiary's stake position.
+    /// * `reference_number` - The reference number for the stake.
+    /// * `amount` - The amount of tokens to stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked.
+    #[cfg(feature = "staking")]
+    pub fn staking_stake_for(
+        ctx: Context<StakingStakeFor>,
+        beneficiary: Pubkey,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        staking::StakingEscrowInstructions::stake_for(ctx, beneficiary, position_index, reference_number, amount, lock_duration)
+    }
+
+    /// Stakes tokens in escrow through a delegate approval.
+    ///
+    /// This function calls the `stake_delegated` function from the `staking::StakingEscrowInstructions` module
+    /// to transfer a staker's delegated tokens into the stake vault on a relayer's transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingStakeDelegated` instruction.
+    /// * `staker` - The wallet whose delegated tokens are staked.
+    /// * `position_index` - The index of the staker's stake position.
+    /// * `reference_number` - The reference number for the stake.
+    /// * `amount` - The amount of tokens to stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked.
+    #[cfg(feature = "staking")]
+    pub fn staking_stake_delegated(
+        ctx: Context<StakingStakeDelegated>,
+        staker: Pubkey,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        staking::StakingEscrowInstructions::stake_delegated(ctx, staker, position_index, reference_number, amount, lock_duration)
+    }
+
+    /// Unstakes tokens held in escrow.
+    ///
+    /// This function calls the `unstake` function from the `staking::StakingEscrowInstructions` module
+    /// to return part or all of the stake from the stake vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingUnstake` instruction.
+    /// * `amount` - The amount of tokens to unstake.
+    /// * `early_exit` - Whether to unstake from a still-locked stake by paying the early-exit penalty.
+    #[cfg(feature = "staking")]
+    pub fn staking_unstake(ctx: Context<StakingUnstake>, amount: u64, early_exit: bool) -> Result<()> {
+        staking::StakingEscrowInstructions::unstake(ctx, amount, early_exit)
+    }
+
+    /// Stakes a MIMING + SOL pair.
+    ///
+    /// This function calls the `stake_pair` function from the `staking::StakingEscrowInstructions` module
+    /// to lock the tokens in the stake vault and SOL at the configured ratio in the pair vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingStakePair` instruction.
+    /// * `position_index` - The index of the stake position to stake into.
+    /// * `reference_number` - A string identifier for the stake.
+    /// * `amount` - The amount of tokens to stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked.
+    #[cfg(feature = "staking")]
+    pub fn staking_stake_pair(
+        ctx: Context<StakingStakePair>,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        staking::StakingEscrowInstructions::stake_pair(ctx, position_index, reference_number, amount, lock_duration)
+    }
+
+    /// Unstakes a MIMING + SOL pair.
+    ///
+    /// This function calls the `unstake_pair` function from the `staking::StakingEscrowInstructions` module
+    /// to return the pair's tokens and SOL in full.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingUnstakePair` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_unstake_pair(ctx: Context<StakingUnstakePair>) -> Result<()> {
+        staking::StakingEscrowInstructions::unstake_pair(ctx)
+    }
+
+    /// Queues an unstake of tokens held in escrow.
+    ///
+    /// This function calls the `queue_unstake` function from the `staking::StakingEscrowInstructions` module
+    /// to add the unstake to the back of the mint's FIFO unstake queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingQueueUnstake` instruction.
+    /// * `amount` - The amount of tokens to unstake.
+    #[cfg(feature = "staking")]
+    pub fn staking_queue_unstake(ctx: Context<StakingQueueUnstake>, amount: u64) -> Result<()> {
+        staking::StakingEscrowInstructions::queue_unstake(ctx, amount)
+    }
+
+    /// Processes the head of an unstake queue.
+    ///
+    /// This function calls the `process_unstake_queue` function from the `staking::StakingEscrowInstructions` module
+    /// to pay out the oldest queued unstake once its ETA has passed and the stake vault can cover it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingProcessUnstakeQueue` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_process_unstake_queue(ctx: Context<StakingProcessUnstakeQueue>) -> Result<()> {
+        staking::StakingEscrowInstructions::process_unstake_queue(ctx)
+    }
+
+    /// Creates a new staking governance proposal.
+    ///
+    /// This function calls the `create_proposal` function from the `staking::StakingProposalInstructions` module
+    /// to create a proposal that must be signed by the multisig before its action can be executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingCreateProposal` instruction.
+    /// * `action` - The staking action the proposal authorizes.
+    #[cfg(feature = "multisig")]
+    pub fn staking_create_proposal(
+        ctx: Context<StakingCreateProposal>,
+        action: StakingProposalAction,
+    ) -> Result<()> {
+        staking::StakingProposalInstructions::create_proposal(ctx, action)
+    }
+
+    /// Signs a staking governance proposal.
+    ///
+    /// This function calls the `sign_proposal` function from the `staking::StakingProposalInstructions` module
+    /// to sign the proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingSignProposal` instruction.
+    /// * `expected_nonce` - The signer's current nonce, checked and advanced when given to guard against replays.
+    #[cfg(feature = "multisig")]
+    pub fn staking_sign_proposal(ctx: Context<StakingSignProposal>, expected_nonce: Option<u64>) -> Result<()> {
+        staking::StakingProposalInstructions::sign_proposal(ctx, expected_nonce)
+    }
+
+    /// Configures the staking mode of a mint.
+    ///
+    /// This function calls the `configure_mint` function from the `staking::StakingProposalInstructions` module
+    /// to execute an approved `ConfigureMint` proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingConfigureMint` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_configure_mint(ctx: Context<StakingConfigureMint>) -> Result<()> {
+        staking::StakingProposalInstructions::configure_mint(ctx)
+    }
+
+    /// Slashes a staker's escrow stake.
+    ///
+    /// This function calls the `slash` function from the `staking::StakingProposalInstructions` module
+    /// to execute an approved `Slash` proposal, moving the seized tokens to the insurance fund.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingSlash` instruction.
+    /// * `staker` - The public key of the staker being slashed.
+    /// * `amount` - The amount of staked tokens to seize.
+    /// * `evidence_hash` - The hash of the slashing evidence.
+    /// * `reason` - The reason for the slash.
+    #[cfg(feature = "staking")]
+    pub fn staking_slash(
+        ctx: Context<StakingSlash>,
+        staker: Pubkey,
+        amount: u64,
+        evidence_hash: [u8; 32],
+        reason: String,
+    ) -> Result<()> {
+        staking::StakingProposalInstructions::slash(ctx, staker, amount, evidence_hash, reason)
+    }
+
+    /// Disputes a slash proposal.
+    ///
+    /// This function calls the `dispute_slash` function from the `staking::StakingProposalInstructions` module
+    /// to record the slashed staker's counter-claim and escalate the approval the slash needs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingDisputeSlash` instruction.
+    /// * `counter_claim_hash` - The hash of the staker's counter-claim.
+    #[cfg(feature = "staking")]
+    pub fn staking_dispute_slash(ctx: Context<StakingDisputeSlash>, counter_claim_hash: [u8; 32]) -> Result<()> {
+        staking::StakingProposalInstructions::dispute_slash(ctx, counter_claim_hash)
+    }
+
+    /// Executes an approved staking emergency thaw proposal.
+    ///
+    /// This function calls the `emergency_thaw` function from the `staking::StakingProposalInstructions` module
+    /// to release a staker's freeze stake without their cooperation.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingEmergencyThaw` instruction.
+    /// * `staker` - The public key of the staker whose stake is released.
+    #[cfg(feature = "staking")]
+    pub fn staking_emergency_thaw(ctx: Context<StakingEmergencyThaw>, staker: Pubkey) -> Result<()> {
+        staking::StakingProposalInstructions::emergency_thaw(ctx, staker)
+    }
+
+    /// Executes an approved staking configuration update proposal.
+    ///
+    /// This function calls the `update_config` function from the `staking::StakingProposalInstructions` module
+    /// to change the staking configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingUpdateConfig` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_update_config(ctx: Context<StakingUpdateConfig>) -> Result<()> {
+        staking::StakingProposalInstructions::update_config(ctx)
+    }
+
+    /// Pauses or resumes staking.
+    ///
+    /// This function calls the `set_paused` function from the `staking::StakingProposalInstructions` module
+    /// to execute an approved proposal toggling the staking pause switch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingSetPaused` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_set_paused(ctx: Context<StakingSetPaused>) -> Result<()> {
+        staking::StakingProposalInstructions::set_paused(ctx)
+    }
+
+    /// Executes an approved thaw attestor configuration proposal.
+    ///
+    /// This function calls the `configure_attestor` function from the `staking::StakingProposalInstructions` module
+    /// to set or clear the thaw attestor.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingConfigureAttestor` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_configure_attestor(ctx: Context<StakingConfigureAttestor>) -> Result<()> {
+        staking::StakingProposalInstructions::configure_attestor(ctx)
+    }
+
+    /// Executes an approved vault delegation validator configuration proposal.
+    ///
+    /// This function calls the `configure_validator` function from the `staking::StakingProposalInstructions` module
+    /// to add a validator to or remove it from the vault delegation allowlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingConfigureValidator` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_configure_validator(ctx: Context<StakingConfigureValidator>) -> Result<()> {
+        staking::StakingProposalInstructions::configure_validator(ctx)
+    }
+
+    /// Executes an approved custodian configuration proposal.
+    ///
+    /// This function calls the `configure_custodian` function from the `staking::StakingProposalInstructions` module
+    /// to allow a custodian to stake on behalf of other wallets or revoke it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingConfigureCustodian` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_configure_custodian(ctx: Context<StakingConfigureCustodian>) -> Result<()> {
+        staking::StakingProposalInstructions::configure_custodian(ctx)
+    }
+
+    /// Executes an approved pair staking configuration proposal.
+    ///
+    /// This function calls the `configure_pair_staking` function from the `staking::StakingProposalInstructions` module
+    /// to change the SOL ratio and multiplier boost of pair stakes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingConfigurePairStaking` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_configure_pair_staking(ctx: Context<StakingConfigurePairStaking>) -> Result<()> {
+        staking::StakingProposalInstructions::configure_pair_staking(ctx)
+    }
+
+    /// Executes an approved reward schedule proposal.
+    ///
+    /// This function calls the `set_reward_schedule` function from the `staking::StakingProposalInstructions` module
+    /// to replace the reward schedule and apply the step in force for the current epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingSetRewardSchedule` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_set_reward_schedule(ctx: Context<StakingSetRewardSchedule>) -> Result<()> {
+        staking::StakingProposalInstructions::set_reward_schedule(ctx)
+    }
+
+    /// Executes an approved vault stake delegation proposal.
+    ///
+    /// This function calls the `delegate_vault_stake` function from the `staking::StakingProposalInstructions` module
+    /// to delegate vault SOL to an allowlisted validator through the native stake program.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingDelegateVaultStake` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_delegate_vault_stake(ctx: Context<StakingDelegateVaultStake>) -> Result<()> {
+        staking::StakingProposalInstructions::delegate_vault_stake(ctx)
+    }
+
+    /// Executes an approved vault stake deactivation proposal.
+    ///
+    /// This function calls the `deactivate_vault_stake` function from the `staking::StakingProposalInstructions` module
+    /// to start the cooldown of a vault stake account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingDeactivateVaultStake` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_deactivate_vault_stake(ctx: Context<StakingDeactivateVaultStake>) -> Result<()> {
+        staking::StakingProposalInstructions::deactivate_vault_stake(ctx)
+    }
+
+    /// Executes an approved vault stake withdrawal proposal.
+    ///
+    /// This function calls the `withdraw_vault_stake` function from the `staking::StakingProposalInstructions` module
+    /// to return a deactivated stake account's SOL to the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingWithdrawVaultStake` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_withdraw_vault_stake(ctx: Context<StakingWithdrawVaultStake>) -> Result<()> {
+        staking::StakingProposalInstructions::withdraw_vault_stake(ctx)
+    }
+
+    /// Executes an approved SOL reward distribution proposal.
+    ///
+    /// This function calls the `distribute_sol_rewards` function from the `staking::StakingProposalInstructions` module
+    /// to fund an epoch's SOL staking rewards from the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `StakingDistributeSolRewards` instruction.
+    #[cfg(feature = "staking")]
+    pub fn staking_distribute_sol_rewards(ctx: Context<StakingDistributeSolRewards>) -> Result<()> {
+        staking::StakingProposalInstructions::distribute_sol_rewards(ctx)
+    }
+
+    /// Creates the identifier counter of a namespace.
+    ///
+    /// This function calls the `initialize` function from the `IdentifierAccount`
+    /// to start the namespace's counter at id zero, and emits an `IdentifierInitializedEvent`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `IdentifierInitialization` instruction.
+    /// * `namespace` - The namespace counted, padded with [`identifier_namespace`].
+    pub fn identifier_initialize(ctx: Context<IdentifierInitialization>, namespace: [u8; IDENTIFIER_NAMESPACE_LEN]) -> Result<()> {
+        ctx.accounts.identifier.initialize(ctx.bumps.identifier);
+
+        emit!(IdentifierInitializedEvent {
+            identifier: ctx.accounts.identifier.key(),
+            namespace,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Multisig, "identifier_initialize", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Migrates an identifier account created with an older layout.
+    ///
+    /// This function calls the `migrate_account` function from the `states::versioning` module
+    /// to upgrade the `IdentifierAccount` in place to its current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AccountMigration` instruction.
+    /// * `seeds` - The seeds of the account's address, from which its canonical bump is cached.
+    pub fn identifier_migrate(ctx: Context<AccountMigration>, seeds: Vec<Vec<u8>>) -> Result<()> {
+        states::versioning::migrate_account::<IdentifierAccount>(ctx, seeds, EventModule::Multisig, "identifier_migrate")
+    }
+}
+
+#[account]
+/// Stores a unique identifier for account management.
+///
+/// The `IdentifierAccount` struct is an on-chain account that holds a single `u64` identifier.
+/// This account can be used to track or reference unique entities within the program, such as
+/// for indexing, mapping, or associating data with a specific ID.
+///
+/// The counters of the multisig, vault and staking modules keep their dedicated seeds, such as
+/// `PROPOSAL_IDENTIFIER_SEED`; later subsystems share the namespaced `[IDENTIFIER_SEED, namespace]` counters.
+///
+/// ## Fields
+///
+/// - `id` - A 64-bit unsigned integer representing the unique identifier.
+/// - `version` - The layout version the account was last written with.
+/// - `bump` - The canonical bump of the account's address, cached when it is created.
+///
+/// ## Size
+///
+/// The size of the account's fields is derived as `IdentifierAccount::INIT_SPACE`; the Anchor
+/// account discriminator is allocated on top of it.
 ///
 /// ## Example
 ///
 /// ```rust
-/// let identifier_account = IdentifierAccount { id: 42 };
+/// # use miming_spoke_solana::IdentifierAccount;
+/// let identifier_account = IdentifierAccount { id: 42, version: 2, bump: 255 };
 /// ```
+#[derive(InitSpace)]
 pub struct IdentifierAccount {
     pub id: u64,
+    pub version: u8,
+    pub bump: u8,
 }
 
 impl IdentifierAccount {
-    pub const LEN: usize = DISCRIMINATOR + U64_SIZE; // id
+    /// Starts the counter at id zero, caching the canonical `bump` of its address.
+    pub fn initialize(&mut self, bump: u8) {
+        self.id = 0;
+        self.version = Self::VERSION;
+        self.bump = bump;
+    }
+
+    /// Returns the current id and advances the counter past it, failing with `ArithmeticError` once the ids are exhausted.
+    pub fn next_id(&mut self) -> Result<u64> {
+        let id = self.id;
+        self.id = id.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+        Ok(id)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(namespace: [u8; IDENTIFIER_NAMESPACE_LEN])]
+pub struct IdentifierInitialization<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + IdentifierAccount::INIT_SPACE,
+        seeds = [IDENTIFIER_SEED, namespace.as_ref()],
+        bump
+    )]
+    pub identifier: Account<'info, IdentifierAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl VersionedAccount for IdentifierAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + IdentifierAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
 }
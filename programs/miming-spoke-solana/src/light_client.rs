@@ -0,0 +1,266 @@
+//! # Light Client Module
+//!
+//! This module verifies merkle proofs against the hub's state roots, so inbound claims can be checked against the hub's
+//! committed state instead of trusting a single attestor's signature.
+//!
+//! ## Features
+//!
+//! - **State Roots:** A quorum of the council, at least its threshold of distinct members signing the same transaction,
+//!   submits the hub's state root at each height; heights must increase, and the `MAX_HUB_STATE_ROOTS` most recent roots
+//!   are kept, so a proof built against a slightly older root still verifies while the next root is submitted.
+//! - **Merkle Proofs:** A claim is hashed as a leaf and folded with its proof's siblings, each pair hashed in sorted
+//!   order, up to the root recorded for the claimed height. Leaves and inner nodes are hashed with distinct prefixes, so
+//!   an inner node cannot be passed off as a leaf.
+//! - **Composable Checks:** [`HubStateRootsAccount::verify`] checks a proof from the program's own handlers, and the
+//!   `light_client_verify_proof` instruction exposes the same check to clients and composing programs.
+//!
+//! ## Main Data Structures
+//!
+//! - [`HubStateRoot`]: A hub state root and the height it was committed at.
+//! - [`HubStateRootsAccount`]: Stores the most recent hub state roots.
+//!
+//! ## Instructions
+//!
+//! - [`LightClientInstructions::submit_root`]: Records the hub's state root at a new height.
+//! - [`LightClientInstructions::verify_proof`]: Checks a claim's merkle proof against a recorded root.
+//!
+//! ## Constants
+//!
+//! - `MAX_HUB_STATE_ROOTS`: Number of most recent hub state roots kept.
+//! - `MAX_PROOF_DEPTH`: Maximum number of siblings in a merkle proof.
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+use crate::{
+    multisig::MultisigAccount,
+    program_config::ProgramConfigAccount,
+    states::{
+        errors::LightClientErrorCode,
+        events::{EventModule, HubStateRootSubmittedEvent, StateMutationEvent},
+        pda::{HUB_STATE_ROOTS_SEED, MULTISIG_SEED, PROGRAM_CONFIG_SEED},
+        signer_set::signer_mask_of,
+        versioning::VersionedAccount,
+    },
+};
+
+/// Number of most recent hub state roots kept.
+pub const MAX_HUB_STATE_ROOTS: usize = 16;
+
+/// Maximum number of siblings in a merkle proof, enough for a tree of 2^32 leaves.
+pub const MAX_PROOF_DEPTH: usize = 32;
+
+/// Prefix of a hashed leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+/// Prefix of a hashed inner node.
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Returns the hash of a leaf holding `data`.
+pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, data]).to_bytes()
+}
+
+/// Returns the hash of the inner node over `left` and `right`, taken in sorted order so proofs need no position bits.
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if left <= right { (left, right) } else { (right, left) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+/// Returns whether folding the hash of `leaf` with `proof`'s siblings yields `root`.
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: &[u8], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf_hash(leaf), |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
+
+/// Checks that `signers` hold a quorum of `multisig`'s council: at least its threshold of distinct members, and at least
+/// one.
+pub fn check_quorum(multisig: &MultisigAccount, signers: &[Pubkey]) -> Result<()> {
+    let council: Vec<Pubkey> = multisig.signers.iter().map(|signer| signer.pubkey).collect();
+    let approvals = signer_mask_of(&council, signers).count_ones();
+    require!(approvals >= u32::from(multisig.threshold.max(1)), LightClientErrorCode::QuorumNotReached);
+
+    Ok(())
+}
+
+/// A hub state root and the height it was committed at.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HubStateRoot {
+    pub height: u64,
+    pub root: [u8; 32],
+    pub submitted_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct HubStateRootsAccount {
+    #[max_len(MAX_HUB_STATE_ROOTS)]
+    pub roots: Vec<HubStateRoot>,
+    pub latest_height: u64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl HubStateRootsAccount {
+    /// Records `root` at `height`, which must be above every recorded height, dropping the oldest root once
+    /// `MAX_HUB_STATE_ROOTS` are kept.
+    pub fn push(&mut self, height: u64, root: [u8; 32], now: i64) -> Result<()> {
+        require!(
+            self.roots.is_empty() || height > self.latest_height,
+            LightClientErrorCode::StaleStateRoot
+        );
+
+        if self.roots.len() == MAX_HUB_STATE_ROOTS {
+            self.roots.remove(0);
+        }
+        self.roots.push(HubStateRoot { height, root, submitted_at: now });
+        self.latest_height = height;
+
+        Ok(())
+    }
+
+    /// Checks that a root is recorded at `height` and that `proof` proves `leaf` against it.
+    pub fn verify(&self, height: u64, leaf: &[u8], proof: &[[u8; 32]]) -> Result<()> {
+        require!(proof.len() <= MAX_PROOF_DEPTH, LightClientErrorCode::ProofTooLong);
+        let state_root = self
+            .roots
+            .iter()
+            .find(|state_root| state_root.height == height)
+            .ok_or(LightClientErrorCode::UnknownStateRoot)?;
+        require!(verify_merkle_proof(&state_root.root, leaf, proof), LightClientErrorCode::InvalidProof);
+
+        Ok(())
+    }
+}
+
+impl VersionedAccount for HubStateRootsAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + HubStateRootsAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[derive(Accounts)]
+pub struct LightClientSubmitRoot<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = HubStateRootsAccount::SPACE,
+        seeds = [HUB_STATE_ROOTS_SEED],
+        bump
+    )]
+    pub hub_state_roots: Account<'info, HubStateRootsAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LightClientVerifyProof<'info> {
+    #[account(
+        seeds = [HUB_STATE_ROOTS_SEED],
+        bump = hub_state_roots.bump
+    )]
+    pub hub_state_roots: Account<'info, HubStateRootsAccount>,
+}
+
+pub struct LightClientInstructions;
+
+impl LightClientInstructions {
+    /// Records the hub's state root at a new height.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the program is not halted.
+    /// - Checks that the signer and the signing remaining accounts hold a quorum of the council, at least its threshold of
+    ///   distinct members, so no single key can record a root that inbound releases and teleports are then proven against.
+    /// - Creates the roots account, at the payer's expense, on the first submission.
+    /// - Records the root, checking that its height is above every recorded one, and drops the oldest root once
+    ///   `MAX_HUB_STATE_ROOTS` are kept.
+    /// - Emits a `HubStateRootSubmittedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the payer, the multisig, the program configuration, and the roots
+    ///   account, with the council's other signing members as remaining accounts.
+    /// * `height` - The hub height the root was committed at.
+    /// * `root` - The hub's state root at that height.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the root is recorded, otherwise returns an error.
+    pub fn submit_root(ctx: Context<LightClientSubmitRoot>, height: u64, root: [u8; 32]) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let signers: Vec<Pubkey> = std::iter::once(ctx.accounts.signer.key())
+            .chain(ctx.remaining_accounts.iter().filter(|account| account.is_signer).map(|account| account.key()))
+            .collect();
+        check_quorum(&ctx.accounts.current_multisig, &signers)?;
+
+        let hub_state_roots = &mut ctx.accounts.hub_state_roots;
+        if hub_state_roots.version == 0 {
+            hub_state_roots.upgrade(ctx.bumps.hub_state_roots);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        hub_state_roots.push(height, root, now)?;
+
+        emit!(HubStateRootSubmittedEvent {
+            height,
+            root,
+            submitter: ctx.accounts.signer.key(),
+            timestamp: now,
+        });
+
+        StateMutationEvent::emit(EventModule::LightClient, "light_client_submit_root", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Checks that `proof` proves `leaf` against the hub state root recorded at `height`.
+    ///
+    /// The instruction changes no state; it succeeds only for a valid proof, so clients can simulate it and programs can
+    /// invoke it before acting on a claim.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the roots account.
+    /// * `height` - The hub height of the root the proof was built against.
+    /// * `leaf` - The claim's data, hashed as the proof's leaf.
+    /// * `proof` - The sibling hashes from the leaf up to the root.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no root is recorded at `height`, the proof exceeds `MAX_PROOF_DEPTH`, or it does not prove the
+    /// leaf against the root.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proof is valid, otherwise returns an error.
+    pub fn verify_proof(
+        ctx: Context<LightClientVerifyProof>,
+        height: u64,
+        leaf: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.hub_state_roots.verify(height, &leaf, &proof)
+    }
+}
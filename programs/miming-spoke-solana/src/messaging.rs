@@ -0,0 +1,87 @@
+//! # Messaging Module
+//!
+//! This module abstracts the cross-chain transport teleports and releases travel over, so the vault talks to the hub
+//! through one adapter interface instead of a particular bridge.
+//!
+//! ## Features
+//!
+//! - **Adapter Interface:** [`MessagingAdapter`] posts outbound payloads and reads verified inbound messages; the vault's
+//!   teleport and release paths only call it.
+//! - **Governed Transport:** The transport in use and its endpoint program are stored in the program configuration and
+//!   changed through approved `ConfigureMessaging` proposals, so switching endpoints is a governance action rather than a
+//!   redeploy of the vault logic.
+//! - **Wormhole Adapter:** Wormhole is the transport available today, implemented by
+//!   [`WormholeAdapter`](crate::wormhole::WormholeAdapter); further messengers are added as new [`MessagingTransport`]
+//!   variants with their own adapter.
+//!
+//! ## Main Data Structures
+//!
+//! - [`MessagingTransport`]: The transports the program can use.
+//! - [`OutboundAccounts`]: The accounts a transport's outbound message is posted with.
+//! - [`InboundMessage`]: A verified inbound message, with its source chain, sender, sequence, and payload.
+use anchor_lang::prelude::*;
+use crate::{program_config::ProgramConfigAccount, wormhole::{WormholeAdapter, WORMHOLE_CORE_BRIDGE_ID}};
+
+/// A cross-chain transport the program can post to and receive from.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MessagingTransport {
+    #[default]
+    Wormhole,
+}
+
+impl MessagingTransport {
+    /// Returns the endpoint program the transport uses when the program configuration does not set one.
+    pub fn default_endpoint(&self) -> Pubkey {
+        match self {
+            MessagingTransport::Wormhole => WORMHOLE_CORE_BRIDGE_ID,
+        }
+    }
+}
+
+/// The accounts an outbound message is posted with. Each transport reads them in its own terms, e.g. Wormhole's bridge
+/// configuration, message account, emitter, and sequence tracker.
+pub struct OutboundAccounts<'a, 'info> {
+    pub config: &'a AccountInfo<'info>,
+    pub message: &'a AccountInfo<'info>,
+    pub emitter: &'a AccountInfo<'info>,
+    pub sequence: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub fee_collector: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub endpoint: &'a AccountInfo<'info>,
+}
+
+/// An inbound message whose authenticity the transport has verified.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InboundMessage {
+    pub source_chain: u16,
+    pub sender: [u8; 32],
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+}
+
+/// A cross-chain transport, as the vault uses it.
+pub trait MessagingAdapter {
+    /// Returns the endpoint program the adapter posts to and reads verified messages from.
+    fn endpoint(&self) -> Pubkey;
+
+    /// Posts `payload`, paying any transport fee from the payer, and returns the sequence it was assigned.
+    ///
+    /// `signer_seeds` carries the seeds of the program's emitter and message accounts, so the transport can record the
+    /// emitter as the sender.
+    fn post_message(&self, accounts: OutboundAccounts, payload: Vec<u8>, signer_seeds: &[&[&[u8]]]) -> Result<u64>;
+
+    /// Decodes an inbound message from the account the transport wrote it to once verified.
+    fn read_message(&self, message: &AccountInfo) -> Result<InboundMessage>;
+}
+
+/// Returns the adapter of the transport configured in the program configuration.
+pub fn messaging_adapter(program_config: &ProgramConfigAccount) -> Box<dyn MessagingAdapter> {
+    let endpoint = program_config.messaging_endpoint();
+
+    match program_config.messaging_transport {
+        MessagingTransport::Wormhole => Box::new(WormholeAdapter { core_bridge: endpoint }),
+    }
+}
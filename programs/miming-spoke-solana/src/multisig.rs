@@ -11,20 +11,29 @@
 //! - **Signature Collection:** Collect signatures from authorized signers to approve proposals.
 //! - **Approval Workflow:** Only apply changes to the multisig account when the required number of signatures is collected.
 //! - **Access Control:** Enforce signer and threshold limits, and prevent unauthorized or duplicate signatures.
+//! - **Stake-Weighted Mode:** Optionally approve proposals once the signers' combined staking voting power reaches a minimum approval weight.
+//!   Each signer's weight counts their reward-earning stakes of the reward mint as they stood when the proposal was created.
 //!
 //! ## Main Data Structures
 //!
 //! - [`MultisigSigners`]: Represents an individual signer with a name and public key.
-//! - [`Multisig`]: Represents the configuration of a multisig account (name, threshold, signers).
-//! - [`MultisigProposalAccount`]: Stores a proposal to update the multisig account, including required signers, collected signatures, and status.
+//! - [`MultisigMode`]: Whether proposals need every signer (`Unanimous`) or enough staking voting weight (`StakeWeighted`).
+//! - [`Multisig`]: Represents the configuration of a multisig account (name, threshold, signers, approval mode).
+//! - [`MultisigProposalSigner`] / [`MultisigProposalData`]: Fixed-size forms of a signer and a proposed configuration.
+//! - [`MultisigProposalAccount`]: A zero-copy account storing a proposal to update the multisig account, including required
+//!   signers, collected signatures, and status, in fixed-size arrays.
 //! - [`MultisigAccount`]: The on-chain account representing the current state of the multisig.
 //!
 //! ## Instructions
 //!
-//! - [`MultisigInstructions::initialize`]: Initializes a new multisig account with default values.
 //! - [`MultisigInstructions::create_proposal`]: Creates a proposal to update the multisig account's configuration.
 //! - [`MultisigInstructions::sign_proposal`]: Allows an authorized signer to sign a pending proposal.
+//! - [`MultisigInstructions::create_and_sign_proposal`]: Creates a proposal and records the proposer's signature in the
+//!   same instruction.
 //! - [`MultisigInstructions::approve_proposal`]: Approves and applies a proposal if enough signatures are collected.
+//! - [`MultisigInstructions::close_proposal`]: Closes an approved proposal, refunding its rent to the configured recipient.
+//! - [`MultisigInstructions::migrate_proposal`]: Converts a proposal created with the older serialized layout to the zero-copy one.
+//! - [`MultisigInstructions::get_state`]: Returns the multisig's current configuration as return data.
 //!
 //! ## Error Handling
 //!
@@ -35,10 +44,11 @@
 //!
 //! - `MAX_THRESHOLD`: Maximum allowed threshold for signatures.
 //! - `MAX_SIGNERS`: Maximum number of allowed signers.
+//! - `MAX_MULTISIG_NAME_LEN`: Maximum length in bytes of the multisig's name and its signers' names.
 //!
 //! ## Usage
 //!
-//! 1. **Initialize** a multisig account using `initialize`.
+//! 1. **Initialize** the multisig with its genesis council through `initialize_program`, in the genesis module.
 //! 2. **Create a proposal** to update the multisig configuration using `create_proposal`.
 //! 3. **Sign the proposal** by collecting signatures from authorized signers using `sign_proposal`.
 //! 4. **Approve the proposal** and apply changes when enough signatures are collected using `approve_proposal`.
@@ -53,140 +63,523 @@
 //!
 //! This module is designed to be used as part of a larger Solana program, and can be integrated to provide robust
 //! multisignature governance or access control for program operations.
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions as instructions_sysvar,
+    Discriminator,
+};
+use bytemuck::Zeroable;
 use crate::{
+    require_with_context,
+    nonce::{consume_nonce, WalletNonceAccount},
     states::{
-        constants::{
-            DISCRIMINATOR, 
-            STRING_LEN, U8_SIZE, U64_SIZE, 
-            ENUM_SIZE, VEC_SIZE, 
-            PUBKEY_SIZE,
+        constants::DISCRIMINATOR,
+        events::{AccountClosedEvent, AccountMigratedEvent, EventModule, MultisigApprovalPreviewEvent, StateMutationEvent},
+        error_context::with_context,
+        errors::{MultisigErrorCode, ProgramConfigErrorCode, SimulationErrorCode, VersioningErrorCode},
+        pda::{
+            find_proposal_address, MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_IDENTIFIER_SEED, PROPOSAL_SEED,
+            STAKING_CONFIG_SEED, WALLET_NONCE_SEED,
         },
-        errors::MultisigErrorCode,
+        audit::{Audited, AUDIT_SPACE},
+        realloc::{grow_account, space_for_len},
+        signer_set::{full_signer_mask, signer_bit, signer_mask_of},
+        versioning::VersionedAccount,
     },
+    access_control::is_member,
+    program_config::ProgramConfigAccount,
+    staking::{staked_voting_power, StakingConfigAccount},
     IdentifierAccount
 };
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
 pub struct MultisigSigners {
+    #[max_len(MAX_MULTISIG_NAME_LEN)]
     pub name: String,
     pub pubkey: Pubkey,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigMode {
+    Unanimous,
+    StakeWeighted,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub struct Multisig {
     pub name: String,
     pub threshold: u8,
     pub signers: Vec<MultisigSigners>,
+    pub mode: MultisigMode,
+    pub min_approval_weight: u64,
 }
 
 pub const MAX_THRESHOLD: u8 = 10;
 pub const MAX_SIGNERS: usize = 10;
+pub const MAX_MULTISIG_NAME_LEN: usize = 32;
 
-pub const MULTISIG_SIGNERS_SIZE: usize = DISCRIMINATOR +
-    // name
-    STRING_LEN + 
-    // pubkey
-    PUBKEY_SIZE; 
-
-pub const MULTISIG_SIZE: usize = DISCRIMINATOR +
-    // name
-    STRING_LEN + 
-    // threshold
-    U8_SIZE + 
-    // data
-    VEC_SIZE + (MAX_SIGNERS * MULTISIG_SIGNERS_SIZE); 
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum MultisigProposalStatus {
     Pending,
     Approved,
 }
 
-#[account]
+impl MultisigMode {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => MultisigMode::StakeWeighted,
+            _ => MultisigMode::Unanimous,
+        }
+    }
+}
+
+/// Copies `name` into a fixed-size, zero-padded buffer, returning the buffer and the name's length.
+fn encode_name(name: &str) -> Result<([u8; MAX_MULTISIG_NAME_LEN], u8)> {
+    require!(
+        name.len() <= MAX_MULTISIG_NAME_LEN,
+        MultisigErrorCode::NameTooLong
+    );
+
+    let mut buffer = [0u8; MAX_MULTISIG_NAME_LEN];
+    buffer[..name.len()].copy_from_slice(name.as_bytes());
+    Ok((buffer, name.len() as u8))
+}
+
+/// Reads a name written by [`encode_name`] back into a `String`.
+fn decode_name(buffer: &[u8; MAX_MULTISIG_NAME_LEN], len: u8) -> String {
+    String::from_utf8_lossy(&buffer[..len as usize]).into_owned()
+}
+
+/// A proposed signer in the fixed-size form stored by [`MultisigProposalAccount`].
+#[zero_copy]
+#[derive(InitSpace)]
+pub struct MultisigProposalSigner {
+    pub pubkey: Pubkey,
+    pub name: [u8; MAX_MULTISIG_NAME_LEN],
+    pub name_len: u8,
+}
+
+/// A proposed multisig configuration in the fixed-size form stored by [`MultisigProposalAccount`].
+#[zero_copy]
+#[derive(InitSpace)]
+pub struct MultisigProposalData {
+    pub min_approval_weight: u64,
+    pub signers: [MultisigProposalSigner; MAX_SIGNERS],
+    pub name: [u8; MAX_MULTISIG_NAME_LEN],
+    pub name_len: u8,
+    pub threshold: u8,
+    pub signer_count: u8,
+    pub mode: u8,
+    pub _padding: [u8; 2],
+}
+
+impl MultisigProposalData {
+    /// Encodes a proposed configuration, checking that its name and signers' names fit the fixed-size buffers.
+    pub fn from_multisig(data: &Multisig) -> Result<Self> {
+        require!(
+            data.signers.len() <= MAX_SIGNERS,
+            MultisigErrorCode::SignerLimitReached
+        );
+
+        let (name, name_len) = encode_name(&data.name)?;
+        let mut signers = [MultisigProposalSigner {
+            pubkey: Pubkey::default(),
+            name: [0u8; MAX_MULTISIG_NAME_LEN],
+            name_len: 0,
+        }; MAX_SIGNERS];
+        for (slot, signer) in signers.iter_mut().zip(data.signers.iter()) {
+            let (name, name_len) = encode_name(&signer.name)?;
+            *slot = MultisigProposalSigner {
+                pubkey: signer.pubkey,
+                name,
+                name_len,
+            };
+        }
+
+        Ok(MultisigProposalData {
+            min_approval_weight: data.min_approval_weight,
+            signers,
+            name,
+            name_len,
+            threshold: data.threshold,
+            signer_count: data.signers.len() as u8,
+            mode: data.mode as u8,
+            _padding: [0u8; 2],
+        })
+    }
+
+    /// Decodes the proposed configuration back into a [`Multisig`].
+    pub fn to_multisig(&self) -> Multisig {
+        Multisig {
+            name: decode_name(&self.name, self.name_len),
+            threshold: self.threshold,
+            signers: self.signers[..self.signer_count as usize]
+                .iter()
+                .map(|signer| MultisigSigners {
+                    name: decode_name(&signer.name, signer.name_len),
+                    pubkey: signer.pubkey,
+                })
+                .collect(),
+            mode: MultisigMode::from_u8(self.mode),
+            min_approval_weight: self.min_approval_weight,
+        }
+    }
+}
+
+/// The serialized layout proposals were stored in before they became zero-copy, decoded by
+/// [`MultisigInstructions::migrate_proposal`].
+#[derive(AnchorDeserialize)]
+struct LegacyMultisigProposal {
+    id: u64,
+    data: Multisig,
+    required_signers: Vec<Pubkey>,
+    signers: Vec<Pubkey>,
+    status: MultisigProposalStatus,
+    approval_mode: MultisigMode,
+    min_approval_weight: u64,
+    signer_weights: Vec<u64>,
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
 pub struct MultisigProposalAccount {
     pub id: u64,
-    pub data: Multisig,
-    pub required_signers: Vec<Pubkey>,
-    pub signers: Vec<Pubkey>,
-    pub status: MultisigProposalStatus,
+    pub min_approval_weight: u64,
+    pub signer_weights: [u64; MAX_SIGNERS],
+    pub data: MultisigProposalData,
+    pub required_signers: [Pubkey; MAX_SIGNERS],
+    pub signers: [Pubkey; MAX_SIGNERS],
+    pub required_signer_count: u8,
+    pub signer_count: u8,
+    pub status: u8,
+    pub approval_mode: u8,
+    pub version: u8,
+    pub signed_mask: [u8; 2],
+    pub _padding: [u8; 1],
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
 }
 
+// The zero-copy layout has no implicit padding, so its `InitSpace` size is its in-memory size.
+const _: () = assert!(MultisigProposalAccount::INIT_SPACE == std::mem::size_of::<MultisigProposalAccount>());
+
 impl MultisigProposalAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // id
-        U64_SIZE + 
-        // data
-        MULTISIG_SIZE + 
-        // required_signers
-        VEC_SIZE + (MAX_SIGNERS * PUBKEY_SIZE) +  
-         // signers
-        VEC_SIZE + (MAX_SIGNERS * PUBKEY_SIZE) + 
-        // status
-        ENUM_SIZE; 
+    /// The space zero-copy proposals are allocated with: the discriminator and fields, plus the 8 bytes of trailing
+    /// slack every zero-copy proposal has been created with. The sign, approve and migrate instructions tell the layouts
+    /// apart by this exact size, so it only changes along with a migration path for the previous one.
+    pub const SPACE: usize = 8 + Self::INIT_SPACE + 8;
+
+    /// The space of zero-copy proposals created before the audit metadata was appended, whose trailing slack the
+    /// metadata now starts in.
+    pub const UNAUDITED_SPACE: usize = Self::SPACE - AUDIT_SPACE;
+
+    /// The current layout version; version 2 is the zero-copy layout, version 3 keeps the signature bitmap in what
+    /// was its padding, and version 4 appends the audit metadata.
+    pub const VERSION: u8 = 4;
+
+    /// Writes a new pending proposal for `data`, requiring the current multisig's `required_signers`.
+    pub fn write(
+        &mut self,
+        id: u64,
+        data: &Multisig,
+        required_signers: &[Pubkey],
+        approval_mode: MultisigMode,
+        min_approval_weight: u64,
+    ) -> Result<()> {
+        require!(
+            required_signers.len() <= MAX_SIGNERS,
+            MultisigErrorCode::SignerLimitReached
+        );
+
+        self.id = id;
+        self.data = MultisigProposalData::from_multisig(data)?;
+        self.required_signers = [Pubkey::default(); MAX_SIGNERS];
+        self.required_signers[..required_signers.len()].copy_from_slice(required_signers);
+        self.required_signer_count = required_signers.len() as u8;
+        self.signers = [Pubkey::default(); MAX_SIGNERS];
+        self.signer_weights = [0u64; MAX_SIGNERS];
+        self.signer_count = 0;
+        self.signed_mask = [0u8; 2];
+        self.status = MultisigProposalStatus::Pending as u8;
+        self.approval_mode = approval_mode as u8;
+        self.min_approval_weight = min_approval_weight;
+        self.version = Self::VERSION;
+        Ok(())
+    }
+
+    /// Returns the public keys required to sign the proposal.
+    pub fn required_signer_keys(&self) -> &[Pubkey] {
+        &self.required_signers[..self.required_signer_count as usize]
+    }
+
+    /// Returns the public keys that have signed the proposal.
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        &self.signers[..self.signer_count as usize]
+    }
+
+    /// Returns the bitmap of the required signers that have signed the proposal.
+    pub fn signed_mask(&self) -> u16 {
+        u16::from_le_bytes(self.signed_mask)
+    }
+
+    /// Returns whether every required signer has signed the proposal.
+    pub fn all_required_signed(&self) -> bool {
+        self.signed_mask() == full_signer_mask(self.required_signer_count as usize)
+    }
+
+    /// Rebuilds the signature bitmap of a zero-copy proposal written before version 3 kept one, whose bitmap bytes were
+    /// padding, and stamps `VERSION`.
+    pub fn sync_signed_mask(&mut self) {
+        if self.version < 3 {
+            self.signed_mask = signer_mask_of(self.required_signer_keys(), self.signer_keys()).to_le_bytes();
+            self.version = Self::VERSION;
+        }
+    }
+
+    /// Returns whether the proposal is still pending.
+    pub fn is_pending(&self) -> bool {
+        self.status == MultisigProposalStatus::Pending as u8
+    }
+
+    /// Returns the approval mode of the multisig the proposal was created under.
+    pub fn mode(&self) -> MultisigMode {
+        MultisigMode::from_u8(self.approval_mode)
+    }
+
+    /// Records a signature with the signer's staking voting weight.
+    pub fn add_signature(&mut self, signer: Pubkey, weight: u64) -> Result<()> {
+        let index = self.signer_count as usize;
+        require!(index < MAX_SIGNERS, MultisigErrorCode::SignerLimitReached);
+
+        if let Some(bit) = signer_bit(self.required_signer_keys(), &signer) {
+            self.signed_mask = (self.signed_mask() | bit).to_le_bytes();
+        }
+        self.signers[index] = signer;
+        self.signer_weights[index] = weight;
+        self.signer_count += 1;
+        Ok(())
+    }
+
+    /// Returns the combined staking voting weight recorded with the collected signatures.
+    pub fn collected_weight(&self) -> u64 {
+        self.signer_weights[..self.signer_count as usize]
+            .iter()
+            .fold(0u64, |total, weight| total.saturating_add(*weight))
+    }
+
+    /// Checks that `signer` may sign the pending proposal: one of its required signers that has not signed yet, or, for
+    /// a proposal without required signers, any key that has not signed yet.
+    pub fn check_can_sign(&self, signer: &Pubkey) -> Result<()> {
+        require!(self.is_pending(), MultisigErrorCode::AlreadyResolved);
+
+        if self.required_signer_keys().is_empty() {
+            require!(
+                !self.signer_keys().contains(signer),
+                MultisigErrorCode::DuplicateSignature
+            );
+        } else {
+            let signer_bit = signer_bit(self.required_signer_keys(), signer)
+                .ok_or_else(|| with_context(MultisigErrorCode::UnauthorizedSigner, *signer, 0, 0))?;
+            require!(
+                self.signed_mask() & signer_bit == 0,
+                MultisigErrorCode::DuplicateSignature
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Approves the pending proposal on behalf of `signer` once it meets its approval mode, and returns the multisig
+    /// configuration it proposes.
+    ///
+    /// `signer` must have signed the proposal, unless nobody has. Unanimous proposals need every required signer's
+    /// signature; stake-weighted proposals need the collected voting weight to reach the proposal's minimum.
+    pub fn approve(&mut self, signer: &Pubkey) -> Result<Multisig> {
+        require!(self.is_pending(), MultisigErrorCode::AlreadyResolved);
+
+        self.sync_signed_mask();
+
+        let has_signed = match signer_bit(self.required_signer_keys(), signer) {
+            Some(signer_bit) => self.signed_mask() & signer_bit != 0,
+            None => self.signer_keys().contains(signer),
+        };
+        if !self.signer_keys().is_empty() {
+            require_with_context!(has_signed, MultisigErrorCode::UnauthorizedSigner, *signer, 0, 0);
+        }
+
+        match self.mode() {
+            MultisigMode::Unanimous => {
+                require_with_context!(
+                    self.all_required_signed(),
+                    MultisigErrorCode::InsufficientSignatures,
+                    *signer,
+                    self.required_signer_count as u64,
+                    self.signed_mask().count_ones() as u64
+                );
+            }
+            MultisigMode::StakeWeighted => {
+                require_with_context!(
+                    self.collected_weight() >= self.min_approval_weight,
+                    MultisigErrorCode::InsufficientVotingWeight,
+                    *signer,
+                    self.min_approval_weight,
+                    self.collected_weight()
+                );
+            }
+        }
+
+        self.status = MultisigProposalStatus::Approved as u8;
+        Ok(self.data.to_multisig())
+    }
+}
+
+/// A view of the multisig's current configuration, returned by [`MultisigInstructions::get_state`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct MultisigStateView {
+    pub name: String,
+    pub threshold: u8,
+    pub signers: Vec<MultisigSigners>,
+    pub mode: MultisigMode,
+    pub min_approval_weight: u64,
+    pub next_proposal_id: u64,
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct MultisigAccount {
+    #[max_len(MAX_MULTISIG_NAME_LEN)]
     pub name: String,
     pub threshold: u8,
+    #[max_len(MAX_SIGNERS)]
     pub signers: Vec<MultisigSigners>,
+    pub mode: MultisigMode,
+    pub min_approval_weight: u64,
+    pub version: u8,
+    pub bump: u8,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
 }
 
 impl MultisigAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // name
-        STRING_LEN + 
-        // threshold
-        U8_SIZE + 
-        // signers
-        VEC_SIZE + (MAX_SIGNERS * MULTISIG_SIGNERS_SIZE); 
+    /// Returns the size of a multisig with room for `signer_count` signers.
+    pub const fn len_for(signer_count: usize) -> usize {
+        space_for_len(Self::INIT_SPACE, MAX_SIGNERS, MultisigSigners::INIT_SPACE, signer_count)
+    }
+}
+
+impl Audited for MultisigProposalAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
+}
+
+/// Version 3 appended the audit metadata, which multisigs migrated from version 2 leave zeroed until their next write.
+impl VersionedAccount for MultisigAccount {
+    const VERSION: u8 = 3;
+    const SPACE: usize = 8 + MultisigAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+impl Audited for MultisigAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
 }
 
 #[derive(Accounts)]
-pub struct MultisigInitialization<'info> {
+pub struct MultisigCreateProposal<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    #[account(init, payer = signer, space = 8 + IdentifierAccount::LEN, seeds = [b"proposal_identifier"], bump)]
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_IDENTIFIER_SEED],
+        bump = proposal_identifier.bump
+    )]
     pub proposal_identifier: Account<'info, IdentifierAccount>,
 
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 8 + MultisigAccount::LEN,
+        init,
+        payer = payer,
+        space = MultisigProposalAccount::SPACE,
         seeds = [
-            b"multisig"
+            PROPOSAL_SEED, 
+            proposal_identifier.id.to_le_bytes().as_ref()
         ],
         bump
     )]
-    pub multisig: Account<'info, MultisigAccount>,
+    pub proposal: AccountLoader<'info, MultisigProposalAccount>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct MultisigCreateProposal<'info> {
+pub struct MultisigCreateAndSignProposal<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
     pub current_multisig: Account<'info, MultisigAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [PROPOSAL_IDENTIFIER_SEED],
+        bump = proposal_identifier.bump
+    )]
     pub proposal_identifier: Account<'info, IdentifierAccount>,
 
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 8 + MultisigProposalAccount::LEN,
+        init,
+        payer = payer,
+        space = MultisigProposalAccount::SPACE,
         seeds = [
-            b"proposal", 
+            PROPOSAL_SEED,
             proposal_identifier.id.to_le_bytes().as_ref()
         ],
         bump
     )]
-    pub proposal: Account<'info, MultisigProposalAccount>,
+    pub proposal: AccountLoader<'info, MultisigProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    /// The staking configuration, naming the reward mint whose stakes carry voting weight. Required in `StakeWeighted` mode.
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Option<Account<'info, StakingConfigAccount>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -196,8 +589,26 @@ pub struct MultisigSignProposal<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    #[account(mut)]
-    pub current_proposal: Account<'info, MultisigProposalAccount>,
+    #[account(
+        mut,
+        constraint = current_proposal.to_account_info().data_len() == MultisigProposalAccount::SPACE @ MultisigErrorCode::ProposalNotMigrated,
+        constraint = current_proposal.key() == find_proposal_address(current_proposal.load()?.id).0 @ MultisigErrorCode::InvalidProposalAccount
+    )]
+    pub current_proposal: AccountLoader<'info, MultisigProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    /// The staking configuration, naming the reward mint whose stakes carry voting weight. Required in `StakeWeighted` mode.
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Option<Account<'info, StakingConfigAccount>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -207,54 +618,123 @@ pub struct MultisigApproveProposal<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    #[account(mut)]
-    pub current_proposal: Account<'info, MultisigProposalAccount>,
+    #[account(
+        mut,
+        constraint = current_proposal.to_account_info().data_len() == MultisigProposalAccount::SPACE @ MultisigErrorCode::ProposalNotMigrated,
+        constraint = current_proposal.key() == find_proposal_address(current_proposal.load()?.id).0 @ MultisigErrorCode::InvalidProposalAccount
+    )]
+    pub current_proposal: AccountLoader<'info, MultisigProposalAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
     pub current_multisig: Account<'info, MultisigAccount>,
 
+    /// CHECK: The program configuration, deserialized for its CPI guard once it has been created after the multisig
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// CHECK: This is the instructions sysvar, read to identify the program calling this instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub struct MultisigInstructions;
+#[derive(Accounts)]
+pub struct MultisigCloseProposal<'info> {
+    pub signer: Signer<'info>,
 
-impl MultisigInstructions {
-    /// Implements the instructions for managing a multisig account.
-    /// Initializes the multisig account and proposal identifier.
-    ///
-    /// This function sets up the initial state for the multisig by:
-    /// - Setting the proposal identifier's `id` to 0.
-    /// - Initializing the multisig account with:
-    ///   - `name` set to "System"
-    ///   - `threshold` set to 0
-    ///   - An empty list of `signers`
-    ///
-    /// ## Arguments
-    ///
-    /// * `ctx` - The context containing the accounts required for multisig initialization.
-    ///
-    /// ## Returns
-    ///
-    /// Returns `Ok(())` if initialization is successful, otherwise returns an error.
-    pub fn initialize(ctx: Context<MultisigInitialization>) -> Result<()> {
-        ctx.accounts.proposal_identifier.id = 0;
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
 
-        let multisig = &mut ctx.accounts.multisig;
-        multisig.name = String::from("System");
-        multisig.threshold = 0;
-        multisig.signers = Vec::new();
+    #[account(
+        mut,
+        close = rent_recipient,
+        constraint = current_proposal.to_account_info().data_len() == MultisigProposalAccount::SPACE @ MultisigErrorCode::ProposalNotMigrated,
+        constraint = current_proposal.key() == find_proposal_address(current_proposal.load()?.id).0 @ MultisigErrorCode::InvalidProposalAccount
+    )]
+    pub current_proposal: AccountLoader<'info, MultisigProposalAccount>,
 
-        Ok(())
-    }
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
 
+#[derive(Accounts)]
+pub struct MultisigMigrateProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Decoded in the older serialized layout before it is rewritten in the zero-copy one; both layouts share the discriminator, which is checked
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub proposal: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MultisigGetState<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        seeds = [PROPOSAL_IDENTIFIER_SEED],
+        bump = proposal_identifier.bump
+    )]
+    pub proposal_identifier: Account<'info, IdentifierAccount>,
+}
+
+pub struct MultisigInstructions;
+
+impl MultisigInstructions {
     /// Creates a new multisig proposal with the specified parameters.
     ///
     /// This function performs the following steps:
     /// - Ensures the provided `threshold` does not exceed `MAX_THRESHOLD`.
     /// - Ensures the number of provided `signers` does not exceed `MAX_SIGNERS`.
+    /// - Ensures the `name` and each signer's name fit in `MAX_MULTISIG_NAME_LEN` bytes.
     /// - Increments the proposal identifier.
-    /// - Initializes a new proposal with the given `name`, `threshold`, and `signers`.
-    /// - Sets the required signers for the proposal based on the current multisig's signers.
+    /// - Initializes a new zero-copy proposal with the given `name`, `threshold`, `signers`, `mode`, and `min_approval_weight`.
+    /// - Sets the required signers, approval mode, and minimum approval weight for the proposal based on the current multisig.
     /// - Sets the proposal status to `Pending`.
     ///
     /// ## Arguments
@@ -263,12 +743,15 @@ impl MultisigInstructions {
     /// * `name` - The name of the new multisig proposal.
     /// * `threshold` - The minimum number of signatures required to approve the proposal.
     /// * `signers` - A vector of `MultisigSigners` representing the signers for the proposal.
+    /// * `mode` - The approval mode of the proposed multisig.
+    /// * `min_approval_weight` - The staking voting weight required to approve proposals in `StakeWeighted` mode.
     ///
     /// ## Errors
     ///
     /// Returns an error if:
     /// - The `threshold` exceeds `MAX_THRESHOLD`.
     /// - The number of `signers` exceeds `MAX_SIGNERS`.
+    /// - A name is longer than `MAX_MULTISIG_NAME_LEN` bytes.
     ///
     /// ## Returns
     ///
@@ -278,49 +761,164 @@ impl MultisigInstructions {
         name: String,
         threshold: u8,
         signers: Vec<MultisigSigners>,
+        mode: MultisigMode,
+        min_approval_weight: u64,
+    ) -> Result<()> {
+        let mut proposal = ctx.accounts.proposal.load_init()?;
+        Self::open_proposal(
+            &mut proposal,
+            &mut ctx.accounts.proposal_identifier,
+            &ctx.accounts.current_multisig,
+            ctx.accounts.signer.key(),
+            Multisig {
+                name,
+                threshold,
+                signers,
+                mode,
+                min_approval_weight,
+            },
+        )?;
+
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_create_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Creates a new multisig proposal and records the proposer's signature on it, saving the proposer a separate
+    /// `sign_proposal` transaction.
+    ///
+    /// This function performs the checks and actions of [`create_proposal`](MultisigInstructions::create_proposal),
+    /// then those of [`sign_proposal`](MultisigInstructions::sign_proposal) for the signer on the new proposal, including
+    /// the nonce check when `expected_nonce` is given and the voting power read from the remaining accounts in
+    /// `StakeWeighted` mode.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for proposal creation and the signer's nonce.
+    /// * `name` - The name of the new multisig proposal.
+    /// * `threshold` - The minimum number of signatures required to approve the proposal.
+    /// * `signers` - A vector of `MultisigSigners` representing the signers for the proposal.
+    /// * `mode` - The approval mode of the proposed multisig.
+    /// * `min_approval_weight` - The staking voting weight required to approve proposals in `StakeWeighted` mode.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the proposal cannot be created, or if the signer is not authorized to sign it.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is created and signed successfully, otherwise returns an error.
+    pub fn create_and_sign_proposal(
+        ctx: Context<MultisigCreateAndSignProposal>,
+        name: String,
+        threshold: u8,
+        signers: Vec<MultisigSigners>,
+        mode: MultisigMode,
+        min_approval_weight: u64,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
+        let mut proposal = ctx.accounts.proposal.load_init()?;
+        Self::open_proposal(
+            &mut proposal,
+            &mut ctx.accounts.proposal_identifier,
+            &ctx.accounts.current_multisig,
+            signer_key,
+            Multisig {
+                name,
+                threshold,
+                signers,
+                mode,
+                min_approval_weight,
+            },
+        )?;
+        Self::add_signer_signature(
+            &mut proposal,
+            signer_key,
+            ctx.accounts.staking_config.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_create_and_sign_proposal", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Writes a new proposal for `multisig`, numbered from the proposal identifier and requiring the current multisig's
+    /// signers, after checking its threshold and signer count.
+    fn open_proposal(
+        proposal: &mut MultisigProposalAccount,
+        proposal_identifier: &mut IdentifierAccount,
+        current_multisig: &MultisigAccount,
+        creator: Pubkey,
+        multisig: Multisig,
     ) -> Result<()> {
         require!(
-            threshold <= MAX_THRESHOLD,
+            multisig.threshold <= MAX_THRESHOLD,
             MultisigErrorCode::ThresholdLimitReached
         );
 
         require!(
-            signers.len() <= MAX_SIGNERS,
+            multisig.signers.len() <= MAX_SIGNERS,
             MultisigErrorCode::SignerLimitReached
         );
 
-        let proposal_identifier = &mut ctx.accounts.proposal_identifier;
+        let required_signers: Vec<Pubkey> = current_multisig.signers.iter().map(|d| d.pubkey).collect();
 
-        let current_multisig = &ctx.accounts.current_multisig;
-        let required_signers = current_multisig.signers.iter().map(|d| d.pubkey).collect();
+        proposal.write(
+            proposal_identifier.next_id()?,
+            &multisig,
+            &required_signers,
+            current_multisig.mode,
+            current_multisig.min_approval_weight,
+        )?;
+        proposal.record_creation(creator)
+    }
 
-        let proposal = &mut ctx.accounts.proposal;
-        proposal.id = proposal_identifier.id;
-        proposal.data = Multisig {
-            name,
-            threshold,
-            signers,
+    /// Records `signer_key`'s signature on a pending proposal, with its staking voting power in `StakeWeighted` mode.
+    ///
+    /// The weight only counts positions left unchanged since the proposal was created, so tokens moved between members
+    /// while the proposal is pending are not counted twice.
+    fn add_signer_signature(
+        proposal: &mut MultisigProposalAccount,
+        signer_key: Pubkey,
+        staking_config: Option<&StakingConfigAccount>,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<()> {
+        proposal.sync_signed_mask();
+        proposal.check_can_sign(&signer_key)?;
+
+        let weight = match proposal.mode() {
+            MultisigMode::Unanimous => 0,
+            MultisigMode::StakeWeighted => {
+                let staking_config = staking_config.ok_or(MultisigErrorCode::StakingConfigRequired)?;
+                staked_voting_power(&signer_key, staking_config, remaining_accounts, proposal.created_at)?
+            }
         };
-        proposal.required_signers = required_signers;
-        proposal.signers = Vec::new();
-        proposal.status = MultisigProposalStatus::Pending;
-        
-        proposal_identifier.id += 1;
 
-        Ok(())
+        proposal.add_signature(signer_key, weight)?;
+        proposal.record_update(signer_key)
     }
         
     /// Signs a multisig proposal by the calling signer.
     ///
     /// This function performs the following checks and actions:
+    /// - Checks and advances the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
     /// - Ensures the proposal status is `Pending`.
     /// - Verifies that the signer is among the required signers (if any are specified).
-    /// - Ensures the signer has not already signed the proposal.
+    /// - Ensures the signer has not already signed the proposal, using the proposal's signature bitmap when it has required signers.
     /// - Adds the signer's public key to the list of signers for the proposal.
+    /// - In `StakeWeighted` mode, records the signer's staking voting power, computed from the staking registries
+    ///   passed as remaining accounts. Only reward-earning stakes of the reward mint that have not changed since the
+    ///   proposal was created count, so the same tokens cannot be moved to another signer and counted again.
     ///
     /// ## Arguments
     ///
     /// * `ctx` - The context containing the accounts required for signing the proposal.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
     ///
     /// ## Errors
     ///
@@ -328,34 +926,25 @@ impl MultisigInstructions {
     /// - The proposal is not in the `Pending` state.
     /// - The signer is not authorized to sign the proposal.
     /// - The signer has already signed the proposal.
+    /// - The proposal is in `StakeWeighted` mode and the staking configuration is not passed.
+    /// - A remaining account is not one of the signer's staking registries.
     ///
     /// ## Returns
     ///
     /// Returns `Ok(())` if the proposal is signed successfully, otherwise returns an error.
-    pub fn sign_proposal(ctx: Context<MultisigSignProposal>) -> Result<()> {
+    pub fn sign_proposal(ctx: Context<MultisigSignProposal>, expected_nonce: Option<u64>) -> Result<()> {
         let signer_key = ctx.accounts.signer.key();
-        let current_proposal = &mut ctx.accounts.current_proposal;
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
 
-        require!(
-            current_proposal.status == MultisigProposalStatus::Pending,
-            MultisigErrorCode::AlreadyResolved
-        );
+        let mut current_proposal = ctx.accounts.current_proposal.load_mut()?;
+        Self::add_signer_signature(
+            &mut current_proposal,
+            signer_key,
+            ctx.accounts.staking_config.as_deref(),
+            ctx.remaining_accounts,
+        )?;
 
-        if current_proposal.required_signers.len() > 0 {
-            require!(
-                current_proposal.required_signers.contains(&signer_key),
-                MultisigErrorCode::UnauthorizedSigner
-            );
-        }
-
-        if current_proposal.signers.len() > 0 {
-            require!(
-                !current_proposal.signers.contains(&signer_key),
-                MultisigErrorCode::DuplicateSignature
-            );
-        }
-
-        current_proposal.signers.push(signer_key);
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_sign_proposal", ctx.accounts.signer.key())?;
 
         Ok(())
     }
@@ -363,56 +952,248 @@ impl MultisigInstructions {
     /// Approves a multisig proposal if all required signatures have been collected.
     ///
     /// This function performs the following checks and actions:
+    /// - Once the program configuration exists and its CPI guard is enabled, checks that the approval is not invoked through CPI
+    ///   by a program outside the guard's allowlist.
+    /// - Checks and advances the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
     /// - Ensures the proposal status is `Pending`.
     /// - Verifies that the signer has already signed the proposal.
-    /// - Checks that all required signers have signed the proposal.
-    /// - Updates the current multisig account with the proposal's data (name, threshold, signers).
+    /// - In `Unanimous` mode, checks that all required signers have signed the proposal, by comparing its signature bitmap to the full mask.
+    /// - In `StakeWeighted` mode, checks that the signers' combined voting weight reaches the minimum approval weight.
+    /// - Grows the multisig account, at the signer's expense, when it has no room for the proposed signers.
+    /// - On a dry run, emits a `MultisigApprovalPreviewEvent` with the multisig the proposal would install and fails with
+    ///   `DryRunCompleted`, so a simulation shows the outcome without committing it.
+    /// - Updates the current multisig account with the proposal's data (name, threshold, signers, mode).
     /// - Sets the proposal status to `Approved`.
     ///
     /// ## Arguments
     ///
     /// * `ctx` - The context containing the accounts required for approving the proposal.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
+    /// * `dry_run` - Whether to only preview the approval, failing once every check has passed.
     ///
     /// ## Errors
     ///
     /// Returns an error if:
     /// - The proposal is not in the `Pending` state.
     /// - The signer has not signed the proposal.
-    /// - Not all required signers have signed the proposal.
+    /// - Not all required signers have signed the proposal, or their voting weight is insufficient.
+    /// - `dry_run` is set, with `DryRunCompleted` once every other check has passed.
     ///
     /// ## Returns
     ///
     /// Returns `Ok(())` if the proposal is approved successfully, otherwise returns an error.
-    pub fn approve_proposal(ctx: Context<MultisigApproveProposal>) -> Result<()> {
-        let signer_key = ctx.accounts.signer.key();
-        let current_proposal = &mut ctx.accounts.current_proposal;
+    pub fn approve_proposal(ctx: Context<MultisigApproveProposal>, expected_nonce: Option<u64>, dry_run: bool) -> Result<()> {
+        if !ctx.accounts.program_config.data_is_empty() {
+            let program_config = ProgramConfigAccount::try_deserialize(&mut &ctx.accounts.program_config.try_borrow_data()?[..])?;
+            program_config.check_cpi_caller(&ctx.accounts.instructions_sysvar)?;
+        }
+
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
+        let mut current_proposal = ctx.accounts.current_proposal.load_mut()?;
+        let data = current_proposal.approve(&ctx.accounts.signer.key())?;
+        let current_multisig = &mut ctx.accounts.current_multisig;
+        grow_account(
+            &current_multisig.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + MultisigAccount::len_for(data.signers.len()),
+        )?;
+
+        if dry_run {
+            emit!(MultisigApprovalPreviewEvent {
+                proposal_id: current_proposal.id,
+                signer: ctx.accounts.signer.key(),
+                multisig: data,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+
+            return err!(SimulationErrorCode::DryRunCompleted);
+        }
+
+        current_multisig.name = data.name;
+        current_multisig.threshold = data.threshold;
+        current_multisig.signers = data.signers;
+        current_multisig.mode = data.mode;
+        current_multisig.min_approval_weight = data.min_approval_weight;
+        current_multisig.record_update(ctx.accounts.signer.key())?;
+        current_proposal.record_update(ctx.accounts.signer.key())?;
+
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_approve_proposal", ctx.accounts.signer.key())?;
+        crate::log_info!(
+            "multisig_approve_proposal",
+            signer = ctx.accounts.signer.key(),
+            threshold = current_multisig.threshold,
+            signers = current_multisig.signers.len()
+        );
 
+        Ok(())
+    }
+
+    /// Closes an approved proposal, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following checks and actions:
+    /// - Ensures a rent recipient is configured and the signer is one of the multisig's signers.
+    /// - Ensures the proposal has been approved; pending proposals are kept.
+    /// - Emits an `AccountClosedEvent`; the account is closed on exit.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the proposal, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the proposal is
+    /// still pending.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is closed, otherwise returns an error.
+    pub fn close_proposal(ctx: Context<MultisigCloseProposal>) -> Result<()> {
         require!(
-            current_proposal.status == MultisigProposalStatus::Pending,
-            MultisigErrorCode::AlreadyResolved
+            !ctx.accounts.current_proposal.load()?.is_pending(),
+            MultisigErrorCode::ProposalNotResolved
         );
 
-        if current_proposal.signers.len() > 0 {
-            require!(
-                current_proposal.signers.iter().any(|s| *s == signer_key),
-                MultisigErrorCode::UnauthorizedSigner
+        AccountClosedEvent::emit(&ctx.accounts.current_proposal.to_account_info(), ctx.accounts.rent_recipient.key())?;
+
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_close_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Converts a proposal created with an older layout to the current zero-copy layout.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the account is a proposal, by its discriminator, and that it is not yet sized for the current layout.
+    /// - For a zero-copy proposal created before the audit metadata, grows it in place, at the signer's expense, leaving
+    ///   the metadata zeroed, and stamps `MultisigProposalAccount::VERSION`.
+    /// - Otherwise, decodes the proposal in the older serialized layout, including its collected signatures, weights, and
+    ///   status, then resizes it to the zero-copy size, topping up its rent from the signer if needed, and rewrites it
+    ///   with `MultisigProposalAccount::VERSION`.
+    /// - Emits an `AccountMigratedEvent`.
+    ///
+    /// Anyone can crank the migration.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer and the proposal to convert.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if:
+    /// - The program is halted.
+    /// - The account is not a proposal, or it already uses the zero-copy layout.
+    /// - A name in the proposal is longer than `MAX_MULTISIG_NAME_LEN` bytes.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is converted, otherwise returns an error.
+    pub fn migrate_proposal(ctx: Context<MultisigMigrateProposal>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let proposal = ctx.accounts.proposal.to_account_info();
+        let space = MultisigProposalAccount::SPACE;
+        require!(
+            proposal.data_len() != space,
+            VersioningErrorCode::AccountAlreadyMigrated
+        );
+        require!(
+            proposal.try_borrow_data()?.starts_with(MultisigProposalAccount::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        if proposal.data_len() == MultisigProposalAccount::UNAUDITED_SPACE {
+            grow_account(
+                &proposal,
+                &ctx.accounts.signer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                space,
+            )?;
+
+            let mut data = proposal.try_borrow_mut_data()?;
+            let migrated: &mut MultisigProposalAccount = bytemuck::from_bytes_mut(
+                &mut data[DISCRIMINATOR..DISCRIMINATOR + std::mem::size_of::<MultisigProposalAccount>()],
             );
+            let previous_version = migrated.version;
+            migrated.sync_signed_mask();
+            migrated.version = MultisigProposalAccount::VERSION;
+
+            emit!(AccountMigratedEvent {
+                account: proposal.key(),
+                previous_version,
+                version: migrated.version,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+
+            return StateMutationEvent::emit(EventModule::Multisig, "multisig_migrate_proposal", ctx.accounts.signer.key());
         }
 
-        let all_signed = current_proposal
-            .required_signers
-            .iter()
-            .all(|req| current_proposal.signers.contains(req));
+        let legacy = {
+            let data = proposal.try_borrow_data()?;
+            LegacyMultisigProposal::deserialize(&mut &data[DISCRIMINATOR..])?
+        };
 
-        require!(all_signed, MultisigErrorCode::InsufficientSignatures);
+        grow_account(
+            &proposal,
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            space,
+        )?;
+        proposal.realloc(space, false)?;
 
-        let current_multisig = &mut ctx.accounts.current_multisig;
-        current_multisig.name = current_proposal.data.name.clone();
-        current_multisig.threshold = current_proposal.data.threshold;
-        current_multisig.signers = current_proposal.data.signers.clone();
+        let mut data = proposal.try_borrow_mut_data()?;
+        let migrated: &mut MultisigProposalAccount = bytemuck::from_bytes_mut(
+            &mut data[DISCRIMINATOR..DISCRIMINATOR + std::mem::size_of::<MultisigProposalAccount>()],
+        );
+        *migrated = MultisigProposalAccount::zeroed();
+        migrated.write(
+            legacy.id,
+            &legacy.data,
+            &legacy.required_signers,
+            legacy.approval_mode,
+            legacy.min_approval_weight,
+        )?;
+        for (index, signer) in legacy.signers.iter().enumerate() {
+            migrated.add_signature(*signer, legacy.signer_weights.get(index).copied().unwrap_or(0))?;
+        }
+        migrated.status = legacy.status as u8;
 
-        current_proposal.status = MultisigProposalStatus::Approved;
+        emit!(AccountMigratedEvent {
+            account: proposal.key(),
+            previous_version: 1,
+            version: migrated.version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Multisig, "multisig_migrate_proposal", ctx.accounts.signer.key())?;
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Returns a view of the multisig's current configuration: its name, threshold, signers, approval mode, and the id
+    /// the next proposal will take.
+    ///
+    /// The result is exposed as the instruction's return data, so clients can read it by simulating the transaction and
+    /// other programs through CPI, independently of the account layout.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the multisig account and the proposal identifier.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the multisig's [`MultisigStateView`].
+    pub fn get_state(ctx: Context<MultisigGetState>) -> Result<MultisigStateView> {
+        let multisig = &ctx.accounts.multisig;
+
+        Ok(MultisigStateView {
+            name: multisig.name.clone(),
+            threshold: multisig.threshold,
+            signers: multisig.signers.clone(),
+            mode: multisig.mode,
+            min_approval_weight: multisig.min_approval_weight,
+            next_proposal_id: ctx.accounts.proposal_identifier.id,
+        })
+    }
+}
@@ -0,0 +1,123 @@
+//! # Nonce Module
+//!
+//! This module implements per-wallet replay protection nonces, so integrators relaying or queueing transactions can retry
+//! an operation without risking that it is applied twice.
+//!
+//! ## Features
+//!
+//! - **Per-Wallet Counters:** Each wallet opens one nonce account at `[WALLET_NONCE_SEED, wallet]`, counting the operations
+//!   it has submitted with an expected nonce.
+//! - **Opt-In Checks:** Signing multisig, vault transfer and staking proposals, approving multisig proposals, executing vault
+//!   transfer proposals, and thawing stakes take an optional `expected_nonce`. When it is given, the signer's nonce account
+//!   must be passed and hold exactly that value, and is advanced by one, so a retried transaction carrying the same nonce
+//!   fails instead of being applied again.
+//! - **Backward Compatible:** Omitting `expected_nonce` leaves these instructions unchanged, and the nonce account may then
+//!   be left out.
+//!
+//! ## Main Data Structures
+//!
+//! - [`WalletNonceAccount`]: Stores a wallet's next expected nonce.
+//!
+//! ## Instructions
+//!
+//! - [`NonceInstructions::initialize`]: Opens the signer's nonce account at zero.
+use anchor_lang::prelude::*;
+use crate::states::{
+    errors::{MathErrorCode, NonceErrorCode},
+    events::{EventModule, StateMutationEvent},
+    pda::WALLET_NONCE_SEED,
+    versioning::VersionedAccount,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct WalletNonceAccount {
+    pub wallet: Pubkey,
+    pub nonce: u64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl WalletNonceAccount {
+    /// Checks that `expected_nonce` is the wallet's current nonce and advances it by one.
+    pub fn consume(&mut self, expected_nonce: u64) -> Result<()> {
+        require!(self.nonce == expected_nonce, NonceErrorCode::NonceMismatch);
+        self.nonce = self.nonce.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+
+        Ok(())
+    }
+}
+
+impl VersionedAccount for WalletNonceAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + WalletNonceAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// Consumes `expected_nonce` from the signer's nonce account, if one is expected.
+///
+/// Instructions taking an optional `expected_nonce` call this before changing any state; without an expected nonce the
+/// nonce account, if passed, is left untouched.
+pub fn consume_nonce(wallet_nonce: &mut Option<Account<'_, WalletNonceAccount>>, expected_nonce: Option<u64>) -> Result<()> {
+    let Some(expected_nonce) = expected_nonce else {
+        return Ok(());
+    };
+
+    wallet_nonce
+        .as_mut()
+        .ok_or(NonceErrorCode::NonceAccountRequired)?
+        .consume(expected_nonce)
+}
+
+#[derive(Accounts)]
+pub struct NonceInitialization<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = WalletNonceAccount::SPACE,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Account<'info, WalletNonceAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub struct NonceInstructions;
+
+impl NonceInstructions {
+    /// Opens the signer's nonce account, starting at zero.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the payer, and the nonce account to create.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the nonce account is created successfully, otherwise returns an error.
+    pub fn initialize(ctx: Context<NonceInitialization>) -> Result<()> {
+        ctx.accounts.wallet_nonce.set_inner(WalletNonceAccount {
+            wallet: ctx.accounts.signer.key(),
+            nonce: 0,
+            version: WalletNonceAccount::VERSION,
+            bump: ctx.bumps.wallet_nonce,
+        });
+
+        StateMutationEvent::emit(EventModule::Nonce, "nonce_initialize", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! # Oracle Module
+//!
+//! This module reads SOL/USD prices from Pyth, with Switchboard as a fallback, so the teleport fee can be set in USD cents
+//! and converted to lamports at execution time instead of being re-tuned by governance as the SOL price moves.
+//!
+//! ## Features
+//!
+//! - **Pyth Price Updates:** Prices are read from the `PriceUpdateV2` accounts the Pyth receiver program writes once it has
+//!   verified a price update; only fully verified updates for the configured feed are accepted.
+//! - **Switchboard Fallback:** A Switchboard On-Demand pull feed can back the Pyth feed. While both prices pass their
+//!   checks they must agree within the configured divergence, and if either fails the other is used alone, so a single
+//!   oracle outage halts nothing and a single manipulated oracle cannot price a fee on its own.
+//! - **Staleness and Confidence Checks:** A price older than the configured staleness window, or whose confidence interval is
+//!   wider than the configured share of the price, is rejected rather than used to price a fee.
+//! - **USD Conversion:** A USD amount in cents is converted to lamports at the price, rounding up so the vault never
+//!   collects less than the configured fee.
+//!
+//! ## Main Data Structures
+//!
+//! - [`OraclePrice`]: A price with its confidence interval, exponent, and publish time.
+//!
+//! ## Constants
+//!
+//! - `PYTH_RECEIVER_ID`: The Pyth receiver program owning the price update accounts.
+//! - `SWITCHBOARD_ON_DEMAND_ID`: The Switchboard On-Demand program owning the pull feed accounts.
+//! - `MAX_ORACLE_DIVERGENCE_BPS`: Upper bound for a governed divergence between the two oracles, in basis points.
+//! - `MAX_ORACLE_STALENESS`: Upper bound for a governed staleness window.
+//! - `MAX_ORACLE_CONFIDENCE_BPS`: Upper bound for a governed confidence interval, in basis points of the price.
+//! - `MAX_TELEPORT_FEE_USD_CENTS`: Upper bound for a governed USD teleport fee.
+use anchor_lang::prelude::*;
+use crate::states::{constants::BASIS_POINTS, errors::OracleErrorCode};
+
+/// The Pyth receiver program, which writes verified price updates on Solana.
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// The Switchboard On-Demand program, which writes pull feed results on Solana.
+pub const SWITCHBOARD_ON_DEMAND_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Upper bound for a governed staleness window, in seconds (1 hour).
+pub const MAX_ORACLE_STALENESS: i64 = 60 * 60;
+
+/// Upper bound for a governed confidence interval, in basis points of the price (10%).
+pub const MAX_ORACLE_CONFIDENCE_BPS: u16 = 1_000;
+
+/// Upper bound for a governed divergence between the Pyth and Switchboard prices, in basis points of the lower one (10%).
+pub const MAX_ORACLE_DIVERGENCE_BPS: u16 = 1_000;
+
+/// Upper bound for a governed USD teleport fee, in cents ($100).
+pub const MAX_TELEPORT_FEE_USD_CENTS: u64 = 10_000;
+
+/// Anchor discriminator of the receiver's `PriceUpdateV2` account.
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Tag of the `Full` verification level, set once the update's signatures have all been verified.
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+/// Anchor discriminator of Switchboard's `PullFeedAccountData` account.
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+/// Offset of the last update's timestamp in a pull feed: the discriminator, 32 oracle submissions, authority, queue, feed
+/// hash, initialization time, permissions, variance, minimum responses, name, padding, and sample settings come first.
+const PULL_FEED_TIMESTAMP_OFFSET: usize = 8 + 32 * 64 + 32 + 32 + 32 + 8 + 8 + 8 + 4 + 32 + 2 + 1 + 1;
+/// Offset of the current result in a pull feed: the last update's timestamp, the lookup table slot, and reserved space
+/// come after the timestamp's offset.
+const PULL_FEED_RESULT_OFFSET: usize = PULL_FEED_TIMESTAMP_OFFSET + 8 + 8 + 32;
+/// Exponent of the fixed-point values a pull feed stores.
+const PULL_FEED_EXPONENT: i32 = -18;
+/// Exponent pull feed values are scaled down to, so they fit the price's integers.
+const PULL_FEED_SCALED_EXPONENT: i32 = -8;
+/// Lamports per SOL, times the cents per dollar a fee is expressed in.
+const LAMPORTS_PER_SOL_CENT: u128 = 1_000_000_000 / 100;
+
+/// A price read from an oracle, worth `price * 10^exponent` USD per SOL, within `conf * 10^exponent`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Decodes a Pyth `PriceUpdateV2` account, checking that the receiver owns it, that it was fully verified, and that
+    /// it carries `feed_id`.
+    pub fn load_pyth(price_update: &AccountInfo, feed_id: &[u8; 32]) -> Result<Self> {
+        require_keys_eq!(*price_update.owner, PYTH_RECEIVER_ID, OracleErrorCode::InvalidPriceAccount);
+
+        let data = price_update.try_borrow_data()?;
+        require!(data.starts_with(&PRICE_UPDATE_V2_DISCRIMINATOR), OracleErrorCode::InvalidPriceAccount);
+
+        // The discriminator is followed by the write authority, then the verification level.
+        let mut fields = data.get(8 + 32..).ok_or(OracleErrorCode::InvalidPriceAccount)?;
+        let verification_level = u8::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        require!(verification_level == VERIFICATION_LEVEL_FULL, OracleErrorCode::InvalidPriceAccount);
+
+        let update_feed_id = <[u8; 32]>::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        require!(update_feed_id == *feed_id, OracleErrorCode::PriceFeedMismatch);
+
+        let price = i64::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        let conf = u64::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        let exponent = i32::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        let publish_time = i64::deserialize(&mut fields).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+
+        Ok(Self { price, conf, exponent, publish_time })
+    }
+
+    /// Decodes a Switchboard `PullFeedAccountData` account, checking that the On-Demand program owns it and that it is the
+    /// configured `feed`. The result's median is used as the price and its standard deviation as the confidence interval.
+    pub fn load_switchboard(pull_feed: &AccountInfo, feed: &Pubkey) -> Result<Self> {
+        require_keys_eq!(*pull_feed.owner, SWITCHBOARD_ON_DEMAND_ID, OracleErrorCode::InvalidPriceAccount);
+        require_keys_eq!(pull_feed.key(), *feed, OracleErrorCode::PriceFeedMismatch);
+
+        let data = pull_feed.try_borrow_data()?;
+        require!(data.starts_with(&PULL_FEED_DISCRIMINATOR), OracleErrorCode::InvalidPriceAccount);
+
+        let mut timestamp = data.get(PULL_FEED_TIMESTAMP_OFFSET..).ok_or(OracleErrorCode::InvalidPriceAccount)?;
+        let publish_time = i64::deserialize(&mut timestamp).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+
+        // The result starts with its median, then its standard deviation.
+        let mut result = data.get(PULL_FEED_RESULT_OFFSET..).ok_or(OracleErrorCode::InvalidPriceAccount)?;
+        let value = i128::deserialize(&mut result).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+        let std_dev = i128::deserialize(&mut result).map_err(|_| OracleErrorCode::InvalidPriceAccount)?;
+
+        let scale = 10i128.pow(PULL_FEED_SCALED_EXPONENT.abs_diff(PULL_FEED_EXPONENT));
+        let price = i64::try_from(value / scale).map_err(|_| OracleErrorCode::InvalidPrice)?;
+        let conf = u64::try_from(std_dev.unsigned_abs() / scale as u128).map_err(|_| OracleErrorCode::InvalidPrice)?;
+
+        Ok(Self { price, conf, exponent: PULL_FEED_SCALED_EXPONENT, publish_time })
+    }
+
+    /// Checks that the price is positive, was published at most `max_staleness` seconds before `now`, and that its
+    /// confidence interval is at most `max_confidence_bps` of the price.
+    pub fn check(&self, now: i64, max_staleness: i64, max_confidence_bps: u16) -> Result<()> {
+        require!(self.price > 0, OracleErrorCode::InvalidPrice);
+        require!(now.saturating_sub(self.publish_time) <= max_staleness, OracleErrorCode::StalePrice);
+        require!(
+            (self.conf as u128) * (BASIS_POINTS as u128) <= (self.price as u128) * (max_confidence_bps as u128),
+            OracleErrorCode::PriceConfidenceTooWide
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the price and `other` differ by at most `max_divergence_bps` of the lower of the two.
+    pub fn check_divergence(&self, other: &OraclePrice, max_divergence_bps: u16) -> Result<()> {
+        let exponent = self.exponent.min(other.exponent);
+        let (price, other_price) = (self.scaled_to(exponent)?, other.scaled_to(exponent)?);
+        require!(
+            price.abs_diff(other_price) * (BASIS_POINTS as u128) <= price.min(other_price) * (max_divergence_bps as u128),
+            OracleErrorCode::PriceDivergence
+        );
+
+        Ok(())
+    }
+
+    /// Returns the price expressed with `exponent`, which must not be above the price's own.
+    fn scaled_to(&self, exponent: i32) -> Result<u128> {
+        require!(self.price > 0, OracleErrorCode::InvalidPrice);
+        let scale = 10u128.checked_pow(self.exponent.abs_diff(exponent)).ok_or(OracleErrorCode::InvalidPrice)?;
+
+        Ok((self.price as u128).checked_mul(scale).ok_or(OracleErrorCode::InvalidPrice)?)
+    }
+
+    /// Converts `usd_cents` to lamports at the price, rounding up.
+    pub fn usd_cents_to_lamports(&self, usd_cents: u64) -> Result<u64> {
+        require!(self.price > 0, OracleErrorCode::InvalidPrice);
+
+        let scale = 10u128.checked_pow(self.exponent.unsigned_abs()).ok_or(OracleErrorCode::InvalidPrice)?;
+        let lamports = (usd_cents as u128).checked_mul(LAMPORTS_PER_SOL_CENT).ok_or(OracleErrorCode::InvalidPrice)?;
+        let (numerator, denominator) = if self.exponent < 0 {
+            (lamports.checked_mul(scale).ok_or(OracleErrorCode::InvalidPrice)?, self.price as u128)
+        } else {
+            (lamports, (self.price as u128).checked_mul(scale).ok_or(OracleErrorCode::InvalidPrice)?)
+        };
+
+        u64::try_from(numerator.div_ceil(denominator)).map_err(|_| error!(OracleErrorCode::InvalidPrice))
+    }
+}
@@ -0,0 +1,1715 @@
+//! # Program Config Module
+//!
+//! This module implements the program-wide configuration shared by the multisig, vault, and staking modules. It gathers the
+//! module switches, fee parameters, and authority that used to be hard-coded constants into a single governed account.
+//!
+//! ## Features
+//!
+//! - **Genesis Creation:** The configuration PDA is created once, together with the multisig by `initialize_program`, with
+//!   the genesis teleport fee, guardian, and rent recipient, and records the multisig account as its governing authority.
+//! - **Module Switches:** Governance can disable the vault or staking modules. A disabled vault rejects teleports and transfer
+//!   executions; disabled staking rejects new freezes and stakes, while exits keep working so funds stay recoverable.
+//! - **Teleport Controls:** The teleport fee is read from the configuration instead of `MIMING_FEE`, and teleports can be paused
+//!   on their own while the rest of the vault keeps operating.
+//! - **Multisig Governance:** The configuration changes only through approved `UpdateProgramConfig` staking proposals.
+//! - **Emergency Stop:** A multisig-appointed guardian can halt the whole program in one transaction. Every user-facing and
+//!   permissionless handler of the vault and staking modules rejects calls while halted; only an approved `ResumeProgram`
+//!   proposal lifts the stop. Governance keeps working so the multisig can remediate before resuming.
+//! - **CPI Guard:** Once governance enables it, the treasury paths (multisig approvals, transfer executions, and thaws) read the
+//!   instructions sysvar and reject invocations arriving through CPI from any program other than the allowlisted composers.
+//! - **Roles:** The configuration records the holders of the guardian, operator, and relayer roles checked by the
+//!   [`access_control`](crate::access_control) helpers; governance appoints the operator and relayer through `ConfigureRole`
+//!   proposals.
+//! - **Hub Emitter:** The configuration records the Wormhole chain and emitter address of the hub, the only sender whose
+//!   messages inbound releases accept, as the hub registry does; governance sets it through `ConfigureHubEmitter` proposals.
+//! - **Hub Registry:** Governance replaces the [`hub_registry`](crate::hub_registry)'s hub and supported teleport routes,
+//!   each with its limit, through `ConfigureHubRegistry` proposals; the hub is mirrored in the configuration, and
+//!   `ConfigureHubEmitter` proposals update it in both accounts while keeping the routes.
+//! - **Messaging Transport:** The configuration records the [`messaging`](crate::messaging) transport teleports and releases
+//!   travel over, and its endpoint program; governance switches them through `ConfigureMessaging` proposals.
+//! - **USD Teleport Fee:** Governance can set the teleport fee in USD cents through `ConfigureUsdFee` proposals, converted to
+//!   lamports at each teleport from the configured Pyth SOL/USD feed, within the configured staleness and confidence
+//!   bounds. A zero USD fee keeps the lamport fee.
+//! - **Oracle Fallback:** Governance can back the Pyth feed with a Switchboard pull feed through `ConfigureOracleFallback`
+//!   proposals; the two prices must agree within the configured divergence, and either one is used alone while the other
+//!   is unavailable.
+//! - **Deposit Receipts:** Governance sets, through `ConfigureDepositReceipts` proposals, the teleport amount from which
+//!   depositors can mint a Metaplex receipt NFT, and the URI prefix of its metadata. A zero threshold disables receipts.
+//! - **Squads Authority:** Governance can register a Squads multisig and vault index through `ConfigureSquadsAuthority`
+//!   proposals; the derived [`squads`](crate::squads) vault may then execute vault transfer proposals, its Squads approval
+//!   standing in for the council's signatures. The default multisig clears it.
+//! - **Realms Governance:** Governance can register an SPL Governance (Realms) governance account through
+//!   `ConfigureRealmsGovernance` proposals; the [`realms`](crate::realms) governance may then execute the configuration's
+//!   proposals and vault transfer proposals without the council's signatures, its voters' approval standing in for them.
+//!   The default key clears it.
+//! - **Compressed Receipts:** Governance can register, through `ConfigureCompressedReceipts` proposals, a
+//!   [`bubblegum`](crate::bubblegum) tree delegated to the deposit receipt authority, into which depositors can mint
+//!   compressed receipt NFTs instead of full ones. The default key clears it.
+//! - **Relayer Fee:** Governance sets, through `ConfigureRelayerFee` proposals, the share of each posted teleport and
+//!   completed inbound transfer paid to the wallet submitting it, up to `MAX_RELAYER_FEE_BPS`, so third-party relayers are
+//!   paid to keep the spoke in sync with the hub. A zero fee disables it.
+//!
+//! ## Main Data Structures
+//!
+//! - [`ProgramConfigAccount`]: Stores the governing authority, guardian, module switches, teleport fee, teleport pause switch,
+//!   emergency stop flag, the rent recipient for closed governance accounts, the CPI guard with its allowlisted callers, and
+//!   the operator and relayer, the hub emitter, the messaging transport and endpoint, the USD teleport fee with its
+//!   price feeds, the deposit receipt threshold and compressed receipt tree, the registered Squads vault and Realms
+//!   governance, and the relayer fee, along with who last changed it and when.
+//!
+//! ## Instructions
+//!
+//! - [`ProgramConfigInstructions::update`]: Executes an approved proposal that changes the module switches, teleport settings,
+//!   guardian, and rent recipient.
+//! - [`ProgramConfigInstructions::emergency_pause`]: Lets the guardian halt the program instantly.
+//! - [`ProgramConfigInstructions::resume`]: Executes an approved proposal that lifts the emergency stop.
+//! - [`ProgramConfigInstructions::configure_cpi_guard`]: Executes an approved proposal that toggles the CPI guard and replaces
+//!   its allowlisted callers.
+//! - [`ProgramConfigInstructions::configure_role`]: Executes an approved proposal that appoints or clears the operator or
+//!   relayer.
+//! - [`ProgramConfigInstructions::configure_hub_emitter`]: Executes an approved proposal that sets the hub's Wormhole chain and
+//!   emitter address.
+//! - [`ProgramConfigInstructions::configure_hub_registry`]: Executes an approved proposal that sets the hub registry's hub
+//!   and supported teleport routes.
+//! - [`ProgramConfigInstructions::configure_messaging`]: Executes an approved proposal that switches the messaging transport
+//!   and its endpoint program.
+//! - [`ProgramConfigInstructions::configure_usd_fee`]: Executes an approved proposal that sets the USD teleport fee and its
+//!   SOL/USD price feed.
+//! - [`ProgramConfigInstructions::configure_oracle_fallback`]: Executes an approved proposal that sets the Switchboard feed
+//!   backing the Pyth feed and the divergence allowed between them.
+//! - [`ProgramConfigInstructions::configure_deposit_receipts`]: Executes an approved proposal that sets the deposit receipt
+//!   threshold and metadata URI prefix.
+//! - [`ProgramConfigInstructions::configure_squads_authority`]: Executes an approved proposal that registers or clears the
+//!   Squads vault approving vault transfer executions.
+//! - [`ProgramConfigInstructions::configure_realms_governance`]: Executes an approved proposal that registers or clears the
+//!   Realms governance acting for the council.
+//! - [`ProgramConfigInstructions::configure_compressed_receipts`]: Executes an approved proposal that registers or clears
+//!   the Bubblegum tree compressed deposit receipts are minted into.
+//! - [`ProgramConfigInstructions::configure_relayer_fee`]: Executes an approved proposal that sets the relayer fee.
+//! - [`ProgramConfigInstructions::get_settings`]: Returns the configuration's values as return data.
+//!
+//! ## Constants
+//!
+//! - `MAX_TELEPORT_FEE`: Upper bound for a governed teleport fee.
+//! - `MAX_CPI_CALLERS`: Maximum number of programs allowlisted to call the treasury paths through CPI.
+//! - `MAX_RECEIPT_URI_PREFIX_LEN`: Maximum length of the deposit receipts' metadata URI prefix.
+//! - `MAX_RELAYER_FEE_BPS`: Upper bound for a governed relayer fee.
+//! - `ProgramConfigAccount::INIT_SPACE`: The size of the program configuration account's fields, derived with `InitSpace`.
+//!
+//! ## Security Considerations
+//!
+//! - The configuration can only be created once, and its authority is always the multisig account.
+//! - Every change requires a staking proposal signed by all multisig signers.
+//! - The guardian can only halt the program, never resume it or change any other setting.
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use crate::{
+    access_control::{has_role, Role},
+    bubblegum::load_tree_delegate,
+    hub_registry::HubRegistryAccount,
+    messaging::MessagingTransport,
+    oracle::OraclePrice,
+    realms::load_governance_realm,
+    squads::{check_multisig, find_vault_authority_address},
+    states::{
+        events::{
+            EventModule, ProgramConfigCompressedReceiptsConfiguredEvent, ProgramConfigCpiGuardConfiguredEvent,
+            ProgramConfigDepositReceiptsConfiguredEvent,
+            ProgramConfigHubEmitterConfiguredEvent, ProgramConfigHubRegistryConfiguredEvent, ProgramConfigMessagingConfiguredEvent,
+            ProgramConfigOracleFallbackConfiguredEvent, ProgramConfigRealmsGovernanceConfiguredEvent,
+            ProgramConfigRelayerFeeConfiguredEvent, ProgramConfigRoleConfiguredEvent, ProgramConfigSquadsAuthorityConfiguredEvent, ProgramConfigUpdatedEvent, ProgramConfigUsdFeeConfiguredEvent, ProgramEmergencyPausedEvent, ProgramResumedEvent, StateMutationEvent,
+        },
+        errors::{
+            BubblegumErrorCode, MathErrorCode, OracleErrorCode, ProgramConfigErrorCode, RealmsErrorCode, SquadsErrorCode, StakingErrorCode,
+            VaultErrorCode,
+        },
+        pda::{find_deposit_receipt_authority_address, HUB_REGISTRY_SEED, PROGRAM_CONFIG_SEED, STAKING_PROPOSAL_SEED},
+        audit::Audited,
+        constants::BASIS_POINTS,
+        realloc::grow_account,
+        versioning::VersionedAccount,
+    },
+    staking::{StakingProposalAccount, StakingProposalAction},
+};
+
+/// Upper bound for a governed teleport fee, in lamports (1 SOL).
+pub const MAX_TELEPORT_FEE: u64 = 1_000_000_000;
+
+/// Maximum number of programs allowlisted to call the treasury paths through CPI.
+pub const MAX_CPI_CALLERS: usize = 4;
+
+/// Maximum length of the deposit receipts' metadata URI prefix, leaving room in Metaplex's 200-byte URI for the ledger id
+/// and the receipt hash.
+pub const MAX_RECEIPT_URI_PREFIX_LEN: usize = 64;
+
+/// Upper bound for a governed relayer fee, in basis points of the relayed amount (5%).
+pub const MAX_RELAYER_FEE_BPS: u16 = 500;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfigAccount {
+    pub authority: Pubkey,
+    pub vault_enabled: bool,
+    pub staking_enabled: bool,
+    pub teleport_paused: bool,
+    pub teleport_fee: u64,
+    pub guardian: Pubkey,
+    pub global_paused: bool,
+    pub version: u8,
+    pub rent_recipient: Pubkey,
+    pub bump: u8,
+    pub cpi_guard_enabled: bool,
+    #[max_len(MAX_CPI_CALLERS)]
+    pub cpi_allowed_callers: Vec<Pubkey>,
+    pub operator: Pubkey,
+    pub relayer: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
+    pub hub_chain: u16,
+    pub hub_emitter: [u8; 32],
+    pub messaging_transport: MessagingTransport,
+    pub messaging_endpoint: Pubkey,
+    pub teleport_fee_usd_cents: u64,
+    pub sol_usd_feed_id: [u8; 32],
+    pub oracle_max_staleness: i64,
+    pub oracle_max_confidence_bps: u16,
+    pub switchboard_sol_usd_feed: Pubkey,
+    pub oracle_max_divergence_bps: u16,
+    pub deposit_receipt_threshold: u64,
+    #[max_len(MAX_RECEIPT_URI_PREFIX_LEN)]
+    pub deposit_receipt_uri_prefix: String,
+    pub squads_multisig: Pubkey,
+    pub squads_vault_index: u8,
+    pub squads_vault_authority: Pubkey,
+    pub realms_governance: Pubkey,
+    pub realms_realm: Pubkey,
+    pub deposit_receipt_tree: Pubkey,
+    pub relayer_fee_bps: u16,
+}
+
+impl ProgramConfigAccount {
+    /// Returns the endpoint program of the messaging transport: the configured one, or the transport's default endpoint
+    /// while governance has not set one.
+    pub fn messaging_endpoint(&self) -> Pubkey {
+        if self.messaging_endpoint == Pubkey::default() {
+            self.messaging_transport.default_endpoint()
+        } else {
+            self.messaging_endpoint
+        }
+    }
+
+    /// Returns `true` if `key` is the registered Squads vault approving vault transfer executions.
+    pub fn is_squads_authority(&self, key: &Pubkey) -> bool {
+        self.squads_vault_authority != Pubkey::default() && self.squads_vault_authority == *key
+    }
+
+    /// Returns `true` if `key` is the registered Realms governance acting for the council.
+    pub fn is_realms_governance(&self, key: &Pubkey) -> bool {
+        self.realms_governance != Pubkey::default() && self.realms_governance == *key
+    }
+
+    /// Returns the share of `amount` paid to the wallet relaying it, rounded down.
+    pub fn relayer_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128) * (self.relayer_fee_bps as u128) / (BASIS_POINTS as u128);
+        u64::try_from(fee).map_err(|_| error!(MathErrorCode::ArithmeticError))
+    }
+
+    /// Checks that the program is not halted by an emergency stop.
+    pub fn check_not_globally_paused(&self) -> Result<()> {
+        require!(!self.global_paused, ProgramConfigErrorCode::GloballyPaused);
+
+        Ok(())
+    }
+
+    /// Checks that the vault module is enabled.
+    pub fn check_vault_enabled(&self) -> Result<()> {
+        require!(self.vault_enabled, VaultErrorCode::VaultDisabled);
+
+        Ok(())
+    }
+
+    /// Checks that the staking module is enabled before a new freeze or stake.
+    pub fn check_staking_enabled(&self) -> Result<()> {
+        require!(self.staking_enabled, StakingErrorCode::StakingDisabled);
+
+        Ok(())
+    }
+
+    /// Returns the teleport fee in lamports: the lamport fee, or, while a USD fee is set, the USD fee converted at the
+    /// SOL/USD price read by [`Self::sol_usd_price`].
+    pub fn teleport_fee_lamports(
+        &self,
+        price_update: Option<&AccountInfo>,
+        fallback_feed: Option<&AccountInfo>,
+    ) -> Result<u64> {
+        if self.teleport_fee_usd_cents == 0 {
+            return Ok(self.teleport_fee);
+        }
+
+        self.sol_usd_price(price_update, fallback_feed)?.usd_cents_to_lamports(self.teleport_fee_usd_cents)
+    }
+
+    /// Returns the SOL/USD price from the Pyth `price_update` and, while a Switchboard fallback is configured, the
+    /// Switchboard `fallback_feed`, each of which must be fresh and precise enough for the configured bounds.
+    ///
+    /// When both prices pass their checks they must agree within the configured divergence, and the Pyth price is used.
+    /// When only one does, it is used alone; when neither does, the Pyth price's error is returned.
+    pub fn sol_usd_price(
+        &self,
+        price_update: Option<&AccountInfo>,
+        fallback_feed: Option<&AccountInfo>,
+    ) -> Result<OraclePrice> {
+        let now = Clock::get()?.unix_timestamp;
+        let checked = |price: OraclePrice| -> Result<OraclePrice> {
+            price.check(now, self.oracle_max_staleness, self.oracle_max_confidence_bps)?;
+            Ok(price)
+        };
+
+        let primary = price_update
+            .ok_or_else(|| error!(OracleErrorCode::PriceUpdateRequired))
+            .and_then(|account| OraclePrice::load_pyth(account, &self.sol_usd_feed_id))
+            .and_then(checked);
+        let fallback = match fallback_feed {
+            Some(account) if self.switchboard_sol_usd_feed != Pubkey::default() => {
+                OraclePrice::load_switchboard(account, &self.switchboard_sol_usd_feed).and_then(checked).ok()
+            }
+            _ => None,
+        };
+
+        match (primary, fallback) {
+            (Ok(price), Some(fallback)) => {
+                price.check_divergence(&fallback, self.oracle_max_divergence_bps)?;
+                Ok(price)
+            }
+            (Ok(price), None) => Ok(price),
+            (Err(_), Some(fallback)) => Ok(fallback),
+            (Err(error), None) => Err(error),
+        }
+    }
+
+    /// Checks, while the CPI guard is enabled, that the current instruction is either called directly or through CPI from
+    /// an allowlisted program.
+    ///
+    /// The instructions sysvar only lists the transaction's top-level instructions, so the program of the instruction
+    /// being executed is the outermost caller: this program itself for a direct call, or the program that invoked it.
+    pub fn check_cpi_caller(&self, instructions_sysvar: &AccountInfo) -> Result<()> {
+        if !self.cpi_guard_enabled {
+            return Ok(());
+        }
+
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+        let caller = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+        require!(
+            caller == crate::ID || self.cpi_allowed_callers.contains(&caller),
+            ProgramConfigErrorCode::CpiCallerNotAllowed
+        );
+
+        Ok(())
+    }
+}
+
+/// Version 2 appended `rent_recipient` after `version`; configurations migrated from version 1 leave it unset until
+/// governance configures it.
+/// Version 3 appended the cached `bump` of the configuration's address.
+/// Version 4 appended the CPI guard, which configurations migrated from version 3 leave disabled with no allowlisted callers.
+/// Version 5 appended the `operator` and `relayer` roles, which configurations migrated from version 4 leave unset.
+/// Version 6 appended the audit metadata, which configurations migrated from version 5 leave zeroed until their next write.
+/// Version 7 appended the hub emitter, which configurations migrated from version 6 leave unset until governance configures it.
+/// Version 8 appended the messaging transport and endpoint, which configurations migrated from version 7 read as Wormhole on
+/// its default core bridge.
+/// Version 9 appended the USD teleport fee and its price feed, which configurations migrated from version 8 leave disabled.
+/// Version 10 appended the Switchboard fallback feed, which configurations migrated from version 9 leave unset.
+/// Version 11 appended the deposit receipt settings, which configurations migrated from version 10 leave disabled.
+/// Version 12 appended the Squads authority, which configurations migrated from version 11 leave unset.
+/// Version 13 appended the Realms governance, which configurations migrated from version 12 leave unset.
+/// Version 14 appended the compressed receipt tree, which configurations migrated from version 13 leave unset.
+/// Version 15 appended the relayer fee, which configurations migrated from version 14 leave disabled.
+impl VersionedAccount for ProgramConfigAccount {
+    const VERSION: u8 = 15;
+    const SPACE: usize = 8 + ProgramConfigAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+impl Audited for ProgramConfigAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
+}
+
+/// A view of the program configuration's values, returned by [`ProgramConfigInstructions::get_settings`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ProgramConfigView {
+    pub authority: Pubkey,
+    pub guardian: Pubkey,
+    pub vault_enabled: bool,
+    pub staking_enabled: bool,
+    pub teleport_paused: bool,
+    pub teleport_fee: u64,
+    pub global_paused: bool,
+    pub rent_recipient: Pubkey,
+    pub cpi_guard_enabled: bool,
+    pub cpi_allowed_callers: Vec<Pubkey>,
+    pub operator: Pubkey,
+    pub relayer: Pubkey,
+    pub hub_chain: u16,
+    pub hub_emitter: [u8; 32],
+    pub messaging_transport: MessagingTransport,
+    pub messaging_endpoint: Pubkey,
+    pub teleport_fee_usd_cents: u64,
+    pub sol_usd_feed_id: [u8; 32],
+    pub oracle_max_staleness: i64,
+    pub oracle_max_confidence_bps: u16,
+    pub switchboard_sol_usd_feed: Pubkey,
+    pub oracle_max_divergence_bps: u16,
+    pub deposit_receipt_threshold: u64,
+    pub deposit_receipt_uri_prefix: String,
+    pub squads_multisig: Pubkey,
+    pub squads_vault_index: u8,
+    pub squads_vault_authority: Pubkey,
+    pub realms_governance: Pubkey,
+    pub realms_realm: Pubkey,
+    pub deposit_receipt_tree: Pubkey,
+    pub relayer_fee_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigGetSettings<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigUpdate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureCpiGuard<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureRole<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureHubEmitter<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = HubRegistryAccount::SPACE,
+        seeds = [HUB_REGISTRY_SEED],
+        bump
+    )]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureMessaging<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureUsdFee<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureOracleFallback<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureDepositReceipts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureSquadsAuthority<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    /// CHECK: The Squads multisig being registered, checked against the proposal and its owner in the handler
+    pub squads_multisig: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureRealmsGovernance<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    /// CHECK: The Realms governance being registered, checked against the proposal and its owner in the handler
+    pub realms_governance: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureCompressedReceipts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    /// CHECK: The Bubblegum configuration of the tree being registered, checked against the proposal in the handler
+    pub tree_config: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureHubRegistry<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = HubRegistryAccount::SPACE,
+        seeds = [HUB_REGISTRY_SEED],
+        bump
+    )]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigConfigureRelayerFee<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigEmergencyPause<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = has_role(&program_config, Role::Guardian, guardian.key) @ ProgramConfigErrorCode::UnauthorizedGuardian
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigResume<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub struct ProgramConfigInstructions;
+
+impl ProgramConfigInstructions {
+    /// Executes an approved `UpdateProgramConfig` proposal, changing the module switches, teleport settings, guardian,
+    /// and rent recipient.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds an `UpdateProgramConfig` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Writes the module switches, the teleport pause switch, the teleport fee, the guardian, and the rent recipient to
+    ///   the configuration.
+    /// - Emits a `ProgramConfigUpdatedEvent` with the new values.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the configuration is updated successfully, otherwise returns an error.
+    pub fn update(ctx: Context<ProgramConfigUpdate>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::UpdateProgramConfig {
+            vault_enabled,
+            staking_enabled,
+            teleport_paused,
+            teleport_fee,
+            guardian,
+            rent_recipient,
+        } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.vault_enabled = vault_enabled;
+        program_config.staking_enabled = staking_enabled;
+        program_config.teleport_paused = teleport_paused;
+        program_config.teleport_fee = teleport_fee;
+        program_config.guardian = guardian;
+        program_config.rent_recipient = rent_recipient;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            vault_enabled,
+            staking_enabled,
+            teleport_paused,
+            teleport_fee,
+            guardian,
+            rent_recipient,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_update", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Halts the whole program through an emergency stop.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the signer is the guardian appointed in the configuration.
+    /// - Sets the emergency stop flag, so every user-facing and permissionless handler rejects calls.
+    /// - Emits a `ProgramEmergencyPausedEvent`.
+    ///
+    /// The stop takes effect immediately and can only be lifted by an approved `ResumeProgram` proposal.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the guardian and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the program is halted successfully, otherwise returns an error.
+    pub fn emergency_pause(ctx: Context<ProgramConfigEmergencyPause>) -> Result<()> {
+        let program_config = &mut ctx.accounts.program_config;
+        require!(!program_config.global_paused, ProgramConfigErrorCode::AlreadyGloballyPaused);
+
+        program_config.global_paused = true;
+        program_config.record_update(ctx.accounts.guardian.key())?;
+
+        emit!(ProgramEmergencyPausedEvent {
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_emergency_pause", ctx.accounts.guardian.key())?;
+        crate::log_warn!("program_config_emergency_pause", guardian = ctx.accounts.guardian.key());
+
+        Ok(())
+    }
+
+    /// Executes an approved `ResumeProgram` proposal, lifting the emergency stop.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ResumeProgram` action and that the program is halted.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Clears the emergency stop flag and emits a `ProgramResumedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the program is resumed successfully, otherwise returns an error.
+    pub fn resume(ctx: Context<ProgramConfigResume>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        require!(
+            current_staking_proposal.action == StakingProposalAction::ResumeProgram,
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        let program_config = &mut ctx.accounts.program_config;
+        require!(program_config.global_paused, ProgramConfigErrorCode::NotGloballyPaused);
+
+        current_staking_proposal.approve_by(program_config, &signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        program_config.global_paused = false;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramResumedEvent {
+            proposal_id: current_staking_proposal.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_resume", ctx.accounts.signer.key())?;
+        crate::log_info!("program_config_resume", signer = signer_key, proposal_id = current_staking_proposal.id);
+
+        Ok(())
+    }
+    /// Executes an approved `ConfigureCpiGuard` proposal, toggling the CPI guard and replacing its allowlisted callers.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureCpiGuard` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the CPI guard, at the signer's expense, to fit the allowlist.
+    /// - Writes the guard switch and the allowlisted callers to the configuration.
+    /// - Emits a `ProgramConfigCpiGuardConfiguredEvent` with the new values.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the CPI guard is configured successfully, otherwise returns an error.
+    pub fn configure_cpi_guard(ctx: Context<ProgramConfigConfigureCpiGuard>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureCpiGuard { enabled, allowed_callers } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.cpi_guard_enabled = enabled;
+        program_config.cpi_allowed_callers = allowed_callers.clone();
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigCpiGuardConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            enabled,
+            allowed_callers,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_cpi_guard", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureRole` proposal, appointing the operator or relayer, or clearing it with the default key.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureRole` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the roles, at the signer's expense, to fit them.
+    /// - Records the new holder of the role in the configuration.
+    /// - Emits a `ProgramConfigRoleConfiguredEvent` with the previous and new holders.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the role is configured successfully, otherwise returns an error.
+    pub fn configure_role(ctx: Context<ProgramConfigConfigureRole>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureRole { role, holder } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_holder = match role {
+            Role::Operator => std::mem::replace(&mut program_config.operator, holder),
+            Role::Relayer => std::mem::replace(&mut program_config.relayer, holder),
+            Role::Admin | Role::Guardian | Role::Member => return err!(StakingErrorCode::InvalidConfigValue),
+        };
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigRoleConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            role,
+            previous_holder,
+            holder,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_role", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureHubEmitter` proposal, setting the hub whose Wormhole messages inbound releases accept.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureHubEmitter` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the hub emitter, at the signer's expense, to fit it.
+    /// - Records the hub's chain and emitter address in the configuration and in the hub registry, created at the signer's
+    ///   expense for deployments bootstrapped before it, keeping the registry's routes.
+    /// - Emits a `ProgramConfigHubEmitterConfiguredEvent` with the previous and new emitters.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the program configuration, and the hub registry.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the hub emitter is configured successfully, otherwise returns an error.
+    pub fn configure_hub_emitter(ctx: Context<ProgramConfigConfigureHubEmitter>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureHubEmitter { chain, emitter } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_chain = std::mem::replace(&mut program_config.hub_chain, chain);
+        let previous_emitter = std::mem::replace(&mut program_config.hub_emitter, emitter);
+        program_config.record_update(signer_key)?;
+
+        let hub_registry = &mut ctx.accounts.hub_registry;
+        hub_registry.initialize_if_needed(ctx.bumps.hub_registry);
+        hub_registry.hub_chain = chain;
+        hub_registry.hub_emitter = emitter;
+
+        emit!(ProgramConfigHubEmitterConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_chain,
+            previous_emitter,
+            chain,
+            emitter,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_hub_emitter", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureMessaging` proposal, switching the transport teleports and releases travel over.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureMessaging` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the messaging transport, at the signer's expense, to fit it.
+    /// - Records the transport and its endpoint program in the configuration.
+    /// - Emits a `ProgramConfigMessagingConfiguredEvent` with the previous and new endpoints.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the messaging transport is configured successfully, otherwise returns an error.
+    pub fn configure_messaging(ctx: Context<ProgramConfigConfigureMessaging>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureMessaging { transport, endpoint } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_endpoint = program_config.messaging_endpoint();
+        let previous_transport = std::mem::replace(&mut program_config.messaging_transport, transport);
+        program_config.messaging_endpoint = endpoint;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigMessagingConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_transport,
+            previous_endpoint,
+            transport,
+            endpoint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_messaging", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureUsdFee` proposal, setting the teleport fee in USD cents and the price feed it is
+    /// converted with.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureUsdFee` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the USD fee, at the signer's expense, to fit it.
+    /// - Records the USD fee, the Pyth SOL/USD feed id, and the staleness and confidence bounds in the configuration.
+    /// - Emits a `ProgramConfigUsdFeeConfiguredEvent` with the previous and new fees.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the USD fee is configured successfully, otherwise returns an error.
+    pub fn configure_usd_fee(ctx: Context<ProgramConfigConfigureUsdFee>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureUsdFee { usd_cents, feed_id, max_staleness, max_confidence_bps } =
+            current_staking_proposal.action.clone()
+        else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_usd_cents = std::mem::replace(&mut program_config.teleport_fee_usd_cents, usd_cents);
+        program_config.sol_usd_feed_id = feed_id;
+        program_config.oracle_max_staleness = max_staleness;
+        program_config.oracle_max_confidence_bps = max_confidence_bps;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigUsdFeeConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_usd_cents,
+            usd_cents,
+            feed_id,
+            max_staleness,
+            max_confidence_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_usd_fee", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureOracleFallback` proposal, setting the Switchboard feed that backs the Pyth SOL/USD
+    /// feed, or clearing it with the default key.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureOracleFallback` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the fallback feed, at the signer's expense, to fit it.
+    /// - Records the Switchboard feed and the divergence allowed between the two prices in the configuration.
+    /// - Emits a `ProgramConfigOracleFallbackConfiguredEvent` with the previous and new feeds.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the fallback feed is configured successfully, otherwise returns an error.
+    pub fn configure_oracle_fallback(ctx: Context<ProgramConfigConfigureOracleFallback>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureOracleFallback { feed, max_divergence_bps } =
+            current_staking_proposal.action.clone()
+        else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_feed = std::mem::replace(&mut program_config.switchboard_sol_usd_feed, feed);
+        program_config.oracle_max_divergence_bps = max_divergence_bps;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigOracleFallbackConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_feed,
+            feed,
+            max_divergence_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_oracle_fallback", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureDepositReceipts` proposal, setting the teleport amount from which depositors can mint
+    /// a receipt NFT and the URI prefix of its metadata.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureDepositReceipts` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the deposit receipt settings, at the signer's expense, to fit them.
+    /// - Records the threshold and the URI prefix in the configuration.
+    /// - Emits a `ProgramConfigDepositReceiptsConfiguredEvent` with the previous and new thresholds.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the deposit receipts are configured successfully, otherwise returns an error.
+    pub fn configure_deposit_receipts(ctx: Context<ProgramConfigConfigureDepositReceipts>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureDepositReceipts { threshold, uri_prefix } =
+            current_staking_proposal.action.clone()
+        else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_threshold = std::mem::replace(&mut program_config.deposit_receipt_threshold, threshold);
+        program_config.deposit_receipt_uri_prefix = uri_prefix.clone();
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigDepositReceiptsConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_threshold,
+            threshold,
+            uri_prefix,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_deposit_receipts", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureSquadsAuthority` proposal, registering the Squads vault that approves vault transfer
+    /// executions, or clearing it when the proposal names the default multisig.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureSquadsAuthority` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Checks, unless the authority is cleared, that the passed `squads_multisig` is the proposed multisig and a Squads
+    ///   `Multisig` account owned by the Squads program.
+    /// - Grows a configuration created before the Squads authority, at the signer's expense, to fit it.
+    /// - Records the multisig, the vault index, and the vault address derived from them in the configuration.
+    /// - Emits a `ProgramConfigSquadsAuthorityConfiguredEvent` with the previous and new vault addresses.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the program configuration, and the Squads multisig.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the Squads authority is configured successfully, otherwise returns an error.
+    pub fn configure_squads_authority(ctx: Context<ProgramConfigConfigureSquadsAuthority>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureSquadsAuthority { multisig, vault_index } = current_staking_proposal.action
+        else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let authority = if multisig == Pubkey::default() {
+            Pubkey::default()
+        } else {
+            let squads_multisig = ctx.accounts.squads_multisig.as_ref().ok_or(SquadsErrorCode::InvalidSquadsMultisig)?;
+            require_keys_eq!(squads_multisig.key(), multisig, SquadsErrorCode::InvalidSquadsMultisig);
+            check_multisig(squads_multisig)?;
+
+            find_vault_authority_address(&multisig, vault_index).0
+        };
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_authority = std::mem::replace(&mut program_config.squads_vault_authority, authority);
+        program_config.squads_multisig = multisig;
+        program_config.squads_vault_index = vault_index;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigSquadsAuthorityConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_authority,
+            multisig,
+            vault_index,
+            authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_squads_authority", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureRealmsGovernance` proposal, registering the SPL Governance (Realms) governance that
+    /// acts for the council, or clearing it when the proposal names the default key.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureRealmsGovernance` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Checks, unless the governance is cleared, that the passed `realms_governance` is the proposed governance and a
+    ///   `GovernanceV2` account owned by the SPL Governance program, and reads the realm it belongs to.
+    /// - Grows a configuration created before the Realms governance, at the signer's expense, to fit it.
+    /// - Records the governance and its realm in the configuration.
+    /// - Emits a `ProgramConfigRealmsGovernanceConfiguredEvent` with the previous and new governances.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the program configuration, and the Realms governance.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the Realms governance is configured successfully, otherwise returns an error.
+    pub fn configure_realms_governance(ctx: Context<ProgramConfigConfigureRealmsGovernance>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureRealmsGovernance { governance } = current_staking_proposal.action else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let realm = if governance == Pubkey::default() {
+            Pubkey::default()
+        } else {
+            let realms_governance =
+                ctx.accounts.realms_governance.as_ref().ok_or(RealmsErrorCode::InvalidRealmsGovernance)?;
+            require_keys_eq!(realms_governance.key(), governance, RealmsErrorCode::InvalidRealmsGovernance);
+
+            load_governance_realm(realms_governance)?
+        };
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_governance = std::mem::replace(&mut program_config.realms_governance, governance);
+        program_config.realms_realm = realm;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigRealmsGovernanceConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_governance,
+            governance,
+            realm,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_realms_governance", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureCompressedReceipts` proposal, registering the Bubblegum tree compressed deposit
+    /// receipts are minted into, or clearing it when the proposal names the default key.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureCompressedReceipts` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Checks, unless the tree is cleared, that the passed `tree_config` is the Bubblegum configuration of the proposed
+    ///   tree and delegates minting to the deposit receipt authority PDA.
+    /// - Grows a configuration created before compressed receipts, at the signer's expense, to fit the tree.
+    /// - Records the tree in the configuration.
+    /// - Emits a `ProgramConfigCompressedReceiptsConfiguredEvent` with the previous and new trees.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the program configuration, and the tree's Bubblegum configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tree is configured successfully, otherwise returns an error.
+    pub fn configure_compressed_receipts(ctx: Context<ProgramConfigConfigureCompressedReceipts>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureCompressedReceipts { merkle_tree } = current_staking_proposal.action else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        if merkle_tree != Pubkey::default() {
+            let tree_config = ctx.accounts.tree_config.as_ref().ok_or(BubblegumErrorCode::InvalidReceiptTree)?;
+            let tree_delegate = load_tree_delegate(tree_config, &merkle_tree)?;
+            require_keys_eq!(
+                tree_delegate,
+                find_deposit_receipt_authority_address().0,
+                BubblegumErrorCode::InvalidReceiptTree
+            );
+        }
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_tree = std::mem::replace(&mut program_config.deposit_receipt_tree, merkle_tree);
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigCompressedReceiptsConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_tree,
+            merkle_tree,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_compressed_receipts", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureHubRegistry` proposal, replacing the hub and the supported teleport routes of the hub
+    /// registry.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureHubRegistry` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Creates the hub registry, at the signer's expense, for deployments bootstrapped before it.
+    /// - Records the hub's chain and emitter address and the routes in the registry, and mirrors the hub in the
+    ///   configuration, growing a configuration created before the hub emitter to fit it.
+    /// - Emits a `ProgramConfigHubRegistryConfiguredEvent` with the previous and new hubs and the new routes.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the program configuration, and the hub registry.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the hub registry is configured successfully, otherwise returns an error.
+    pub fn configure_hub_registry(ctx: Context<ProgramConfigConfigureHubRegistry>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureHubRegistry { hub_chain, hub_emitter, routes } =
+            current_staking_proposal.action.clone()
+        else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let hub_registry = &mut ctx.accounts.hub_registry;
+        hub_registry.initialize_if_needed(ctx.bumps.hub_registry);
+        let previous_chain = std::mem::replace(&mut hub_registry.hub_chain, hub_chain);
+        let previous_emitter = std::mem::replace(&mut hub_registry.hub_emitter, hub_emitter);
+        hub_registry.routes = routes.clone();
+
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.hub_chain = hub_chain;
+        program_config.hub_emitter = hub_emitter;
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigHubRegistryConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_chain,
+            previous_emitter,
+            chain: hub_chain,
+            emitter: hub_emitter,
+            routes,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_hub_registry", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureRelayerFee` proposal, setting the share of posted teleports and completed inbound
+    /// transfers paid to the wallet submitting them.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureRelayerFee` action.
+    /// - Approves the proposal if all required multisig signers have signed it, or at once for the registered Realms
+    ///   governance.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Grows a configuration created before the relayer fee, at the signer's expense, to fit it.
+    /// - Records the fee in the configuration.
+    /// - Emits a `ProgramConfigRelayerFeeConfiguredEvent` with the previous and new fees.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the fee is configured successfully, otherwise returns an error.
+    pub fn configure_relayer_fee(ctx: Context<ProgramConfigConfigureRelayerFee>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureRelayerFee { fee_bps } = current_staking_proposal.action else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve_by(&ctx.accounts.program_config, &signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        grow_account(
+            &ctx.accounts.program_config.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramConfigAccount::INIT_SPACE,
+        )?;
+
+        let program_config = &mut ctx.accounts.program_config;
+        let previous_fee_bps = std::mem::replace(&mut program_config.relayer_fee_bps, fee_bps);
+        program_config.record_update(signer_key)?;
+
+        emit!(ProgramConfigRelayerFeeConfiguredEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_fee_bps,
+            fee_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::ProgramConfig, "program_config_configure_relayer_fee", signer_key)?;
+
+        Ok(())
+    }
+
+    /// Returns the program configuration's values: its authority, guardian, module switches, teleport settings, emergency
+    /// stop flag, rent recipient, CPI guard, operator, relayer, hub emitter, messaging transport, USD
+    /// teleport fee, oracle fallback, deposit receipt settings, Squads authority, Realms governance, compressed receipt
+    /// tree, and relayer fee.
+    ///
+    /// The result is exposed as the instruction's return data, so clients can read it by simulating the transaction and
+    /// other programs through CPI, independently of the account layout.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the program configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the configuration's [`ProgramConfigView`].
+    pub fn get_settings(ctx: Context<ProgramConfigGetSettings>) -> Result<ProgramConfigView> {
+        let program_config = &ctx.accounts.program_config;
+
+        Ok(ProgramConfigView {
+            authority: program_config.authority,
+            guardian: program_config.guardian,
+            vault_enabled: program_config.vault_enabled,
+            staking_enabled: program_config.staking_enabled,
+            teleport_paused: program_config.teleport_paused,
+            teleport_fee: program_config.teleport_fee,
+            global_paused: program_config.global_paused,
+            rent_recipient: program_config.rent_recipient,
+            cpi_guard_enabled: program_config.cpi_guard_enabled,
+            cpi_allowed_callers: program_config.cpi_allowed_callers.clone(),
+            operator: program_config.operator,
+            relayer: program_config.relayer,
+            hub_chain: program_config.hub_chain,
+            hub_emitter: program_config.hub_emitter,
+            messaging_transport: program_config.messaging_transport,
+            messaging_endpoint: program_config.messaging_endpoint(),
+            teleport_fee_usd_cents: program_config.teleport_fee_usd_cents,
+            sol_usd_feed_id: program_config.sol_usd_feed_id,
+            oracle_max_staleness: program_config.oracle_max_staleness,
+            oracle_max_confidence_bps: program_config.oracle_max_confidence_bps,
+            switchboard_sol_usd_feed: program_config.switchboard_sol_usd_feed,
+            oracle_max_divergence_bps: program_config.oracle_max_divergence_bps,
+            deposit_receipt_threshold: program_config.deposit_receipt_threshold,
+            deposit_receipt_uri_prefix: program_config.deposit_receipt_uri_prefix.clone(),
+            squads_multisig: program_config.squads_multisig,
+            squads_vault_index: program_config.squads_vault_index,
+            squads_vault_authority: program_config.squads_vault_authority,
+            realms_governance: program_config.realms_governance,
+            realms_realm: program_config.realms_realm,
+            deposit_receipt_tree: program_config.deposit_receipt_tree,
+            relayer_fee_bps: program_config.relayer_fee_bps,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+//! # Realms Module
+//!
+//! This module lets an SPL Governance (Realms) governance act for the council, so communities voting with tokens on Realms
+//! do not duplicate their membership in this program's multisig.
+//!
+//! ## Features
+//!
+//! - **Registered Governance:** Governance registers a Realms governance account through an approved
+//!   `ConfigureRealmsGovernance` proposal; only the SPL Governance program can sign for it, which it does when executing
+//!   the transactions of a proposal its voters approved.
+//! - **Ownership Checks:** The registered governance must be a `GovernanceV2` account owned by the SPL Governance program,
+//!   so a look-alike account cannot be registered in its place.
+//! - **CPI Checks:** A vault transfer execution signed by the registered governance must be invoked by the SPL Governance
+//!   program, i.e. through an executed proposal transaction.
+//!
+//! ## Constants
+//!
+//! - `SPL_GOVERNANCE_ID`: The SPL Governance program owning the governance account and signing for it.
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use crate::states::errors::RealmsErrorCode;
+
+/// The SPL Governance program on Solana, which Realms runs on.
+pub const SPL_GOVERNANCE_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+/// Tag of the `GovernanceV2` account type, the leading byte of a governance account.
+const GOVERNANCE_V2_ACCOUNT_TYPE: u8 = 18;
+
+/// Checks that `governance` is a `GovernanceV2` account owned by the SPL Governance program, and returns the realm it
+/// belongs to, which follows the account type.
+pub fn load_governance_realm(governance: &AccountInfo) -> Result<Pubkey> {
+    require_keys_eq!(*governance.owner, SPL_GOVERNANCE_ID, RealmsErrorCode::InvalidRealmsGovernance);
+
+    let data = governance.try_borrow_data()?;
+    require!(data.first() == Some(&GOVERNANCE_V2_ACCOUNT_TYPE), RealmsErrorCode::InvalidRealmsGovernance);
+
+    let mut fields = data.get(1..).ok_or(RealmsErrorCode::InvalidRealmsGovernance)?;
+    let realm = Pubkey::deserialize(&mut fields).map_err(|_| RealmsErrorCode::InvalidRealmsGovernance)?;
+
+    Ok(realm)
+}
+
+/// Checks that the current instruction is invoked through CPI by the SPL Governance program.
+///
+/// The instructions sysvar only lists the transaction's top-level instructions, so the program of the instruction being
+/// executed is the outermost caller, which must be SPL Governance executing a proposal transaction.
+pub fn check_invoked_by_realms(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let caller = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+    require_keys_eq!(caller, SPL_GOVERNANCE_ID, RealmsErrorCode::NotInvokedByRealms);
+
+    Ok(())
+}
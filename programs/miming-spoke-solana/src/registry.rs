@@ -0,0 +1,279 @@
+//! # Registry Module
+//!
+//! This module implements an on-chain directory of the program's long-lived singleton PDAs, so explorers, indexers, and the
+//! hub can discover a deployment's state from one account instead of hard-coding every seed.
+//!
+//! ## Features
+//!
+//! - **Single Entry Point:** The registry lives at the fixed `PROGRAM_REGISTRY_SEED` address and lists each singleton with a
+//!   type tag.
+//! - **Verified Entries:** An entry is only recorded for the address its tag derives to, and, for accounts holding program
+//!   state, once the program has created it; authority PDAs that never hold data, such as the vault, are recorded by address.
+//! - **Permissionless Cranking:** Anyone can create the registry and register a singleton, since every entry is checked
+//!   against its canonical address; each tag is recorded at most once.
+//! - **Sized on Demand:** The registry is created empty and grows, at the payer's expense, by one entry per registration.
+//!
+//! ## Main Data Structures
+//!
+//! - [`ProgramRegistryKind`]: Type tag of a registered singleton, including namespaced identifier counters.
+//! - [`ProgramRegistryEntry`]: A registered singleton's tag and address.
+//! - [`ProgramRegistryAccount`]: Stores the registered entries.
+//!
+//! ## Instructions
+//!
+//! - [`RegistryInstructions::initialize`]: Creates the empty registry.
+//! - [`RegistryInstructions::register`]: Records a singleton under its type tag.
+//!
+//! ## Constants
+//!
+//! - `MAX_REGISTRY_ENTRIES`: Maximum number of singletons the registry lists.
+use anchor_lang::prelude::*;
+use crate::{
+    states::{
+        events::{EventModule, ProgramRegistryEntryAddedEvent, StateMutationEvent},
+        errors::RegistryErrorCode,
+        pda::{
+            IDENTIFIER_NAMESPACE_LEN, PROGRAM_REGISTRY_SEED, find_bridge_stats_address, find_hub_registry_address,
+            find_hub_state_roots_address, find_identifier_address, find_insurance_fund_address, find_ledger_identifier_address, find_multisig_address,
+            find_program_config_address,
+            find_proposal_identifier_address, find_staking_activity_address, find_staking_authority_address,
+            find_staking_config_address, find_staking_pair_vault_address, find_staking_pool_address,
+            find_staking_proposal_identifier_address, find_staking_report_identifier_address,
+            find_staking_reward_schedule_address, find_transfer_proposal_identifier_address, find_vault_address,
+        },
+        realloc::{grow_account, space_for_len},
+        versioning::VersionedAccount,
+    },
+};
+
+/// Maximum number of singletons the registry lists.
+pub const MAX_REGISTRY_ENTRIES: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgramRegistryKind {
+    ProgramConfig,
+    Multisig,
+    ProposalIdentifier,
+    Vault,
+    LedgerIdentifier,
+    TransferProposalIdentifier,
+    StakingConfig,
+    StakingAuthority,
+    StakingPool,
+    StakingActivity,
+    StakingPairVault,
+    StakingRewardSchedule,
+    InsuranceFund,
+    StakingProposalIdentifier,
+    StakingReportIdentifier,
+    Identifier { namespace: [u8; IDENTIFIER_NAMESPACE_LEN] },
+    HubRegistry,
+    BridgeStats,
+    HubStateRoots,
+}
+
+impl ProgramRegistryKind {
+    /// Returns the canonical address of the singleton this tag stands for.
+    pub fn address(&self) -> Pubkey {
+        match self {
+            ProgramRegistryKind::ProgramConfig => find_program_config_address(),
+            ProgramRegistryKind::Multisig => find_multisig_address(),
+            ProgramRegistryKind::ProposalIdentifier => find_proposal_identifier_address(),
+            ProgramRegistryKind::Vault => find_vault_address(),
+            ProgramRegistryKind::LedgerIdentifier => find_ledger_identifier_address(),
+            ProgramRegistryKind::TransferProposalIdentifier => find_transfer_proposal_identifier_address(),
+            ProgramRegistryKind::StakingConfig => find_staking_config_address(),
+            ProgramRegistryKind::StakingAuthority => find_staking_authority_address(),
+            ProgramRegistryKind::StakingPool => find_staking_pool_address(),
+            ProgramRegistryKind::StakingActivity => find_staking_activity_address(),
+            ProgramRegistryKind::StakingPairVault => find_staking_pair_vault_address(),
+            ProgramRegistryKind::StakingRewardSchedule => find_staking_reward_schedule_address(),
+            ProgramRegistryKind::InsuranceFund => find_insurance_fund_address(),
+            ProgramRegistryKind::StakingProposalIdentifier => find_staking_proposal_identifier_address(),
+            ProgramRegistryKind::StakingReportIdentifier => find_staking_report_identifier_address(),
+            ProgramRegistryKind::Identifier { namespace } => find_identifier_address(namespace),
+            ProgramRegistryKind::HubRegistry => find_hub_registry_address(),
+            ProgramRegistryKind::BridgeStats => find_bridge_stats_address(),
+            ProgramRegistryKind::HubStateRoots => find_hub_state_roots_address(),
+        }
+        .0
+    }
+
+    /// Returns whether the singleton is an authority PDA that signs for the program without holding its state.
+    pub fn is_authority(&self) -> bool {
+        matches!(
+            self,
+            ProgramRegistryKind::Vault | ProgramRegistryKind::StakingAuthority | ProgramRegistryKind::InsuranceFund
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramRegistryEntry {
+    pub kind: ProgramRegistryKind,
+    pub address: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramRegistryAccount {
+    #[max_len(MAX_REGISTRY_ENTRIES)]
+    pub entries: Vec<ProgramRegistryEntry>,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl ProgramRegistryAccount {
+    /// Returns the size of a registry with room for `entry_count` entries.
+    pub const fn len_for(entry_count: usize) -> usize {
+        space_for_len(Self::INIT_SPACE, MAX_REGISTRY_ENTRIES, ProgramRegistryEntry::INIT_SPACE, entry_count)
+    }
+}
+
+impl VersionedAccount for ProgramRegistryAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + ProgramRegistryAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[derive(Accounts)]
+pub struct RegistryInitialization<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProgramRegistryAccount::len_for(0),
+        seeds = [PROGRAM_REGISTRY_SEED],
+        bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistryAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegistryRegister<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_REGISTRY_SEED],
+        bump = program_registry.bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistryAccount>,
+
+    /// CHECK: Compared with the canonical address of the registered kind, and checked to be created by the program unless it is an authority PDA
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub struct RegistryInstructions;
+
+impl RegistryInstructions {
+    /// Creates the empty program registry.
+    ///
+    /// The registry can only be created once; anyone can create it, since it starts empty and every entry is verified when
+    /// registered.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the payer, and the registry to create.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the registry is created successfully, otherwise returns an error.
+    pub fn initialize(ctx: Context<RegistryInitialization>) -> Result<()> {
+        ctx.accounts.program_registry.set_inner(ProgramRegistryAccount {
+            entries: Vec::new(),
+            version: ProgramRegistryAccount::VERSION,
+            bump: ctx.bumps.program_registry,
+        });
+
+        StateMutationEvent::emit(EventModule::Registry, "registry_initialize", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Records a singleton PDA in the program registry under its type tag.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that `kind` is not registered yet and that the registry has room for another entry.
+    /// - Checks that the passed account is the canonical address of `kind`.
+    /// - Unless `kind` is an authority PDA, checks that the account has been created and is owned by the program.
+    /// - Grows the registry, at the payer's expense, and appends the entry.
+    /// - Emits a `ProgramRegistryEntryAddedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the payer, the registry, and the account to register.
+    /// * `kind` - The type tag of the account.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the kind is already registered, the registry is full, the account is not the kind's canonical
+    /// address, or it has not been created by the program.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the account is registered successfully, otherwise returns an error.
+    pub fn register(ctx: Context<RegistryRegister>, kind: ProgramRegistryKind) -> Result<()> {
+        let program_registry = &mut ctx.accounts.program_registry;
+        let account = &ctx.accounts.account;
+
+        require!(
+            !program_registry.entries.iter().any(|entry| entry.kind == kind),
+            RegistryErrorCode::AlreadyRegistered
+        );
+        require!(
+            program_registry.entries.len() < MAX_REGISTRY_ENTRIES,
+            RegistryErrorCode::RegistryFull
+        );
+        require_keys_eq!(account.key(), kind.address(), RegistryErrorCode::InvalidRegistryAddress);
+        if !kind.is_authority() {
+            require!(
+                account.owner == &crate::ID && !account.data_is_empty(),
+                RegistryErrorCode::AccountNotCreated
+            );
+        }
+
+        grow_account(
+            &program_registry.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + ProgramRegistryAccount::len_for(program_registry.entries.len() + 1),
+        )?;
+
+        program_registry.entries.push(ProgramRegistryEntry {
+            kind,
+            address: account.key(),
+        });
+
+        emit!(ProgramRegistryEntryAddedEvent {
+            kind,
+            address: account.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Registry, "registry_register", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! # Squads Module
+//!
+//! This module lets a Squads v4 multisig approve vault transfer executions, so teams already signing with Squads keep
+//! their workflow instead of duplicating their membership in this program's multisig.
+//!
+//! ## Features
+//!
+//! - **Registered Vault Authority:** Governance registers a Squads multisig and vault index through an approved
+//!   `ConfigureSquadsAuthority` proposal; the program stores the vault PDA derived from them, which only the Squads program
+//!   can sign for.
+//! - **Ownership Checks:** The registered multisig must be a Squads `Multisig` account owned by the Squads program, so a
+//!   look-alike account cannot be registered in its place.
+//! - **CPI Checks:** An execution signed by the registered vault must be invoked by the Squads program, i.e. through an
+//!   executed Squads vault transaction, which carries the Squads members' approval.
+//!
+//! ## Constants
+//!
+//! - `SQUADS_PROGRAM_ID`: The Squads v4 program owning the multisig and signing for its vaults.
+//! - `SQUADS_SEED_PREFIX`, `SQUADS_VAULT_SEED`: Seeds of a Squads vault, derived from
+//!   `[SQUADS_SEED_PREFIX, multisig, SQUADS_VAULT_SEED, vault index]` under the Squads program.
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use crate::states::errors::SquadsErrorCode;
+
+/// The Squads v4 program on Solana.
+pub const SQUADS_PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+/// Leading seed of every Squads PDA.
+pub const SQUADS_SEED_PREFIX: &[u8] = b"multisig";
+/// Seed of a Squads vault, following the multisig's address.
+pub const SQUADS_VAULT_SEED: &[u8] = b"vault";
+
+/// Anchor discriminator of Squads' `Multisig` account.
+const MULTISIG_DISCRIMINATOR: [u8; 8] = [224, 116, 121, 186, 68, 161, 79, 236];
+
+/// Checks that `multisig` is a Squads `Multisig` account owned by the Squads program.
+pub fn check_multisig(multisig: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*multisig.owner, SQUADS_PROGRAM_ID, SquadsErrorCode::InvalidSquadsMultisig);
+
+    let data = multisig.try_borrow_data()?;
+    require!(data.starts_with(&MULTISIG_DISCRIMINATOR), SquadsErrorCode::InvalidSquadsMultisig);
+
+    Ok(())
+}
+
+/// Returns the address and bump of the vault at `vault_index` of the Squads `multisig`.
+pub fn find_vault_authority_address(multisig: &Pubkey, vault_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SQUADS_SEED_PREFIX, multisig.as_ref(), SQUADS_VAULT_SEED, &[vault_index]],
+        &SQUADS_PROGRAM_ID,
+    )
+}
+
+/// Checks that the current instruction is invoked through CPI by the Squads program.
+///
+/// The instructions sysvar only lists the transaction's top-level instructions, so the program of the instruction being
+/// executed is the outermost caller, which must be Squads executing a vault transaction.
+pub fn check_invoked_by_squads(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let caller = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+    require_keys_eq!(caller, SQUADS_PROGRAM_ID, SquadsErrorCode::NotInvokedBySquads);
+
+    Ok(())
+}
@@ -8,18 +8,176 @@
 //!
 //! - **Staking Freeze:** Users can freeze their associated token accounts if they meet the minimum staking amount requirement.
 //! - **Staking Thaw:** Users can thaw (unfreeze) their token accounts, effectively ending the staking period.
-//! - **Minimum Staking Enforcement:** The module enforces a configurable minimum staking amount before allowing freezing.
-//! - **Staking Registry:** Each staker has a registry account to track their staking reference ID.
+//! - **Lockups:** Stakes can be locked for a chosen duration; releasing them early requires the explicit early-exit path and its penalty,
+//!   which for escrow stakes is moved into the insurance fund.
+//! - **Unstake Cooldown:** With a cooldown configured, releasing a stake takes two steps: `request_unstake`, then `thaw` or `unstake` once the cooldown has elapsed.
+//! - **Lock Boosts:** Longer remaining lockups boost the reward multiplier, and stakers can extend their lock to raise it.
+//! - **Loyalty Boosts:** Stakes held without interruption past the configured loyalty milestones (by default 6 and 12 months)
+//!   earn incremental multiplier boosts, computed on-chain from the registry's `started_at` whenever the position is updated.
+//! - **Minimum Staking Enforcement:** The module enforces a configurable minimum staking amount before allowing freezing; whether
+//!   staking exactly the minimum is allowed is set by the configuration's `min_inclusive` flag.
+//! - **Staking Registry:** Each stake position has a registry account to track its staking reference ID, validated for length and characters.
+//! - **Referrals:** Freezes and stakes can name a referrer, recorded on the stake position and counted in a per-referrer statistics account.
+//! - **Registry Migrations:** Registries carry a layout version; when the layout grows, `migrate_registry` reallocates legacy
+//!   registries and backfills the new fields with safe defaults, so existing stakers are not locked out by an upgrade. Voting
+//!   power and the position views decode registries through `StakingRegistryData`, so they read unmigrated registries too.
+//! - **Config Migrations:** The staking configuration carries a layout version too; `migrate_config` reallocates a configuration
+//!   created with an older layout and backfills the fields appended since, so new rates, caps, and switches can be added safely.
+//! - **Account Versioning:** Every other staking account (users, referrers, pool, snapshots, reports, queues, proposals, and
+//!   allowlists) also ends with a layout version and has its own `migrate_*` instruction built on
+//!   [`migrate_account`](crate::states::versioning::migrate_account). The zero-copy pool statistics are stamped in place by
+//!   `migrate_pool`, and the zero-copy activity window keeps its fixed layout.
+//! - **Closing Positions:** Once a position is fully released and its rewards claimed, its registry can be closed to reclaim rent.
+//! - **Closing Governance Records:** Approved proposals and retired validator and custodian allowlist entries can be closed by a
+//!   multisig signer, refunding their rent to the program configuration's rent recipient; SOL claim receipts can be closed by
+//!   their staker once the epoch's distribution is fully claimed.
+//! - **Multiple Positions:** Registries are seeded by staker and position index, so a wallet can hold several stakes with different lockups and mints at once.
+//! - **Batch Freeze/Thaw:** Power users and custodial integrators can freeze or thaw up to `MAX_BATCH_POSITIONS` positions in one
+//!   transaction, passing each position's accounts as remaining accounts.
+//! - **Staking Tiers:** Tier definitions in the staking configuration map staked amounts to reward multipliers.
+//! - **Rewards:** Staked amounts accrue rewards at the configured rate, boosted by the position's tier and lock multiplier, claimable from the reward vault.
+//! - **Reward Schedule:** The base reward rate follows a governed schedule of epoch-range steps that decay over time. Schedules
+//!   are only replaced through `SetRewardSchedule` proposals, and a permissionless crank applies each step as its epoch begins.
+//! - **Reward Budget:** Anyone can fund the reward vault; claims draw down the funded budget, so reward liabilities are always backed on-chain.
+//! - **Staked-Supply Cap:** A governed `max_total_staked` bounds the pool's total staked, so emissions cannot be diluted past a
+//!   planned cap; freezes, stakes, and compounding claims beyond it fail with `StakingCapReached`.
+//! - **Staking Pool Statistics:** A global pool account tracks total staked, per-tier totals, staker count, rewards paid, and current-epoch flows.
+//! - **Activity Metrics:** A zero-copy activity account keeps the last `EPOCH_ACTIVITY_WINDOW` epochs' stake and unstake counts,
+//!   amounts, and net flow, updated by every handler that changes a stake, so protocol health can be read directly on-chain.
+//! - **Epoch Snapshots:** A permissionless crank copies the pool's total and per-tier stake into an epoch-indexed snapshot, so reward
+//!   distribution is computed against a stable value rather than the live totals.
+//! - **Custodial Staking:** Custodians allowed through `ConfigureCustodian` proposals can stake on behalf of a beneficiary wallet;
+//!   the registry records both, and only the beneficiary can unstake or claim, with unstakes paid to the beneficiary's token account.
+//! - **Delegated Staking:** Stakers can approve the staking authority PDA as a delegate of their token account, letting a relayer
+//!   submit and pay for an escrow stake that the PDA executes with the delegated authority, for gasless onboarding.
+//! - **Pair Staking:** `stake_pair` locks the staked tokens in the stake vault together with SOL at the governed `pair_sol_ratio_bps`,
+//!   held in a program-owned pair vault as protocol-owned liquidity; the registry records both amounts, pair positions earn the
+//!   `pair_boost_bps` multiplier boost, and `unstake_pair` returns both in full once the lock and cooldown allow it.
+//! - **SOL Rewards:** Multisig-approved `DistributeSolRewards` proposals move an epoch's SOL reward budget from the vault into a
+//!   distribution account; positions that were staked when the epoch snapshot was taken claim their share of it pro rata to the
+//!   snapshot's total staked, once per position.
+//! - **Teleport Fee Discounts:** Active stakers pay a reduced teleport fee on vault teleports, discounted by the configuration's fee
+//!   discount table according to the live staked amount of the registry they pass to `teleport`.
+//! - **Staking Ledger:** Every freeze, thaw, reward claim, and slash emits a `StakingLedgerLogEvent` numbered by the staker's
+//!   gapless ledger sequence, carrying the action and the position's resulting staked amount, so user statements can be
+//!   reconstructed deterministically from events alone.
+//! - **Hub Reports:** A permissionless `report` instruction packages the total staked, staker count, and epoch into a sequenced
+//!   outbound report account and event, consumed by the hub chain's relayer to reflect spoke stake levels in hub governance.
+//! - **Escrow Staking:** As an alternative to freezing, tokens can be transferred into a program-owned stake vault, allowing partial unstakes and mints without a freeze authority.
+//! - **Mint Allowlist:** Once enabled by governance, only mints allowed through `ConfigureMint` proposals can be frozen or staked.
+//! - **Thaw Attestations:** With an attestor configured, thaws must carry the attestor's ed25519 signature over the staker, reference ID,
+//!   and an expiry, verified through the instructions sysvar, so off-chain settlement can be confirmed before a stake is released.
+//! - **Token-2022 Support:** Mints owned by either the SPL Token or Token-2022 program can be staked; escrow stakes credit the amount received after transfer fees.
+//! - **Custodial Freeze Authority:** Mints configured as custodial are frozen and thawed by the staking authority PDA instead of the staker.
+//!   Only custodial freezes accrue rewards, since a staker holding the freeze authority can thaw without the program.
+//! - **Slashing:** Multisig-approved proposals can seize part of an escrow stake into the insurance fund, used when stake serves as a security bond.
+//!   Slashes and early-exit penalties are recorded in the vault ledger, so insurance fund inflows share the vault's accounting.
+//!   Slash proposals must reference an evidence hash, wait out a dispute window, and execute with the multisig's threshold
+//!   of signatures. During the window the staker can post a counter-claim that discards the collected signatures, restarts
+//!   the window, and requires every required signer to approve the slash.
+//! - **Voting Power:** Stake positions give their staker voting power (amount × multiplier), used by stake-weighted multisig approvals.
+//! - **Pause Switch:** Multisig-approved proposals can pause staking during incidents, rejecting new freezes and stakes with
+//!   `StakingPaused` while thaws, unstakes, and claims keep working.
+//! - **Pause Recovery:** If staking stays paused for longer than the configured `pause_grace_period`, any staker can thaw or
+//!   unstake without attestations, lockups, cooldowns, or penalties, so funds stay recoverable if the operators disappear.
+//! - **Emergency Thaw:** Multisig-approved proposals can release a staker's freeze stake if the normal thaw flow is unavailable.
+//! - **Vault Delegation:** Multisig-approved proposals can delegate vault SOL to allowlisted validators through the native stake program,
+//!   then deactivate and withdraw it; delegated amounts and validator rewards are tracked in the staking pool statistics.
+//! - **Staking Governance:** Staking configuration changes are executed through proposals approved by the multisig signers.
 //!
 //! ## Main Data Structures
 //!
-//! - [`StakingConfigAccount`]: Stores the minimum staking amount required to participate in staking.
-//! - [`StakingRegistryAccount`]: Tracks a reference ID for each staker, used to identify or associate staking actions.
+//! - [`StakingMode`]: Whether a stake is held by freezing the staker's token account, by escrow in the stake vault, or as a MIMING + SOL pair.
+//! - [`StakingPositionView`]: A live view of a stake position returned by `get_position`.
+//! - [`StakingStakeInfo`]: A compact view of a stake position returned by `get_stake_info` for CPI consumers.
+//! - [`StakingConfigView`]: A view of the staking configuration's scalar values returned by `get_config`.
+//! - [`StakingTier`]: A threshold amount and the reward multiplier (in basis points) granted once it is reached.
+//! - [`StakingFeeDiscount`]: A live staked amount and the teleport fee discount (in basis points) granted once it is reached.
+//! - [`StakingLoyaltyMilestone`]: A holding duration and the multiplier boost (in basis points) added once a stake is held that long.
+//! - [`StakingConfigAccount`]: Stores the minimum staking amount, reward mint and rate, the staking tiers, the loyalty milestones,
+//!   the pair staking ratio and boost, the teleport fee discount table, and its layout version.
+//! - [`StakingUserAccount`]: Counts a staker's opened and currently active stake positions and numbers their ledger entries.
+//! - [`StakingLedger`]: A staking ledger entry with its sequence number, position, [`StakingLedgerAction`], and resulting staked amount.
+//! - [`StakingRegistryAccount`]: Tracks a reference ID, the staked mint and token account, the staked amount, the applied multiplier
+//!   and loyalty boost, when the stake started, the custodian that staked on the staker's behalf or the staking authority PDA
+//!   holding a custodial mint's freeze authority, if any, the SOL locked by a pair stake,
+//!   and accrued rewards for each stake position.
+//! - [`StakingRegistryData`]: A staking registry decoded with the layout it was written with, upgraded to the current one on read.
+//! - [`StakingReferrerAccount`]: Counts the stake positions a referrer brought in and the amount staked into them.
+//! - [`StakingPoolAccount`]: A zero-copy account aggregating protocol-wide staking totals and the current epoch's staked,
+//!   unstaked, and paid-out amounts, loaded in place by every stake-changing instruction.
+//! - [`StakingActivityAccount`]: A rolling window of per-epoch [`StakingEpochActivity`] entries counting new stakes, unstakes, and net flow.
+//! - [`StakingEpochSnapshotAccount`]: Freezes the pool's total and per-tier stake at an epoch boundary for reward distribution.
+//! - [`StakingReportAccount`]: A sequenced outbound report of the pool's total staked, staker count, and epoch for the hub chain.
+//! - [`StakingSolDistributionAccount`]: Holds an epoch's SOL reward budget funded from the vault and tracks the amount claimed from it.
+//! - [`StakingSolClaimAccount`]: Records a stake position's claim from an epoch's SOL reward distribution.
+//! - [`StakingPairVaultAccount`]: Holds the SOL locked by pair stakes and tracks its total and the number of pair positions.
+//! - [`StakingRewardScheduleAccount`]: Holds the governed [`StakingRewardScheduleStep`]s that set the base reward rate per epoch range.
+//! - [`StakingUnstakeQueueAccount`]: A mint's FIFO queue of escrow unstake requests, each with its sequence and ETA.
+//! - [`StakingMintConfigAccount`]: Records whether a mint is staked in custodial mode and whether it is on the staking allowlist.
+//! - [`StakingCustodianAccount`]: Records whether a custodian may stake on behalf of other wallets.
+//! - [`StakingValidatorAccount`]: Records whether vault SOL may be delegated to a validator's vote account.
+//! - [`StakingVaultDelegationAccount`]: Tracks a vault stake account's validator, delegated amount, and lifecycle status.
+//! - [`StakingProposalAccount`]: Stores a staking governance proposal, including its action, required signers, collected signatures, status,
+//!   and when it was created and disputed.
+//! - [`StakingSlashDisputeAccount`]: Records the counter-claim hash a staker posted against a slash proposal.
 //!
 //! ## Instructions
 //!
 //! - [`StakingInstructions::freeze`]: Freezes the staker's token account if the minimum staking amount is met and records a reference ID.
 //! - [`StakingInstructions::thaw`]: Thaws the staker's token account and clears the reference ID in the registry.
+//! - [`StakingInstructions::batch_freeze`]: Freezes several of the staker's token accounts, one stake position each, in one transaction.
+//! - [`StakingInstructions::batch_thaw`]: Thaws several of the staker's frozen positions in one transaction.
+//! - [`StakingInstructions::fund_rewards`]: Transfers reward tokens into the reward vault and adds them to the reward budget.
+//! - [`StakingInstructions::claim_rewards`]: Transfers the staker's accrued rewards from the reward vault, or compounds them into an escrow stake.
+//! - [`StakingInstructions::get_voting_power`]: Returns a staker's voting power from their stake positions as return data.
+//! - [`StakingInstructions::snapshot_epoch`]: Records the pool's total and per-tier stake into the current epoch's snapshot account.
+//! - [`StakingInstructions::apply_reward_schedule`]: Moves the base reward rate to the reward schedule step in force for the current epoch.
+//! - [`StakingInstructions::report`]: Records the pool's total staked, staker count, and epoch into the next sequenced hub report.
+//! - [`StakingInstructions::claim_sol_rewards`]: Transfers a position's pro-rata share of an epoch's SOL reward distribution.
+//! - [`StakingInstructions::get_position`]: Returns a stake position's amount, unclaimed rewards, effective APR, and unlock time as return data.
+//! - [`StakingInstructions::get_stake_info`]: Returns a stake position's amount, unlock time, multiplier, and slashed flag as compact return data for CPI.
+//! - [`StakingInstructions::get_config`]: Returns the staking configuration's amounts, rates, durations, and pause state as return data.
+//! - [`StakingInstructions::request_unstake`]: Starts the unstake cooldown of a stake position.
+//! - [`StakingInstructions::extend_lock`]: Pushes out a stake position's unlock time and recalculates its multiplier.
+//! - [`StakingInstructions::migrate_registry`]: Reallocates a registry created with a legacy layout and rewrites it in the current one.
+//! - [`StakingInstructions::migrate_config`]: Reallocates the staking configuration created with an older layout and stamps the current version.
+//! - [`StakingInstructions::close_position`]: Closes a fully released stake position's registry to reclaim its rent.
+//! - [`StakingInstructions::close_sol_claim`]: Closes a SOL claim receipt once its epoch's distribution is fully claimed.
+//! - [`StakingEscrowInstructions::stake`]: Transfers tokens into the stake vault and records the escrow stake in the registry.
+//! - [`StakingEscrowInstructions::stake_for`]: Transfers a custodian's tokens into the stake vault as an escrow stake of a beneficiary wallet.
+//! - [`StakingEscrowInstructions::stake_delegated`]: Transfers a staker's tokens into the stake vault through the staking authority's delegate approval.
+//! - [`StakingEscrowInstructions::unstake`]: Returns part or all of an escrow stake from the stake vault.
+//! - [`StakingEscrowInstructions::stake_pair`]: Locks tokens in the stake vault and SOL at the configured ratio in the pair vault as a pair stake.
+//! - [`StakingEscrowInstructions::unstake_pair`]: Releases a pair stake in full, returning both its tokens and its SOL.
+//! - [`StakingEscrowInstructions::queue_unstake`]: Queues an escrow unstake to be paid out in FIFO order as stake vault liquidity returns.
+//! - [`StakingEscrowInstructions::process_unstake_queue`]: Pays out the request at the head of a mint's unstake queue once its ETA has passed.
+//! - [`StakingProposalInstructions::create_proposal`]: Creates a staking governance proposal requiring multisig approval.
+//! - [`StakingProposalInstructions::sign_proposal`]: Allows an authorized signer to sign a pending staking proposal.
+//! - [`StakingProposalInstructions::configure_mint`]: Executes an approved proposal that sets a mint's staking mode and allowlist status.
+//! - [`StakingProposalInstructions::slash`]: Executes an approved proposal that seizes part of an escrow stake into the insurance fund.
+//! - [`StakingProposalInstructions::dispute_slash`]: Posts the slashed staker's counter-claim against a pending slash proposal.
+//! - [`StakingProposalInstructions::emergency_thaw`]: Executes an approved proposal that releases a staker's freeze stake.
+//! - [`StakingProposalInstructions::update_config`]: Executes an approved proposal that changes the minimum staking amount, unstake cooldown,
+//!   mint allowlist switch, staked-supply cap, and pause grace period.
+//! - [`StakingProposalInstructions::set_paused`]: Executes an approved proposal that pauses or resumes new freezes and stakes.
+//! - [`StakingProposalInstructions::configure_attestor`]: Executes an approved proposal that sets or clears the thaw attestor.
+//! - [`StakingProposalInstructions::configure_validator`]: Executes an approved proposal that adds a validator to or removes it from the vault delegation allowlist.
+//! - [`StakingProposalInstructions::configure_custodian`]: Executes an approved proposal that allows a custodian to stake on behalf of other wallets or revokes it.
+//! - [`StakingProposalInstructions::configure_pair_staking`]: Executes an approved proposal that changes the SOL ratio and multiplier boost of pair stakes.
+//! - [`StakingProposalInstructions::set_reward_schedule`]: Executes an approved proposal that replaces the reward schedule.
+//! - [`StakingProposalInstructions::delegate_vault_stake`]: Executes an approved proposal that delegates vault SOL to an allowlisted validator.
+//! - [`StakingProposalInstructions::deactivate_vault_stake`]: Executes an approved proposal that deactivates a vault stake account.
+//! - [`StakingProposalInstructions::withdraw_vault_stake`]: Executes an approved proposal that withdraws a deactivated vault stake account into the vault.
+//! - [`StakingProposalInstructions::distribute_sol_rewards`]: Executes an approved proposal that funds an epoch's SOL rewards from the vault.
+//! - [`StakingProposalInstructions::close_proposal`]: Closes an approved staking proposal, refunding its rent to the configured recipient.
+//! - [`StakingProposalInstructions::close_validator`] / [`StakingProposalInstructions::close_custodian`]: Close retired allowlist entries.
+//!
+//! ## Events
+//!
+//! Freezes, thaws, and reward claims emit `StakeFrozenEvent`, `StakeThawedEvent`, and `RewardsClaimedEvent`, so indexers
+//! can follow staking activity without diffing registry accounts. The same actions, and slashes, also emit a sequenced
+//! `StakingLedgerLogEvent` per staker.
 //!
 //! ## Error Handling
 //!
@@ -27,18 +185,45 @@
 //!
 //! ## Constants
 //!
-//! - `StakingConfigAccount::LEN`: The size of the staking configuration account.
-//! - `StakingRegistryAccount::LEN`: The size of the staking registry account.
+//! - `MAX_STAKING_TIERS`: Maximum number of tiers in the staking configuration.
+//! - `MAX_LOYALTY_MILESTONES`: Maximum number of loyalty milestones in the staking configuration.
+//! - `MAX_FEE_DISCOUNTS`: Maximum number of teleport fee discount entries in the staking configuration.
+//! - `MAX_MIN_STAKING_AMOUNT`: Upper bound for a governed minimum staking amount.
+//! - `MAX_UNSTAKE_COOLDOWN`: Upper bound for a governed unstake cooldown.
+//! - `MAX_PAUSE_GRACE_PERIOD`: Upper bound for a governed pause grace period.
+//! - `MAX_PAIR_BOOST_BPS`: Upper bound for a governed pair staking multiplier boost.
+//! - `MAX_REWARD_SCHEDULE_STEPS`: Maximum number of steps in the reward schedule.
+//! - `SLASH_DISPUTE_WINDOW`: Time a slashed staker has to dispute a slash proposal before it can execute.
+//! - `CONFIG_ACTIVATION_DELAY`: Minimum time between proposing a configuration change and activating it.
+//! - `MAX_UNSTAKE_QUEUE_LEN`: Maximum number of pending requests in a mint's unstake queue.
+//! - `MAX_REFERENCE_ID_LEN`: Maximum length of a stake position's reference ID.
+//! - `MAX_BATCH_POSITIONS`: Maximum number of positions in a batch freeze or thaw.
+//! - `EPOCH_ACTIVITY_WINDOW`: Number of epochs kept in the rolling staking activity window.
+//! - `REGISTRY_VERSION`: The current layout version of the staking registry.
+//! - `CONFIG_VERSION`: The current layout version of the staking configuration.
+//! - `StakingRegistryAccount::SPACE`: The size registries are allocated with; other account sizes are derived with `InitSpace`.
 //!
 //! ## Usage
 //!
-//! 1. **Freeze tokens:** Call `freeze` with a reference number to freeze the user's token account for staking.
-//! 2. **Thaw tokens:** Call `thaw` to unfreeze the user's token account and clear the staking registry.
+//! 1. **Initialize staking:** The staking configuration is created with the reward mint by `initialize_program`, in the
+//!    genesis module.
+//! 2. **Freeze tokens:** Call `freeze` with a reference number to freeze the user's token account for staking.
+//! 3. **Claim rewards:** Call `claim_rewards` to receive the rewards accrued while staked.
+//! 4. **Thaw tokens:** Call `thaw` to unfreeze the user's token account and clear the staking registry.
 //!
 //! ## Security Considerations
 //!
-//! - Only the freeze authority (the staker) can freeze or thaw their token account.
+//! - Only the freeze authority (the staker, or the staking authority PDA for custodial mints) can freeze or thaw the token account.
+//! - Custodial thaws require an active stake in the staker's registry, so the PDA cannot release accounts frozen for other reasons.
+//! - Thaws and unstakes must name the exact mint and token account recorded when the position was staked.
+//! - Mint staking modes and allowlist status can only be changed by proposals signed by all multisig signers.
+//! - Queued unstakes are paid strictly in request order, and only once the stake vault holds enough to cover the head request.
+//! - Staking paused past its grace period lets stakers release their stakes without operator involvement.
+//! - Disabling the staking module in the program configuration blocks new freezes and stakes, never exits.
+//! - A guardian's emergency stop halts every staker-facing and permissionless handler, exits included, until the multisig
+//!   resumes the program; governance-executed proposals keep working so the multisig can remediate first.
 //! - The minimum staking amount is enforced to prevent staking with insufficient tokens.
+//! - Rewards are paid from a reward vault owned by the staking authority PDA and can only be claimed by the staker, up to the funded reward budget.
 //! - All account constraints are validated to ensure correct and secure operation.
 //!
 //! ## Integration
@@ -46,201 +231,8677 @@
 //! This module can be integrated into larger DeFi or staking protocols on Solana to provide basic staking functionality
 //! with SPL tokens, leveraging Anchor's security and account management features.
 use crate::{
+    require_with_context,
+    access_control::{can_operate, is_member, Role},
+    nonce::{consume_nonce, WalletNonceAccount},
     states::{
+        audit::{Audited, AUDIT_SPACE},
         constants::{
-            DISCRIMINATOR, 
-            STRING_LEN, U64_SIZE, 
+            STRING_LEN, U8_SIZE, U64_SIZE, PUBKEY_SIZE,
+            BASIS_POINTS, SECONDS_PER_YEAR,
         },
-        errors::StakingErrorCode,
-    }
+        events::{
+            AccountClosedEvent, AccountMigratedEvent, ConfigChangeProposedEvent, EventModule, PairStakedEvent, PairUnstakedEvent,
+            RewardsClaimedEvent, RewardsFundedEvent, SlashDisputedEvent, SolRewardsClaimedEvent,
+            SolRewardsDistributedEvent, StakeFrozenEvent, StakeLockExtendedEvent, StakePositionClosedEvent,
+            StakeSlashedEvent, StakeThawedEvent, StakedOnBehalfEvent, StakingAttestorUpdatedEvent,
+            StakingConfigMigratedEvent, StakingConfigUpdatedEvent, StakingLedgerLogEvent, StakingOperationBlockedEvent,
+            StakingPairConfigUpdatedEvent, StakingPauseUpdatedEvent, StakingRegistryMigratedEvent, StakingReportedEvent,
+            StakingRewardRateAppliedEvent, StakingRewardScheduleUpdatedEvent, StakingSnapshotRecordedEvent,
+            StateMutationEvent, UnstakeQueueProcessedEvent, UnstakeQueuedEvent, UnstakeRequestedEvent,
+            VaultStakeDeactivatedEvent, VaultStakeDelegatedEvent, VaultStakeWithdrawnEvent,
+        },
+        error_context::with_context,
+        errors::{MathErrorCode, MultisigErrorCode, ProgramConfigErrorCode, StakingErrorCode, VersioningErrorCode},
+        math::negate_amount,
+        pda::{
+            INSURANCE_FUND_SEED, LEDGER_IDENTIFIER_SEED, LEDGER_SEED, MULTISIG_SEED, PROGRAM_CONFIG_SEED,
+            STAKING_ACTIVITY_SEED, STAKING_AUTHORITY_SEED, STAKING_CONFIG_SEED, STAKING_CUSTODIAN_SEED,
+            STAKING_MINT_CONFIG_SEED, STAKING_PAIR_VAULT_SEED, STAKING_POOL_SEED, STAKING_PROPOSAL_IDENTIFIER_SEED,
+            STAKING_PROPOSAL_SEED, STAKING_REFERRER_SEED, STAKING_REGISTRY_SEED, STAKING_REPORT_IDENTIFIER_SEED,
+            STAKING_REPORT_SEED, STAKING_REWARD_SCHEDULE_SEED, STAKING_SLASH_DISPUTE_SEED, STAKING_SNAPSHOT_SEED,
+            STAKING_SOL_CLAIM_SEED, STAKING_SOL_DISTRIBUTION_SEED, STAKING_UNSTAKE_QUEUE_SEED, STAKING_USER_SEED,
+            STAKING_VALIDATOR_SEED, VAULT_DELEGATION_SEED, VAULT_SEED, VAULT_STAKE_SEED, WALLET_NONCE_SEED,
+            find_staking_registry_address,
+        },
+        realloc::{grow_account, space_for_len},
+        signer_set::{full_signer_mask, signer_bit, signer_mask_of},
+        versioning::VersionedAccount,
+    },
+    hub_registry::{validate_hub_routing, HubRoute, MAX_HUB_ROUTES},
+    multisig::{MAX_SIGNERS, MultisigAccount},
+    messaging::MessagingTransport,
+    oracle::{MAX_ORACLE_CONFIDENCE_BPS, MAX_ORACLE_DIVERGENCE_BPS, MAX_ORACLE_STALENESS, MAX_TELEPORT_FEE_USD_CENTS},
+    program_config::{
+        MAX_CPI_CALLERS, MAX_RECEIPT_URI_PREFIX_LEN, MAX_RELAYER_FEE_BPS, MAX_TELEPORT_FEE, ProgramConfigAccount,
+    },
+    vault::{VaultLedgerAccount, VaultTransaction},
+    wormhole::SOLANA_CHAIN_ID,
+    IdentifierAccount
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        program::{invoke, invoke_signed},
+        program_option::COption,
+        pubkey,
+        sysvar::{
+            instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked},
+            stake_history,
+        },
+    },
+    system_program::{create_account, transfer, CreateAccount, Transfer},
 };
-use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{freeze_account, thaw_account, FreezeAccount, Mint, ThawAccount, Token, TokenAccount},
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{
+        freeze_account, thaw_account, transfer_checked, 
+        FreezeAccount, Mint, ThawAccount, TokenAccount, TokenInterface, TransferChecked
+    },
+};
+use solana_stake_interface::{
+    instruction as stake_instruction,
+    program as stake_program,
+    state::{Authorized, Lockup, StakeStateV2},
 };
 
+/// The legacy stake config account, which the stake program's `DelegateStake` instruction still expects.
+pub const STAKE_CONFIG_ID: Pubkey = pubkey!("StakeConfig11111111111111111111111111111111");
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub struct StakingTier {
+    pub min_amount: u64,
+    pub multiplier_bps: u16,
+}
+
+pub const MAX_STAKING_TIERS: usize = 5;
+
+/// A holding duration and the reward multiplier boost (in basis points) it adds once a stake has been held that long.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub struct StakingLoyaltyMilestone {
+    pub min_duration: i64,
+    pub boost_bps: u16,
+}
+
+pub const MAX_LOYALTY_MILESTONES: usize = 4;
+
+/// A live staked amount and the teleport fee discount (in basis points) a staker gets once they stake at least that much.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub struct StakingFeeDiscount {
+    pub min_staked: u64,
+    pub discount_bps: u16,
+}
+
+pub const MAX_FEE_DISCOUNTS: usize = 4;
+
+/// The first epoch of a reward schedule step and the base reward rate (in basis points) that applies from it onwards.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub struct StakingRewardScheduleStep {
+    pub start_epoch: u64,
+    pub reward_rate_bps: u16,
+}
+
+pub const MAX_REWARD_SCHEDULE_STEPS: usize = 8;
+
+/// Number of epochs kept in the rolling staking activity window.
+pub const EPOCH_ACTIVITY_WINDOW: usize = 16;
+
+/// Upper bound for a governed `min_staking_amount`, guarding against mistyped proposals locking everyone out of staking.
+pub const MAX_MIN_STAKING_AMOUNT: u64 = 1_000_000_000_000;
+
+/// Upper bound for a governed unstake cooldown, in seconds (30 days).
+pub const MAX_UNSTAKE_COOLDOWN: i64 = 30 * 24 * 60 * 60;
+
+/// Upper bound for a governed pause grace period, in seconds (180 days).
+pub const MAX_PAUSE_GRACE_PERIOD: i64 = 180 * 24 * 60 * 60;
+
+/// Upper bound for a governed pair staking multiplier boost, in basis points (1x).
+pub const MAX_PAIR_BOOST_BPS: u16 = 10_000;
+
+/// Time, in seconds, a slashed staker has to dispute a slash proposal before it can execute (3 days). A counter-claim
+/// restarts the window from the dispute and raises the slash's approvals from the multisig's threshold to every signer.
+pub const SLASH_DISPUTE_WINDOW: i64 = 3 * 24 * 60 * 60;
+
+/// Minimum time, in seconds, between proposing a configuration change and activating it (1 day), so a pending change is
+/// visible on chain before it takes effect.
+pub const CONFIG_ACTIVATION_DELAY: i64 = 24 * 60 * 60;
+
+/// Maximum number of pending requests in a mint's unstake queue.
+pub const MAX_UNSTAKE_QUEUE_LEN: usize = 32;
+
+/// Maximum length, in bytes, of a stake position's reference ID.
+pub const MAX_REFERENCE_ID_LEN: usize = 32;
+
+/// Bytes reserved for a registry's `reference_id`, which registries created before `MAX_REFERENCE_ID_LEN` was enforced
+/// may use in full.
+pub const REGISTRY_REFERENCE_ID_CAPACITY: usize = 60;
+
+/// Checks that `reference_id` is a non-empty identifier of at most `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`,
+/// so it always fits the registry's string allocation and can be indexed off-chain as-is.
+pub fn validate_reference_id(reference_id: &str) -> Result<()> {
+    require!(
+        reference_id.len() <= MAX_REFERENCE_ID_LEN,
+        StakingErrorCode::ReferenceIdTooLong
+    );
+    require!(
+        !reference_id.is_empty()
+            && reference_id
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'),
+        StakingErrorCode::InvalidReferenceId
+    );
+
+    Ok(())
+}
+
+/// Offset of the signature offsets table in an ed25519 program instruction, after the signature count and padding bytes.
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Size of one entry of the ed25519 program's signature offsets table: seven `u16` fields.
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Returns the message an attestor signs to approve thawing a stake position: `staker || reference_id || expires_at`.
+pub fn attestation_message(staker: &Pubkey, reference_id: &str, expires_at: i64) -> Vec<u8> {
+    [staker.as_ref(), reference_id.as_bytes(), &expires_at.to_le_bytes()].concat()
+}
+
+/// Checks that the instruction right before the current one is an ed25519 program instruction verifying
+/// a single signature by `attestor` over `message`.
+///
+/// The ed25519 program fails the whole transaction if the signature is invalid, so this only needs to check
+/// which key and message it verified. All offsets must point into the ed25519 instruction itself.
+pub fn verify_attestation(instructions_sysvar: &AccountInfo, attestor: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, StakingErrorCode::MissingAttestation);
+
+    let instruction = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        instruction.program_id == ed25519_program::ID,
+        StakingErrorCode::MissingAttestation
+    );
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        StakingErrorCode::InvalidAttestation
+    );
+
+    let read_u16 = |index: usize| {
+        let offset = ED25519_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[offset], data[offset + 1]])
+    };
+    let signature_instruction_index = read_u16(1);
+    let public_key_offset = read_u16(2) as usize;
+    let public_key_instruction_index = read_u16(3);
+    let message_data_offset = read_u16(4) as usize;
+    let message_data_size = read_u16(5) as usize;
+    let message_instruction_index = read_u16(6);
+
+    require!(
+        [signature_instruction_index, public_key_instruction_index, message_instruction_index]
+            .iter()
+            .all(|index| *index == u16::MAX),
+        StakingErrorCode::InvalidAttestation
+    );
+
+    let public_key = data.get(public_key_offset..public_key_offset + PUBKEY_SIZE);
+    let signed_message = data.get(message_data_offset..message_data_offset + message_data_size);
+    require!(
+        public_key == Some(attestor.as_ref()) && signed_message == Some(message),
+        StakingErrorCode::InvalidAttestation
+    );
+
+    Ok(())
+}
+
+/// Checks that `steps` is a non-empty decay ladder of at most `MAX_REWARD_SCHEDULE_STEPS` steps, with strictly increasing
+/// start epochs and a reward rate that never rises from one step to the next.
+pub fn validate_reward_schedule(steps: &[StakingRewardScheduleStep]) -> Result<()> {
+    require!(
+        !steps.is_empty() && steps.len() <= MAX_REWARD_SCHEDULE_STEPS,
+        StakingErrorCode::InvalidRewardSchedule
+    );
+    require!(
+        steps
+            .windows(2)
+            .all(|pair| pair[0].start_epoch < pair[1].start_epoch && pair[0].reward_rate_bps >= pair[1].reward_rate_bps),
+        StakingErrorCode::InvalidRewardSchedule
+    );
+
+    Ok(())
+}
+
 #[account]
+#[derive(InitSpace)]
 pub struct StakingConfigAccount {
     pub min_staking_amount: u64,
+    pub reward_mint: Pubkey,
+    pub reward_rate_bps: u16,
+    #[max_len(MAX_STAKING_TIERS)]
+    pub tiers: Vec<StakingTier>,
+    pub max_lock_duration: i64,
+    pub early_exit_penalty_bps: u16,
+    pub max_lock_boost_bps: u16,
+    pub unstake_cooldown: i64,
+    pub mint_allowlist_enabled: bool,
+    pub attestor: Pubkey,
+    pub min_inclusive: bool,
+    pub reward_budget: u64,
+    pub staking_paused: bool,
+    pub max_total_staked: u64,
+    #[max_len(MAX_LOYALTY_MILESTONES)]
+    pub loyalty_milestones: Vec<StakingLoyaltyMilestone>,
+    pub paused_at: i64,
+    pub pause_grace_period: i64,
+    pub pair_sol_ratio_bps: u64,
+    pub pair_boost_bps: u16,
+    #[max_len(MAX_FEE_DISCOUNTS)]
+    pub fee_discounts: Vec<StakingFeeDiscount>,
+    pub version: u8,
+    pub bump: u8,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
 }
 
+/// The current layout version of [`StakingConfigAccount`], written when the configuration is created or migrated.
+///
+/// Configurations created before the `version` field was added decode as version 0, since the byte after their last field
+/// is zero padding. Fields added by later versions are appended after `version` and backfilled by [`StakingConfigAccount::upgrade`].
+pub const CONFIG_VERSION: u8 = 3;
+
 impl Default for StakingConfigAccount {
     fn default() -> Self {
         Self {
             min_staking_amount: 10_000,
+            reward_mint: Pubkey::default(),
+            reward_rate_bps: 1_000,
+            tiers: vec![
+                StakingTier { min_amount: 0, multiplier_bps: 10_000 },
+                StakingTier { min_amount: 100_000, multiplier_bps: 12_500 },
+                StakingTier { min_amount: 1_000_000, multiplier_bps: 15_000 },
+            ],
+            max_lock_duration: SECONDS_PER_YEAR as i64,
+            early_exit_penalty_bps: 1_000,
+            max_lock_boost_bps: 5_000,
+            unstake_cooldown: 0,
+            mint_allowlist_enabled: false,
+            attestor: Pubkey::default(),
+            min_inclusive: true,
+            reward_budget: 0,
+            staking_paused: false,
+            max_total_staked: 0,
+            loyalty_milestones: vec![
+                StakingLoyaltyMilestone { min_duration: SECONDS_PER_YEAR as i64 / 2, boost_bps: 500 },
+                StakingLoyaltyMilestone { min_duration: SECONDS_PER_YEAR as i64, boost_bps: 500 },
+            ],
+            paused_at: 0,
+            pause_grace_period: 30 * 24 * 60 * 60,
+            pair_sol_ratio_bps: 10_000,
+            pair_boost_bps: 2_500,
+            fee_discounts: vec![
+                StakingFeeDiscount { min_staked: 100_000, discount_bps: 2_500 },
+                StakingFeeDiscount { min_staked: 1_000_000, discount_bps: 5_000 },
+            ],
+            version: CONFIG_VERSION,
+            bump: 0,
+            created_at: 0,
+            updated_at: 0,
+            last_actor: Pubkey::default(),
         }
     }
 }
 
 impl StakingConfigAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // min_staking_amount
-        U64_SIZE;
+    /// Backfills the fields added since `version` with their defaults, caches the canonical `bump`, and stamps `CONFIG_VERSION`.
+    ///
+    /// Each new layout version adds a step here for the fields it appends; version 1 only introduced `version` itself and
+    /// version 2 appended `bump`. Version 3 appended the audit metadata, which migrated configurations leave zeroed until
+    /// their next write.
+    pub fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = CONFIG_VERSION;
+    }
+
+    /// Returns `true` if `amount` meets the minimum staking amount, which it may equal only when `min_inclusive` is set.
+    pub fn meets_minimum(&self, amount: u64) -> bool {
+        if self.min_inclusive {
+            amount >= self.min_staking_amount
+        } else {
+            amount > self.min_staking_amount
+        }
+    }
+
+    /// Checks that staking is not paused before `operation` opens or adds to a stake.
+    ///
+    /// While paused, emits a `StakingOperationBlockedEvent` so the blocked attempt shows up in the transaction logs.
+    pub fn check_not_paused(&self, staker: &Pubkey, operation: &str) -> Result<()> {
+        if self.staking_paused {
+            emit!(StakingOperationBlockedEvent {
+                staker: *staker,
+                operation: operation.to_string(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+
+            return err!(StakingErrorCode::StakingPaused);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if staking has been paused for longer than `pause_grace_period` at `now`.
+    ///
+    /// Past the grace period, stakers can release their stakes without attestations, lockups, cooldowns, or penalties,
+    /// so funds stay recoverable even if the operators never resume staking.
+    pub fn pause_grace_elapsed(&self, now: i64) -> bool {
+        self.staking_paused && now >= self.paused_at.saturating_add(self.pause_grace_period)
+    }
+
+    /// Returns the lamports a pair stake must lock alongside `amount` tokens at the configured ratio.
+    ///
+    /// Fails with `PairStakingDisabled` while the ratio is zero, and with `InvalidAmount` if `amount` is too small to require any SOL.
+    pub fn pair_lamports(&self, amount: u64) -> Result<u64> {
+        require!(self.pair_sol_ratio_bps > 0, StakingErrorCode::PairStakingDisabled);
+
+        let lamports = (amount as u128) * (self.pair_sol_ratio_bps as u128) / (BASIS_POINTS as u128);
+        let lamports = u64::try_from(lamports).map_err(|_| error!(StakingErrorCode::InvalidAmount))?;
+        require!(lamports > 0, StakingErrorCode::InvalidAmount);
+
+        Ok(lamports)
+    }
+
+    /// Returns `true` if thaws must carry a signature from the configured attestor.
+    pub fn attestation_required(&self) -> bool {
+        self.attestor != Pubkey::default()
+    }
+
+    /// Checks that a mint with the given staking mint configuration may be staked.
+    ///
+    /// While the allowlist is enabled, only mints whose configuration marks them as allowed can be staked.
+    pub fn check_mint_allowed(&self, staking_mint_config: Option<&StakingMintConfigAccount>) -> Result<()> {
+        require!(
+            !self.mint_allowlist_enabled || staking_mint_config.is_some_and(|config| config.allowed),
+            StakingErrorCode::MintNotAllowed
+        );
+
+        Ok(())
+    }
+
+    /// Returns `amount` if a position in `mint` that does or does not `earn_rewards` counts towards the pool's totals and
+    /// the staking cap, or zero otherwise.
+    ///
+    /// Only reward-earning positions in the reward mint count: other mints' amounts are in their own units, and a freeze
+    /// of a mint the staker controls locks nothing, so neither may fill the cap or weigh in the epoch snapshots.
+    pub fn pooled_amount(&self, mint: &Pubkey, earns_rewards: bool, amount: u64) -> u64 {
+        if *mint == self.reward_mint && earns_rewards {
+            amount
+        } else {
+            0
+        }
+    }
+
+    /// Returns the multiplier (in basis points) of the highest tier reached by `amount`,
+    /// or `BASIS_POINTS` (1x) if no tier applies.
+    pub fn tier_multiplier(&self, amount: u64) -> u16 {
+        self.tier_index(amount)
+            .map(|index| self.tiers[index].multiplier_bps)
+            .unwrap_or(BASIS_POINTS as u16)
+    }
+
+    /// Returns the teleport fee discount (in basis points) of the highest fee discount entry reached by `staked_amount`,
+    /// or zero if none applies.
+    pub fn fee_discount_bps(&self, staked_amount: u64) -> u16 {
+        self.fee_discounts
+            .iter()
+            .filter(|discount| staked_amount >= discount.min_staked)
+            .max_by_key(|discount| discount.min_staked)
+            .map_or(0, |discount| discount.discount_bps.min(BASIS_POINTS as u16))
+    }
+
+    /// Returns the index in `tiers` of the highest tier reached by `amount`, or `None` if no tier applies.
+    pub fn tier_index(&self, amount: u64) -> Option<usize> {
+        self.tiers
+            .iter()
+            .enumerate()
+            .filter(|(_, tier)| amount >= tier.min_amount)
+            .max_by_key(|(_, tier)| tier.min_amount)
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the loyalty boost (in basis points) of a stake held without interruption since `started_at`.
+    ///
+    /// Milestones are incremental: the boosts of every milestone whose duration has been reached are added together.
+    /// A stake that has not started (`started_at` of zero) earns no boost.
+    pub fn loyalty_boost(&self, started_at: i64, now: i64) -> u16 {
+        if started_at == 0 {
+            return 0;
+        }
+
+        let held_for = now.saturating_sub(started_at);
+        self.loyalty_milestones
+            .iter()
+            .filter(|milestone| held_for >= milestone.min_duration)
+            .fold(0u16, |boost, milestone| boost.saturating_add(milestone.boost_bps))
+    }
+
+    /// Returns the reward multiplier (in basis points) for a stake of `amount` locked until `unlocks_at`.
+    ///
+    /// The tier multiplier is boosted by up to `max_lock_boost_bps`, in proportion to the remaining lock time
+    /// relative to `max_lock_duration`.
+    pub fn multiplier(&self, amount: u64, unlocks_at: i64, now: i64) -> u16 {
+        let remaining_lock = unlocks_at.saturating_sub(now).clamp(0, self.max_lock_duration.max(0));
+        let lock_boost = if self.max_lock_duration > 0 {
+            (self.max_lock_boost_bps as i64 * remaining_lock / self.max_lock_duration) as u16
+        } else {
+            0
+        };
+
+        self.tier_multiplier(amount).saturating_add(lock_boost)
+    }
+
+    /// Returns the timestamp at which a stake locked at `now` for `lock_duration` seconds unlocks.
+    pub fn unlock_time(&self, now: i64, lock_duration: i64) -> Result<i64> {
+        require!(
+            (0..=self.max_lock_duration).contains(&lock_duration),
+            StakingErrorCode::InvalidLockDuration
+        );
+
+        Ok(now.saturating_add(lock_duration))
+    }
+
+    /// Returns the part of `amount` withheld as the early-exit penalty.
+    pub fn early_exit_penalty(&self, amount: u64) -> u64 {
+        ((amount as u128) * (self.early_exit_penalty_bps as u128) / (BASIS_POINTS as u128)) as u64
+    }
+}
+
+impl Audited for StakingConfigAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum StakingMode {
+    Freeze,
+    Escrow,
+    Pair,
+}
+
+/// Maximum number of stake positions a single batch freeze or thaw may operate on.
+pub const MAX_BATCH_POSITIONS: usize = 8;
+
+/// Number of remaining accounts describing one stake position in a batch freeze or thaw:
+/// the mint, the staker's token account, the mint configuration (or the program ID when absent), and the staking registry.
+pub const BATCH_ACCOUNTS_PER_POSITION: usize = 4;
+
+/// One position to freeze in [`StakingInstructions::batch_freeze`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct StakingBatchFreezeEntry {
+    pub position_index: u64,
+    pub reference_number: String,
+    pub lock_duration: i64,
+}
+
+/// The accounts of one stake position in a batch freeze or thaw, loaded from its remaining accounts.
+struct StakingBatchPosition<'info> {
+    token: InterfaceAccount<'info, Mint>,
+    staker_token: InterfaceAccount<'info, TokenAccount>,
+    staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+    staking_registry: &'info AccountInfo<'info>,
+}
+
+impl<'info> StakingBatchPosition<'info> {
+    /// Loads a position from its `BATCH_ACCOUNTS_PER_POSITION` remaining accounts.
+    ///
+    /// The token account must be the staker's associated token account for the mint under `token_program`, and a mint
+    /// configuration, when passed, must belong to the mint.
+    fn load(accounts: &'info [AccountInfo<'info>], staker: &Pubkey, token_program: &Pubkey) -> Result<Self> {
+        let token = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let staker_token = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        require!(
+            staker_token.key() == get_associated_token_address_with_program_id(staker, &token.key(), token_program),
+            StakingErrorCode::InvalidBatchAccounts
+        );
+
+        let staking_mint_config = if accounts[2].key() == crate::ID {
+            None
+        } else {
+            let staking_mint_config = Account::<StakingMintConfigAccount>::try_from(&accounts[2])?;
+            require!(
+                staking_mint_config.mint == token.key(),
+                StakingErrorCode::InvalidBatchAccounts
+            );
+            Some(staking_mint_config)
+        };
+
+        Ok(Self {
+            token,
+            staker_token,
+            staking_mint_config,
+            staking_registry: &accounts[3],
+        })
+    }
+}
+
+/// A live view of a stake position, returned by [`StakingInstructions::get_position`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct StakingPositionView {
+    pub staked_amount: u64,
+    pub accrued_rewards: u64,
+    pub apr_bps: u64,
+    pub unlocks_at: i64,
+}
+
+/// A compact view of a stake position for CPI consumers, returned by [`StakingInstructions::get_stake_info`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct StakingStakeInfo {
+    pub staked_amount: u64,
+    pub unlocks_at: i64,
+    pub multiplier_bps: u16,
+    pub slashed: bool,
+}
+
+/// A view of the staking configuration's scalar values, returned by [`StakingInstructions::get_config`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct StakingConfigView {
+    pub min_staking_amount: u64,
+    pub reward_mint: Pubkey,
+    pub reward_rate_bps: u16,
+    pub max_lock_duration: i64,
+    pub early_exit_penalty_bps: u16,
+    pub max_lock_boost_bps: u16,
+    pub unstake_cooldown: i64,
+    pub staking_paused: bool,
+    pub max_total_staked: u64,
+    pub reward_budget: u64,
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct StakingRegistryAccount {
+    pub position_index: u64,
+    #[max_len(REGISTRY_REFERENCE_ID_CAPACITY)]
     pub reference_id: String,
+    pub mode: StakingMode,
+    pub mint: Pubkey,
+    pub staked_amount: u64,
+    pub multiplier_bps: u16,
+    pub accrued_rewards: u64,
+    pub last_accrued_at: i64,
+    pub slashed_amount: u64,
+    pub unlocks_at: i64,
+    pub emergency_thawed_at: i64,
+    pub unstake_requested_at: i64,
+    pub cooldown_ends_at: i64,
+    pub referrer: Pubkey,
+    pub token_account: Pubkey,
+    pub version: u8,
+    pub started_at: i64,
+    pub loyalty_boost_bps: u16,
+    pub custodian: Pubkey,
+    pub paired_lamports: u64,
+    pub bump: u8,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
 }
 
-impl StakingRegistryAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // reference_id
-        STRING_LEN; 
+/// The current layout version of [`StakingRegistryAccount`], written whenever a registry is created or migrated.
+pub const REGISTRY_VERSION: u8 = 6;
+
+/// The staking registry layout before the `version` field was added, used to decode registries awaiting migration.
+///
+/// Version 1 registries only append the `version` byte to this layout, so they are decoded with it as well.
+/// Version 2 registries also carry [`StakingRegistryV2Fields`] after it, version 3 registries [`StakingRegistryV3Fields`] after
+/// those, and version 4 registries [`StakingRegistryV4Fields`] after those. Version 5 registries only append the `bump`
+/// the migration recomputes, so they are decoded as version 4 ones.
+#[derive(AnchorDeserialize)]
+pub struct StakingRegistryAccountV0 {
+    pub position_index: u64,
+    pub reference_id: String,
+    pub mode: StakingMode,
+    pub mint: Pubkey,
+    pub staked_amount: u64,
+    pub multiplier_bps: u16,
+    pub accrued_rewards: u64,
+    pub last_accrued_at: i64,
+    pub slashed_amount: u64,
+    pub unlocks_at: i64,
+    pub emergency_thawed_at: i64,
+    pub unstake_requested_at: i64,
+    pub cooldown_ends_at: i64,
+    pub referrer: Pubkey,
+    pub token_account: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct StakingFreeze<'info> {
-    #[account(mut)]
-    pub staker: Signer<'info>,
+/// The fields version 2 of the staking registry appends to [`StakingRegistryAccountV0`].
+#[derive(AnchorDeserialize)]
+pub struct StakingRegistryV2Fields {
+    pub version: u8,
+    pub started_at: i64,
+    pub loyalty_boost_bps: u16,
+}
 
-    #[account(
-        mut,
-        constraint = token.freeze_authority.unwrap() == *staker.key,
-    )]
-    pub token: Account<'info, Mint>,
+impl StakingRegistryV2Fields {
+    /// The size of a version 2 registry account, which lacks the `custodian`, `paired_lamports`, `bump`, and audit metadata
+    /// of the current layout.
+    pub const ACCOUNT_SPACE: usize = StakingRegistryAccount::SPACE - PUBKEY_SIZE - U64_SIZE - U8_SIZE - AUDIT_SPACE;
+}
 
-    #[account(
-        mut,
-        associated_token::mint = token,
-        associated_token::authority = staker,
-    )]
-    pub staker_token: Account<'info, TokenAccount>,
+/// The field version 3 of the staking registry appends to [`StakingRegistryV2Fields`].
+#[derive(AnchorDeserialize)]
+pub struct StakingRegistryV3Fields {
+    pub custodian: Pubkey,
+}
 
-    #[account(
-        init_if_needed,
-        payer = staker,
-        space = 8 + StakingConfigAccount::LEN,
-        seeds = [b"staking_config"],
-        bump
-    )]
-    pub staking_config: Account<'info, StakingConfigAccount>,
+impl StakingRegistryV3Fields {
+    /// The size of a version 3 registry account, which lacks the `paired_lamports`, `bump`, and audit metadata of the
+    /// current layout.
+    pub const ACCOUNT_SPACE: usize = StakingRegistryAccount::SPACE - U64_SIZE - U8_SIZE - AUDIT_SPACE;
+}
 
-    #[account(
-        init_if_needed,
-        payer = staker,
-        space = 8 + StakingRegistryAccount::LEN,
-        seeds = [
-            b"staking_registry",
-            staker.key().as_ref(),
-        ],
-        bump
-    )]
-    pub staking_registry: Account<'info, StakingRegistryAccount>,
+/// The field version 4 of the staking registry appends to [`StakingRegistryV3Fields`].
+#[derive(AnchorDeserialize)]
+pub struct StakingRegistryV4Fields {
+    pub paired_lamports: u64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+impl StakingRegistryV4Fields {
+    /// The size of a version 4 registry account, which lacks the `bump` and audit metadata of the current layout.
+    pub const ACCOUNT_SPACE: usize = StakingRegistryAccount::SPACE - U8_SIZE - AUDIT_SPACE;
 }
 
-#[derive(Accounts)]
-pub struct StakingThaw<'info> {
-    #[account(mut)]
-    pub staker: Signer<'info>,
+/// A staking registry decoded with the layout it was written with, so a registry created before a layout upgrade can be
+/// read without being migrated first and upgraded by whichever instruction touches it next.
+///
+/// Each variant is named after the oldest layout version it decodes; see [`StakingRegistryAccountV0`] for the versions
+/// each layout covers.
+pub enum StakingRegistryData {
+    V0(StakingRegistryAccountV0),
+    V2(StakingRegistryAccountV0, StakingRegistryV2Fields),
+    V3(StakingRegistryAccountV0, StakingRegistryV2Fields, StakingRegistryV3Fields),
+    V4(StakingRegistryAccountV0, StakingRegistryV2Fields, StakingRegistryV3Fields, StakingRegistryV4Fields),
+    Current(StakingRegistryAccount),
+}
 
-    #[account(
-        mut,
-        constraint = token.freeze_authority.unwrap() == *staker.key,
-    )]
-    pub token: Account<'info, Mint>,
+impl StakingRegistryData {
+    /// Decodes a registry account's data with the layout its size identifies.
+    ///
+    /// Registries allocated with the current `SPACE` are decoded in the current layout. Smaller ones are decoded with the
+    /// legacy layout and the fields their size shows were appended after it, so stale bytes past their serialized fields
+    /// are never read as newer fields.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(StakingRegistryAccount::DISCRIMINATOR),
+            StakingErrorCode::InvalidStakingRegistry
+        );
+        if data.len() >= StakingRegistryAccount::SPACE {
+            return Ok(Self::Current(StakingRegistryAccount::try_deserialize(&mut &data[..])?));
+        }
 
-    #[account(
-        mut,
-        associated_token::mint = token,
-        associated_token::authority = staker,
-    )]
-    pub staker_token: Account<'info, TokenAccount>,
+        let mut legacy_data = &data[StakingRegistryAccount::DISCRIMINATOR.len()..];
+        let legacy = decode_registry_fields(&mut legacy_data)?;
+        if data.len() < StakingRegistryV2Fields::ACCOUNT_SPACE {
+            return Ok(Self::V0(legacy));
+        }
+        let v2_fields = decode_registry_fields(&mut legacy_data)?;
+        if data.len() < StakingRegistryV3Fields::ACCOUNT_SPACE {
+            return Ok(Self::V2(legacy, v2_fields));
+        }
+        let v3_fields = decode_registry_fields(&mut legacy_data)?;
+        if data.len() < StakingRegistryV4Fields::ACCOUNT_SPACE {
+            return Ok(Self::V3(legacy, v2_fields, v3_fields));
+        }
+        let v4_fields = decode_registry_fields(&mut legacy_data)?;
 
-    #[account(
-        mut,
-        seeds = [b"staking_config"],
-        bump
-    )]
-    pub staking_config: Account<'info, StakingConfigAccount>,
+        Ok(Self::V4(legacy, v2_fields, v3_fields, v4_fields))
+    }
 
-    #[account(
-        mut,
-        seeds = [
-            b"staking_registry",
-            staker.key().as_ref(),
-        ],
-        bump
-    )]
-    pub staking_registry: Account<'info, StakingRegistryAccount>,
+    /// Returns `true` if the registry already uses the current layout.
+    pub fn is_current(&self) -> bool {
+        matches!(self, Self::Current(_))
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    /// Returns the index of the stake position the registry records.
+    pub fn position_index(&self) -> u64 {
+        match self {
+            Self::V0(legacy) | Self::V2(legacy, ..) | Self::V3(legacy, ..) | Self::V4(legacy, ..) => legacy.position_index,
+            Self::Current(staking_registry) => staking_registry.position_index,
+        }
+    }
+
+    /// Carries the registry over to the current layout, backfilling the fields its layout lacks with safe defaults.
+    ///
+    /// An active stake's loyalty holding time starts at `now` if its original start was never recorded. Legacy layouts never
+    /// stored the `bump` or audit metadata, so callers writing the upgraded registry back record those themselves.
+    pub fn upgrade(self, now: i64) -> StakingRegistryAccount {
+        let (legacy, v2_fields, v3_fields, v4_fields) = match self {
+            Self::Current(staking_registry) => return staking_registry,
+            Self::V0(legacy) => (legacy, None, None, None),
+            Self::V2(legacy, v2_fields) => (legacy, Some(v2_fields), None, None),
+            Self::V3(legacy, v2_fields, v3_fields) => (legacy, Some(v2_fields), Some(v3_fields), None),
+            Self::V4(legacy, v2_fields, v3_fields, v4_fields) => (legacy, Some(v2_fields), Some(v3_fields), Some(v4_fields)),
+        };
+
+        let mut staking_registry = StakingRegistryAccount::from(legacy);
+        if let Some(v2_fields) = v2_fields {
+            staking_registry.started_at = v2_fields.started_at;
+            staking_registry.loyalty_boost_bps = v2_fields.loyalty_boost_bps;
+        } else if staking_registry.staked_amount > 0 {
+            staking_registry.started_at = now;
+        }
+        if let Some(v3_fields) = v3_fields {
+            staking_registry.custodian = v3_fields.custodian;
+        }
+        if let Some(v4_fields) = v4_fields {
+            staking_registry.paired_lamports = v4_fields.paired_lamports;
+        }
+
+        staking_registry
+    }
 }
 
-pub struct StakingInstructions;
+/// Decodes the next part of a legacy registry layout, failing with `InvalidStakingRegistry` if the data does not hold it.
+fn decode_registry_fields<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T> {
+    T::deserialize(data).map_err(|_| error!(StakingErrorCode::InvalidStakingRegistry))
+}
 
-impl StakingInstructions {
-    /// Freezes the staker's token account and records a reference identifier in the staking registry.
-    ///
-    /// This function performs the following actions:
-    /// - Checks that the staker's token account balance is greater than the minimum required staking amount.
-    /// - Freezes the staker's token account using the SPL Token program.
-    /// - Stores the provided reference number in the staking registry for tracking purposes.
-    ///
-    /// ## Arguments
-    ///
-    /// * `ctx` - The context containing the accounts required for the freeze operation, including the staker, token account, staking configuration, and staking registry.
-    /// * `reference_number` - A string identifier to associate with this staking freeze operation.
-    ///
-    /// ## Returns
-    ///
-    /// Returns `Ok(())` if the freeze operation is successful, otherwise returns an error.
-    pub fn freeze(ctx: Context<StakingFreeze>, reference_number: String) -> Result<()> {
-        let user_balance = ctx.accounts.staker_token.amount;
-        let min_required = ctx.accounts.staking_config.min_staking_amount;
+impl From<StakingRegistryAccountV0> for StakingRegistryAccount {
+    /// Carries a legacy registry over to the current layout, backfilling the fields it lacks with safe defaults.
+    fn from(legacy: StakingRegistryAccountV0) -> Self {
+        Self {
+            position_index: legacy.position_index,
+            reference_id: legacy.reference_id,
+            mode: legacy.mode,
+            mint: legacy.mint,
+            staked_amount: legacy.staked_amount,
+            multiplier_bps: legacy.multiplier_bps,
+            accrued_rewards: legacy.accrued_rewards,
+            last_accrued_at: legacy.last_accrued_at,
+            slashed_amount: legacy.slashed_amount,
+            unlocks_at: legacy.unlocks_at,
+            emergency_thawed_at: legacy.emergency_thawed_at,
+            unstake_requested_at: legacy.unstake_requested_at,
+            cooldown_ends_at: legacy.cooldown_ends_at,
+            referrer: legacy.referrer,
+            token_account: legacy.token_account,
+            version: REGISTRY_VERSION,
+            started_at: 0,
+            loyalty_boost_bps: 0,
+            custodian: Pubkey::default(),
+            paired_lamports: 0,
+            bump: 0,
+            created_at: 0,
+            updated_at: 0,
+            last_actor: Pubkey::default(),
+        }
+    }
+}
 
-        require!(
-            user_balance > min_required,
-            StakingErrorCode::InsufficientStakingBalance
-        );
+impl StakingRegistryAccount {
+    /// The space registries are allocated with: the discriminator and fields, plus the 8 bytes of trailing slack every
+    /// registry has been created with. `migrate_registry` tells layout versions apart by size, so each layout that grows
+    /// it also shifts the `ACCOUNT_SPACE` of the older ones.
+    pub const SPACE: usize = 8 + Self::INIT_SPACE + 8;
 
-        freeze_account(CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            FreezeAccount {
-                account: ctx.accounts.staker_token.to_account_info(),
-                mint: ctx.accounts.token.to_account_info(),
-                authority: ctx.accounts.staker.to_account_info(),
-            },
-        ))?;
+    /// Returns `true` if the registry holds a stake made in a different staking mode than `mode`.
+    pub fn has_active_stake_in_other_mode(&self, mode: StakingMode) -> bool {
+        self.staked_amount > 0 && self.mode != mode
+    }
 
-        let staking_registry = &mut ctx.accounts.staking_registry;
-        staking_registry.reference_id = String::from(reference_number);
+    /// Checks that an active stake is only topped up or refreshed with the mint and token account it was made with,
+    /// and by the same custodian (the default key when the staker stakes for themself, or the staking authority PDA for
+    /// freezes of custodial mints).
+    pub fn check_staked_accounts(&self, mint: &Pubkey, token_account: &Pubkey, custodian: &Pubkey) -> Result<()> {
+        if self.staked_amount == 0 {
+            return Ok(());
+        }
+
+        require!(self.mint == *mint, StakingErrorCode::MintMismatch);
+        require!(self.token_account == *token_account, StakingErrorCode::TokenAccountMismatch);
+        require!(self.custodian == *custodian, StakingErrorCode::CustodianMismatch);
 
         Ok(())
     }
 
-    /// Thaws the staker's previously frozen token account and clears the reference identifier in the staking registry.
-    ///
-    /// This function performs the following actions:
-    /// - Unfreezes the staker's token account using the SPL Token program.
-    /// - Clears the reference number in the staking registry to indicate the staking freeze has been lifted.
+    /// Returns whether releasing the stake at `now` is an early exit.
     ///
-    /// ## Arguments
-    ///
-    /// * `ctx` - The context containing the accounts required for the thaw operation, including the staker, token account, and staking registry.
+    /// Fails with `StakeStillLocked` if the stake is still locked and the early-exit path was not explicitly requested.
+    pub fn is_early_exit(&self, now: i64, early_exit: bool) -> Result<bool> {
+        if now >= self.unlocks_at {
+            return Ok(false);
+        }
+
+        require!(early_exit, StakingErrorCode::StakeStillLocked);
+
+        Ok(true)
+    }
+
+    /// Recalculates the position's multiplier at `now` from its staked amount, remaining lock, and uninterrupted holding time,
+    /// adding the configured pair boost to pair stakes.
+    ///
+    /// The holding clock starts when the position first holds a stake and is reset once the stake is fully released, so the
+    /// loyalty boost recorded in `loyalty_boost_bps` only rewards continuous stakes. A released position's multiplier is zero.
+    pub fn refresh_multiplier(&mut self, staking_config: &StakingConfigAccount, now: i64) {
+        if self.staked_amount == 0 {
+            self.started_at = 0;
+            self.loyalty_boost_bps = 0;
+            self.multiplier_bps = 0;
+            return;
+        }
+
+        if self.started_at == 0 {
+            self.started_at = now;
+        }
+        self.loyalty_boost_bps = staking_config.loyalty_boost(self.started_at, now);
+        self.multiplier_bps = staking_config
+            .multiplier(self.staked_amount, self.unlocks_at, now)
+            .saturating_add(self.loyalty_boost_bps);
+        if self.mode == StakingMode::Pair {
+            self.multiplier_bps = self.multiplier_bps.saturating_add(staking_config.pair_boost_bps);
+        }
+    }
+
+    /// Returns the position's voting power: its staked amount scaled by its tier and lock multiplier.
+    pub fn voting_power(&self) -> u64 {
+        let voting_power = (self.staked_amount as u128) * (self.multiplier_bps as u128) / (BASIS_POINTS as u128);
+
+        u64::try_from(voting_power).unwrap_or(u64::MAX)
+    }
+
+    /// Returns `true` unless the position is a freeze of a mint whose freeze authority is the staker.
+    ///
+    /// Such a staker can thaw their token account without the program, so the stake is not locked and earns nothing. Freezes
+    /// of custodial mints, where the staking authority PDA holds the freeze authority and is recorded as the position's
+    /// custodian, and escrow and pair stakes do earn rewards.
+    pub fn earns_rewards(&self) -> bool {
+        self.mode != StakingMode::Freeze || self.custodian != Pubkey::default()
+    }
+
+    /// Returns `amount` if the position is a reward-earning stake of the reward mint, or zero otherwise, for recording a
+    /// change of the position in the pool.
+    pub fn pooled_amount(&self, staking_config: &StakingConfigAccount, amount: u64) -> u64 {
+        staking_config.pooled_amount(&self.mint, self.earns_rewards(), amount)
+    }
+
+    /// Returns the position's [voting power](Self::voting_power) in a vote opened at `opened_at`, or zero unless it is a
+    /// reward-earning stake of the reward mint last written before the vote opened.
+    ///
+    /// Every change to a position stamps its `updated_at`, so a position that still counts holds the stake it held when
+    /// the vote opened, and tokens released from one position and staked into another after that count in neither.
+    pub fn voting_power_at(&self, staking_config: &StakingConfigAccount, opened_at: i64) -> u64 {
+        if self.updated_at >= opened_at {
+            return 0;
+        }
+
+        self.pooled_amount(staking_config, self.voting_power())
+    }
+
+    /// Returns the stake the position shares an epoch's distributions with, for a snapshot recorded at `recorded_at`: its
+    /// staked amount if it is a reward-earning stake of the reward mint, or zero otherwise.
+    ///
+    /// Fails with `StakedAfterSnapshot` unless the position started before the snapshot and was last written before it,
+    /// so the amount is the one the snapshot's total counted, and a top-up after the snapshot does not raise the share.
+    pub fn snapshot_stake(&self, staking_config: &StakingConfigAccount, recorded_at: i64) -> Result<u64> {
+        require!(self.staked_amount > 0, StakingErrorCode::NoActiveStake);
+        require!(
+            self.started_at > 0 && self.started_at <= recorded_at && self.updated_at < recorded_at,
+            StakingErrorCode::StakedAfterSnapshot
+        );
+
+        Ok(self.pooled_amount(staking_config, self.staked_amount))
+    }
+
+    /// Returns the position's effective annual reward rate in basis points: the base rate scaled by its multiplier.
+    ///
+    /// Positions with no stake, a pending unstake request, or that do not earn rewards earn nothing, so their rate is zero.
+    pub fn effective_apr_bps(&self, reward_rate_bps: u16) -> u64 {
+        if self.staked_amount == 0 || self.unstake_requested_at > 0 || !self.earns_rewards() {
+            return 0;
+        }
+
+        (reward_rate_bps as u64) * (self.multiplier_bps as u64) / BASIS_POINTS
+    }
+
+    /// Checks that the stake can be released at `now`, returning whether the release is an early exit.
+    ///
+    /// Applies the lockup and unstake cooldown checks, unless staking has been paused past its grace period, in which case
+    /// the stake can always be released and the release is never an early exit.
+    pub fn check_release(&self, staking_config: &StakingConfigAccount, now: i64, early_exit: bool) -> Result<bool> {
+        if staking_config.pause_grace_elapsed(now) {
+            return Ok(false);
+        }
+
+        let is_early_exit = self.is_early_exit(now, early_exit)?;
+        self.check_cooldown(staking_config.unstake_cooldown, now)?;
+
+        Ok(is_early_exit)
+    }
+
+    /// Checks that the stake can be released at `now` under the unstake cooldown.
+    ///
+    /// With a cooldown configured, an unstake must have been requested and its cooldown must have elapsed.
+    pub fn check_cooldown(&self, unstake_cooldown: i64, now: i64) -> Result<()> {
+        if unstake_cooldown == 0 && self.unstake_requested_at == 0 {
+            return Ok(());
+        }
+
+        require!(self.unstake_requested_at > 0, StakingErrorCode::UnstakeNotRequested);
+        require!(now >= self.cooldown_ends_at, StakingErrorCode::CooldownNotElapsed);
+
+        Ok(())
+    }
+
+    /// Attributes `amount` newly staked into the position to `referrer`, recording it in the referrer's statistics.
+    ///
+    /// A position keeps the referrer it was first attributed to; later stakes may only name that same referrer.
+    /// The referrer and its statistics account must either both be provided or both be omitted.
+    pub fn attribute_referral(
+        &mut self,
+        staker: &Pubkey,
+        referrer: Option<Pubkey>,
+        staking_referrer: Option<&mut StakingReferrerAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let (referrer, staking_referrer) = match (referrer, staking_referrer) {
+            (Some(referrer), Some(staking_referrer)) => (referrer, staking_referrer),
+            (None, None) => return Ok(()),
+            _ => return err!(StakingErrorCode::InvalidReferrer),
+        };
+
+        require!(referrer != *staker, StakingErrorCode::SelfReferral);
+
+        let new_referral = self.referrer == Pubkey::default();
+        require!(new_referral || self.referrer == referrer, StakingErrorCode::InvalidReferrer);
+
+        self.referrer = referrer;
+        staking_referrer.record_referral(referrer, new_referral, amount);
+
+        Ok(())
+    }
+
+    /// Returns `true` if the position can be closed at `now`: it holds no stake, lock, pending unstake, or unclaimed rewards.
+    pub fn is_closable(&self, now: i64) -> bool {
+        self.staked_amount == 0
+            && self.accrued_rewards == 0
+            && self.unstake_requested_at == 0
+            && self.unlocks_at <= now
+    }
+
+    /// Clears a pending unstake request, so further releases need a new request and rewards accrue again.
+    pub fn clear_unstake_request(&mut self) {
+        self.unstake_requested_at = 0;
+        self.cooldown_ends_at = 0;
+    }
+
+    /// Adds the rewards earned since `last_accrued_at` to `accrued_rewards` and moves the accrual checkpoint to `now`.
+    ///
+    /// Rewards are computed as `staked_amount * reward_rate_bps * multiplier_bps * elapsed / (BASIS_POINTS² * SECONDS_PER_YEAR)`,
+    /// so the multiplier last recorded on the position boosts the base annual rate; a loyalty milestone reached since then
+    /// applies from the next update of the position. Rewards stop accruing once an unstake is requested, and never accrue
+    /// on positions that do not [earn rewards](Self::earns_rewards).
+    pub fn accrue_rewards(&mut self, reward_rate_bps: u16, now: i64) {
+        if !self.earns_rewards() {
+            self.last_accrued_at = now;
+            return;
+        }
+
+        let accrue_until = if self.unstake_requested_at > 0 {
+            now.min(self.unstake_requested_at)
+        } else {
+            now
+        };
+        let elapsed = accrue_until.saturating_sub(self.last_accrued_at).max(0) as u128;
+        let rewards = (self.staked_amount as u128)
+            .saturating_mul(reward_rate_bps as u128)
+            .saturating_mul(self.multiplier_bps as u128)
+            .saturating_mul(elapsed)
+            / (BASIS_POINTS as u128 * BASIS_POINTS as u128 * SECONDS_PER_YEAR as u128);
+
+        self.accrued_rewards = self
+            .accrued_rewards
+            .saturating_add(u64::try_from(rewards).unwrap_or(u64::MAX));
+        self.last_accrued_at = now;
+    }
+}
+
+impl Audited for StakingRegistryAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
+}
+
+/// Sums the voting power of `staker`'s stake positions passed as `registries` in a vote opened at `opened_at`.
+///
+/// Every account must be a staking registry PDA of `staker`, and no position may be passed twice.
+/// Positions left out simply do not count towards the total, and registries awaiting migration count as if migrated.
+/// Only reward-earning stakes of the reward mint that have not changed since the vote opened count, as described in
+/// [`StakingRegistryAccount::voting_power_at`].
+pub fn staked_voting_power(
+    staker: &Pubkey,
+    staking_config: &StakingConfigAccount,
+    registries: &[AccountInfo],
+    opened_at: i64,
+) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut position_indexes: Vec<u64> = Vec::with_capacity(registries.len());
+    let mut voting_power: u64 = 0;
+
+    for registry in registries {
+        require!(registry.owner == &crate::ID, StakingErrorCode::InvalidStakingRegistry);
+
+        let staking_registry = StakingRegistryData::decode(&registry.try_borrow_data()?)?.upgrade(now);
+        let (expected_address, _) = find_staking_registry_address(staker, staking_registry.position_index);
+
+        require!(
+            registry.key() == expected_address && !position_indexes.contains(&staking_registry.position_index),
+            StakingErrorCode::InvalidStakingRegistry
+        );
+
+        position_indexes.push(staking_registry.position_index);
+        voting_power = voting_power.saturating_add(staking_registry.voting_power_at(staking_config, opened_at));
+    }
+
+    Ok(voting_power)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum StakingLedgerAction {
+    Freeze { amount: u64 },
+    Thaw { amount: u64, early_exit: bool },
+    Claim { amount: u64, compounded: bool },
+    Slash { amount: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct StakingLedger {
+    pub sequence: u64,
+    pub staker: Pubkey,
+    pub mint: Pubkey,
+    pub position_index: u64,
+    pub action: StakingLedgerAction,
+    pub staked_amount: u64,
+    pub timestamp: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingUserAccount {
+    pub position_count: u64,
+    pub active_position_count: u64,
+    pub ledger_sequence: u64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl StakingUserAccount {
+    /// Emits `action` as the staker's next `StakingLedgerLogEvent`, numbered by `ledger_sequence`, and advances the sequence.
+    ///
+    /// The entry carries the position's staked amount after the action, so a statement replayed from the events in sequence
+    /// order reproduces each position's balance and exposes any missing entry as a gap.
+    pub fn record_ledger(
+        &mut self,
+        staker: Pubkey,
+        staking_registry: &StakingRegistryAccount,
+        action: StakingLedgerAction,
+        timestamp: i64,
+    ) -> Result<()> {
+        emit!(StakingLedgerLogEvent {
+            sequence: self.ledger_sequence,
+            data: StakingLedger {
+                sequence: self.ledger_sequence,
+                staker,
+                mint: staking_registry.mint,
+                position_index: staking_registry.position_index,
+                action,
+                staked_amount: staking_registry.staked_amount,
+                timestamp,
+            },
+        });
+
+        self.ledger_sequence = self.ledger_sequence.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Validates `position_index` against the staker's positions, opening a new position when it is the next unused index.
+    ///
+    /// A new account is stamped with the current layout version and the canonical `bump` of its address.
+    pub fn open_position(&mut self, position_index: u64, bump: u8) -> Result<()> {
+        require!(
+            position_index <= self.position_count,
+            StakingErrorCode::InvalidPositionIndex
+        );
+
+        if self.position_count == 0 {
+            self.version = Self::VERSION;
+            self.bump = bump;
+        }
+        if position_index == self.position_count {
+            self.position_count = self.position_count.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl VersionedAccount for StakingUserAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingUserAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingReferrerAccount {
+    pub referrer: Pubkey,
+    pub referral_count: u64,
+    pub referred_amount: u64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl StakingReferrerAccount {
+    /// Records `amount` staked into a position referred by `referrer`, counting the position once when it is newly attributed.
+    pub fn record_referral(&mut self, referrer: Pubkey, new_referral: bool, amount: u64) {
+        if self.referrer == Pubkey::default() {
+            self.version = Self::VERSION;
+        }
+        self.referrer = referrer;
+        if new_referral {
+            self.referral_count = self.referral_count.saturating_add(1);
+        }
+        self.referred_amount = self.referred_amount.saturating_add(amount);
+    }
+}
+
+impl VersionedAccount for StakingReferrerAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingReferrerAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct StakingPoolAccount {
+    pub total_staked: u64,
+    pub staker_count: u64,
+    pub total_rewards_paid: u64,
+    pub epoch: u64,
+    pub epoch_staked: u64,
+    pub epoch_unstaked: u64,
+    pub epoch_rewards_paid: u64,
+    pub vault_delegated: u64,
+    pub vault_stake_rewards: u64,
+    pub tier_staked: [u64; MAX_STAKING_TIERS],
+    pub version: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+}
+
+impl StakingPoolAccount {
+    /// The current layout version, stamped at initialization and by `migrate_pool`.
+    ///
+    /// Version 2 caches the canonical `bump` in a byte that was padding before, so the layout keeps its size.
+    pub const VERSION: u8 = 2;
+
+    /// Resets the per-epoch aggregates when `epoch` is newer than the one currently tracked.
+    pub fn roll_epoch(&mut self, epoch: u64) {
+        if epoch > self.epoch {
+            self.epoch = epoch;
+            self.epoch_staked = 0;
+            self.epoch_unstaked = 0;
+            self.epoch_rewards_paid = 0;
+        }
+    }
+
+    /// Records one of a staker's positions moving from `previous_amount` to `current_amount`,
+    /// updating the total staked, the per-tier totals, the staker's active positions, the staker count, the current epoch's flows,
+    /// and the epoch's entry in the rolling `staking_activity` window.
+    ///
+    /// A staker is counted once no matter how many of their positions are active. A position counts towards the tier its
+    /// staked amount reaches under `staking_config`, so it moves between tier totals as its amount changes. Callers pass
+    /// the position's [pooled amounts](StakingConfigAccount::pooled_amount), so positions that are not reward stakes leave
+    /// the pool untouched.
+    pub fn record_stake_change(
+        &mut self,
+        staking_user: &mut StakingUserAccount,
+        staking_activity: &mut StakingActivityAccount,
+        staking_config: &StakingConfigAccount,
+        previous_amount: u64,
+        current_amount: u64,
+        epoch: u64,
+    ) {
+        self.roll_epoch(epoch);
+        staking_activity.record(previous_amount, current_amount, epoch);
+
+        if let Some(index) = staking_config.tier_index(previous_amount).filter(|_| previous_amount > 0) {
+            self.tier_staked[index] = self.tier_staked[index].saturating_sub(previous_amount);
+        }
+        if let Some(index) = staking_config.tier_index(current_amount).filter(|_| current_amount > 0) {
+            self.tier_staked[index] = self.tier_staked[index].saturating_add(current_amount);
+        }
+
+        if current_amount >= previous_amount {
+            let staked = current_amount - previous_amount;
+            self.total_staked = self.total_staked.saturating_add(staked);
+            self.epoch_staked = self.epoch_staked.saturating_add(staked);
+        } else {
+            let unstaked = previous_amount - current_amount;
+            self.total_staked = self.total_staked.saturating_sub(unstaked);
+            self.epoch_unstaked = self.epoch_unstaked.saturating_add(unstaked);
+        }
+
+        if previous_amount == 0 && current_amount > 0 {
+            staking_user.active_position_count = staking_user.active_position_count.saturating_add(1);
+            if staking_user.active_position_count == 1 {
+                self.staker_count = self.staker_count.saturating_add(1);
+            }
+        } else if previous_amount > 0 && current_amount == 0 {
+            staking_user.active_position_count = staking_user.active_position_count.saturating_sub(1);
+            if staking_user.active_position_count == 0 {
+                self.staker_count = self.staker_count.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Checks that moving a position from `previous_amount` to `current_amount` keeps the total staked within `max_total_staked`.
+    ///
+    /// A cap of zero leaves the total staked uncapped, and decreases are always allowed.
+    pub fn check_stake_cap(&self, max_total_staked: u64, previous_amount: u64, current_amount: u64) -> Result<()> {
+        if max_total_staked == 0 || current_amount <= previous_amount {
+            return Ok(());
+        }
+
+        let total_staked = self.total_staked.saturating_add(current_amount - previous_amount);
+        require!(
+            total_staked <= max_total_staked,
+            StakingErrorCode::StakingCapReached
+        );
+
+        Ok(())
+    }
+
+    /// Records rewards paid out to a staker.
+    pub fn record_rewards_paid(&mut self, amount: u64, epoch: u64) {
+        self.roll_epoch(epoch);
+
+        self.total_rewards_paid = self.total_rewards_paid.saturating_add(amount);
+        self.epoch_rewards_paid = self.epoch_rewards_paid.saturating_add(amount);
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingEpochSnapshotAccount {
+    pub epoch: u64,
+    pub total_staked: u64,
+    pub staker_count: u64,
+    pub tier_staked: [u64; MAX_STAKING_TIERS],
+    pub reward_rate_bps: u16,
+    pub recorded_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingEpochSnapshotAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingEpochSnapshotAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingReportAccount {
+    pub sequence: u64,
+    pub total_staked: u64,
+    pub staker_count: u64,
+    pub epoch: u64,
+    pub reported_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingReportAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingReportAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingSolDistributionAccount {
+    pub epoch: u64,
+    pub proposal_id: u64,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub claimed_amount: u64,
+    pub distributed_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl StakingSolDistributionAccount {
+    /// Returns the SOL owed to a position holding `staked_amount`: its pro-rata share of the distribution against the
+    /// snapshot's total staked, capped at what is left unclaimed.
+    pub fn share(&self, staked_amount: u64) -> u64 {
+        if self.total_staked == 0 {
+            return 0;
+        }
+
+        let share = (self.amount as u128) * (staked_amount as u128) / (self.total_staked as u128);
+
+        u64::try_from(share)
+            .unwrap_or(u64::MAX)
+            .min(self.amount.saturating_sub(self.claimed_amount))
+    }
+}
+
+impl VersionedAccount for StakingSolDistributionAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingSolDistributionAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingSolClaimAccount {
+    pub epoch: u64,
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingSolClaimAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingSolClaimAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// One epoch's staking activity in the rolling [`StakingActivityAccount`] window.
+#[zero_copy]
+#[derive(InitSpace)]
+pub struct StakingEpochActivity {
+    pub epoch: u64,
+    pub stake_count: u64,
+    pub unstake_count: u64,
+    pub staked_amount: u64,
+    pub unstaked_amount: u64,
+    pub net_flow: i64,
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct StakingActivityAccount {
+    pub epochs: [StakingEpochActivity; EPOCH_ACTIVITY_WINDOW],
+}
+
+impl StakingActivityAccount {
+    /// Records a position moving from `previous_amount` to `current_amount` in `epoch`'s entry.
+    ///
+    /// Entries are indexed by `epoch % EPOCH_ACTIVITY_WINDOW`; an entry still holding an older epoch is reset
+    /// before it is reused, so the window always covers the most recent epochs with activity.
+    pub fn record(&mut self, previous_amount: u64, current_amount: u64, epoch: u64) {
+        let entry = &mut self.epochs[(epoch % EPOCH_ACTIVITY_WINDOW as u64) as usize];
+        if entry.epoch != epoch {
+            *entry = StakingEpochActivity {
+                epoch,
+                stake_count: 0,
+                unstake_count: 0,
+                staked_amount: 0,
+                unstaked_amount: 0,
+                net_flow: 0,
+            };
+        }
+
+        if current_amount > previous_amount {
+            let staked = current_amount - previous_amount;
+            entry.stake_count = entry.stake_count.saturating_add(1);
+            entry.staked_amount = entry.staked_amount.saturating_add(staked);
+            entry.net_flow = entry.net_flow.saturating_add_unsigned(staked);
+        } else if current_amount < previous_amount {
+            let unstaked = previous_amount - current_amount;
+            entry.unstake_count = entry.unstake_count.saturating_add(1);
+            entry.unstaked_amount = entry.unstaked_amount.saturating_add(unstaked);
+            entry.net_flow = entry.net_flow.saturating_sub_unsigned(unstaked);
+        }
+    }
+}
+
+/// A pending escrow unstake in a [`StakingUnstakeQueueAccount`].
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub struct StakingUnstakeQueueEntry {
+    pub sequence: u64,
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub requested_at: i64,
+    pub eta: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingUnstakeQueueAccount {
+    pub mint: Pubkey,
+    pub next_sequence: u64,
+    pub processed_sequence: u64,
+    pub queued_amount: u64,
+    #[max_len(MAX_UNSTAKE_QUEUE_LEN)]
+    pub entries: Vec<StakingUnstakeQueueEntry>,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl StakingUnstakeQueueAccount {
+    /// Returns the size of a queue with room for `entry_count` pending requests.
+    pub const fn len_for(entry_count: usize) -> usize {
+        space_for_len(Self::INIT_SPACE, MAX_UNSTAKE_QUEUE_LEN, StakingUnstakeQueueEntry::INIT_SPACE, entry_count)
+    }
+
+    /// Appends a request to the back of the queue and returns its sequence number.
+    ///
+    /// A request's position in the queue is its sequence minus `processed_sequence`, so clients can read both
+    /// the position and the ETA of a request straight from the queue account.
+    pub fn enqueue(&mut self, staker: Pubkey, position_index: u64, amount: u64, requested_at: i64, eta: i64) -> Result<u64> {
+        require!(
+            self.entries.len() < MAX_UNSTAKE_QUEUE_LEN,
+            StakingErrorCode::UnstakeQueueFull
+        );
+
+        let sequence = self.next_sequence;
+        self.entries.push(StakingUnstakeQueueEntry {
+            sequence,
+            staker,
+            position_index,
+            amount,
+            requested_at,
+            eta,
+        });
+        self.next_sequence = self.next_sequence.saturating_add(1);
+        self.queued_amount = self.queued_amount.saturating_add(amount);
+
+        Ok(sequence)
+    }
+
+    /// Returns the position index of the request at the front of the queue, or zero if the queue is empty.
+    pub fn head_position_index(&self) -> u64 {
+        self.entries.first().map_or(0, |entry| entry.position_index)
+    }
+
+    /// Removes the request at the front of the queue and advances `processed_sequence` past it.
+    pub fn dequeue(&mut self) -> Option<StakingUnstakeQueueEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let entry = self.entries.remove(0);
+        self.processed_sequence = entry.sequence.saturating_add(1);
+        self.queued_amount = self.queued_amount.saturating_sub(entry.amount);
+
+        Some(entry)
+    }
+}
+
+impl VersionedAccount for StakingUnstakeQueueAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingUnstakeQueueAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingPairVaultAccount {
+    pub total_lamports: u64,
+    pub position_count: u64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingPairVaultAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingPairVaultAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingRewardScheduleAccount {
+    pub proposal_id: u64,
+    #[max_len(MAX_REWARD_SCHEDULE_STEPS)]
+    pub steps: Vec<StakingRewardScheduleStep>,
+    pub updated_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl StakingRewardScheduleAccount {
+    /// Returns the reward rate of the last step that has started by `epoch`, or `None` before the schedule's first step.
+    pub fn rate_at(&self, epoch: u64) -> Option<u16> {
+        self.steps
+            .iter()
+            .take_while(|step| step.start_epoch <= epoch)
+            .last()
+            .map(|step| step.reward_rate_bps)
+    }
+}
+
+impl VersionedAccount for StakingRewardScheduleAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingRewardScheduleAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingMintConfigAccount {
+    pub mint: Pubkey,
+    pub custodial: bool,
+    pub allowed: bool,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingMintConfigAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingMintConfigAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingValidatorAccount {
+    pub vote_account: Pubkey,
+    pub allowed: bool,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingValidatorAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingValidatorAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingCustodianAccount {
+    pub custodian: Pubkey,
+    pub allowed: bool,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingCustodianAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingCustodianAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum StakingVaultDelegationStatus {
+    Active,
+    Deactivating,
+    Withdrawn,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingVaultDelegationAccount {
+    pub proposal_id: u64,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub amount: u64,
+    pub status: StakingVaultDelegationStatus,
+    pub delegated_at: i64,
+    pub deactivated_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingVaultDelegationAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingVaultDelegationAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u64, reference_number: String, lock_duration: i64, referrer: Option<Pubkey>)]
+pub struct StakingFreeze<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = StakingRegistryAccount::SPACE,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingReferrerAccount::INIT_SPACE,
+        seeds = [
+            STAKING_REFERRER_SEED,
+            referrer.unwrap_or_default().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_referrer: Option<Account<'info, StakingReferrerAccount>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingThaw<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        mut,
+        address = staking_registry.mint @ StakingErrorCode::MintMismatch
+    )]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = staking_registry.token_account @ StakingErrorCode::TokenAccountMismatch,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the instructions sysvar, read to verify the attestor's ed25519 signature
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, staker.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingBatchFreeze<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingBatchThaw<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the instructions sysvar, read to identify the program calling this instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct StakingClaimRewards<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    #[account(address = staking_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_reward_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingFundRewards<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(address = staking_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = funder,
+        associated_token::token_program = token_program,
+    )]
+    pub funder_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct StakingRequestUnstake<'info> {
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingGetVotingPower<'info> {
+    /// The positions are passed as remaining accounts; the configuration identifies the reward mint they must stake.
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(staker: Pubkey, position_index: u64)]
+pub struct StakingGetPosition<'info> {
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    /// CHECK: Decoded as a `StakingRegistryData`, so positions can be read before their registry is migrated
+    #[account(
+        owner = crate::ID,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakingGetConfig<'info> {
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(staker: Pubkey, position_index: u64)]
+pub struct StakingGetStakeInfo<'info> {
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    /// CHECK: Decoded as a `StakingRegistryData`, so positions can be read before their registry is migrated
+    #[account(
+        owner = crate::ID,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct StakingSnapshotEpoch<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingEpochSnapshotAccount::INIT_SPACE,
+        seeds = [STAKING_SNAPSHOT_SEED, epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub staking_snapshot: Account<'info, StakingEpochSnapshotAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingReport<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IdentifierAccount::INIT_SPACE,
+        seeds = [STAKING_REPORT_IDENTIFIER_SEED],
+        bump
+    )]
+    pub staking_report_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingReportAccount::INIT_SPACE,
+        seeds = [
+            STAKING_REPORT_SEED,
+            staking_report_identifier.id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_report: Account<'info, StakingReportAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = can_operate(&program_config, signer.key) @ ProgramConfigErrorCode::UnauthorizedOperator
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingApplyRewardSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        seeds = [STAKING_REWARD_SCHEDULE_SEED],
+        bump = staking_reward_schedule.bump
+    )]
+    pub staking_reward_schedule: Account<'info, StakingRewardScheduleAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingClaimSolRewards<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        seeds = [STAKING_SNAPSHOT_SEED, staking_sol_distribution.epoch.to_le_bytes().as_ref()],
+        bump = staking_snapshot.bump
+    )]
+    pub staking_snapshot: Account<'info, StakingEpochSnapshotAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_SOL_DISTRIBUTION_SEED, staking_sol_distribution.epoch.to_le_bytes().as_ref()],
+        bump = staking_sol_distribution.bump
+    )]
+    pub staking_sol_distribution: Account<'info, StakingSolDistributionAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingSolClaimAccount::INIT_SPACE,
+        seeds = [
+            STAKING_SOL_CLAIM_SEED,
+            staking_sol_distribution.epoch.to_le_bytes().as_ref(),
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_sol_claim: Account<'info, StakingSolClaimAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingExtendLock<'info> {
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingClosePosition<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = staker,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u64)]
+pub struct StakingMigrateRegistry<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    /// CHECK: Decoded manually with the legacy layout, since a registry awaiting migration cannot be loaded as `StakingRegistryAccount`
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingMigrateConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Reallocated before it is decoded, since a configuration awaiting migration may be too small to load as `StakingConfigAccount`
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [STAKING_CONFIG_SEED],
+        bump
+    )]
+    pub staking_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingMigratePool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingCloseSolClaim<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_SOL_DISTRIBUTION_SEED, staking_sol_claim.epoch.to_le_bytes().as_ref()],
+        bump = staking_sol_distribution.bump
+    )]
+    pub staking_sol_distribution: Account<'info, StakingSolDistributionAccount>,
+
+    #[account(
+        mut,
+        close = staker,
+        seeds = [
+            STAKING_SOL_CLAIM_SEED,
+            staking_sol_claim.epoch.to_le_bytes().as_ref(),
+            staker.key().as_ref(),
+            staking_sol_claim.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_sol_claim.bump
+    )]
+    pub staking_sol_claim: Account<'info, StakingSolClaimAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+pub struct StakingInstructions;
+
+impl StakingInstructions {
+    /// Freezes the staker's token account and records a reference identifier in the staking registry.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that staking is not paused.
+    /// - Validates the reference number's length and characters.
+    /// - Checks that the mint is on the staking allowlist, if the allowlist is enabled.
+    /// - Opens a new stake position if `position_index` is the staker's next unused index.
+    /// - Checks that the staker's token account balance meets the minimum required staking amount.
+    /// - Checks that the position holds no active escrow stake or active stake of another mint or token account, and that the
+    ///   token account is not already frozen, for example by a Token-2022 default account state.
+    /// - Checks that the mint's freeze authority is the staking authority PDA for custodial mints, or the staker otherwise.
+    /// - Freezes the staker's token account using the SPL Token program, signing with the staking authority PDA for custodial mints.
+    /// - Accrues any rewards pending from a previous staking period.
+    /// - Stores the provided reference number, the mint and token account, the staked amount, the unlock time, and the multiplier
+    ///   boosted by the lock in the staking registry, with the staking authority PDA as the custodian of custodial mints' stakes.
+    ///   Only those stakes accrue rewards.
+    /// - If a referrer is provided, attributes the position to it and adds the newly staked amount to its referral statistics.
+    /// - Adds the staked amount to the staking pool statistics and emits a `StakeFrozenEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for the freeze operation, including the staker, token account, staking configuration, and staking registry.
+    /// * `position_index` - The index of the staker's position to freeze into, either an existing one or the next new one.
+    /// * `reference_number` - A string identifier to associate with this staking freeze operation, of up to `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`.
+    /// * `lock_duration` - The number of seconds the stake stays locked, up to the configured maximum.
+    /// * `referrer` - The wallet that referred the staker, if any.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the freeze operation is successful, otherwise returns an error.
+    pub fn freeze(
+        ctx: Context<StakingFreeze>,
+        position_index: u64,
+        reference_number: String,
+        lock_duration: i64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&ctx.accounts.staker.key(), "freeze")?;
+        validate_reference_id(&reference_number)?;
+        ctx.accounts
+            .staking_config
+            .check_mint_allowed(ctx.accounts.staking_mint_config.as_deref())?;
+        ctx.accounts.staking_user.open_position(position_index, ctx.bumps.staking_user)?;
+
+        let user_balance = ctx.accounts.staker_token.amount;
+
+        require_with_context!(
+            ctx.accounts.staking_config.meets_minimum(user_balance),
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staker_token.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            user_balance
+        );
+
+        require!(
+            !ctx.accounts.staking_registry.has_active_stake_in_other_mode(StakingMode::Freeze),
+            StakingErrorCode::StakingModeMismatch
+        );
+        let custodial = ctx.accounts.staking_mint_config.as_ref().is_some_and(|config| config.custodial);
+        let freeze_authority = if custodial {
+            ctx.accounts.staking_authority.to_account_info()
+        } else {
+            ctx.accounts.staker.to_account_info()
+        };
+        let custodian = if custodial { ctx.accounts.staking_authority.key() } else { Pubkey::default() };
+
+        ctx.accounts
+            .staking_registry
+            .check_staked_accounts(&ctx.accounts.token.key(), &ctx.accounts.staker_token.key(), &custodian)?;
+
+        require!(
+            !ctx.accounts.staker_token.is_frozen(),
+            StakingErrorCode::TokenAccountFrozen
+        );
+
+        require!(
+            ctx.accounts.token.freeze_authority == COption::Some(freeze_authority.key()),
+            StakingErrorCode::InvalidFreezeAuthority
+        );
+
+        let staking_authority_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.staker_token.to_account_info(),
+                mint: ctx.accounts.token.to_account_info(),
+                authority: freeze_authority,
+            },
+            if custodial { staking_authority_seeds } else { &[] },
+        ))?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        if let (Some(staking_referrer), Some(bump)) =
+            (ctx.accounts.staking_referrer.as_deref_mut(), ctx.bumps.staking_referrer)
+        {
+            staking_referrer.bump = bump;
+        }
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let referred_amount = user_balance.saturating_sub(staking_registry.staked_amount);
+        staking_registry.attribute_referral(
+            &ctx.accounts.staker.key(),
+            referrer,
+            ctx.accounts.staking_referrer.as_deref_mut(),
+            referred_amount,
+        )?;
+        let pooled_previous = ctx.accounts.staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            custodial,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = ctx.accounts.staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            custodial,
+            user_balance,
+        );
+        ctx.accounts.staking_pool.load()?.check_stake_cap(
+            ctx.accounts.staking_config.max_total_staked,
+            pooled_previous,
+            pooled_current,
+        )?;
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.position_index = position_index;
+        staking_registry.reference_id = reference_number;
+        staking_registry.mode = StakingMode::Freeze;
+        staking_registry.mint = ctx.accounts.token.key();
+        staking_registry.token_account = ctx.accounts.staker_token.key();
+        staking_registry.custodian = custodian;
+        staking_registry.version = REGISTRY_VERSION;
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.staked_amount = user_balance;
+        staking_registry.unlocks_at = staking_config.unlock_time(clock.unix_timestamp, lock_duration)?;
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_write(ctx.accounts.staker.key())?;
+
+        emit!(StakeFrozenEvent {
+            staker: ctx.accounts.staker.key(),
+            mint: staking_registry.mint,
+            position_index,
+            amount: user_balance,
+            reference_id: staking_registry.reference_id.clone(),
+            unlocks_at: staking_registry.unlocks_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        ctx.accounts.staking_user.record_ledger(
+            ctx.accounts.staker.key(),
+            staking_registry,
+            StakingLedgerAction::Freeze { amount: user_balance },
+            clock.unix_timestamp,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_freeze", ctx.accounts.staker.key())?;
+        crate::log_info!(
+            "staking_freeze",
+            staker = ctx.accounts.staker.key(),
+            position_index = position_index,
+            amount = user_balance
+        );
+
+        Ok(())
+    }
+
+    /// Thaws the staker's previously frozen token account and clears the reference identifier in the staking registry.
+    ///
+    /// This function performs the following actions:
+    /// - Checks, while the CPI guard is enabled, that the thaw is not invoked through CPI by a program outside its allowlist.
+    /// - Checks and advances the staker's nonce when `expected_nonce` is given, so a replayed transaction fails.
+    /// - Checks, through account constraints, that the mint and token account are the ones recorded in the staking registry at freeze time.
+    /// - Checks that the staker has no active escrow stake, which must be released with `unstake` instead.
+    /// - If an attestor is configured, checks that the attestation has not expired and that the preceding instruction verifies the
+    ///   attestor's ed25519 signature over the staker, the position's reference ID, and the expiry.
+    /// - Checks that the stake is unlocked, unless `early_exit` is set, in which case the accrued rewards are forfeited as the penalty.
+    /// - If an unstake cooldown is configured, checks that an unstake was requested and its cooldown has elapsed.
+    /// - Skips the attestation, lockup, and cooldown checks once staking has been paused past its grace period.
+    /// - Checks that the mint's freeze authority matches its staking mode.
+    /// - For custodial mints, checks that the staking registry holds an active stake, since the staking authority PDA can thaw any account of the mint.
+    /// - Unfreezes the staker's token account using the SPL Token program, signing with the staking authority PDA for custodial mints.
+    /// - Accrues the rewards earned up to now so they remain claimable after thawing.
+    /// - Clears the reference number, staked amount, and multiplier in the staking registry to indicate the staking freeze has been lifted.
+    /// - Removes the released amount from the staking pool statistics and emits a `StakeThawedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for the thaw operation, including the staker, token account, and staking registry.
+    /// * `early_exit` - Whether to release a still-locked stake by forfeiting its accrued rewards.
+    /// * `attestation_expires_at` - The expiry signed by the attestor, required while an attestor is configured.
+    /// * `expected_nonce` - The staker's current nonce, required to match when given.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the thaw operation is successful, otherwise returns an error.
+    pub fn thaw(
+        ctx: Context<StakingThaw>,
+        early_exit: bool,
+        attestation_expires_at: Option<i64>,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+        ctx.accounts.program_config.check_cpi_caller(&ctx.accounts.instructions_sysvar)?;
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
+        require!(
+            !ctx.accounts.staking_registry.has_active_stake_in_other_mode(StakingMode::Freeze),
+            StakingErrorCode::StakingModeMismatch
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+        if staking_config.attestation_required() && !staking_config.pause_grace_elapsed(clock.unix_timestamp) {
+            let expires_at = attestation_expires_at.ok_or(StakingErrorCode::MissingAttestation)?;
+            require!(
+                clock.unix_timestamp <= expires_at,
+                StakingErrorCode::AttestationExpired
+            );
+
+            verify_attestation(
+                &ctx.accounts.instructions_sysvar,
+                &staking_config.attestor,
+                &attestation_message(
+                    &ctx.accounts.staker.key(),
+                    &ctx.accounts.staking_registry.reference_id,
+                    expires_at,
+                ),
+            )?;
+        }
+
+        let is_early_exit = ctx.accounts.staking_registry.check_release(staking_config, clock.unix_timestamp, early_exit)?;
+
+        let custodial = ctx.accounts.staking_mint_config.as_ref().is_some_and(|config| config.custodial);
+        let freeze_authority = if custodial {
+            ctx.accounts.staking_authority.to_account_info()
+        } else {
+            ctx.accounts.staker.to_account_info()
+        };
+
+        require!(
+            ctx.accounts.token.freeze_authority == COption::Some(freeze_authority.key()),
+            StakingErrorCode::InvalidFreezeAuthority
+        );
+
+        if custodial {
+            require!(
+                ctx.accounts.staking_registry.staked_amount > 0,
+                StakingErrorCode::NoActiveStake
+            );
+        }
+
+        let staking_authority_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.staker_token.to_account_info(),
+                mint: ctx.accounts.token.to_account_info(),
+                authority: freeze_authority,
+            },
+            if custodial { staking_authority_seeds } else { &[] },
+        ))?;
+
+        let reward_rate_bps = ctx.accounts.staking_config.reward_rate_bps;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_amount = staking_registry.pooled_amount(
+            &ctx.accounts.staking_config,
+            staking_registry.staked_amount,
+        );
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_amount,
+            0,
+            clock.epoch,
+        );
+
+        let thawed_amount = staking_registry.staked_amount;
+        emit!(StakeThawedEvent {
+            staker: ctx.accounts.staker.key(),
+            mint: staking_registry.mint,
+            position_index: staking_registry.position_index,
+            amount: thawed_amount,
+            reference_id: staking_registry.reference_id.clone(),
+            early_exit: is_early_exit,
+            timestamp: clock.unix_timestamp,
+        });
+
+        staking_registry.accrue_rewards(reward_rate_bps, clock.unix_timestamp);
+        if is_early_exit {
+            staking_registry.accrued_rewards = 0;
+        }
+        staking_registry.reference_id = String::from("");
+        staking_registry.staked_amount = 0;
+        staking_registry.unlocks_at = 0;
+        staking_registry.refresh_multiplier(&ctx.accounts.staking_config, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        ctx.accounts.staking_user.record_ledger(
+            ctx.accounts.staker.key(),
+            staking_registry,
+            StakingLedgerAction::Thaw { amount: thawed_amount, early_exit: is_early_exit },
+            clock.unix_timestamp,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_thaw", ctx.accounts.staker.key())?;
+        crate::log_info!(
+            "staking_thaw",
+            staker = ctx.accounts.staker.key(),
+            position_index = staking_registry.position_index,
+            amount = thawed_amount,
+            early_exit = is_early_exit
+        );
+
+        Ok(())
+    }
+
+    /// Freezes several of the staker's token accounts in one transaction, opening or refreshing one stake position for each.
+    ///
+    /// This function performs the following actions for each entry in `positions`, reading its accounts from the next
+    /// `BATCH_ACCOUNTS_PER_POSITION` remaining accounts (the mint, the staker's token account, the mint configuration or the
+    /// program ID when the mint has none, and the staking registry):
+    /// - Validates the reference number and the position's accounts, and checks that the mint is on the staking allowlist, if enabled.
+    /// - Opens a new stake position if `position_index` is the staker's next unused index, creating its staking registry.
+    /// - Applies the same balance, staking mode, and freeze authority checks as `freeze`, then freezes the token account.
+    /// - Records the stake in the staking registry and pool statistics, and emits a `StakeFrozenEvent`.
+    ///
+    /// The batch is all-or-nothing: if any position fails its checks, no token account is frozen. It is rejected outright
+    /// while staking is paused.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the staking configuration, user and pool accounts, and the positions as remaining accounts.
+    /// * `positions` - The position index, reference number, and lock duration of each position to freeze.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if every position is frozen, otherwise returns an error.
+    pub fn batch_freeze<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakingBatchFreeze<'info>>,
+        positions: Vec<StakingBatchFreezeEntry>,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&ctx.accounts.staker.key(), "batch_freeze")?;
+        require!(
+            !positions.is_empty() && positions.len() <= MAX_BATCH_POSITIONS,
+            StakingErrorCode::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == positions.len() * BATCH_ACCOUNTS_PER_POSITION,
+            StakingErrorCode::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let staker = ctx.accounts.staker.key();
+        let token_program = ctx.accounts.token_program.key();
+        let staking_authority_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+
+        for (entry, accounts) in positions
+            .into_iter()
+            .zip(ctx.remaining_accounts.chunks(BATCH_ACCOUNTS_PER_POSITION))
+        {
+            validate_reference_id(&entry.reference_number)?;
+            let position = StakingBatchPosition::load(accounts, &staker, &token_program)?;
+            ctx.accounts
+                .staking_config
+                .check_mint_allowed(position.staking_mint_config.as_deref())?;
+            ctx.accounts.staking_user.open_position(entry.position_index, ctx.bumps.staking_user)?;
+
+            let position_index_bytes = entry.position_index.to_le_bytes();
+            let (registry_address, registry_bump) = find_staking_registry_address(&staker, entry.position_index);
+            require!(
+                position.staking_registry.key() == registry_address,
+                StakingErrorCode::InvalidStakingRegistry
+            );
+
+            let mut staking_registry = if position.staking_registry.owner == &crate::ID {
+                Account::<StakingRegistryAccount>::try_from(position.staking_registry)?
+            } else {
+                let space = StakingRegistryAccount::SPACE;
+                create_account(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        CreateAccount {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: position.staking_registry.clone(),
+                        },
+                        &[&[STAKING_REGISTRY_SEED, staker.as_ref(), position_index_bytes.as_ref(), &[registry_bump]]],
+                    ),
+                    Rent::get()?.minimum_balance(space),
+                    space as u64,
+                    &crate::ID,
+                )?;
+                Account::<StakingRegistryAccount>::try_from_unchecked(position.staking_registry)?
+            };
+
+            let user_balance = position.staker_token.amount;
+            require_with_context!(
+                ctx.accounts.staking_config.meets_minimum(user_balance),
+                StakingErrorCode::InsufficientStakingBalance,
+                position.staker_token.key(),
+                ctx.accounts.staking_config.min_staking_amount,
+                user_balance
+            );
+            require!(
+                !staking_registry.has_active_stake_in_other_mode(StakingMode::Freeze),
+                StakingErrorCode::StakingModeMismatch
+            );
+            let custodial = position.staking_mint_config.as_ref().is_some_and(|config| config.custodial);
+            let freeze_authority = if custodial {
+                ctx.accounts.staking_authority.to_account_info()
+            } else {
+                ctx.accounts.staker.to_account_info()
+            };
+            let custodian = if custodial { ctx.accounts.staking_authority.key() } else { Pubkey::default() };
+
+            staking_registry.check_staked_accounts(&position.token.key(), &position.staker_token.key(), &custodian)?;
+            require!(
+                !position.staker_token.is_frozen(),
+                StakingErrorCode::TokenAccountFrozen
+            );
+
+            require!(
+                position.token.freeze_authority == COption::Some(freeze_authority.key()),
+                StakingErrorCode::InvalidFreezeAuthority
+            );
+
+            freeze_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                FreezeAccount {
+                    account: position.staker_token.to_account_info(),
+                    mint: position.token.to_account_info(),
+                    authority: freeze_authority,
+                },
+                if custodial { staking_authority_seeds } else { &[] },
+            ))?;
+
+            let staking_config = &ctx.accounts.staking_config;
+            let pooled_previous = staking_config.pooled_amount(
+                &position.token.key(),
+                custodial,
+                staking_registry.staked_amount,
+            );
+            let pooled_current = staking_config.pooled_amount(&position.token.key(), custodial, user_balance);
+            ctx.accounts.staking_pool.load()?.check_stake_cap(
+                staking_config.max_total_staked,
+                pooled_previous,
+                pooled_current,
+            )?;
+            ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+                &mut ctx.accounts.staking_user,
+                &mut *ctx.accounts.staking_activity.load_mut()?,
+                staking_config,
+                pooled_previous,
+                pooled_current,
+                clock.epoch,
+            );
+
+            staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+            staking_registry.clear_unstake_request();
+            staking_registry.position_index = entry.position_index;
+            staking_registry.reference_id = entry.reference_number;
+            staking_registry.mode = StakingMode::Freeze;
+            staking_registry.mint = position.token.key();
+            staking_registry.token_account = position.staker_token.key();
+            staking_registry.custodian = custodian;
+            staking_registry.version = REGISTRY_VERSION;
+            staking_registry.bump = registry_bump;
+            staking_registry.staked_amount = user_balance;
+            staking_registry.unlocks_at = staking_config.unlock_time(clock.unix_timestamp, entry.lock_duration)?;
+            staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+            staking_registry.record_write(ctx.accounts.staker.key())?;
+
+            emit!(StakeFrozenEvent {
+                staker,
+                mint: staking_registry.mint,
+                position_index: entry.position_index,
+                amount: user_balance,
+                reference_id: staking_registry.reference_id.clone(),
+                unlocks_at: staking_registry.unlocks_at,
+                timestamp: clock.unix_timestamp,
+            });
+
+            ctx.accounts.staking_user.record_ledger(
+                staker,
+                &staking_registry,
+                StakingLedgerAction::Freeze { amount: user_balance },
+                clock.unix_timestamp,
+            )?;
+
+            staking_registry.exit(&crate::ID)?;
+        }
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_batch_freeze", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Thaws several of the staker's frozen token accounts in one transaction, releasing one stake position for each.
+    ///
+    /// This function performs the following actions for each position, reading its accounts from the next
+    /// `BATCH_ACCOUNTS_PER_POSITION` remaining accounts (the mint, the staker's token account, the mint configuration or the
+    /// program ID when the mint has none, and the staking registry), once it has checked, while the CPI guard is enabled, that
+    /// the batch is not invoked through CPI by a program outside its allowlist:
+    /// - Validates the position's accounts and checks that the registry is one of the staker's freeze stakes.
+    /// - Applies the same lockup, cooldown, and freeze authority checks as `thaw`, then thaws the token account.
+    /// - Accrues the rewards earned so far, forfeiting them on an early exit, clears the position in the staking registry,
+    ///   removes it from the pool statistics, and emits a `StakeThawedEvent`.
+    ///
+    /// Batch thaws cannot carry attestations, so they are rejected while an attestor is configured, unless staking has been
+    /// paused past its grace period; thaw those positions one at a time instead. The batch is all-or-nothing: if any position fails its checks, no token account is thawed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the staking configuration, user and pool accounts, and the positions as remaining accounts.
+    /// * `early_exit` - Whether to release still-locked stakes by forfeiting their accrued rewards.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if every position is thawed, otherwise returns an error.
+    pub fn batch_thaw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakingBatchThaw<'info>>,
+        early_exit: bool,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+        ctx.accounts.program_config.check_cpi_caller(&ctx.accounts.instructions_sysvar)?;
+
+        let position_count = ctx.remaining_accounts.len() / BATCH_ACCOUNTS_PER_POSITION;
+        require!(
+            position_count > 0 && position_count <= MAX_BATCH_POSITIONS,
+            StakingErrorCode::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(BATCH_ACCOUNTS_PER_POSITION),
+            StakingErrorCode::InvalidBatchAccounts
+        );
+        let clock = Clock::get()?;
+        require!(
+            !ctx.accounts.staking_config.attestation_required()
+                || ctx.accounts.staking_config.pause_grace_elapsed(clock.unix_timestamp),
+            StakingErrorCode::MissingAttestation
+        );
+
+        let staker = ctx.accounts.staker.key();
+        let token_program = ctx.accounts.token_program.key();
+        let staking_authority_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+
+        for accounts in ctx.remaining_accounts.chunks(BATCH_ACCOUNTS_PER_POSITION) {
+            let position = StakingBatchPosition::load(accounts, &staker, &token_program)?;
+
+            let mut staking_registry = Account::<StakingRegistryAccount>::try_from(position.staking_registry)?;
+            let (registry_address, _) = find_staking_registry_address(&staker, staking_registry.position_index);
+            require!(
+                position.staking_registry.key() == registry_address,
+                StakingErrorCode::InvalidStakingRegistry
+            );
+            require!(
+                !staking_registry.has_active_stake_in_other_mode(StakingMode::Freeze),
+                StakingErrorCode::StakingModeMismatch
+            );
+            require!(
+                staking_registry.mint == position.token.key(),
+                StakingErrorCode::MintMismatch
+            );
+            require!(
+                staking_registry.token_account == position.staker_token.key(),
+                StakingErrorCode::TokenAccountMismatch
+            );
+
+            let is_early_exit = staking_registry.check_release(&ctx.accounts.staking_config, clock.unix_timestamp, early_exit)?;
+
+            let custodial = position.staking_mint_config.as_ref().is_some_and(|config| config.custodial);
+            let freeze_authority = if custodial {
+                ctx.accounts.staking_authority.to_account_info()
+            } else {
+                ctx.accounts.staker.to_account_info()
+            };
+
+            require!(
+                position.token.freeze_authority == COption::Some(freeze_authority.key()),
+                StakingErrorCode::InvalidFreezeAuthority
+            );
+
+            if custodial {
+                require!(
+                    staking_registry.staked_amount > 0,
+                    StakingErrorCode::NoActiveStake
+                );
+            }
+
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: position.staker_token.to_account_info(),
+                    mint: position.token.to_account_info(),
+                    authority: freeze_authority,
+                },
+                if custodial { staking_authority_seeds } else { &[] },
+            ))?;
+
+            let pooled_amount = staking_registry.pooled_amount(
+                &ctx.accounts.staking_config,
+                staking_registry.staked_amount,
+            );
+            ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+                &mut ctx.accounts.staking_user,
+                &mut *ctx.accounts.staking_activity.load_mut()?,
+                &ctx.accounts.staking_config,
+                pooled_amount,
+                0,
+                clock.epoch,
+            );
+
+            let thawed_amount = staking_registry.staked_amount;
+            emit!(StakeThawedEvent {
+                staker,
+                mint: staking_registry.mint,
+                position_index: staking_registry.position_index,
+                amount: thawed_amount,
+                reference_id: staking_registry.reference_id.clone(),
+                early_exit: is_early_exit,
+                timestamp: clock.unix_timestamp,
+            });
+
+            staking_registry.accrue_rewards(ctx.accounts.staking_config.reward_rate_bps, clock.unix_timestamp);
+            if is_early_exit {
+                staking_registry.accrued_rewards = 0;
+            }
+            staking_registry.reference_id = String::from("");
+            staking_registry.staked_amount = 0;
+            staking_registry.unlocks_at = 0;
+            staking_registry.refresh_multiplier(&ctx.accounts.staking_config, clock.unix_timestamp);
+            staking_registry.clear_unstake_request();
+            staking_registry.record_update(ctx.accounts.staker.key())?;
+
+            ctx.accounts.staking_user.record_ledger(
+                staker,
+                &staking_registry,
+                StakingLedgerAction::Thaw { amount: thawed_amount, early_exit: is_early_exit },
+                clock.unix_timestamp,
+            )?;
+
+            staking_registry.exit(&crate::ID)?;
+        }
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_batch_thaw", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Claims the staker's accrued rewards from the reward vault, or compounds them into the stake.
+    ///
+    /// This function performs the following actions:
+    /// - Accrues the rewards earned by the current stake up to now.
+    /// - Checks that there are rewards to claim, that the funded reward budget covers them, and that the reward vault can pay them.
+    /// - Without `compound`, transfers the rewards from the reward vault to the staker's reward token account, signed by the staking authority PDA.
+    /// - With `compound`, adds the rewards to the escrow stake and recalculates its multiplier. Since the stake vault of the
+    ///   reward mint is the reward vault itself, no tokens move.
+    /// - Resets the accrued rewards in the staking registry, deducts them from the reward budget, adds the payout to the
+    ///   staking pool statistics, and emits a `RewardsClaimedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for the claim, including the staker, staking registry, and reward vault.
+    /// * `compound` - Whether to add the rewards to the stake instead of transferring them out.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if:
+    /// - No rewards have accrued.
+    /// - The remaining reward budget is lower than the accrued rewards.
+    /// - The reward vault balance is lower than the accrued rewards.
+    /// - `compound` is set but the position is not an active escrow stake of the reward mint.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the rewards are claimed successfully, otherwise returns an error.
+    pub fn claim_rewards(ctx: Context<StakingClaimRewards>, compound: bool) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+        let rewards = staking_registry.accrued_rewards;
+        require!(rewards > 0, StakingErrorCode::NoRewardsToClaim);
+        require!(
+            staking_config.reward_budget >= rewards,
+            StakingErrorCode::RewardBudgetExhausted
+        );
+        require_with_context!(
+            ctx.accounts.reward_vault.amount >= rewards,
+            StakingErrorCode::InsufficientRewardBalance,
+            ctx.accounts.reward_vault.key(),
+            rewards,
+            ctx.accounts.reward_vault.amount
+        );
+
+        if compound {
+            require!(
+                staking_registry.mode == StakingMode::Escrow
+                    && staking_registry.staked_amount > 0
+                    && staking_registry.mint == staking_config.reward_mint,
+                StakingErrorCode::CompoundNotSupported
+            );
+
+            let staked_amount = staking_registry.staked_amount.saturating_add(rewards);
+            let pooled_previous = staking_registry.pooled_amount(
+                &ctx.accounts.staking_config,
+                staking_registry.staked_amount,
+            );
+            let pooled_current = staking_registry.pooled_amount(&ctx.accounts.staking_config, staked_amount);
+            ctx.accounts.staking_pool.load()?.check_stake_cap(
+                ctx.accounts.staking_config.max_total_staked,
+                pooled_previous,
+                pooled_current,
+            )?;
+            ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+                &mut ctx.accounts.staking_user,
+                &mut *ctx.accounts.staking_activity.load_mut()?,
+                &ctx.accounts.staking_config,
+                pooled_previous,
+                pooled_current,
+                clock.epoch,
+            );
+
+            staking_registry.staked_amount = staked_amount;
+            staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+            staking_registry.accrued_rewards = 0;
+            staking_registry.record_update(ctx.accounts.staker.key())?;
+            ctx.accounts.staking_pool.load_mut()?.record_rewards_paid(rewards, clock.epoch);
+
+            emit!(RewardsClaimedEvent {
+                staker: ctx.accounts.staker.key(),
+                reward_mint: staking_config.reward_mint,
+                position_index: staking_registry.position_index,
+                amount: rewards,
+                reference_id: staking_registry.reference_id.clone(),
+                compounded: true,
+                timestamp: clock.unix_timestamp,
+            });
+
+            ctx.accounts.staking_user.record_ledger(
+                ctx.accounts.staker.key(),
+                staking_registry,
+                StakingLedgerAction::Claim { amount: rewards, compounded: true },
+                clock.unix_timestamp,
+            )?;
+
+            ctx.accounts.staking_config.reward_budget = ctx.accounts.staking_config.reward_budget
+                .checked_sub(rewards)
+                .ok_or(MathErrorCode::ArithmeticError)?;
+            ctx.accounts.staking_config.record_update(ctx.accounts.staker.key())?;
+
+            StateMutationEvent::emit(EventModule::Staking, "staking_claim_rewards", ctx.accounts.staker.key())?;
+
+
+            return Ok(());
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.staker_reward_token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rewards,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+
+        ctx.accounts.staking_registry.accrued_rewards = 0;
+        ctx.accounts.staking_registry.refresh_multiplier(&ctx.accounts.staking_config, clock.unix_timestamp);
+        ctx.accounts.staking_registry.record_update(ctx.accounts.staker.key())?;
+        ctx.accounts.staking_config.reward_budget = ctx.accounts.staking_config.reward_budget
+            .checked_sub(rewards)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        ctx.accounts.staking_config.record_update(ctx.accounts.staker.key())?;
+        ctx.accounts.staking_pool.load_mut()?.record_rewards_paid(rewards, clock.epoch);
+
+        emit!(RewardsClaimedEvent {
+            staker: ctx.accounts.staker.key(),
+            reward_mint: ctx.accounts.reward_mint.key(),
+            position_index: ctx.accounts.staking_registry.position_index,
+            amount: rewards,
+            reference_id: ctx.accounts.staking_registry.reference_id.clone(),
+            compounded: false,
+            timestamp: clock.unix_timestamp,
+        });
+
+        ctx.accounts.staking_user.record_ledger(
+            ctx.accounts.staker.key(),
+            &ctx.accounts.staking_registry,
+            StakingLedgerAction::Claim { amount: rewards, compounded: false },
+            clock.unix_timestamp,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_claim_rewards", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Funds the reward vault from the funder's reward token account and adds the tokens to the reward budget.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the amount is non-zero.
+    /// - Transfers the reward tokens from the funder's token account to the reward vault owned by the staking authority PDA.
+    /// - Adds the amount the vault actually received to the reward budget in the staking configuration, so claims can only
+    ///   pay out rewards that were funded on-chain.
+    /// - Emits a `RewardsFundedEvent` with the new budget.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the funder, their reward token account, the staking configuration, and the reward vault.
+    /// * `amount` - The amount of reward tokens to fund.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the reward vault is funded successfully, otherwise returns an error.
+    pub fn fund_rewards(ctx: Context<StakingFundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+
+        let vault_balance_before = ctx.accounts.reward_vault.amount;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.funder_token.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+
+        ctx.accounts.reward_vault.reload()?;
+        let received_amount = ctx.accounts.reward_vault.amount.saturating_sub(vault_balance_before);
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.reward_budget = staking_config.reward_budget.saturating_add(received_amount);
+        staking_config.record_update(ctx.accounts.funder.key())?;
+
+        emit!(RewardsFundedEvent {
+            funder: ctx.accounts.funder.key(),
+            reward_mint: staking_config.reward_mint,
+            amount: received_amount,
+            reward_budget: staking_config.reward_budget,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_fund_rewards", ctx.accounts.funder.key())?;
+
+        Ok(())
+    }
+
+    /// Requests to unstake a stake position, starting the configured unstake cooldown.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the position holds an active stake with no pending unstake request.
+    /// - Accrues the rewards earned so far; the position stops earning rewards while it cools down.
+    /// - Records the request time and the end of the cooldown in the staking registry.
+    /// - Emits an `UnstakeRequestedEvent`.
+    ///
+    /// Once the cooldown has elapsed, the stake can be released with `thaw` or `unstake`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, staking configuration, and the registry of the stake position.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the unstake is requested successfully, otherwise returns an error.
+    pub fn request_unstake(ctx: Context<StakingRequestUnstake>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+        let staking_registry = &mut ctx.accounts.staking_registry;
+
+        require!(staking_registry.staked_amount > 0, StakingErrorCode::NoActiveStake);
+        require!(
+            staking_registry.unstake_requested_at == 0,
+            StakingErrorCode::UnstakeAlreadyRequested
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.unstake_requested_at = clock.unix_timestamp;
+        staking_registry.cooldown_ends_at = clock.unix_timestamp.saturating_add(staking_config.unstake_cooldown);
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        emit!(UnstakeRequestedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            amount: staking_registry.staked_amount,
+            cooldown_ends_at: staking_registry.cooldown_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_request_unstake", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Returns a staker's voting power, computed from the stake positions passed as remaining accounts.
+    ///
+    /// Each reward-earning position in the reward mint contributes its staked amount scaled by its tier and lock multiplier,
+    /// as it stands now; other positions count for nothing. The result is exposed as the instruction's return data, so
+    /// clients can read it by simulating the transaction.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context whose remaining accounts are the staker's staking registries.
+    /// * `staker` - The public key of the staker whose voting power is computed.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the staker's total voting power, or an error if a remaining account is not one of the staker's registries.
+    pub fn get_voting_power(ctx: Context<StakingGetVotingPower>, staker: Pubkey) -> Result<u64> {
+        staked_voting_power(&staker, &ctx.accounts.staking_config, ctx.remaining_accounts, i64::MAX)
+    }
+
+    /// Returns a live view of a stake position: its staked amount, unclaimed rewards, effective APR, and unlock time.
+    ///
+    /// Unclaimed rewards include those accrued since the last checkpoint, computed with the same accrual math as claims
+    /// without writing to the registry. The result is exposed as the instruction's return data, so wallets and other
+    /// programs can read it by simulating the transaction or through CPI.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staking configuration and the position's staking registry.
+    /// * `staker` - The public key of the staker owning the position.
+    /// * `position_index` - The index of the stake position.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the position's [`StakingPositionView`], or an error if the registry does not exist.
+    pub fn get_position(ctx: Context<StakingGetPosition>, _staker: Pubkey, _position_index: u64) -> Result<StakingPositionView> {
+        let reward_rate_bps = ctx.accounts.staking_config.reward_rate_bps;
+        let now = Clock::get()?.unix_timestamp;
+
+        let mut staking_registry = StakingRegistryData::decode(&ctx.accounts.staking_registry.try_borrow_data()?)?.upgrade(now);
+        staking_registry.accrue_rewards(reward_rate_bps, now);
+        staking_registry.refresh_multiplier(&ctx.accounts.staking_config, now);
+
+        Ok(StakingPositionView {
+            staked_amount: staking_registry.staked_amount,
+            accrued_rewards: staking_registry.accrued_rewards,
+            apr_bps: staking_registry.effective_apr_bps(reward_rate_bps),
+            unlocks_at: staking_registry.unlocks_at,
+        })
+    }
+
+    /// Returns a compact view of a stake position for other programs: its staked amount, unlock time, live multiplier,
+    /// and whether it has ever been slashed.
+    ///
+    /// The multiplier is recalculated as of now without writing to the registry, so it includes loyalty boosts reached since
+    /// the position was last updated. The result is exposed as the instruction's return data, so programs such as lending
+    /// markets can read it through CPI with `get_return_data`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staking configuration and the position's staking registry.
+    /// * `staker` - The public key of the staker owning the position.
+    /// * `position_index` - The index of the stake position.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the position's [`StakingStakeInfo`], or an error if the registry does not exist.
+    pub fn get_stake_info(ctx: Context<StakingGetStakeInfo>, _staker: Pubkey, _position_index: u64) -> Result<StakingStakeInfo> {
+        let now = Clock::get()?.unix_timestamp;
+        let mut staking_registry = StakingRegistryData::decode(&ctx.accounts.staking_registry.try_borrow_data()?)?.upgrade(now);
+        staking_registry.refresh_multiplier(&ctx.accounts.staking_config, now);
+
+        Ok(StakingStakeInfo {
+            staked_amount: staking_registry.staked_amount,
+            unlocks_at: staking_registry.unlocks_at,
+            multiplier_bps: staking_registry.multiplier_bps,
+            slashed: staking_registry.slashed_amount > 0,
+        })
+    }
+
+    /// Returns the staking configuration's scalar values: the minimum stake, reward mint and rate, lock and cooldown
+    /// durations, penalty and boost rates, pause state, staking cap, and remaining reward budget.
+    ///
+    /// The tier, loyalty and fee discount tables are left out; read them from the configuration account. The result is
+    /// exposed as the instruction's return data, so clients can read it by simulating the transaction and other programs
+    /// through CPI.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staking configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the configuration's [`StakingConfigView`], or an error if staking has not been initialized.
+    pub fn get_config(ctx: Context<StakingGetConfig>) -> Result<StakingConfigView> {
+        let staking_config = &ctx.accounts.staking_config;
+
+        Ok(StakingConfigView {
+            min_staking_amount: staking_config.min_staking_amount,
+            reward_mint: staking_config.reward_mint,
+            reward_rate_bps: staking_config.reward_rate_bps,
+            max_lock_duration: staking_config.max_lock_duration,
+            early_exit_penalty_bps: staking_config.early_exit_penalty_bps,
+            max_lock_boost_bps: staking_config.max_lock_boost_bps,
+            unstake_cooldown: staking_config.unstake_cooldown,
+            staking_paused: staking_config.staking_paused,
+            max_total_staked: staking_config.max_total_staked,
+            reward_budget: staking_config.reward_budget,
+        })
+    }
+
+    /// Records the staking pool's totals for the current epoch into an epoch-indexed snapshot account.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that `epoch` is the current epoch, so each snapshot is taken during the epoch it describes.
+    /// - Returns without changes if the epoch's snapshot was already recorded, so the first snapshot of an epoch stands.
+    /// - Copies the total staked, the staker count, the per-tier totals, and the reward rate into the snapshot account.
+    /// - Emits a `StakingSnapshotRecordedEvent`.
+    ///
+    /// Anyone can crank this instruction, and keepers can call it on a schedule: it is due once per epoch, as soon as the
+    /// epoch starts. Reward distribution for the epoch is then computed against the snapshot instead of the live pool
+    /// totals, which keep changing as stakers come and go.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer, the staking configuration and pool, and the snapshot account to create.
+    /// * `epoch` - The current epoch, used to derive the snapshot account.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the snapshot is recorded, otherwise returns an error.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `epoch` is not the current epoch.
+    pub fn snapshot_epoch(ctx: Context<StakingSnapshotEpoch>, epoch: u64) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        require!(epoch == clock.epoch, StakingErrorCode::InvalidSnapshotEpoch);
+
+        if ctx.accounts.staking_snapshot.version != 0 {
+            crate::log_info!("staking_snapshot_epoch_skipped", epoch = epoch);
+            return Ok(());
+        }
+
+        let staking_pool = ctx.accounts.staking_pool.load()?;
+        ctx.accounts.staking_snapshot.set_inner(StakingEpochSnapshotAccount {
+            epoch,
+            total_staked: staking_pool.total_staked,
+            staker_count: staking_pool.staker_count,
+            tier_staked: staking_pool.tier_staked,
+            reward_rate_bps: ctx.accounts.staking_config.reward_rate_bps,
+            recorded_at: clock.unix_timestamp,
+            version: StakingEpochSnapshotAccount::VERSION,
+            bump: ctx.bumps.staking_snapshot,
+        });
+
+        emit!(StakingSnapshotRecordedEvent {
+            epoch,
+            total_staked: staking_pool.total_staked,
+            staker_count: staking_pool.staker_count,
+            tier_staked: staking_pool.tier_staked.to_vec(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_snapshot_epoch", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Packages the staking pool's totals into the next sequenced outbound report for the hub chain's relayer.
+    ///
+    /// This function performs the following actions:
+    /// - Writes the total staked, the staker count, and the current epoch into a report account numbered by the report identifier.
+    /// - Advances the report identifier, so reports form a gap-free sequence the relayer can replay in order.
+    /// - Emits a `StakingReportedEvent` carrying the same values.
+    ///
+    /// Anyone can crank this instruction; the hub reflects spoke stake levels in its governance from the latest report it relayed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer, the staking pool, the report identifier, and the report account to create.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the report is recorded, otherwise returns an error.
+    pub fn report(ctx: Context<StakingReport>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_pool = ctx.accounts.staking_pool.load()?;
+        if ctx.accounts.staking_report_identifier.id == 0 {
+            ctx.accounts.staking_report_identifier.initialize(ctx.bumps.staking_report_identifier);
+        }
+        let sequence = ctx.accounts.staking_report_identifier.next_id()?;
+
+        ctx.accounts.staking_report.set_inner(StakingReportAccount {
+            sequence,
+            total_staked: staking_pool.total_staked,
+            staker_count: staking_pool.staker_count,
+            epoch: clock.epoch,
+            reported_at: clock.unix_timestamp,
+            version: StakingReportAccount::VERSION,
+            bump: ctx.bumps.staking_report,
+        });
+
+        emit!(StakingReportedEvent {
+            sequence,
+            total_staked: staking_pool.total_staked,
+            staker_count: staking_pool.staker_count,
+            epoch: clock.epoch,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_report", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Moves the staking configuration's base reward rate to the reward schedule step in force for the current epoch.
+    ///
+    /// This function performs the following actions:
+    /// - Looks up the last step of the governed reward schedule that has started by the current epoch.
+    /// - Returns without changes if the schedule's first step has not started yet or its rate is already in force.
+    /// - Writes its rate to the staking configuration's `reward_rate_bps`, which the accrual math reads.
+    /// - Emits a `StakingRewardRateAppliedEvent` with the previous and new rates.
+    ///
+    /// Anyone can crank this instruction, and keepers can call it on a schedule: it is due once an epoch starts a new step.
+    /// Positions accrue lazily, so time a position has not yet accrued is credited at the rate in force when it is next
+    /// updated.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staking configuration and the reward schedule.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the scheduled rate is applied, otherwise returns an error.
+    pub fn apply_reward_schedule(ctx: Context<StakingApplyRewardSchedule>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let Some(reward_rate_bps) = ctx.accounts.staking_reward_schedule.rate_at(clock.epoch) else {
+            crate::log_info!("staking_apply_reward_schedule_skipped", epoch = clock.epoch);
+            return Ok(());
+        };
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        let previous_rate_bps = staking_config.reward_rate_bps;
+        if previous_rate_bps == reward_rate_bps {
+            crate::log_info!("staking_apply_reward_schedule_skipped", epoch = clock.epoch);
+            return Ok(());
+        }
+
+        staking_config.reward_rate_bps = reward_rate_bps;
+        staking_config.record_update(Pubkey::default())?;
+
+        emit!(StakingRewardRateAppliedEvent {
+            epoch: clock.epoch,
+            previous_rate_bps,
+            reward_rate_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_apply_reward_schedule", Pubkey::default())?;
+
+        Ok(())
+    }
+
+    /// Claims a stake position's pro-rata share of an epoch's SOL reward distribution.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the position holds an active stake that started before the epoch snapshot was recorded and has not
+    ///   changed since, so stakes opened or topped up after the snapshot cannot claim a larger share of it.
+    /// - Computes the position's share as the distribution amount times its staked amount over the snapshot's total staked,
+    ///   capped at the SOL left unclaimed. Only reward-earning stakes of the reward mint are in the snapshot's total, so
+    ///   other positions have no share.
+    /// - Moves the share from the distribution account to the staker and adds it to the claimed amount.
+    /// - Records the claim in a claim account seeded by epoch, staker, and position, so each position claims at most once per epoch.
+    /// - Emits a `SolRewardsClaimedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the staking configuration, the position's staking registry, the epoch
+    ///   snapshot, the distribution account, and the claim account to create.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the share is claimed successfully, otherwise returns an error.
+    pub fn claim_sol_rewards(ctx: Context<StakingClaimSolRewards>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        let snapshot_stake =
+            staking_registry.snapshot_stake(&ctx.accounts.staking_config, ctx.accounts.staking_snapshot.recorded_at)?;
+
+        let staking_sol_distribution = &mut ctx.accounts.staking_sol_distribution;
+        let amount = staking_sol_distribution.share(snapshot_stake);
+        require!(amount > 0, StakingErrorCode::NoRewardsToClaim);
+
+        staking_sol_distribution.sub_lamports(amount)?;
+        ctx.accounts.staker.add_lamports(amount)?;
+        staking_sol_distribution.claimed_amount = staking_sol_distribution.claimed_amount.saturating_add(amount);
+
+        let clock = Clock::get()?;
+        ctx.accounts.staking_sol_claim.set_inner(StakingSolClaimAccount {
+            epoch: staking_sol_distribution.epoch,
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            amount,
+            claimed_at: clock.unix_timestamp,
+            version: StakingSolClaimAccount::VERSION,
+            bump: ctx.bumps.staking_sol_claim,
+        });
+
+        emit!(SolRewardsClaimedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            epoch: staking_sol_distribution.epoch,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_claim_sol_rewards", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Pushes out the unlock time of an active stake position in exchange for a higher reward multiplier.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the position holds an active stake and that `new_unlock_ts` is later than its current unlock time.
+    /// - Checks that the new lock does not exceed the configured maximum lock duration.
+    /// - Accrues the rewards earned so far at the current multiplier.
+    /// - Stores the new unlock time and recalculates the multiplier from the staked amount and remaining lock time.
+    /// - Emits a `StakeLockExtendedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, staking configuration, and the registry of the stake position.
+    /// * `new_unlock_ts` - The Unix timestamp at which the stake will unlock.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the lock is extended successfully, otherwise returns an error.
+    pub fn extend_lock(ctx: Context<StakingExtendLock>, new_unlock_ts: i64) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+        let staking_registry = &mut ctx.accounts.staking_registry;
+
+        require!(staking_registry.staked_amount > 0, StakingErrorCode::NoActiveStake);
+        require!(
+            new_unlock_ts > staking_registry.unlocks_at,
+            StakingErrorCode::LockNotExtended
+        );
+        require!(
+            new_unlock_ts.saturating_sub(clock.unix_timestamp) <= staking_config.max_lock_duration,
+            StakingErrorCode::InvalidLockDuration
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+        let previous_unlocks_at = staking_registry.unlocks_at;
+        staking_registry.unlocks_at = new_unlock_ts;
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        emit!(StakeLockExtendedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            previous_unlocks_at,
+            unlocks_at: new_unlock_ts,
+            multiplier_bps: staking_registry.multiplier_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_extend_lock", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Migrates a stake position's registry created with a legacy layout to the current one.
+    ///
+    /// This function performs the following actions:
+    /// - Decodes the registry as a [`StakingRegistryData`] with the layout its size identifies, checking that it is smaller than
+    ///   the current layout, which identifies registries created before it grew.
+    /// - Tops up the registry's rent from the staker and reallocates it to the current size.
+    /// - Rewrites it in the current layout, backfilling the new fields with safe defaults, caching the registry's canonical bump,
+    ///   recording the staker as its last actor, and stamping `REGISTRY_VERSION`.
+    ///   An active stake's loyalty holding time starts at the migration if its original start was never recorded.
+    /// - Emits a `StakingRegistryMigratedEvent`.
+    ///
+    /// Registries must be migrated before instructions that load them as a `StakingRegistryAccount` can use them after a
+    /// layout upgrade; voting power and the position views read them through [`StakingRegistryData`] instead.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker and the registry to migrate.
+    /// * `position_index` - The index of the stake position whose registry is migrated.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the registry is migrated, otherwise returns an error.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if:
+    /// - The registry already uses the current layout.
+    /// - The registry cannot be decoded with the layout its size identifies.
+    pub fn migrate_registry(ctx: Context<StakingMigrateRegistry>, position_index: u64) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let registry = ctx.accounts.staking_registry.to_account_info();
+        let staking_registry_data = StakingRegistryData::decode(&registry.try_borrow_data()?)?;
+        require!(
+            !staking_registry_data.is_current(),
+            StakingErrorCode::RegistryAlreadyMigrated
+        );
+
+        grow_account(
+            &registry,
+            &ctx.accounts.staker.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            StakingRegistryAccount::SPACE,
+        )?;
+
+        let clock = Clock::get()?;
+        let mut staking_registry = staking_registry_data.upgrade(clock.unix_timestamp);
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+        staking_registry.try_serialize(&mut &mut registry.try_borrow_mut_data()?[..])?;
+
+        emit!(StakingRegistryMigratedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index,
+            version: staking_registry.version,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_migrate_registry", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Migrates the staking configuration created with an older layout to the current one.
+    ///
+    /// This function performs the following actions:
+    /// - Tops up the configuration's rent from the signer and reallocates it to the current size, zero-filling the new bytes.
+    /// - Decodes it in the current layout, where fields past the old allocation read as zero and `version` as the old layout's version.
+    /// - Checks that it is older than `CONFIG_VERSION`, then backfills the fields added since, caches its canonical bump, and
+    ///   stamps the current version.
+    /// - Emits a `StakingConfigMigratedEvent`.
+    ///
+    /// Anyone can crank the migration, since it only backfills defaults; the configuration must be migrated before other
+    /// staking instructions can load it after a layout upgrade.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer and the staking configuration.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the configuration already uses the current layout.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the configuration is migrated, otherwise returns an error.
+    pub fn migrate_config(ctx: Context<StakingMigrateConfig>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let staking_config = ctx.accounts.staking_config.to_account_info();
+        grow_account(
+            &staking_config,
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + StakingConfigAccount::INIT_SPACE,
+        )?;
+
+        let mut config = StakingConfigAccount::try_deserialize(&mut &staking_config.try_borrow_data()?[..])?;
+        require!(
+            config.version < CONFIG_VERSION,
+            StakingErrorCode::ConfigAlreadyMigrated
+        );
+
+        let previous_version = config.version;
+        config.upgrade(ctx.bumps.staking_config);
+        config.try_serialize(&mut &mut staking_config.try_borrow_mut_data()?[..])?;
+
+        emit!(StakingConfigMigratedEvent {
+            previous_version,
+            version: config.version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_migrate_config", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Migrates the zero-copy staking pool statistics created with an older layout to the current one.
+    ///
+    /// This function performs the following actions:
+    /// - Loads the pool in place, where a pool created before the layout was versioned reads its `version` from the
+    ///   zero-filled padding.
+    /// - Checks that it does not already use `StakingPoolAccount::VERSION`, then caches its canonical bump and stamps the
+    ///   current version.
+    /// - Emits an `AccountMigratedEvent`.
+    ///
+    /// The zero-copy layout is fixed-size, so the pool is never reallocated; anyone can crank the migration.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer and the staking pool.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program is halted or the pool already uses the current layout.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the pool is migrated, otherwise returns an error.
+    pub fn migrate_pool(ctx: Context<StakingMigratePool>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let mut staking_pool = ctx.accounts.staking_pool.load_mut()?;
+        let previous_version = staking_pool.version;
+        require!(
+            previous_version != StakingPoolAccount::VERSION,
+            VersioningErrorCode::AccountAlreadyMigrated
+        );
+
+        staking_pool.bump = ctx.bumps.staking_pool;
+        staking_pool.version = StakingPoolAccount::VERSION;
+
+        emit!(AccountMigratedEvent {
+            account: ctx.accounts.staking_pool.key(),
+            previous_version,
+            version: staking_pool.version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_migrate_pool", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Closes a SOL reward claim receipt, returning its rent to the staker.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the epoch's distribution has been fully claimed, so no position can claim from it again once the
+    ///   receipt is gone.
+    /// - Emits an `AccountClosedEvent`; the receipt is then closed to the staker.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the epoch's distribution, and the claim receipt to close.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program is halted or the distribution still has unclaimed SOL.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the receipt is closed successfully, otherwise returns an error.
+    pub fn close_sol_claim(ctx: Context<StakingCloseSolClaim>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let staking_sol_distribution = &ctx.accounts.staking_sol_distribution;
+        require!(
+            staking_sol_distribution.claimed_amount >= staking_sol_distribution.amount,
+            StakingErrorCode::DistributionNotFullyClaimed
+        );
+
+        AccountClosedEvent::emit(&ctx.accounts.staking_sol_claim.to_account_info(), ctx.accounts.staker.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_close_sol_claim", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Closes a fully released stake position, returning the registry's rent to the staker.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the position holds no stake, no remaining lock, no pending unstake request, and no unclaimed rewards.
+    /// - Emits a `StakePositionClosedEvent`; the registry account is then closed to the staker.
+    ///
+    /// The position index can be reopened later by freezing or staking into it again.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker and the registry of the stake position to close.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the position is closed successfully, otherwise returns an error.
+    pub fn close_position(ctx: Context<StakingClosePosition>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_registry = &ctx.accounts.staking_registry;
+
+        require!(
+            staking_registry.is_closable(clock.unix_timestamp),
+            StakingErrorCode::PositionNotClosable
+        );
+
+        emit!(StakePositionClosedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_close_position", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(position_index: u64, reference_number: String, amount: u64, lock_duration: i64, referrer: Option<Pubkey>)]
+pub struct StakingStake<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = StakingRegistryAccount::SPACE,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingReferrerAccount::INIT_SPACE,
+        seeds = [
+            STAKING_REFERRER_SEED,
+            referrer.unwrap_or_default().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_referrer: Option<Account<'info, StakingReferrerAccount>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey, position_index: u64)]
+pub struct StakingStakeFor<'info> {
+    #[account(mut)]
+    pub custodian: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            STAKING_CUSTODIAN_SEED,
+            custodian.key().as_ref(),
+        ],
+        bump = staking_custodian.bump,
+        constraint = staking_custodian.allowed @ StakingErrorCode::CustodianNotAllowed
+    )]
+    pub staking_custodian: Account<'info, StakingCustodianAccount>,
+
+    /// CHECK: The beneficiary's wallet, only used as the seed of its staking accounts and the owner of its token account
+    #[account(address = beneficiary)]
+    pub beneficiary_wallet: AccountInfo<'info>,
+
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = custodian,
+        associated_token::token_program = token_program,
+    )]
+    pub custodian_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = beneficiary_wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            beneficiary.as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = StakingRegistryAccount::SPACE,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            beneficiary.as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(staker: Pubkey, position_index: u64)]
+pub struct StakingStakeDelegated<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: The staker's wallet, only used as the seed of its staking accounts and the owner of its token account
+    #[account(address = staker)]
+    pub staker_wallet: AccountInfo<'info>,
+
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staker_wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = StakingRegistryAccount::SPACE,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingUnstake<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = staking_registry.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = staking_registry.token_account @ StakingErrorCode::TokenAccountMismatch,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is the PDA authority for the insurance fund, no need to deserialize
+    #[account(
+        seeds = [INSURANCE_FUND_SEED],
+        bump
+    )]
+    pub insurance_fund: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = insurance_fund,
+        associated_token::token_program = token_program,
+    )]
+    pub insurance_fund_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED,
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ledger: Option<Account<'info, VaultLedgerAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingQueueUnstake<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = staking_registry.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUnstakeQueueAccount::len_for(0),
+        seeds = [STAKING_UNSTAKE_QUEUE_SEED, token.key().as_ref()],
+        bump
+    )]
+    pub staking_unstake_queue: Account<'info, StakingUnstakeQueueAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingProcessUnstakeQueue<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: The staker of the request at the head of the queue, only used to derive their accounts
+    #[account(
+        constraint = staking_unstake_queue
+            .entries
+            .first()
+            .is_some_and(|entry| entry.staker == staker.key()) @ StakingErrorCode::UnstakeQueueMismatch
+    )]
+    pub staker: AccountInfo<'info>,
+
+    #[account(address = staking_unstake_queue.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = token,
+        token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    /// CHECK: The head request's staking registry, deserialized by the instruction since a closed position leaves it empty
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_unstake_queue.head_position_index().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_UNSTAKE_QUEUE_SEED, token.key().as_ref()],
+        bump = staking_unstake_queue.bump
+    )]
+    pub staking_unstake_queue: Account<'info, StakingUnstakeQueueAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_index: u64)]
+pub struct StakingStakePair<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingUserAccount::INIT_SPACE,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = StakingRegistryAccount::SPACE,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingPairVaultAccount::INIT_SPACE,
+        seeds = [STAKING_PAIR_VAULT_SEED],
+        bump
+    )]
+    pub staking_pair_vault: Account<'info, StakingPairVaultAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingUnstakePair<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(address = staking_registry.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = staking_registry.token_account @ StakingErrorCode::TokenAccountMismatch,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.key().as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_PAIR_VAULT_SEED],
+        bump = staking_pair_vault.bump
+    )]
+    pub staking_pair_vault: Account<'info, StakingPairVaultAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub struct StakingEscrowInstructions;
+
+impl StakingEscrowInstructions {
+    /// Stakes tokens by transferring them into the program-owned stake vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that staking is not paused.
+    /// - Checks that the amount is non-zero and that the reference number's length and characters are valid.
+    /// - Checks that the mint is on the staking allowlist, if the allowlist is enabled.
+    /// - Opens a new stake position if `position_index` is the staker's next unused index.
+    /// - Checks that the position holds no active freeze stake or escrow stake of another mint.
+    /// - Thaws the stake vault if the mint creates accounts frozen by default and the staking authority PDA is its freeze authority.
+    /// - Transfers the tokens from the staker's token account to the stake vault owned by the staking authority PDA.
+    /// - Credits the amount the vault actually received, so Token-2022 transfer fees are not counted as stake.
+    /// - Checks that the resulting staked amount meets the minimum required staking amount.
+    /// - Accrues the rewards earned so far, then records the reference number, mint, and staked amount in the staking registry.
+    /// - Extends the unlock time to cover the requested lock duration; adding to a stake never shortens its existing lock.
+    /// - Cancels any pending unstake request, since the position is bonded again.
+    /// - Recalculates the multiplier from the new staked amount and remaining lock time.
+    /// - If a referrer is provided, attributes the position to it and adds the received amount to its referral statistics.
+    /// - Adds the staked amount to the staking pool statistics.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required to stake, including the staker, token accounts, stake vault, and staking registry.
+    /// * `position_index` - The index of the staker's position to stake into, either an existing one or the next new one.
+    /// * `reference_number` - A string identifier to associate with this stake, of up to `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`.
+    /// * `amount` - The amount of tokens to add to the stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked, up to the configured maximum.
+    /// * `referrer` - The wallet that referred the staker, if any.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tokens are staked successfully, otherwise returns an error.
+    pub fn stake(
+        ctx: Context<StakingStake>,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&ctx.accounts.staker.key(), "stake")?;
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+        validate_reference_id(&reference_number)?;
+        ctx.accounts
+            .staking_config
+            .check_mint_allowed(ctx.accounts.staking_mint_config.as_deref())?;
+
+        ctx.accounts.staking_user.open_position(position_index, ctx.bumps.staking_user)?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            !staking_registry.has_active_stake_in_other_mode(StakingMode::Escrow),
+            StakingErrorCode::StakingModeMismatch
+        );
+        staking_registry.check_staked_accounts(&ctx.accounts.token.key(), &ctx.accounts.staker_token.key(), &Pubkey::default())?;
+
+        require_with_context!(
+            ctx.accounts.staker_token.amount >= amount,
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staker_token.key(),
+            amount,
+            ctx.accounts.staker_token.amount
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        if ctx.accounts.stake_vault.is_frozen() {
+            require!(
+                ctx.accounts.token.freeze_authority == COption::Some(ctx.accounts.staking_authority.key()),
+                StakingErrorCode::TokenAccountFrozen
+            );
+
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let vault_balance_before = ctx.accounts.stake_vault.amount;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.staker_token.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.staker.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+
+        ctx.accounts.stake_vault.reload()?;
+        let received_amount = ctx.accounts.stake_vault.amount.saturating_sub(vault_balance_before);
+        let staked_amount = ctx.accounts.staking_registry.staked_amount
+            .checked_add(received_amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            ctx.accounts.staking_config.meets_minimum(staked_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staking_registry.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            staked_amount
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        if let (Some(staking_referrer), Some(bump)) =
+            (ctx.accounts.staking_referrer.as_deref_mut(), ctx.bumps.staking_referrer)
+        {
+            staking_referrer.bump = bump;
+        }
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        staking_registry.attribute_referral(
+            &ctx.accounts.staker.key(),
+            referrer,
+            ctx.accounts.staking_referrer.as_deref_mut(),
+            received_amount,
+        )?;
+        let pooled_previous = ctx.accounts.staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            true,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = ctx.accounts.staking_config.pooled_amount(&ctx.accounts.token.key(), true, staked_amount);
+        ctx.accounts.staking_pool.load()?.check_stake_cap(
+            ctx.accounts.staking_config.max_total_staked,
+            pooled_previous,
+            pooled_current,
+        )?;
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.position_index = position_index;
+        staking_registry.reference_id = reference_number;
+        staking_registry.mode = StakingMode::Escrow;
+        staking_registry.mint = ctx.accounts.token.key();
+        staking_registry.token_account = ctx.accounts.staker_token.key();
+        staking_registry.custodian = Pubkey::default();
+        staking_registry.version = REGISTRY_VERSION;
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.staked_amount = staked_amount;
+        staking_registry.unlocks_at = staking_registry
+            .unlocks_at
+            .max(staking_config.unlock_time(clock.unix_timestamp, lock_duration)?);
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_write(ctx.accounts.staker.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_stake", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Stakes tokens on behalf of a beneficiary wallet, transferring them from an authorized custodian into the stake vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks, through account constraints, that the custodian is allowed to stake on behalf of other wallets.
+    /// - Checks that staking is not paused, that the amount is non-zero, and that the reference number's length and characters are valid.
+    /// - Checks that the mint is on the staking allowlist, if the allowlist is enabled.
+    /// - Opens a new stake position for the beneficiary if `position_index` is their next unused index.
+    /// - Checks that the position holds no active freeze stake, escrow stake of another mint, or stake opened by another custodian.
+    /// - Thaws the stake vault if needed, then transfers the tokens from the custodian's token account to the stake vault,
+    ///   crediting the amount the vault actually received.
+    /// - Checks that the resulting staked amount meets the minimum required staking amount and the staked-supply cap.
+    /// - Records the stake in the beneficiary's staking registry along with the custodian, and the beneficiary's token account
+    ///   as the one unstakes pay out to.
+    /// - Adds the staked amount to the staking pool statistics and emits a `StakedOnBehalfEvent`.
+    ///
+    /// The position belongs to the beneficiary: only they can request an unstake, unstake, or claim its rewards.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the custodian, the beneficiary's wallet and token account, the stake vault, and the beneficiary's staking registry.
+    /// * `beneficiary` - The wallet the stake is made for.
+    /// * `position_index` - The index of the beneficiary's position to stake into, either an existing one or the next new one.
+    /// * `reference_number` - A string identifier to associate with this stake, of up to `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`.
+    /// * `amount` - The amount of tokens to add to the stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked, up to the configured maximum.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tokens are staked successfully, otherwise returns an error.
+    pub fn stake_for(
+        ctx: Context<StakingStakeFor>,
+        beneficiary: Pubkey,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let custodian = ctx.accounts.custodian.key();
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&custodian, "stake_for")?;
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+        validate_reference_id(&reference_number)?;
+        ctx.accounts
+            .staking_config
+            .check_mint_allowed(ctx.accounts.staking_mint_config.as_deref())?;
+
+        ctx.accounts.staking_user.open_position(position_index, ctx.bumps.staking_user)?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            !staking_registry.has_active_stake_in_other_mode(StakingMode::Escrow),
+            StakingErrorCode::StakingModeMismatch
+        );
+        staking_registry.check_staked_accounts(&ctx.accounts.token.key(), &ctx.accounts.beneficiary_token.key(), &custodian)?;
+
+        require_with_context!(
+            ctx.accounts.custodian_token.amount >= amount,
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.custodian_token.key(),
+            amount,
+            ctx.accounts.custodian_token.amount
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        if ctx.accounts.stake_vault.is_frozen() {
+            require!(
+                ctx.accounts.token.freeze_authority == COption::Some(ctx.accounts.staking_authority.key()),
+                StakingErrorCode::TokenAccountFrozen
+            );
+
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let vault_balance_before = ctx.accounts.stake_vault.amount;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.custodian_token.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.custodian.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+
+        ctx.accounts.stake_vault.reload()?;
+        let received_amount = ctx.accounts.stake_vault.amount.saturating_sub(vault_balance_before);
+        let staked_amount = ctx.accounts.staking_registry.staked_amount
+            .checked_add(received_amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            ctx.accounts.staking_config.meets_minimum(staked_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staking_registry.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            staked_amount
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_previous = staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            true,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = staking_config.pooled_amount(&ctx.accounts.token.key(), true, staked_amount);
+        ctx.accounts.staking_pool.load()?.check_stake_cap(
+            staking_config.max_total_staked,
+            pooled_previous,
+            pooled_current,
+        )?;
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.position_index = position_index;
+        staking_registry.reference_id = reference_number;
+        staking_registry.mode = StakingMode::Escrow;
+        staking_registry.mint = ctx.accounts.token.key();
+        staking_registry.token_account = ctx.accounts.beneficiary_token.key();
+        staking_registry.custodian = custodian;
+        staking_registry.version = REGISTRY_VERSION;
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.staked_amount = staked_amount;
+        staking_registry.unlocks_at = staking_registry
+            .unlocks_at
+            .max(staking_config.unlock_time(clock.unix_timestamp, lock_duration)?);
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_write(ctx.accounts.custodian.key())?;
+
+        emit!(StakedOnBehalfEvent {
+            custodian,
+            beneficiary,
+            mint: staking_registry.mint,
+            position_index,
+            amount: received_amount,
+            reference_id: staking_registry.reference_id.clone(),
+            unlocks_at: staking_registry.unlocks_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_stake_for", ctx.accounts.custodian.key())?;
+
+        Ok(())
+    }
+
+    /// Stakes tokens from a staker's token account using the delegate approval they granted the staking authority PDA,
+    /// so a relayer can submit and pay for the stake without the staker signing.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that staking is not paused, that the amount is non-zero, and that the reference number's length and characters are valid.
+    /// - Checks that the mint is on the staking allowlist, if the allowlist is enabled.
+    /// - Checks that the staker's token account delegates at least `amount` to the staking authority PDA; the approval is the
+    ///   staker's consent to the stake, and is consumed by it.
+    /// - Opens a new stake position for the staker if `position_index` is their next unused index.
+    /// - Checks that the position holds no active freeze stake, escrow stake of another mint, or stake opened by a custodian.
+    /// - Thaws the stake vault if needed, then transfers the tokens to the stake vault signed by the staking authority PDA as delegate,
+    ///   crediting the amount the vault actually received.
+    /// - Checks that the resulting staked amount meets the minimum required staking amount and the staked-supply cap.
+    /// - Records the escrow stake in the staker's registry and adds it to the staking pool statistics.
+    ///
+    /// Freezes need the mint's freeze authority, which a token delegation cannot grant, so relayed onboarding always stakes in escrow.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the relayer, the staker's wallet and token account, the stake vault, and the staker's staking registry.
+    /// * `staker` - The wallet whose delegated tokens are staked.
+    /// * `position_index` - The index of the staker's position to stake into, either an existing one or the next new one.
+    /// * `reference_number` - A string identifier to associate with this stake, of up to `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`.
+    /// * `amount` - The amount of tokens to add to the stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked, up to the configured maximum.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tokens are staked successfully, otherwise returns an error.
+    pub fn stake_delegated(
+        ctx: Context<StakingStakeDelegated>,
+        staker: Pubkey,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&staker, "stake_delegated")?;
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+        validate_reference_id(&reference_number)?;
+        ctx.accounts
+            .staking_config
+            .check_mint_allowed(ctx.accounts.staking_mint_config.as_deref())?;
+        require!(
+            ctx.accounts.staker_token.delegate == COption::Some(ctx.accounts.staking_authority.key())
+                && ctx.accounts.staker_token.delegated_amount >= amount,
+            StakingErrorCode::DelegationMissing
+        );
+
+        ctx.accounts.staking_user.open_position(position_index, ctx.bumps.staking_user)?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            !staking_registry.has_active_stake_in_other_mode(StakingMode::Escrow),
+            StakingErrorCode::StakingModeMismatch
+        );
+        staking_registry.check_staked_accounts(&ctx.accounts.token.key(), &ctx.accounts.staker_token.key(), &Pubkey::default())?;
+
+        require_with_context!(
+            ctx.accounts.staker_token.amount >= amount,
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staker_token.key(),
+            amount,
+            ctx.accounts.staker_token.amount
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        if ctx.accounts.stake_vault.is_frozen() {
+            require!(
+                ctx.accounts.token.freeze_authority == COption::Some(ctx.accounts.staking_authority.key()),
+                StakingErrorCode::TokenAccountFrozen
+            );
+
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let vault_balance_before = ctx.accounts.stake_vault.amount;
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.staker_token.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+
+        ctx.accounts.stake_vault.reload()?;
+        let received_amount = ctx.accounts.stake_vault.amount.saturating_sub(vault_balance_before);
+        let staked_amount = ctx.accounts.staking_registry.staked_amount
+            .checked_add(received_amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            ctx.accounts.staking_config.meets_minimum(staked_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staking_registry.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            staked_amount
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_previous = staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            true,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = staking_config.pooled_amount(&ctx.accounts.token.key(), true, staked_amount);
+        ctx.accounts.staking_pool.load()?.check_stake_cap(
+            staking_config.max_total_staked,
+            pooled_previous,
+            pooled_current,
+        )?;
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.position_index = position_index;
+        staking_registry.reference_id = reference_number;
+        staking_registry.mode = StakingMode::Escrow;
+        staking_registry.mint = ctx.accounts.token.key();
+        staking_registry.token_account = ctx.accounts.staker_token.key();
+        staking_registry.custodian = Pubkey::default();
+        staking_registry.version = REGISTRY_VERSION;
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.staked_amount = staked_amount;
+        staking_registry.unlocks_at = staking_registry
+            .unlocks_at
+            .max(staking_config.unlock_time(clock.unix_timestamp, lock_duration)?);
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_write(ctx.accounts.relayer.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_stake_delegated", ctx.accounts.relayer.key())?;
+
+        Ok(())
+    }
+
+    /// Unstakes part or all of an escrow stake, returning the tokens from the stake vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the registry holds an escrow stake of at least `amount`.
+    /// - Checks that the remaining stake is either zero or still meets the minimum required staking amount.
+    /// - Checks that the stake is unlocked, unless `early_exit` is set, in which case the configured penalty is withheld.
+    /// - If an unstake cooldown is configured, checks that an unstake was requested and its cooldown has elapsed; the request is consumed.
+    ///   Once staking has been paused past its grace period, the lockup and cooldown no longer apply and no penalty is withheld.
+    /// - Accrues the rewards earned so far.
+    /// - Transfers the tokens from the stake vault back to the staker, signed by the staking authority PDA.
+    /// - Moves any penalty from the stake vault into the insurance fund and records it as an `InsuranceDeposit` vault ledger entry;
+    ///   the insurance fund token account and ledger account are only required when a penalty is collected.
+    /// - Updates the staked amount and multiplier, clearing the reference number once the stake is fully withdrawn.
+    /// - Removes the withdrawn amount from the staking pool statistics.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required to unstake, including the staker, token accounts, stake vault, staking registry,
+    ///   and, for early exits, the insurance fund and vault ledger.
+    /// * `amount` - The amount of tokens to withdraw from the stake.
+    /// * `early_exit` - Whether to withdraw from a still-locked stake by paying the early-exit penalty.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tokens are unstaked successfully, otherwise returns an error.
+    pub fn unstake(ctx: Context<StakingUnstake>, amount: u64, early_exit: bool) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            staking_registry.mode == StakingMode::Escrow,
+            StakingErrorCode::StakingModeMismatch
+        );
+        require_with_context!(
+            amount <= staking_registry.staked_amount,
+            StakingErrorCode::InsufficientStakedAmount,
+            staking_registry.key(),
+            amount,
+            staking_registry.staked_amount
+        );
+
+        let remaining_amount = staking_registry.staked_amount
+            .checked_sub(amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            remaining_amount == 0 || ctx.accounts.staking_config.meets_minimum(remaining_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            staking_registry.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            remaining_amount
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let is_early_exit = staking_registry.check_release(staking_config, clock.unix_timestamp, early_exit)?;
+        let penalty = if is_early_exit {
+            staking_config.early_exit_penalty(amount)
+        } else {
+            0
+        };
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_previous = staking_registry.pooled_amount(
+            &ctx.accounts.staking_config,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = staking_registry.pooled_amount(&ctx.accounts.staking_config, remaining_amount);
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.staker_token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount.checked_sub(penalty).ok_or(MathErrorCode::ArithmeticError)?,
+            ctx.accounts.token.decimals,
+        )?;
+
+        if penalty > 0 {
+            let (Some(insurance_fund_token), Some(ledger), Some(ledger_bump)) =
+                (&ctx.accounts.insurance_fund_token, &mut ctx.accounts.ledger, ctx.bumps.ledger)
+            else {
+                return err!(StakingErrorCode::MissingInsuranceFund);
+            };
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        mint: ctx.accounts.token.to_account_info(),
+                        to: insurance_fund_token.to_account_info(),
+                        authority: ctx.accounts.staking_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                penalty,
+                ctx.accounts.token.decimals,
+            )?;
+
+            let staker = ctx.accounts.staker.key();
+            ledger.record(
+                &mut ctx.accounts.ledger_identifier,
+                staker,
+                VaultTransaction::InsuranceDeposit {
+                    from: staker,
+                    mint: ctx.accounts.token.key(),
+                    amount: penalty,
+                },
+                penalty,
+                0,
+                ledger_bump,
+            )?;
+        }
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        staking_registry.staked_amount = remaining_amount;
+        staking_registry.clear_unstake_request();
+        if remaining_amount == 0 {
+            staking_registry.reference_id = String::from("");
+            staking_registry.unlocks_at = 0;
+        }
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_unstake", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Queues an unstake of part or all of an escrow stake, to be paid out in FIFO order as stake vault liquidity returns.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the registry holds an unlocked escrow stake of at least `amount` with no pending unstake request.
+    /// - Checks that the remaining stake is either zero or still meets the minimum required staking amount.
+    /// - Accrues the rewards earned so far; the position stops earning rewards while its request is queued.
+    /// - Records the request in the staking registry, with the end of the unstake cooldown as its ETA.
+    /// - Appends the request to the back of the mint's unstake queue, creating the queue if needed and growing it, at the
+    ///   payer's expense, when it has no room left for another request.
+    /// - Emits an `UnstakeQueuedEvent` with the request's sequence number and ETA.
+    ///
+    /// The request is paid out by `process_unstake_queue` once every earlier request has been paid, its ETA has passed,
+    /// and the stake vault holds enough tokens to cover it.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the staked mint, the staking configuration, the staking registry,
+    ///   and the mint's unstake queue.
+    /// * `amount` - The amount of tokens to withdraw from the stake.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the unstake is queued successfully, otherwise returns an error.
+    pub fn queue_unstake(ctx: Context<StakingQueueUnstake>, amount: u64) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+        let staking_registry = &mut ctx.accounts.staking_registry;
+
+        require!(
+            staking_registry.mode == StakingMode::Escrow,
+            StakingErrorCode::StakingModeMismatch
+        );
+        require_with_context!(
+            amount <= staking_registry.staked_amount,
+            StakingErrorCode::InsufficientStakedAmount,
+            staking_registry.key(),
+            amount,
+            staking_registry.staked_amount
+        );
+        require!(
+            staking_registry.unstake_requested_at == 0,
+            StakingErrorCode::UnstakeAlreadyRequested
+        );
+
+        let remaining_amount = staking_registry.staked_amount
+            .checked_sub(amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            remaining_amount == 0 || staking_config.meets_minimum(remaining_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            staking_registry.key(),
+            staking_config.min_staking_amount,
+            remaining_amount
+        );
+        if !staking_config.pause_grace_elapsed(clock.unix_timestamp) {
+            staking_registry.is_early_exit(clock.unix_timestamp, false)?;
+        }
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.unstake_requested_at = clock.unix_timestamp;
+        staking_registry.cooldown_ends_at = clock.unix_timestamp.saturating_add(staking_config.unstake_cooldown);
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        let staking_unstake_queue = &mut ctx.accounts.staking_unstake_queue;
+        grow_account(
+            &staking_unstake_queue.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + StakingUnstakeQueueAccount::len_for((staking_unstake_queue.entries.len() + 1).min(MAX_UNSTAKE_QUEUE_LEN)),
+        )?;
+        if staking_unstake_queue.next_sequence == 0 {
+            staking_unstake_queue.version = StakingUnstakeQueueAccount::VERSION;
+            staking_unstake_queue.bump = ctx.bumps.staking_unstake_queue;
+        }
+        staking_unstake_queue.mint = ctx.accounts.token.key();
+        let sequence = staking_unstake_queue.enqueue(
+            ctx.accounts.staker.key(),
+            staking_registry.position_index,
+            amount,
+            staking_registry.unstake_requested_at,
+            staking_registry.cooldown_ends_at,
+        )?;
+
+        emit!(UnstakeQueuedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            mint: staking_unstake_queue.mint,
+            sequence,
+            position: sequence.saturating_sub(staking_unstake_queue.processed_sequence),
+            amount,
+            eta: staking_registry.cooldown_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_queue_unstake", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Pays out the request at the head of a mint's unstake queue. Anyone can crank the queue.
+    ///
+    /// This function performs the following actions:
+    /// - Returns without changes until the head request's ETA has passed.
+    /// - If the position still holds the queued request, returns without changes while the stake vault cannot cover it,
+    ///   then accrues rewards, transfers the tokens to the staker's recorded token account, and updates the registry
+    ///   and staking pool statistics the same way as `unstake`.
+    /// - Drops a request the position no longer holds, for example because it was released by `unstake` or closed
+    ///   in the meantime, without paying it, so a stale request never blocks the requests behind it.
+    /// - Removes the request from the queue and emits an `UnstakeQueueProcessedEvent` with the amount paid.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the head request's staker accounts, the mint's unstake queue, the stake vault,
+    ///   and the staking authority.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the head request is processed or not due yet, so keepers can call it on a schedule; returns an
+    /// error if the queue is empty.
+    pub fn process_unstake_queue(ctx: Context<StakingProcessUnstakeQueue>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let Some(entry) = ctx.accounts.staking_unstake_queue.entries.first().cloned() else {
+            return err!(StakingErrorCode::UnstakeQueueEmpty);
+        };
+        if clock.unix_timestamp < entry.eta {
+            crate::log_info!("staking_process_unstake_queue_skipped", sequence = entry.sequence, eta = entry.eta);
+            return Ok(());
+        }
+
+        let registry = ctx.accounts.staking_registry.to_account_info();
+        let mut amount = 0;
+        if registry.owner == &crate::ID && !registry.data_is_empty() {
+            let mut staking_registry = StakingRegistryAccount::try_deserialize(&mut &registry.try_borrow_data()?[..])?;
+            if staking_registry.unstake_requested_at == entry.requested_at {
+                amount = entry.amount.min(staking_registry.staked_amount);
+            }
+
+            if amount > 0 {
+                require!(
+                    ctx.accounts.staker_token.key() == staking_registry.token_account,
+                    StakingErrorCode::TokenAccountMismatch
+                );
+                if ctx.accounts.stake_vault.amount < amount {
+                    crate::log_info!("staking_process_unstake_queue_skipped", sequence = entry.sequence, amount = amount);
+                    return Ok(());
+                }
+
+                let remaining_amount = staking_registry.staked_amount
+                    .checked_sub(amount)
+                    .ok_or(MathErrorCode::ArithmeticError)?;
+                let pooled_previous = staking_registry.pooled_amount(staking_config, staking_registry.staked_amount);
+                let pooled_current = staking_registry.pooled_amount(staking_config, remaining_amount);
+                ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+                    &mut ctx.accounts.staking_user,
+                    &mut *ctx.accounts.staking_activity.load_mut()?,
+                    staking_config,
+                    pooled_previous,
+                    pooled_current,
+                    clock.epoch,
+                );
+
+                staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+                let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.stake_vault.to_account_info(),
+                            mint: ctx.accounts.token.to_account_info(),
+                            to: ctx.accounts.staker_token.to_account_info(),
+                            authority: ctx.accounts.staking_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                    ctx.accounts.token.decimals,
+                )?;
+
+                staking_registry.staked_amount = remaining_amount;
+                staking_registry.clear_unstake_request();
+                if remaining_amount == 0 {
+                    staking_registry.reference_id = String::from("");
+                    staking_registry.unlocks_at = 0;
+                }
+                staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+                staking_registry.record_update(ctx.accounts.signer.key())?;
+                staking_registry.try_serialize(&mut &mut registry.try_borrow_mut_data()?[..])?;
+            }
+        }
+
+        ctx.accounts.staking_unstake_queue.dequeue();
+
+        emit!(UnstakeQueueProcessedEvent {
+            staker: entry.staker,
+            position_index: entry.position_index,
+            mint: ctx.accounts.staking_unstake_queue.mint,
+            sequence: entry.sequence,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_process_unstake_queue", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Stakes a MIMING + SOL pair, locking the tokens in the stake vault and SOL at the configured ratio in the pair vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that staking is not paused, that the amount is non-zero, and that the reference number is valid.
+    /// - Checks that the mint is on the staking allowlist, if the allowlist is enabled.
+    /// - Opens a new stake position if `position_index` is the staker's next unused index.
+    /// - Checks that the position holds no active freeze or escrow stake, or a pair stake of another mint.
+    /// - Computes the SOL to lock from `pair_sol_ratio_bps`; pair staking is disabled while the ratio is zero.
+    /// - Transfers the tokens into the stake vault and the SOL into the pair vault, which tracks the protocol-owned SOL.
+    /// - Credits the amount the stake vault actually received, and checks that the resulting stake meets the minimum and the staked-supply cap.
+    /// - Records both amounts in the staking registry, extends the unlock time, and recalculates the multiplier with the pair boost.
+    /// - Adds the staked amount to the staking pool statistics and emits a `PairStakedEvent`.
+    ///
+    /// Topping up a pair position locks more SOL at the ratio in force at the time; `unstake_pair` always returns everything that was locked.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, token accounts, stake vault, pair vault, and staking registry.
+    /// * `position_index` - The index of the staker's position to stake into, either an existing one or the next new one.
+    /// * `reference_number` - A string identifier to associate with this stake, of up to `MAX_REFERENCE_ID_LEN` ASCII letters, digits, `-` or `_`.
+    /// * `amount` - The amount of tokens to add to the stake.
+    /// * `lock_duration` - The number of seconds the stake stays locked, up to the configured maximum.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the pair is staked successfully, otherwise returns an error.
+    pub fn stake_pair(
+        ctx: Context<StakingStakePair>,
+        position_index: u64,
+        reference_number: String,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        ctx.accounts.program_config.check_staking_enabled()?;
+        ctx.accounts
+            .staking_config
+            .check_not_paused(&ctx.accounts.staker.key(), "stake_pair")?;
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+        validate_reference_id(&reference_number)?;
+        ctx.accounts
+            .staking_config
+            .check_mint_allowed(ctx.accounts.staking_mint_config.as_deref())?;
+        let lamports = ctx.accounts.staking_config.pair_lamports(amount)?;
+
+        ctx.accounts.staking_user.open_position(position_index, ctx.bumps.staking_user)?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            !staking_registry.has_active_stake_in_other_mode(StakingMode::Pair),
+            StakingErrorCode::StakingModeMismatch
+        );
+        staking_registry.check_staked_accounts(&ctx.accounts.token.key(), &ctx.accounts.staker_token.key(), &Pubkey::default())?;
+
+        require_with_context!(
+            ctx.accounts.staker_token.amount >= amount,
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staker_token.key(),
+            amount,
+            ctx.accounts.staker_token.amount
+        );
+
+        let vault_balance_before = ctx.accounts.stake_vault.amount;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.staker_token.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.staker.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.staker.to_account_info(),
+                    to: ctx.accounts.staking_pair_vault.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        ctx.accounts.stake_vault.reload()?;
+        let received_amount = ctx.accounts.stake_vault.amount.saturating_sub(vault_balance_before);
+        let staked_amount = ctx.accounts.staking_registry.staked_amount
+            .checked_add(received_amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        require_with_context!(
+            ctx.accounts.staking_config.meets_minimum(staked_amount),
+            StakingErrorCode::InsufficientStakingBalance,
+            ctx.accounts.staking_registry.key(),
+            ctx.accounts.staking_config.min_staking_amount,
+            staked_amount
+        );
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_previous = staking_config.pooled_amount(
+            &ctx.accounts.token.key(),
+            true,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = staking_config.pooled_amount(&ctx.accounts.token.key(), true, staked_amount);
+        ctx.accounts.staking_pool.load()?.check_stake_cap(
+            staking_config.max_total_staked,
+            pooled_previous,
+            pooled_current,
+        )?;
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        let staking_pair_vault = &mut ctx.accounts.staking_pair_vault;
+        if staking_pair_vault.position_count == 0 && staking_pair_vault.total_lamports == 0 {
+            staking_pair_vault.version = StakingPairVaultAccount::VERSION;
+            staking_pair_vault.bump = ctx.bumps.staking_pair_vault;
+        }
+        if staking_registry.paired_lamports == 0 {
+            staking_pair_vault.position_count = staking_pair_vault.position_count.saturating_add(1);
+        }
+        staking_pair_vault.total_lamports = staking_pair_vault.total_lamports.saturating_add(lamports);
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+        staking_registry.clear_unstake_request();
+        staking_registry.position_index = position_index;
+        staking_registry.reference_id = reference_number;
+        staking_registry.mode = StakingMode::Pair;
+        staking_registry.mint = ctx.accounts.token.key();
+        staking_registry.token_account = ctx.accounts.staker_token.key();
+        staking_registry.custodian = Pubkey::default();
+        staking_registry.version = REGISTRY_VERSION;
+        staking_registry.bump = ctx.bumps.staking_registry;
+        staking_registry.staked_amount = staked_amount;
+        staking_registry.paired_lamports = staking_registry.paired_lamports.saturating_add(lamports);
+        staking_registry.unlocks_at = staking_registry
+            .unlocks_at
+            .max(staking_config.unlock_time(clock.unix_timestamp, lock_duration)?);
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_write(ctx.accounts.staker.key())?;
+
+        emit!(PairStakedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index,
+            mint: staking_registry.mint,
+            amount: received_amount,
+            lamports,
+            staked_amount: staking_registry.staked_amount,
+            paired_lamports: staking_registry.paired_lamports,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_stake_pair", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Releases a pair stake, returning both its tokens and its locked SOL.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the registry holds a pair stake.
+    /// - Checks that the stake is unlocked and, with a cooldown configured, that its unstake cooldown has elapsed;
+    ///   pairs have no early-exit path, but once staking has been paused past its grace period the lockup and cooldown no longer apply.
+    /// - Accrues the rewards earned so far.
+    /// - Transfers the tokens from the stake vault back to the staker, signed by the staking authority PDA,
+    ///   and moves the locked SOL from the pair vault back to the staker.
+    /// - Clears the position's staked amount, paired SOL, and reference number, and removes it from the staking pool statistics.
+    /// - Emits a `PairUnstakedEvent`.
+    ///
+    /// Pair stakes are always released in full, so the tokens and SOL left locked never drift from the recorded pair.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, token accounts, stake vault, pair vault, and staking registry.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the pair is released successfully, otherwise returns an error.
+    pub fn unstake_pair(ctx: Context<StakingUnstakePair>) -> Result<()> {
+        ctx.accounts.program_config.check_not_globally_paused()?;
+
+        let clock = Clock::get()?;
+        let staking_config = &ctx.accounts.staking_config;
+        let staking_registry = &mut ctx.accounts.staking_registry;
+
+        require!(
+            staking_registry.mode == StakingMode::Pair && staking_registry.staked_amount > 0,
+            StakingErrorCode::StakingModeMismatch
+        );
+        staking_registry.check_release(staking_config, clock.unix_timestamp, false)?;
+
+        let amount = staking_registry.staked_amount;
+        let lamports = staking_registry.paired_lamports;
+        let pooled_amount = staking_registry.pooled_amount(staking_config, amount);
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            staking_config,
+            pooled_amount,
+            0,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.staker_token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+
+        let staking_pair_vault = &mut ctx.accounts.staking_pair_vault;
+        staking_pair_vault.sub_lamports(lamports)?;
+        ctx.accounts.staker.add_lamports(lamports)?;
+        staking_pair_vault.total_lamports = staking_pair_vault.total_lamports.saturating_sub(lamports);
+        staking_pair_vault.position_count = staking_pair_vault.position_count.saturating_sub(1);
+
+        staking_registry.staked_amount = 0;
+        staking_registry.paired_lamports = 0;
+        staking_registry.clear_unstake_request();
+        staking_registry.reference_id = String::from("");
+        staking_registry.unlocks_at = 0;
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_update(ctx.accounts.staker.key())?;
+
+        emit!(PairUnstakedEvent {
+            staker: ctx.accounts.staker.key(),
+            position_index: staking_registry.position_index,
+            mint: staking_registry.mint,
+            amount,
+            lamports,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_unstake_pair", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub enum StakingProposalAction {
+    ConfigureMint { mint: Pubkey, custodial: bool, allowed: bool },
+    Slash {
+        staker: Pubkey,
+        position_index: u64,
+        amount: u64,
+        evidence_hash: [u8; 32],
+        #[max_len(STRING_LEN)]
+        reason: String,
+    },
+    EmergencyThaw { staker: Pubkey, position_index: u64 },
+    UpdateConfig {
+        min_staking_amount: u64,
+        unstake_cooldown: i64,
+        mint_allowlist_enabled: bool,
+        min_inclusive: bool,
+        max_total_staked: u64,
+        pause_grace_period: i64,
+    },
+    ConfigureAttestor { attestor: Pubkey },
+    SetPaused { paused: bool },
+    ConfigureValidator { vote_account: Pubkey, allowed: bool },
+    ConfigureCustodian { custodian: Pubkey, allowed: bool },
+    ConfigurePairStaking { sol_ratio_bps: u64, boost_bps: u16 },
+    SetRewardSchedule {
+        #[max_len(MAX_REWARD_SCHEDULE_STEPS)]
+        steps: Vec<StakingRewardScheduleStep>,
+    },
+    UpdateProgramConfig {
+        vault_enabled: bool,
+        staking_enabled: bool,
+        teleport_paused: bool,
+        teleport_fee: u64,
+        guardian: Pubkey,
+        rent_recipient: Pubkey,
+    },
+    ResumeProgram,
+    DelegateVaultStake { vote_account: Pubkey, amount: u64 },
+    DistributeSolRewards { epoch: u64, amount: u64 },
+    DeactivateVaultStake { stake_account: Pubkey },
+    WithdrawVaultStake { stake_account: Pubkey },
+    ConfigureCpiGuard {
+        enabled: bool,
+        #[max_len(MAX_CPI_CALLERS)]
+        allowed_callers: Vec<Pubkey>,
+    },
+    ConfigureRole { role: Role, holder: Pubkey },
+    ConfigureHubEmitter { chain: u16, emitter: [u8; 32] },
+    ConfigureMessaging { transport: MessagingTransport, endpoint: Pubkey },
+    ConfigureUsdFee { usd_cents: u64, feed_id: [u8; 32], max_staleness: i64, max_confidence_bps: u16 },
+    ConfigureOracleFallback { feed: Pubkey, max_divergence_bps: u16 },
+    ConfigureDepositReceipts {
+        threshold: u64,
+        #[max_len(MAX_RECEIPT_URI_PREFIX_LEN)]
+        uri_prefix: String,
+    },
+    ConfigureSquadsAuthority { multisig: Pubkey, vault_index: u8 },
+    ConfigureRealmsGovernance { governance: Pubkey },
+    ConfigureCompressedReceipts { merkle_tree: Pubkey },
+    ConfigureHubRegistry {
+        hub_chain: u16,
+        hub_emitter: [u8; 32],
+        #[max_len(MAX_HUB_ROUTES)]
+        routes: Vec<HubRoute>,
+    },
+    ConfigureRelayerFee { fee_bps: u16 },
+}
+
+impl StakingProposalAction {
+    /// Checks that the action's parameters are within bounds before it is proposed or executed.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            StakingProposalAction::Slash { evidence_hash, reason, .. } => {
+                require!(
+                    reason.len() <= STRING_LEN,
+                    StakingErrorCode::ReasonTooLong
+                );
+                require!(
+                    *evidence_hash != [0u8; 32],
+                    StakingErrorCode::MissingSlashEvidence
+                );
+            }
+            StakingProposalAction::UpdateConfig { min_staking_amount, unstake_cooldown, pause_grace_period, .. } => {
+                require!(
+                    (1..=MAX_MIN_STAKING_AMOUNT).contains(min_staking_amount)
+                        && (0..=MAX_UNSTAKE_COOLDOWN).contains(unstake_cooldown)
+                        && (1..=MAX_PAUSE_GRACE_PERIOD).contains(pause_grace_period),
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::DelegateVaultStake { amount, .. }
+            | StakingProposalAction::DistributeSolRewards { amount, .. } => {
+                require!(*amount > 0, StakingErrorCode::InvalidAmount);
+            }
+            StakingProposalAction::ConfigurePairStaking { boost_bps, .. } => {
+                require!(
+                    *boost_bps <= MAX_PAIR_BOOST_BPS,
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::SetRewardSchedule { steps } => {
+                validate_reward_schedule(steps)?;
+            }
+            StakingProposalAction::UpdateProgramConfig { teleport_fee, .. } => {
+                require!(
+                    *teleport_fee <= MAX_TELEPORT_FEE,
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureCpiGuard { allowed_callers, .. } => {
+                require!(
+                    allowed_callers.len() <= MAX_CPI_CALLERS,
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureRole { role, .. } => {
+                require!(role.is_appointed(), StakingErrorCode::InvalidConfigValue);
+            }
+            StakingProposalAction::ConfigureHubEmitter { chain, emitter } => {
+                require!(
+                    *chain != 0 && *chain != SOLANA_CHAIN_ID && *emitter != [0u8; 32],
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureHubRegistry { hub_chain, hub_emitter, routes } => {
+                validate_hub_routing(*hub_chain, hub_emitter, routes)?;
+            }
+            StakingProposalAction::ConfigureMessaging { endpoint, .. } => {
+                require!(*endpoint != Pubkey::default(), StakingErrorCode::InvalidConfigValue);
+            }
+            StakingProposalAction::ConfigureUsdFee { usd_cents, feed_id, max_staleness, max_confidence_bps } => {
+                require!(
+                    *usd_cents <= MAX_TELEPORT_FEE_USD_CENTS
+                        && (*usd_cents == 0
+                            || (*feed_id != [0u8; 32]
+                                && (1..=MAX_ORACLE_STALENESS).contains(max_staleness)
+                                && (1..=MAX_ORACLE_CONFIDENCE_BPS).contains(max_confidence_bps))),
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureOracleFallback { feed, max_divergence_bps } => {
+                require!(
+                    *feed == Pubkey::default() || (1..=MAX_ORACLE_DIVERGENCE_BPS).contains(max_divergence_bps),
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureRelayerFee { fee_bps } => {
+                require!(*fee_bps <= MAX_RELAYER_FEE_BPS, StakingErrorCode::InvalidConfigValue);
+            }
+            StakingProposalAction::ConfigureDepositReceipts { uri_prefix, .. } => {
+                require!(
+                    uri_prefix.len() <= MAX_RECEIPT_URI_PREFIX_LEN,
+                    StakingErrorCode::InvalidConfigValue
+                );
+            }
+            StakingProposalAction::ConfigureMint { .. }
+            | StakingProposalAction::EmergencyThaw { .. }
+            | StakingProposalAction::ConfigureAttestor { .. }
+            | StakingProposalAction::SetPaused { .. }
+            | StakingProposalAction::ConfigureValidator { .. }
+            | StakingProposalAction::ConfigureCustodian { .. }
+            | StakingProposalAction::DeactivateVaultStake { .. }
+            | StakingProposalAction::WithdrawVaultStake { .. }
+            | StakingProposalAction::ConfigureSquadsAuthority { .. }
+            | StakingProposalAction::ConfigureRealmsGovernance { .. }
+            | StakingProposalAction::ConfigureCompressedReceipts { .. }
+            | StakingProposalAction::ResumeProgram => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` for actions that change the program or staking configuration, which take effect only
+    /// `CONFIG_ACTIVATION_DELAY` after they are proposed. Emergency pauses and resumes are left out, so they act at once.
+    pub fn is_config_change(&self) -> bool {
+        matches!(
+            self,
+            StakingProposalAction::UpdateConfig { .. }
+                | StakingProposalAction::ConfigureAttestor { .. }
+                | StakingProposalAction::ConfigurePairStaking { .. }
+                | StakingProposalAction::SetRewardSchedule { .. }
+                | StakingProposalAction::UpdateProgramConfig { .. }
+                | StakingProposalAction::ConfigureCpiGuard { .. }
+                | StakingProposalAction::ConfigureRole { .. }
+                | StakingProposalAction::ConfigureHubEmitter { .. }
+                | StakingProposalAction::ConfigureMessaging { .. }
+                | StakingProposalAction::ConfigureUsdFee { .. }
+                | StakingProposalAction::ConfigureOracleFallback { .. }
+                | StakingProposalAction::ConfigureDepositReceipts { .. }
+                | StakingProposalAction::ConfigureSquadsAuthority { .. }
+                | StakingProposalAction::ConfigureRealmsGovernance { .. }
+                | StakingProposalAction::ConfigureCompressedReceipts { .. }
+                | StakingProposalAction::ConfigureHubRegistry { .. }
+                | StakingProposalAction::ConfigureRelayerFee { .. }
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub enum StakingProposalStatus {
+    Pending,
+    Approved,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingProposalAccount {
+    pub id: u64,
+    pub action: StakingProposalAction,
+    #[max_len(MAX_SIGNERS)]
+    pub multisig_required_signers: Vec<Pubkey>,
+    #[max_len(MAX_SIGNERS)]
+    pub multisig_signers: Vec<Pubkey>,
+    pub status: StakingProposalStatus,
+    pub created_at: i64,
+    pub disputed_at: i64,
+    pub version: u8,
+    pub bump: u8,
+    pub signed_mask: u16,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
+}
+
+impl StakingProposalAccount {
+    /// Returns the size of a proposal with room for `signer_count` required signers and signatures.
+    pub const fn len_for(signer_count: usize) -> usize {
+        // multisig_required_signers and multisig_signers each hold up to `MAX_SIGNERS` keys
+        space_for_len(Self::INIT_SPACE, 2 * MAX_SIGNERS, PUBKEY_SIZE, 2 * signer_count)
+    }
+
+    /// Rebuilds `signed_mask` from the collected signatures of a proposal recorded before it kept one.
+    pub fn sync_signed_mask(&mut self) {
+        if self.version < Self::VERSION {
+            self.signed_mask = signer_mask_of(&self.multisig_required_signers, &self.multisig_signers);
+        }
+    }
+
+    /// Returns when the proposal's slash dispute window closes: `SLASH_DISPUTE_WINDOW` after its creation, or after the
+    /// staker's counter-claim once the slash has been disputed.
+    pub fn dispute_ends_at(&self) -> i64 {
+        self.created_at.max(self.disputed_at).saturating_add(SLASH_DISPUTE_WINDOW)
+    }
+
+    /// Returns when the proposal's configuration change can be activated: `CONFIG_ACTIVATION_DELAY` after its creation.
+    pub fn activates_at(&self) -> i64 {
+        self.created_at.saturating_add(CONFIG_ACTIVATION_DELAY)
+    }
+
+    /// Checks that the proposal's configuration change has waited out its activation delay.
+    pub fn check_activation_delay_elapsed(&self, now: i64) -> Result<()> {
+        require!(now >= self.activates_at(), StakingErrorCode::ActivationDelayNotElapsed);
+
+        Ok(())
+    }
+
+    /// Marks the proposal as approved once every required multisig signer has signed it.
+    ///
+    /// The executing signer must be one of the required signers (if any are specified), and the proposal must still be pending.
+    pub fn approve(&mut self, signer_key: &Pubkey) -> Result<()> {
+        self.approve_with(signer_key, self.multisig_required_signers.len())
+    }
+
+    /// Returns how many required signers must sign a `Slash` proposal before it executes: the council's `threshold`, or
+    /// every required signer once the staker has disputed the slash, so a counter-claim can only be overruled unanimously.
+    pub fn slash_approvals_required(&self, threshold: u8) -> usize {
+        let required_signers = self.multisig_required_signers.len();
+        if self.disputed_at != 0 {
+            return required_signers;
+        }
+
+        usize::from(threshold.max(1)).min(required_signers)
+    }
+
+    /// Marks the proposal as approved once `approvals_required` of its required multisig signers have signed it, checked
+    /// the same way as [`approve`](Self::approve).
+    pub fn approve_with(&mut self, signer_key: &Pubkey, approvals_required: usize) -> Result<()> {
+        require!(
+            self.status == StakingProposalStatus::Pending,
+            StakingErrorCode::AlreadyResolved
+        );
+
+        if !self.multisig_required_signers.is_empty() {
+            require_with_context!(
+                self.multisig_required_signers.contains(signer_key),
+                StakingErrorCode::UnauthorizedSigner,
+                *signer_key,
+                0,
+                0
+            );
+        }
+
+        self.sync_signed_mask();
+
+        let approvals = (self.signed_mask & full_signer_mask(self.multisig_required_signers.len())).count_ones() as usize;
+
+        require_with_context!(
+            approvals >= approvals_required,
+            StakingErrorCode::InsufficientSignatures,
+            *signer_key,
+            approvals_required as u64,
+            approvals as u64
+        );
+
+        self.status = StakingProposalStatus::Approved;
+
+        Ok(())
+    }
+
+    /// Marks the proposal as approved for execution by `signer_key`: at once when it is the Realms governance registered
+    /// in `program_config`, which only signs for a Realms proposal its voters approved, and otherwise through [`approve`].
+    ///
+    /// [`approve`]: StakingProposalAccount::approve
+    pub fn approve_by(&mut self, program_config: &ProgramConfigAccount, signer_key: &Pubkey) -> Result<()> {
+        if !program_config.is_realms_governance(signer_key) {
+            return self.approve(signer_key);
+        }
+
+        require!(
+            self.status == StakingProposalStatus::Pending,
+            StakingErrorCode::AlreadyResolved
+        );
+        self.status = StakingProposalStatus::Approved;
+
+        Ok(())
+    }
+}
+
+/// Version 4 appended the rest of the audit metadata next to `created_at`, which proposals migrated from version 3
+/// leave zeroed until their next write.
+impl VersionedAccount for StakingProposalAccount {
+    const VERSION: u8 = 4;
+    const SPACE: usize = 8 + StakingProposalAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.sync_signed_mask();
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+impl Audited for StakingProposalAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
+}
+
+#[derive(Accounts)]
+pub struct StakingCreateProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_PROPOSAL_IDENTIFIER_SEED],
+        bump = staking_proposal_identifier.bump
+    )]
+    pub staking_proposal_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingProposalAccount::len_for(current_multisig.signers.len()),
+        seeds = [
+            STAKING_PROPOSAL_SEED, 
+            staking_proposal_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub staking_proposal: Account<'info, StakingProposalAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingSlashDisputeAccount {
+    pub proposal_id: u64,
+    pub staker: Pubkey,
+    pub counter_claim_hash: [u8; 32],
+    pub disputed_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VersionedAccount for StakingSlashDisputeAccount {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + StakingSlashDisputeAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+#[derive(Accounts)]
+pub struct StakingDisputeSlash<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingSlashDisputeAccount::INIT_SPACE,
+        seeds = [
+            STAKING_SLASH_DISPUTE_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_slash_dispute: Account<'info, StakingSlashDisputeAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingSignProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingConfigureMint<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingMintConfigAccount::INIT_SPACE,
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Account<'info, StakingMintConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(staker: Pubkey)]
+pub struct StakingSlash<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Box<Account<'info, MultisigAccount>>,
+
+    #[account(address = staking_registry.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = staking_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is the PDA authority for the insurance fund, no need to deserialize
+    #[account(
+        seeds = [INSURANCE_FUND_SEED],
+        bump
+    )]
+    pub insurance_fund: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token,
+        associated_token::authority = insurance_fund,
+        associated_token::token_program = token_program,
+    )]
+    pub insurance_fund_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED,
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ledger: Account<'info, VaultLedgerAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(staker: Pubkey)]
+pub struct StakingEmergencyThaw<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(address = staking_registry.mint @ StakingErrorCode::MintMismatch)]
+    pub token: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            STAKING_MINT_CONFIG_SEED,
+            token.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_mint_config: Option<Account<'info, StakingMintConfigAccount>>,
+
+    /// CHECK: This is the PDA authority for the staking vaults, no need to deserialize
+    #[account(
+        seeds = [STAKING_AUTHORITY_SEED],
+        bump
+    )]
+    pub staking_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = staking_registry.token_account @ StakingErrorCode::TokenAccountMismatch,
+        associated_token::mint = token,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program,
+    )]
+    pub staker_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_USER_SEED,
+            staker.as_ref(),
+        ],
+        bump = staking_user.bump
+    )]
+    pub staking_user: Account<'info, StakingUserAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            staker.as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump = staking_registry.bump
+    )]
+    pub staking_registry: Account<'info, StakingRegistryAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_ACTIVITY_SEED],
+        bump
+    )]
+    pub staking_activity: AccountLoader<'info, StakingActivityAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingUpdateConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingConfigureAttestor<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingSetPaused<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingConfigurePairStaking<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingSetRewardSchedule<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingRewardScheduleAccount::INIT_SPACE,
+        seeds = [STAKING_REWARD_SCHEDULE_SEED],
+        bump
+    )]
+    pub staking_reward_schedule: Account<'info, StakingRewardScheduleAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingConfigureValidator<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: The validator's vote account, only used as the seed of its allowlist entry
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingValidatorAccount::INIT_SPACE,
+        seeds = [
+            STAKING_VALIDATOR_SEED,
+            vote_account.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_validator: Account<'info, StakingValidatorAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingConfigureCustodian<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: The custodian's wallet, only used as the seed of its allowlist entry
+    pub custodian: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StakingCustodianAccount::INIT_SPACE,
+        seeds = [
+            STAKING_CUSTODIAN_SEED,
+            custodian.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_custodian: Account<'info, StakingCustodianAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingDelegateVaultStake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: The validator's vote account, validated by the stake program when delegating
+    pub vote_account: AccountInfo<'info>,
+
+    #[account(
+        seeds = [
+            STAKING_VALIDATOR_SEED,
+            vote_account.key().as_ref(),
+        ],
+        bump = staking_validator.bump,
+        constraint = staking_validator.allowed @ StakingErrorCode::ValidatorNotAllowed
+    )]
+    pub staking_validator: Account<'info, StakingValidatorAccount>,
+
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The stake account created and initialized by this instruction
+    #[account(
+        mut,
+        seeds = [
+            VAULT_STAKE_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub stake_account: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingVaultDelegationAccount::INIT_SPACE,
+        seeds = [
+            VAULT_DELEGATION_SEED,
+            stake_account.key().as_ref(),
+        ],
+        bump
+    )]
+    pub vault_delegation: Account<'info, StakingVaultDelegationAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: This is the stake history sysvar, required by the stake program
+    #[account(address = stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+
+    /// CHECK: This is the legacy stake config account, required by the stake program
+    #[account(address = STAKE_CONFIG_ID)]
+    pub stake_config: AccountInfo<'info>,
+
+    /// CHECK: This is the native stake program
+    #[account(address = stake_program::ID)]
+    pub stake_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingDeactivateVaultStake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The delegated stake account, matched against its delegation record
+    #[account(mut)]
+    pub stake_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_DELEGATION_SEED,
+            stake_account.key().as_ref(),
+        ],
+        bump = vault_delegation.bump
+    )]
+    pub vault_delegation: Account<'info, StakingVaultDelegationAccount>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: This is the native stake program
+    #[account(address = stake_program::ID)]
+    pub stake_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingWithdrawVaultStake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The deactivated stake account, matched against its delegation record
+    #[account(mut)]
+    pub stake_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_DELEGATION_SEED,
+            stake_account.key().as_ref(),
+        ],
+        bump = vault_delegation.bump
+    )]
+    pub vault_delegation: Account<'info, StakingVaultDelegationAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.load()?.bump
+    )]
+    pub staking_pool: AccountLoader<'info, StakingPoolAccount>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: This is the stake history sysvar, required by the stake program
+    #[account(address = stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+
+    /// CHECK: This is the native stake program
+    #[account(address = stake_program::ID)]
+    pub stake_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingDistributeSolRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    #[account(
+        seeds = [STAKING_SNAPSHOT_SEED, staking_snapshot.epoch.to_le_bytes().as_ref()],
+        bump = staking_snapshot.bump
+    )]
+    pub staking_snapshot: Account<'info, StakingEpochSnapshotAccount>,
+
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakingSolDistributionAccount::INIT_SPACE,
+        seeds = [STAKING_SOL_DISTRIBUTION_SEED, staking_snapshot.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub staking_sol_distribution: Account<'info, StakingSolDistributionAccount>,
+
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED,
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ledger: Account<'info, VaultLedgerAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingCloseProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            STAKING_PROPOSAL_SEED,
+            current_staking_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_staking_proposal.bump
+    )]
+    pub current_staking_proposal: Account<'info, StakingProposalAccount>,
+
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingCloseValidator<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            STAKING_VALIDATOR_SEED,
+            staking_validator.vote_account.as_ref(),
+        ],
+        bump = staking_validator.bump
+    )]
+    pub staking_validator: Account<'info, StakingValidatorAccount>,
+
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct StakingCloseCustodian<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            STAKING_CUSTODIAN_SEED,
+            staking_custodian.custodian.as_ref(),
+        ],
+        bump = staking_custodian.bump
+    )]
+    pub staking_custodian: Account<'info, StakingCustodianAccount>,
+
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+pub struct StakingProposalInstructions;
+
+impl StakingProposalInstructions {
+    /// Creates a new staking governance proposal.
+    ///
+    /// This function performs the following actions:
+    /// - Validates the action's parameters, such as the slashing reason length and evidence hash or configuration bounds.
+    /// - Collects the current multisig's signers as the proposal's required signers.
+    /// - Initializes a new proposal with the given `action`, its creation time, and status set to `Pending`.
+    /// - Emits a `ConfigChangeProposedEvent` with its activation time when the action changes the program or staking
+    ///   configuration, so monitoring can alert on the pending change.
+    /// - Increments the staking proposal identifier.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required to create a staking proposal.
+    /// * `action` - The staking action the proposal authorizes once approved.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is created successfully, otherwise returns an error.
+    pub fn create_proposal(ctx: Context<StakingCreateProposal>, action: StakingProposalAction) -> Result<()> {
+        action.validate()?;
+
+        let staking_proposal_identifier = &mut ctx.accounts.staking_proposal_identifier;
+
+        let current_multisig = &ctx.accounts.current_multisig;
+        let multisig_required_signers: Vec<Pubkey> = current_multisig.signers.iter().map(|d| d.pubkey).collect();
+
+        let staking_proposal = &mut ctx.accounts.staking_proposal;
+        staking_proposal.id = staking_proposal_identifier.next_id()?;
+        staking_proposal.action = action;
+        staking_proposal.multisig_required_signers = multisig_required_signers;
+        staking_proposal.multisig_signers = Vec::new();
+        staking_proposal.signed_mask = 0;
+        staking_proposal.status = StakingProposalStatus::Pending;
+        staking_proposal.disputed_at = 0;
+        staking_proposal.version = StakingProposalAccount::VERSION;
+        staking_proposal.bump = ctx.bumps.staking_proposal;
+        staking_proposal.record_creation(ctx.accounts.signer.key())?;
+
+        if staking_proposal.action.is_config_change() {
+            emit!(ConfigChangeProposedEvent {
+                proposal_id: staking_proposal.id,
+                action: staking_proposal.action.clone(),
+                activates_at: staking_proposal.activates_at(),
+                timestamp: staking_proposal.created_at,
+            });
+        }
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_create_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Signs a staking governance proposal.
+    ///
+    /// This function performs the following actions:
+    /// - Checks and advances the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
+    /// - Verifies the proposal is still in the `Pending` status.
+    /// - Ensures the signer is among the required multisig signers (if any are specified).
+    /// - Prevents duplicate signatures from the same signer, using the proposal's signature bitmap when it has required signers.
+    /// - Grows the proposal, at the signer's expense, when it has no room left for another signature.
+    /// - Sets the signer's bit in the bitmap and appends their public key to the list of actual signers for the proposal.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required to sign the staking proposal.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is signed successfully, otherwise returns an error.
+    pub fn sign_proposal(ctx: Context<StakingSignProposal>, expected_nonce: Option<u64>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        require!(
+            current_staking_proposal.status == StakingProposalStatus::Pending,
+            StakingErrorCode::AlreadyResolved
+        );
+
+        current_staking_proposal.sync_signed_mask();
+
+        if current_staking_proposal.multisig_required_signers.is_empty() {
+            require!(
+                !current_staking_proposal.multisig_signers.contains(&signer_key),
+                StakingErrorCode::DuplicateSignature
+            );
+        } else {
+            let signer_bit = signer_bit(&current_staking_proposal.multisig_required_signers, &signer_key)
+                .ok_or_else(|| with_context(StakingErrorCode::UnauthorizedSigner, signer_key, 0, 0))?;
+            require!(
+                current_staking_proposal.signed_mask & signer_bit == 0,
+                StakingErrorCode::DuplicateSignature
+            );
+            current_staking_proposal.signed_mask |= signer_bit;
+        }
+
+        let signer_count = current_staking_proposal
+            .multisig_required_signers
+            .len()
+            .max(current_staking_proposal.multisig_signers.len() + 1)
+            .min(MAX_SIGNERS);
+        grow_account(
+            &current_staking_proposal.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + StakingProposalAccount::len_for(signer_count),
+        )?;
+
+        current_staking_proposal.multisig_signers.push(signer_key);
+        current_staking_proposal.record_update(signer_key)?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_sign_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Disputes a pending `Slash` proposal with the affected staker's counter-claim.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal is a pending `Slash` of the signing staker and that its dispute window is still open.
+    /// - Records the hash of the staker's counter-claim in a dispute account seeded by the proposal, so a slash can be
+    ///   disputed only once.
+    /// - Escalates the approval the slash needs from the multisig's threshold to every required signer: the signatures
+    ///   collected so far are discarded, so the whole council must sign again after reviewing the counter-claim, and the
+    ///   dispute window restarts from the dispute.
+    /// - Emits a `SlashDisputedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the staker, the slash proposal, and the dispute account to create.
+    /// * `counter_claim_hash` - The hash of the staker's counter-claim, published off-chain.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the slash is disputed successfully, otherwise returns an error.
+    pub fn dispute_slash(ctx: Context<StakingDisputeSlash>, counter_claim_hash: [u8; 32]) -> Result<()> {
+        let clock = Clock::get()?;
+        let staker = ctx.accounts.staker.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::Slash { staker: slashed_staker, .. } = current_staking_proposal.action else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require_with_context!(slashed_staker == staker, StakingErrorCode::UnauthorizedSigner, staker, 0, 0);
+        require!(
+            current_staking_proposal.status == StakingProposalStatus::Pending,
+            StakingErrorCode::AlreadyResolved
+        );
+        require!(
+            clock.unix_timestamp < current_staking_proposal.dispute_ends_at(),
+            StakingErrorCode::SlashDisputeWindowClosed
+        );
+        require!(
+            counter_claim_hash != [0u8; 32],
+            StakingErrorCode::MissingSlashEvidence
+        );
+
+        current_staking_proposal.multisig_signers.clear();
+        current_staking_proposal.signed_mask = 0;
+        current_staking_proposal.disputed_at = clock.unix_timestamp;
+        current_staking_proposal.record_update(staker)?;
+
+        ctx.accounts.staking_slash_dispute.set_inner(StakingSlashDisputeAccount {
+            proposal_id: current_staking_proposal.id,
+            staker,
+            counter_claim_hash,
+            disputed_at: clock.unix_timestamp,
+            version: StakingSlashDisputeAccount::VERSION,
+            bump: ctx.bumps.staking_slash_dispute,
+        });
+
+        emit!(SlashDisputedEvent {
+            proposal_id: current_staking_proposal.id,
+            staker,
+            counter_claim_hash,
+            dispute_ends_at: current_staking_proposal.dispute_ends_at(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_dispute_slash", ctx.accounts.staker.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureMint` proposal, setting the staking mode of a mint.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes configuring this mint.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Records whether the mint is staked in custodial mode, where the staking authority PDA holds the freeze authority.
+    /// - Records whether the mint is on the staking allowlist.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the mint, and its staking mint configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the mint is configured successfully, otherwise returns an error.
+    pub fn configure_mint(ctx: Context<StakingConfigureMint>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureMint { mint, custodial, allowed } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            mint == ctx.accounts.token.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_mint_config = &mut ctx.accounts.staking_mint_config;
+        staking_mint_config.mint = mint;
+        staking_mint_config.custodial = custodial;
+        staking_mint_config.allowed = allowed;
+        staking_mint_config.version = StakingMintConfigAccount::VERSION;
+        staking_mint_config.bump = ctx.bumps.staking_mint_config;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_configure_mint", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `Slash` proposal, seizing part of a staker's escrow stake into the insurance fund.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes slashing this position of this staker by this amount, with this evidence, for this reason.
+    /// - Checks that the proposal's dispute window has closed, so the staker had `SLASH_DISPUTE_WINDOW` to counter-claim.
+    /// - Approves the proposal once the multisig's threshold of required signers has signed it, or, if the staker disputed
+    ///   the slash, once every required signer has signed it since the dispute.
+    /// - Checks that the staker holds an escrow stake of at least `amount`.
+    /// - Accrues the rewards earned so far, then transfers the slashed tokens from the stake vault to the insurance fund,
+    ///   recording them as an `InsuranceDeposit` vault ledger entry.
+    /// - Reduces the staked amount, recalculates the multiplier, and records the slashed amount in the registry.
+    /// - Updates the staking pool statistics and emits a `StakeSlashedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the registry of the slashed position, the stake vault, and the insurance fund.
+    /// * `staker` - The public key of the staker being slashed.
+    /// * `amount` - The amount of staked tokens to seize.
+    /// * `evidence_hash` - The hash of the slashing evidence, which must match the proposal.
+    /// * `reason` - The reason for the slash, which must match the proposal.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the stake is slashed successfully, otherwise returns an error.
+    pub fn slash(
+        ctx: Context<StakingSlash>,
+        staker: Pubkey,
+        amount: u64,
+        evidence_hash: [u8; 32],
+        reason: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        require!(
+            current_staking_proposal.action == StakingProposalAction::Slash {
+                staker,
+                position_index: ctx.accounts.staking_registry.position_index,
+                amount,
+                evidence_hash,
+                reason: reason.clone(),
+            },
+            StakingErrorCode::InvalidProposalAction
+        );
+        require!(
+            clock.unix_timestamp >= current_staking_proposal.dispute_ends_at(),
+            StakingErrorCode::SlashDisputeWindowOpen
+        );
+
+        let approvals_required = current_staking_proposal.slash_approvals_required(ctx.accounts.current_multisig.threshold);
+        current_staking_proposal.approve_with(&signer_key, approvals_required)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        require!(amount > 0, StakingErrorCode::InvalidAmount);
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(
+            staking_registry.mode == StakingMode::Escrow,
+            StakingErrorCode::StakingModeMismatch
+        );
+        require_with_context!(
+            amount <= staking_registry.staked_amount,
+            StakingErrorCode::InsufficientStakedAmount,
+            staking_registry.key(),
+            amount,
+            staking_registry.staked_amount
+        );
+
+        let remaining_amount = staking_registry.staked_amount
+            .checked_sub(amount)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+
+        let staking_config = &ctx.accounts.staking_config;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_previous = staking_registry.pooled_amount(
+            &ctx.accounts.staking_config,
+            staking_registry.staked_amount,
+        );
+        let pooled_current = staking_registry.pooled_amount(&ctx.accounts.staking_config, remaining_amount);
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_previous,
+            pooled_current,
+            clock.epoch,
+        );
+
+        staking_registry.accrue_rewards(staking_config.reward_rate_bps, clock.unix_timestamp);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    to: ctx.accounts.insurance_fund_token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.token.decimals,
+        )?;
+
+        ctx.accounts.ledger.record(
+            &mut ctx.accounts.ledger_identifier,
+            staker,
+            VaultTransaction::InsuranceDeposit {
+                from: staker,
+                mint: ctx.accounts.token.key(),
+                amount,
+            },
+            amount,
+            0,
+            ctx.bumps.ledger,
+        )?;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        staking_registry.staked_amount = remaining_amount;
+        staking_registry.slashed_amount = staking_registry.slashed_amount.saturating_add(amount);
+        staking_registry.refresh_multiplier(staking_config, clock.unix_timestamp);
+        staking_registry.record_update(ctx.accounts.signer.key())?;
+
+        emit!(StakeSlashedEvent {
+            proposal_id: ctx.accounts.current_staking_proposal.id,
+            staker,
+            mint: ctx.accounts.token.key(),
+            amount,
+            evidence_hash,
+            reason,
+            timestamp: clock.unix_timestamp,
+        });
+
+        ctx.accounts.staking_user.record_ledger(
+            staker,
+            staking_registry,
+            StakingLedgerAction::Slash { amount },
+            clock.unix_timestamp,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_slash", ctx.accounts.signer.key())?;
+        crate::log_warn!(
+            "staking_slash",
+            proposal_id = ctx.accounts.current_staking_proposal.id,
+            staker = staker,
+            amount = amount
+        );
+
+        Ok(())
+    }
+
+    /// Executes an approved `EmergencyThaw` proposal, releasing a staker's freeze stake without their cooperation.
+    ///
+    /// This is the recovery path for stakers left stuck when the backend issuing reference numbers is unavailable
+    /// or a mint's freeze authority flow breaks.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes an emergency thaw of this position of this staker.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that the position holds an active freeze stake; the lock is not enforced.
+    /// - For custodial mints, thaws the staker's token account, signing with the staking authority PDA. For other mints the
+    ///   staker holds the freeze authority and can thaw the account directly once the registry is released.
+    /// - Accrues the rewards earned so far, which remain claimable.
+    /// - Clears the stake from the registry, records when the emergency thaw happened, and updates the staking pool statistics.
+    /// - Emits a `StakeThawedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the staker's token account, and the registry of the stake position.
+    /// * `staker` - The public key of the staker whose stake is released.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the stake is released successfully, otherwise returns an error.
+    pub fn emergency_thaw(ctx: Context<StakingEmergencyThaw>, staker: Pubkey) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        require!(
+            current_staking_proposal.action == StakingProposalAction::EmergencyThaw {
+                staker,
+                position_index: ctx.accounts.staking_registry.position_index,
+            },
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_registry = &ctx.accounts.staking_registry;
+        require!(staking_registry.staked_amount > 0, StakingErrorCode::NoActiveStake);
+        require!(
+            staking_registry.mode == StakingMode::Freeze,
+            StakingErrorCode::StakingModeMismatch
+        );
+
+        let custodial = ctx.accounts.staking_mint_config.as_ref().is_some_and(|config| config.custodial);
+        if custodial && ctx.accounts.staker_token.is_frozen() {
+            require!(
+                ctx.accounts.token.freeze_authority == COption::Some(ctx.accounts.staking_authority.key()),
+                StakingErrorCode::InvalidFreezeAuthority
+            );
+
+            let signer_seeds: &[&[&[u8]]] = &[&[STAKING_AUTHORITY_SEED, &[ctx.bumps.staking_authority]]];
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: ctx.accounts.staker_token.to_account_info(),
+                    mint: ctx.accounts.token.to_account_info(),
+                    authority: ctx.accounts.staking_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let clock = Clock::get()?;
+        let reward_rate_bps = ctx.accounts.staking_config.reward_rate_bps;
+
+        let staking_registry = &mut ctx.accounts.staking_registry;
+        let pooled_amount = staking_registry.pooled_amount(
+            &ctx.accounts.staking_config,
+            staking_registry.staked_amount,
+        );
+        ctx.accounts.staking_pool.load_mut()?.record_stake_change(
+            &mut ctx.accounts.staking_user,
+            &mut *ctx.accounts.staking_activity.load_mut()?,
+            &ctx.accounts.staking_config,
+            pooled_amount,
+            0,
+            clock.epoch,
+        );
+
+        let thawed_amount = staking_registry.staked_amount;
+        emit!(StakeThawedEvent {
+            staker,
+            mint: staking_registry.mint,
+            position_index: staking_registry.position_index,
+            amount: thawed_amount,
+            reference_id: staking_registry.reference_id.clone(),
+            early_exit: false,
+            timestamp: clock.unix_timestamp,
+        });
+
+        staking_registry.accrue_rewards(reward_rate_bps, clock.unix_timestamp);
+        staking_registry.reference_id = String::from("");
+        staking_registry.staked_amount = 0;
+        staking_registry.unlocks_at = 0;
+        staking_registry.refresh_multiplier(&ctx.accounts.staking_config, clock.unix_timestamp);
+        staking_registry.emergency_thawed_at = clock.unix_timestamp;
+        staking_registry.clear_unstake_request();
+        staking_registry.record_update(ctx.accounts.signer.key())?;
+
+        ctx.accounts.staking_user.record_ledger(
+            staker,
+            staking_registry,
+            StakingLedgerAction::Thaw { amount: thawed_amount, early_exit: false },
+            clock.unix_timestamp,
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_emergency_thaw", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `UpdateConfig` proposal, changing the staking configuration.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds an `UpdateConfig` action and that its values are within bounds.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Writes the new minimum staking amount and its comparison mode, unstake cooldown, mint allowlist switch,
+    ///   staked-supply cap, and pause grace period to the staking configuration.
+    /// - Emits a `StakingConfigUpdatedEvent` with the previous and new values.
+    ///
+    /// Existing stakes are not re-checked against the new minimum or cap, and pending unstake requests keep their cooldown end;
+    /// the new values apply to subsequent stakes, unstakes, and requests. A new grace period applies to an ongoing pause too.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the staking configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the configuration is updated successfully, otherwise returns an error.
+    pub fn update_config(ctx: Context<StakingUpdateConfig>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::UpdateConfig {
+            min_staking_amount,
+            unstake_cooldown,
+            mint_allowlist_enabled,
+            min_inclusive,
+            max_total_staked,
+            pause_grace_period,
+        } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        current_staking_proposal.action.validate()?;
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.record_update(signer_key)?;
+        let previous_min_staking_amount = staking_config.min_staking_amount;
+        let previous_unstake_cooldown = staking_config.unstake_cooldown;
+        let previous_mint_allowlist_enabled = staking_config.mint_allowlist_enabled;
+        let previous_min_inclusive = staking_config.min_inclusive;
+        let previous_max_total_staked = staking_config.max_total_staked;
+        let previous_pause_grace_period = staking_config.pause_grace_period;
+        staking_config.min_staking_amount = min_staking_amount;
+        staking_config.unstake_cooldown = unstake_cooldown;
+        staking_config.mint_allowlist_enabled = mint_allowlist_enabled;
+        staking_config.min_inclusive = min_inclusive;
+        staking_config.max_total_staked = max_total_staked;
+        staking_config.pause_grace_period = pause_grace_period;
+
+        emit!(StakingConfigUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_min_staking_amount,
+            min_staking_amount,
+            previous_unstake_cooldown,
+            unstake_cooldown,
+            previous_mint_allowlist_enabled,
+            mint_allowlist_enabled,
+            previous_min_inclusive,
+            min_inclusive,
+            previous_max_total_staked,
+            max_total_staked,
+            previous_pause_grace_period,
+            pause_grace_period,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_update_config", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureAttestor` proposal, setting the key whose attestation thaws require.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigureAttestor` action.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Writes the attestor to the staking configuration; the default public key turns attestations off.
+    /// - Emits a `StakingAttestorUpdatedEvent` with the previous and new attestor.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the staking configuration.
     ///
     /// ## Returns
     ///
-    /// Returns `Ok(())` if the thaw operation is successful, otherwise returns an error.
-    pub fn thaw(ctx: Context<StakingThaw>) -> Result<()> {
-        thaw_account(CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            ThawAccount {
-                account: ctx.accounts.staker_token.to_account_info(),
-                mint: ctx.accounts.token.to_account_info(),
-                authority: ctx.accounts.staker.to_account_info(),
-            },
-        ))?;
+    /// Returns `Ok(())` if the attestor is configured successfully, otherwise returns an error.
+    pub fn configure_attestor(ctx: Context<StakingConfigureAttestor>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
 
-        let staking_registry = &mut ctx.accounts.staking_registry;
-        staking_registry.reference_id = String::from("");
+        let StakingProposalAction::ConfigureAttestor { attestor } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.record_update(signer_key)?;
+        let previous_attestor = staking_config.attestor;
+        staking_config.attestor = attestor;
+
+        emit!(StakingAttestorUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_attestor,
+            attestor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_configure_attestor", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `SetPaused` proposal, pausing or resuming new stakes.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `SetPaused` action.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Writes the pause switch to the staking configuration. While paused, freezes and escrow stakes are rejected, but
+    ///   thaws, unstakes, and reward claims keep working so stakers can always exit.
+    /// - Records when the pause started, keeping the original start if staking is already paused, and clears it on resume.
+    ///   Once the pause outlasts the grace period, stakes can be released without attestations, lockups, cooldowns, or penalties.
+    /// - Emits a `StakingPauseUpdatedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the staking configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the pause switch is updated successfully, otherwise returns an error.
+    pub fn set_paused(ctx: Context<StakingSetPaused>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::SetPaused { paused } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let clock = Clock::get()?;
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.record_update(signer_key)?;
+        if !paused {
+            staking_config.paused_at = 0;
+        } else if !staking_config.staking_paused {
+            staking_config.paused_at = clock.unix_timestamp;
+        }
+        staking_config.staking_paused = paused;
+
+        emit!(StakingPauseUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            paused,
+            paused_at: staking_config.paused_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_set_paused", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigurePairStaking` proposal, changing the SOL ratio and multiplier boost of pair stakes.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `ConfigurePairStaking` action.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Writes the SOL ratio and boost to the staking configuration. A zero ratio disables new pair stakes; existing pairs keep
+    ///   the SOL they locked, and their multiplier picks up the new boost the next time they are updated.
+    /// - Emits a `StakingPairConfigUpdatedEvent` with the previous and new values.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal and the staking configuration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if pair staking is configured successfully, otherwise returns an error.
+    pub fn configure_pair_staking(ctx: Context<StakingConfigurePairStaking>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigurePairStaking { sol_ratio_bps, boost_bps } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.record_update(signer_key)?;
+        let previous_sol_ratio_bps = staking_config.pair_sol_ratio_bps;
+        let previous_boost_bps = staking_config.pair_boost_bps;
+        staking_config.pair_sol_ratio_bps = sol_ratio_bps;
+        staking_config.pair_boost_bps = boost_bps;
+
+        emit!(StakingPairConfigUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            previous_sol_ratio_bps,
+            sol_ratio_bps,
+            previous_boost_bps,
+            boost_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_configure_pair_staking", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `SetRewardSchedule` proposal, replacing the reward schedule that sets the base reward rate per epoch range.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal holds a `SetRewardSchedule` action and that its steps still form a valid decay ladder.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that `CONFIG_ACTIVATION_DELAY` has passed since the change was proposed.
+    /// - Writes the steps to the reward schedule account, creating it on first use, and records the proposal that set them.
+    /// - Applies the step in force for the current epoch to the staking configuration right away, if one has started.
+    /// - Emits a `StakingRewardScheduleUpdatedEvent` with the new steps and the resulting reward rate.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the staking configuration, and the reward schedule.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the reward schedule is set successfully, otherwise returns an error.
+    pub fn set_reward_schedule(ctx: Context<StakingSetRewardSchedule>) -> Result<()> {
+        let clock = Clock::get()?;
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::SetRewardSchedule { steps } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        validate_reward_schedule(&steps)?;
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.check_activation_delay_elapsed(Clock::get()?.unix_timestamp)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_reward_schedule = &mut ctx.accounts.staking_reward_schedule;
+        staking_reward_schedule.proposal_id = current_staking_proposal.id;
+        staking_reward_schedule.steps = steps.clone();
+        staking_reward_schedule.updated_at = clock.unix_timestamp;
+        staking_reward_schedule.version = StakingRewardScheduleAccount::VERSION;
+        staking_reward_schedule.bump = ctx.bumps.staking_reward_schedule;
+
+        let staking_config = &mut ctx.accounts.staking_config;
+        staking_config.record_update(signer_key)?;
+        if let Some(reward_rate_bps) = staking_reward_schedule.rate_at(clock.epoch) {
+            staking_config.reward_rate_bps = reward_rate_bps;
+        }
+
+        emit!(StakingRewardScheduleUpdatedEvent {
+            proposal_id: current_staking_proposal.id,
+            steps,
+            reward_rate_bps: staking_config.reward_rate_bps,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_set_reward_schedule", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureValidator` proposal, adding a validator to or removing it from the vault delegation allowlist.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes configuring this vote account.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Records whether vault SOL may be delegated to the validator.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the vote account, and its allowlist entry.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the validator is configured successfully, otherwise returns an error.
+    pub fn configure_validator(ctx: Context<StakingConfigureValidator>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureValidator { vote_account, allowed } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            vote_account == ctx.accounts.vote_account.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_validator = &mut ctx.accounts.staking_validator;
+        staking_validator.vote_account = vote_account;
+        staking_validator.allowed = allowed;
+        staking_validator.version = StakingValidatorAccount::VERSION;
+        staking_validator.bump = ctx.bumps.staking_validator;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_configure_validator", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `ConfigureCustodian` proposal, allowing a custodian to stake on behalf of other wallets or revoking it.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes configuring this custodian.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Records whether the custodian may call `stake_for`. Revoking a custodian does not affect the stakes it already made,
+    ///   which belong to their beneficiaries.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the custodian, and its allowlist entry.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the custodian is configured successfully, otherwise returns an error.
+    pub fn configure_custodian(ctx: Context<StakingConfigureCustodian>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::ConfigureCustodian { custodian, allowed } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            custodian == ctx.accounts.custodian.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let staking_custodian = &mut ctx.accounts.staking_custodian;
+        staking_custodian.custodian = custodian;
+        staking_custodian.allowed = allowed;
+        staking_custodian.version = StakingCustodianAccount::VERSION;
+        staking_custodian.bump = ctx.bumps.staking_custodian;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_configure_custodian", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `DelegateVaultStake` proposal, delegating vault SOL to an allowlisted validator.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes delegating to this vote account, which must be on the allowlist.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that the vault holds the amount to delegate.
+    /// - Creates a stake account PDA funded from the vault, with the vault as its staker and withdrawer, and delegates it to the validator.
+    /// - Records the delegation, adds the amount to the staking pool's delegated total, and emits a `VaultStakeDelegatedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the validator, the vault, the new stake account, and the stake program accounts.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the stake is delegated successfully, otherwise returns an error.
+    pub fn delegate_vault_stake(ctx: Context<StakingDelegateVaultStake>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::DelegateVaultStake { vote_account, amount } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            vote_account == ctx.accounts.vote_account.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let vault = &ctx.accounts.vault;
+        let stake_account = &ctx.accounts.stake_account;
+        require_with_context!(
+            vault.lamports() >= amount,
+            StakingErrorCode::InsufficientVaultBalance,
+            vault.key(),
+            amount,
+            vault.lamports()
+        );
+
+        let proposal_id_bytes = current_staking_proposal.id.to_le_bytes();
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[ctx.bumps.vault]];
+        let stake_account_seeds: &[&[u8]] = &[VAULT_STAKE_SEED, proposal_id_bytes.as_ref(), &[ctx.bumps.stake_account]];
+
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: vault.to_account_info(),
+                    to: stake_account.to_account_info(),
+                },
+                &[vault_seeds, stake_account_seeds],
+            ),
+            amount,
+            StakeStateV2::size_of() as u64,
+            &stake_program::ID,
+        )?;
+
+        let authorized = Authorized {
+            staker: vault.key(),
+            withdrawer: vault.key(),
+        };
+        invoke(
+            &stake_instruction::initialize(&stake_account.key(), &authorized, &Lockup::default()),
+            &[stake_account.to_account_info(), ctx.accounts.rent.to_account_info()],
+        )?;
+
+        invoke_signed(
+            &stake_instruction::delegate_stake(&stake_account.key(), &vault.key(), &vote_account),
+            &[
+                stake_account.to_account_info(),
+                ctx.accounts.vote_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+                ctx.accounts.stake_config.to_account_info(),
+                vault.to_account_info(),
+            ],
+            &[vault_seeds],
+        )?;
+
+        let timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts.vault_delegation.set_inner(StakingVaultDelegationAccount {
+            proposal_id: current_staking_proposal.id,
+            stake_account: stake_account.key(),
+            vote_account,
+            amount,
+            status: StakingVaultDelegationStatus::Active,
+            delegated_at: timestamp,
+            deactivated_at: 0,
+            version: StakingVaultDelegationAccount::VERSION,
+            bump: ctx.bumps.vault_delegation,
+        });
+
+        let mut staking_pool = ctx.accounts.staking_pool.load_mut()?;
+        staking_pool.vault_delegated = staking_pool.vault_delegated.saturating_add(amount);
+
+        emit!(VaultStakeDelegatedEvent {
+            proposal_id: current_staking_proposal.id,
+            stake_account: stake_account.key(),
+            vote_account,
+            amount,
+            timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_delegate_vault_stake", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `DeactivateVaultStake` proposal, starting the cooldown of a vault stake account.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes deactivating this stake account and that its delegation is active.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Deactivates the stake account, signed by the vault as its staker.
+    /// - Marks the delegation as deactivating and emits a `VaultStakeDeactivatedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the vault, the stake account, and its delegation record.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the stake is deactivated successfully, otherwise returns an error.
+    pub fn deactivate_vault_stake(ctx: Context<StakingDeactivateVaultStake>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::DeactivateVaultStake { stake_account } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            stake_account == ctx.accounts.stake_account.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+        require!(
+            ctx.accounts.vault_delegation.status == StakingVaultDelegationStatus::Active,
+            StakingErrorCode::InvalidDelegationStatus
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let vault = &ctx.accounts.vault;
+        invoke_signed(
+            &stake_instruction::deactivate_stake(&stake_account, &vault.key()),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                vault.to_account_info(),
+            ],
+            &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+        )?;
+
+        let timestamp = ctx.accounts.clock.unix_timestamp;
+        let vault_delegation = &mut ctx.accounts.vault_delegation;
+        vault_delegation.status = StakingVaultDelegationStatus::Deactivating;
+        vault_delegation.deactivated_at = timestamp;
+
+        emit!(VaultStakeDeactivatedEvent {
+            proposal_id: current_staking_proposal.id,
+            stake_account,
+            vote_account: vault_delegation.vote_account,
+            timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_deactivate_vault_stake", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `WithdrawVaultStake` proposal, returning a deactivated stake account's SOL to the vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal authorizes withdrawing this stake account and that its delegation is deactivating.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Withdraws the stake account's whole balance to the vault, signed by the vault as its withdrawer; the stake program
+    ///   rejects the withdrawal until the cooldown has completed.
+    /// - Marks the delegation as withdrawn, removes it from the staking pool's delegated total, and records the
+    ///   balance above the delegated amount as validator rewards.
+    /// - Emits a `VaultStakeWithdrawnEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the vault, the stake account, its delegation record, and the staking pool.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the stake is withdrawn successfully, otherwise returns an error.
+    pub fn withdraw_vault_stake(ctx: Context<StakingWithdrawVaultStake>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::WithdrawVaultStake { stake_account } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        require!(
+            stake_account == ctx.accounts.stake_account.key(),
+            StakingErrorCode::InvalidProposalAction
+        );
+        require!(
+            ctx.accounts.vault_delegation.status == StakingVaultDelegationStatus::Deactivating,
+            StakingErrorCode::InvalidDelegationStatus
+        );
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let vault = &ctx.accounts.vault;
+        let withdrawn_amount = ctx.accounts.stake_account.lamports();
+        invoke_signed(
+            &stake_instruction::withdraw(&stake_account, &vault.key(), &vault.key(), withdrawn_amount, None),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                vault.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+            ],
+            &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+        )?;
+
+        let vault_delegation = &mut ctx.accounts.vault_delegation;
+        let rewards = withdrawn_amount.saturating_sub(vault_delegation.amount);
+        vault_delegation.status = StakingVaultDelegationStatus::Withdrawn;
+
+        let mut staking_pool = ctx.accounts.staking_pool.load_mut()?;
+        staking_pool.vault_delegated = staking_pool.vault_delegated.saturating_sub(vault_delegation.amount);
+        staking_pool.vault_stake_rewards = staking_pool.vault_stake_rewards.saturating_add(rewards);
+
+        emit!(VaultStakeWithdrawnEvent {
+            proposal_id: current_staking_proposal.id,
+            stake_account,
+            vote_account: vault_delegation.vote_account,
+            amount: withdrawn_amount,
+            rewards,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_withdraw_vault_stake", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Executes an approved `DistributeSolRewards` proposal, funding an epoch's SOL reward budget from the vault.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that the proposal distributes rewards for the epoch of the snapshot passed in, and that the snapshot recorded stake.
+    /// - Approves the proposal if all required multisig signers have signed it.
+    /// - Checks that the vault holds the amount, then transfers it into the epoch's distribution account, signed by the vault PDA.
+    /// - Records the epoch, amount, and the snapshot's total staked in the distribution account, which stakers claim from.
+    /// - Records the outflow as a `RewardDistribution` vault ledger entry and emits a `SolRewardsDistributedEvent`.
+    ///
+    /// Each epoch can be funded only once, since its distribution account is created by this instruction.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the proposal, the epoch snapshot, the vault, the distribution account, and the vault ledger.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the rewards are distributed successfully, otherwise returns an error.
+    pub fn distribute_sol_rewards(ctx: Context<StakingDistributeSolRewards>) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        let current_staking_proposal = &mut ctx.accounts.current_staking_proposal;
+
+        let StakingProposalAction::DistributeSolRewards { epoch, amount } = current_staking_proposal.action.clone() else {
+            return err!(StakingErrorCode::InvalidProposalAction);
+        };
+        let staking_snapshot = &ctx.accounts.staking_snapshot;
+        require!(epoch == staking_snapshot.epoch, StakingErrorCode::InvalidProposalAction);
+        require!(staking_snapshot.total_staked > 0, StakingErrorCode::NoActiveStake);
+
+        current_staking_proposal.approve(&signer_key)?;
+        current_staking_proposal.record_update(signer_key)?;
+
+        let vault = &ctx.accounts.vault;
+        require_with_context!(
+            vault.lamports() >= amount,
+            StakingErrorCode::InsufficientVaultBalance,
+            vault.key(),
+            amount,
+            vault.lamports()
+        );
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: ctx.accounts.staking_sol_distribution.to_account_info(),
+                },
+                &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+            ),
+            amount,
+        )?;
+
+        let clock = Clock::get()?;
+        ctx.accounts.staking_sol_distribution.set_inner(StakingSolDistributionAccount {
+            epoch,
+            proposal_id: current_staking_proposal.id,
+            amount,
+            total_staked: staking_snapshot.total_staked,
+            claimed_amount: 0,
+            distributed_at: clock.unix_timestamp,
+            version: StakingSolDistributionAccount::VERSION,
+            bump: ctx.bumps.staking_sol_distribution,
+        });
+
+        ctx.accounts.ledger.record(
+            &mut ctx.accounts.ledger_identifier,
+            vault.key(),
+            VaultTransaction::RewardDistribution { epoch, amount },
+            negate_amount(amount)?,
+            0,
+            ctx.bumps.ledger,
+        )?;
+
+        emit!(SolRewardsDistributedEvent {
+            proposal_id: current_staking_proposal.id,
+            epoch,
+            amount,
+            total_staked: staking_snapshot.total_staked,
+            timestamp: clock.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_distribute_sol_rewards", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Closes an approved staking proposal, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that a rent recipient is configured and that the signer is one of the multisig's signers.
+    /// - Checks that the proposal has been approved; pending proposals, including disputed slashes, are kept.
+    /// - Emits an `AccountClosedEvent`; the proposal is then closed to the rent recipient.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the proposal, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the proposal is
+    /// still pending.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is closed, otherwise returns an error.
+    pub fn close_proposal(ctx: Context<StakingCloseProposal>) -> Result<()> {
+        require!(
+            ctx.accounts.current_staking_proposal.status == StakingProposalStatus::Approved,
+            StakingErrorCode::ProposalNotResolved
+        );
+
+        AccountClosedEvent::emit(
+            &ctx.accounts.current_staking_proposal.to_account_info(),
+            ctx.accounts.rent_recipient.key(),
+        )?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_close_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Closes a retired validator allowlist entry, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that a rent recipient is configured and that the signer is one of the multisig's signers.
+    /// - Checks that the entry no longer allows the validator; a missing entry rejects delegations just like a retired one,
+    ///   and a later `ConfigureValidator` proposal recreates it.
+    /// - Emits an `AccountClosedEvent`; the entry is then closed to the rent recipient.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the allowlist entry, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the validator is
+    /// still allowed.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the entry is closed, otherwise returns an error.
+    pub fn close_validator(ctx: Context<StakingCloseValidator>) -> Result<()> {
+        require!(
+            !ctx.accounts.staking_validator.allowed,
+            StakingErrorCode::AllowlistEntryActive
+        );
+
+        AccountClosedEvent::emit(&ctx.accounts.staking_validator.to_account_info(), ctx.accounts.rent_recipient.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_close_validator", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Closes a retired custodian allowlist entry, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following actions:
+    /// - Checks that a rent recipient is configured and that the signer is one of the multisig's signers.
+    /// - Checks that the entry no longer allows the custodian; a missing entry rejects custodial stakes just like a retired
+    ///   one, and a later `ConfigureCustodian` proposal recreates it.
+    /// - Emits an `AccountClosedEvent`; the entry is then closed to the rent recipient.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the allowlist entry, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the custodian is
+    /// still allowed.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the entry is closed, otherwise returns an error.
+    pub fn close_custodian(ctx: Context<StakingCloseCustodian>) -> Result<()> {
+        require!(
+            !ctx.accounts.staking_custodian.allowed,
+            StakingErrorCode::AllowlistEntryActive
+        );
+
+        AccountClosedEvent::emit(&ctx.accounts.staking_custodian.to_account_info(), ctx.accounts.rent_recipient.key())?;
+
+        StateMutationEvent::emit(EventModule::Staking, "staking_close_custodian", ctx.accounts.signer.key())?;
 
         Ok(())
     }
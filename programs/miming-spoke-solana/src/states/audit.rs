@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::states::constants::{I64_SIZE, PUBKEY_SIZE};
+
+/// The space the `created_at`, `updated_at`, and `last_actor` fields of an [`Audited`] account take.
+pub const AUDIT_SPACE: usize = 2 * I64_SIZE + PUBKEY_SIZE;
+
+/// An account that records when it was created, when it was last written, and who last wrote it, so investigations can
+/// reconstruct who touched what and when from on-chain state alone.
+///
+/// Accounts created before their layout carried these fields read them as zero once migrated, and keep a zero
+/// `created_at` after their next write.
+pub trait Audited {
+    /// Returns the account's `created_at`, `updated_at`, and `last_actor` fields.
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey);
+
+    /// Stamps a newly created account as created and last written by `actor` at the current time.
+    fn record_creation(&mut self, actor: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (created_at, updated_at, last_actor) = self.audit_fields();
+        *created_at = now;
+        *updated_at = now;
+        *last_actor = actor;
+
+        Ok(())
+    }
+
+    /// Records that `actor` wrote the account at the current time.
+    fn record_update(&mut self, actor: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (_, updated_at, last_actor) = self.audit_fields();
+        *updated_at = now;
+        *last_actor = actor;
+
+        Ok(())
+    }
+
+    /// Records a write by `actor` to an account an `init_if_needed` instruction may have just created, stamping it as
+    /// created if it was never written before.
+    fn record_write(&mut self, actor: Pubkey) -> Result<()> {
+        let (_, updated_at, _) = self.audit_fields();
+        if *updated_at == 0 {
+            self.record_creation(actor)
+        } else {
+            self.record_update(actor)
+        }
+    }
+}
@@ -3,8 +3,12 @@ pub const DISCRIMINATOR: usize = 8;
 pub const STRING_LEN: usize = 64;
 pub const U8_SIZE: usize = 1;
 pub const U64_SIZE: usize = 8;
-pub const ENUM_SIZE: usize = 1;
-pub const VEC_SIZE: usize = 8;
+pub const I64_SIZE: usize = 8;
 pub const PUBKEY_SIZE: usize = 32;
 
-pub const MIMING_FEE: u64 = 10_000_000u64; 
\ No newline at end of file
+pub const MIMING_FEE: u64 = 10_000_000u64; 
+
+pub const STATE_MUTATION_EVENT_VERSION: u8 = 1;
+
+pub const BASIS_POINTS: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
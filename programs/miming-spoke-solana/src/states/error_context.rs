@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::states::events::ErrorContextEvent;
+
+/// Returns `error`, reporting the values that made its check fail in an `ErrorContextEvent` first when the program is
+/// built with the `error-telemetry` feature.
+///
+/// `account` is the offending key (e.g. the unauthorized signer), and `expected` and `actual` the compared values (e.g.
+/// the required and the available balance); pass the default key or zero for whichever the check does not involve.
+/// The event is logged before the instruction fails, so it shows up in the failed transaction's logs and in simulations.
+pub fn with_context<E>(error: E, account: Pubkey, expected: u64, actual: u64) -> Error
+where
+    E: Into<u32> + Into<Error> + Copy,
+{
+    if cfg!(feature = "error-telemetry") {
+        emit!(ErrorContextEvent {
+            error_code: error.into(),
+            account,
+            expected,
+            actual,
+        });
+    }
+
+    error.into()
+}
+
+/// Like `require!`, but fails through [`with_context`], so the offending `account`, `expected` and `actual` values are
+/// reported alongside the error.
+#[macro_export]
+macro_rules! require_with_context {
+    ($condition:expr, $error:expr, $account:expr, $expected:expr, $actual:expr $(,)?) => {
+        if !($condition) {
+            return Err($crate::states::error_context::with_context($error, $account, $expected, $actual)
+                .with_source(anchor_lang::source!()));
+        }
+    };
+}
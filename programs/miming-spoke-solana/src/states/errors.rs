@@ -19,12 +19,228 @@ pub enum MultisigErrorCode {
 
     #[msg("Not enough signatures have been collected to proceed.")]
     InsufficientSignatures,
+
+    #[msg("The signers' combined staking voting weight is below the required approval weight.")]
+    InsufficientVotingWeight,
+
+    #[msg("The name is longer than the maximum allowed length.")]
+    NameTooLong,
+
+    #[msg("The proposal uses an older layout and must be migrated first.")]
+    ProposalNotMigrated,
+
+    #[msg("The proposal is still pending and cannot be closed.")]
+    ProposalNotResolved,
+
+    #[msg("The account is not a proposal at its derived address.")]
+    InvalidProposalAccount,
+
+    #[msg("Signing a stake-weighted proposal requires the staking configuration.")]
+    StakingConfigRequired,
 }
 
 #[error_code]
 pub enum StakingErrorCode {
     #[msg("Token balance is too low to complete the staking request.")]
     InsufficientStakingBalance,
+
+    #[msg("There are no accrued rewards to claim.")]
+    NoRewardsToClaim,
+
+    #[msg("The reward vault does not hold enough tokens to pay the claim.")]
+    InsufficientRewardBalance,
+
+    #[msg("The mint's freeze authority does not match the staking mode configured for it.")]
+    InvalidFreezeAuthority,
+
+    #[msg("The staking registry has no active stake to release.")]
+    NoActiveStake,
+
+    #[msg("This staking proposal has already been processed and cannot be updated.")]
+    AlreadyResolved,
+
+    #[msg("The public key is not authorized to sign this staking proposal.")]
+    UnauthorizedSigner,
+
+    #[msg("This public key has already signed the staking proposal.")]
+    DuplicateSignature,
+
+    #[msg("Not enough signatures have been collected to execute the staking proposal.")]
+    InsufficientSignatures,
+
+    #[msg("The staking proposal does not authorize this action.")]
+    InvalidProposalAction,
+
+    #[msg("The amount must be greater than zero.")]
+    InvalidAmount,
+
+    #[msg("The staking registry holds an active stake in a different staking mode.")]
+    StakingModeMismatch,
+
+    #[msg("The requested amount exceeds the staked amount.")]
+    InsufficientStakedAmount,
+
+    #[msg("The token does not match the mint recorded in the staking registry.")]
+    MintMismatch,
+
+    #[msg("The token account does not match the one recorded in the staking registry.")]
+    TokenAccountMismatch,
+
+    #[msg("The slashing reason exceeds the maximum allowed length.")]
+    ReasonTooLong,
+
+    #[msg("The stake is still locked; use the early-exit path to release it before the unlock time.")]
+    StakeStillLocked,
+
+    #[msg("The lock duration must be between zero and the configured maximum.")]
+    InvalidLockDuration,
+
+    #[msg("The stake position index must refer to an existing position or the next new one.")]
+    InvalidPositionIndex,
+
+    #[msg("The new unlock time must be later than the current one.")]
+    LockNotExtended,
+
+    #[msg("Rewards can only be compounded into an active escrow stake of the reward mint.")]
+    CompoundNotSupported,
+
+    #[msg("The staking configuration value is outside the allowed bounds.")]
+    InvalidConfigValue,
+
+    #[msg("An unstake must be requested before the stake can be released.")]
+    UnstakeNotRequested,
+
+    #[msg("An unstake has already been requested for this stake position.")]
+    UnstakeAlreadyRequested,
+
+    #[msg("The unstake cooldown has not elapsed yet.")]
+    CooldownNotElapsed,
+
+    #[msg("The account is not a distinct staking registry of the staker.")]
+    InvalidStakingRegistry,
+
+    #[msg("The token account is frozen outside of staking and cannot be staked.")]
+    TokenAccountFrozen,
+
+    #[msg("A staker cannot refer their own stake.")]
+    SelfReferral,
+
+    #[msg("The referrer does not match the stake position's referrer or its referral statistics account.")]
+    InvalidReferrer,
+
+    #[msg("The reference ID exceeds the maximum allowed length.")]
+    ReferenceIdTooLong,
+
+    #[msg("The reference ID must be non-empty and contain only ASCII letters, digits, '-' or '_'.")]
+    InvalidReferenceId,
+
+    #[msg("The mint is not on the staking allowlist.")]
+    MintNotAllowed,
+
+    #[msg("Thawing requires an attestation, verified by an ed25519 instruction right before this one.")]
+    MissingAttestation,
+
+    #[msg("The attestation is not signed by the configured attestor over this stake position.")]
+    InvalidAttestation,
+
+    #[msg("The attestation has expired.")]
+    AttestationExpired,
+
+    #[msg("The stake position still holds a stake, a lock, a pending unstake, or unclaimed rewards.")]
+    PositionNotClosable,
+
+    #[msg("The validator is not on the vault delegation allowlist.")]
+    ValidatorNotAllowed,
+
+    #[msg("The vault delegation is not in a state that allows this operation.")]
+    InvalidDelegationStatus,
+
+    #[msg("The vault does not hold enough SOL for this delegation.")]
+    InsufficientVaultBalance,
+
+    #[msg("The funded reward budget does not cover the claim.")]
+    RewardBudgetExhausted,
+
+    #[msg("Snapshots can only be recorded for the current epoch.")]
+    InvalidSnapshotEpoch,
+
+    #[msg("The insurance fund and vault ledger accounts are required to collect a penalty.")]
+    MissingInsuranceFund,
+
+    #[msg("A batch must hold between one and the maximum number of positions.")]
+    InvalidBatchSize,
+
+    #[msg("Each batched position needs its mint, staker token account, mint config (or program ID), and registry.")]
+    InvalidBatchAccounts,
+
+    #[msg("The staking registry already uses the current layout.")]
+    RegistryAlreadyMigrated,
+
+    #[msg("Staking is paused; new freezes and stakes are not accepted.")]
+    StakingPaused,
+
+    #[msg("Staking this amount would exceed the maximum total staked.")]
+    StakingCapReached,
+
+    #[msg("The custodian is not authorized to stake on behalf of other wallets.")]
+    CustodianNotAllowed,
+
+    #[msg("The stake position was opened by a different custodian.")]
+    CustodianMismatch,
+
+    #[msg("The staking authority has not been approved as a delegate of the token account for this amount.")]
+    DelegationMissing,
+
+    #[msg("The unstake queue is full; wait for queued requests to be processed.")]
+    UnstakeQueueFull,
+
+    #[msg("The unstake queue has no pending requests.")]
+    UnstakeQueueEmpty,
+
+    #[msg("The accounts do not belong to the request at the head of the unstake queue.")]
+    UnstakeQueueMismatch,
+
+    #[msg("The stake vault does not hold enough tokens to pay the request at the head of the unstake queue yet.")]
+    InsufficientQueueLiquidity,
+
+    #[msg("Pair staking is disabled while the configured SOL ratio is zero.")]
+    PairStakingDisabled,
+
+    #[msg("The stake position started or changed after the epoch snapshot and has no share in its distribution.")]
+    StakedAfterSnapshot,
+
+    #[msg("The reward schedule must list up to the maximum number of steps, with increasing start epochs and non-increasing rates.")]
+    InvalidRewardSchedule,
+
+    #[msg("The reward schedule has no step in force for the current epoch yet.")]
+    RewardScheduleNotStarted,
+
+    #[msg("Slash proposals and counter-claims must reference a non-zero hash.")]
+    MissingSlashEvidence,
+
+    #[msg("The slash proposal's dispute window has not closed yet.")]
+    SlashDisputeWindowOpen,
+
+    #[msg("The slash proposal's dispute window has already closed.")]
+    SlashDisputeWindowClosed,
+
+    #[msg("The staking configuration already uses the current layout.")]
+    ConfigAlreadyMigrated,
+
+    #[msg("The staking module is disabled in the program configuration.")]
+    StakingDisabled,
+
+    #[msg("The staking proposal is still pending and cannot be closed.")]
+    ProposalNotResolved,
+
+    #[msg("The distribution still has unclaimed SOL, so its claim receipts must be kept.")]
+    DistributionNotFullyClaimed,
+
+    #[msg("The allowlist entry is still allowed and cannot be closed.")]
+    AllowlistEntryActive,
+
+    #[msg("The configuration change's activation delay has not elapsed yet.")]
+    ActivationDelayNotElapsed,
 }
 
 #[error_code]
@@ -43,4 +259,237 @@ pub enum VaultErrorCode {
 
     #[msg("The minimum required signatures have not been met.")]
     InsufficientSignatures,
+
+    #[msg("The vault module is disabled in the program configuration.")]
+    VaultDisabled,
+
+    #[msg("Teleports are paused in the program configuration.")]
+    TeleportPaused,
+
+    #[msg("The transfer proposal is still pending and cannot be closed.")]
+    ProposalNotResolved,
+
+    #[msg("The ledger entry is still within the retention window and cannot be closed.")]
+    LedgerEntryRetained,
+
+    #[msg("Deposit receipts are disabled in the program configuration.")]
+    DepositReceiptsDisabled,
+
+    #[msg("Only teleport ledger entries can be receipted.")]
+    NotADeposit,
+
+    #[msg("Only the depositor can mint the deposit's receipt.")]
+    NotDepositor,
+
+    #[msg("The deposit is below the receipt threshold.")]
+    DepositBelowReceiptThreshold,
+
+    #[msg("A receipt was already minted for the deposit.")]
+    DepositReceiptAlreadyMinted,
+}
+
+#[error_code]
+pub enum ProgramConfigErrorCode {
+    #[msg("The program is halted by an emergency stop.")]
+    GloballyPaused,
+
+    #[msg("Only the guardian can trigger an emergency stop.")]
+    UnauthorizedGuardian,
+
+    #[msg("The program is already halted by an emergency stop.")]
+    AlreadyGloballyPaused,
+
+    #[msg("The program is not halted by an emergency stop.")]
+    NotGloballyPaused,
+
+    #[msg("No rent recipient has been configured for closed accounts.")]
+    RentRecipientNotConfigured,
+
+    #[msg("The instruction was invoked through CPI by a program that is not allowlisted.")]
+    CpiCallerNotAllowed,
+
+    #[msg("Only the operator can run this crank while one is configured.")]
+    UnauthorizedOperator,
+}
+
+#[error_code]
+pub enum VersioningErrorCode {
+    #[msg("The account already uses the current layout.")]
+    AccountAlreadyMigrated,
+    #[msg("The seeds do not derive the account's address.")]
+    InvalidAccountSeeds,
+}
+
+#[error_code]
+pub enum RegistryErrorCode {
+    #[msg("The account is already registered under this kind.")]
+    AlreadyRegistered,
+
+    #[msg("The registry has no room for another entry.")]
+    RegistryFull,
+
+    #[msg("The account is not the canonical address of this kind.")]
+    InvalidRegistryAddress,
+
+    #[msg("The account has not been created by the program.")]
+    AccountNotCreated,
+}
+
+#[error_code]
+pub enum NonceErrorCode {
+    #[msg("An expected nonce was given without the signer's nonce account.")]
+    NonceAccountRequired,
+
+    #[msg("The expected nonce does not match the signer's current nonce.")]
+    NonceMismatch,
+}
+
+#[error_code]
+pub enum GenesisErrorCode {
+    #[msg("The program data account does not belong to this program.")]
+    InvalidProgramData,
+
+    #[msg("Only the program's upgrade authority can initialize the program.")]
+    NotUpgradeAuthority,
+
+    #[msg("The genesis council has no signers.")]
+    EmptyCouncil,
+
+    #[msg("The genesis threshold must be at least one and at most the number of signers.")]
+    InvalidThreshold,
+
+    #[msg("The genesis council lists the same signer more than once.")]
+    DuplicateSigner,
+}
+
+#[error_code]
+pub enum SimulationErrorCode {
+    #[msg("The dry run passed every check and was rolled back.")]
+    DryRunCompleted,
+}
+
+#[error_code]
+pub enum WormholeErrorCode {
+    #[msg("The Wormhole core bridge account is too short to hold the expected fields.")]
+    InvalidBridgeAccount,
+
+    #[msg("Only teleport ledger entries can be posted to the hub.")]
+    NotATeleport,
+
+    #[msg("Only the teleport's sender can post it to the hub.")]
+    UnauthorizedSender,
+
+    #[msg("The teleport has already been posted to the hub.")]
+    MessageAlreadyPosted,
+
+    #[msg("The account is not a VAA posted by the Wormhole core bridge.")]
+    InvalidVaa,
+
+    #[msg("The hub emitter has not been configured.")]
+    HubEmitterNotConfigured,
+
+    #[msg("The VAA was not emitted by the configured hub emitter.")]
+    UnknownEmitter,
+
+    #[msg("The VAA's sequence does not match the given sequence.")]
+    SequenceMismatch,
+
+    #[msg("The VAA's payload is not a valid release.")]
+    InvalidPayload,
+
+    #[msg("The recipient account does not match the release's recipient.")]
+    RecipientMismatch,
+
+    #[msg("Releasing MIMING requires the mint, the recipient's token account, the mint authority, and the token program.")]
+    MintAccountsRequired,
+
+    #[msg("The message at this chain and sequence was already consumed.")]
+    MessageAlreadyConsumed,
+
+    #[msg("The destination does not match the one the teleport committed to.")]
+    DestinationMismatch,
+}
+
+#[error_code]
+pub enum OracleErrorCode {
+    #[msg("A USD-denominated teleport fee requires the SOL/USD price update account.")]
+    PriceUpdateRequired,
+
+    #[msg("The account is not a fully verified price update written by the oracle.")]
+    InvalidPriceAccount,
+
+    #[msg("The price update is not for the configured SOL/USD feed.")]
+    PriceFeedMismatch,
+
+    #[msg("The price was published longer ago than the configured staleness window.")]
+    StalePrice,
+
+    #[msg("The price's confidence interval is wider than the configured bound.")]
+    PriceConfidenceTooWide,
+
+    #[msg("The price is not positive or cannot convert the fee.")]
+    InvalidPrice,
+
+    #[msg("The Pyth and Switchboard prices diverge by more than the configured bound.")]
+    PriceDivergence,
+}
+
+#[error_code]
+pub enum SquadsErrorCode {
+    #[msg("The account is not a multisig owned by the Squads program.")]
+    InvalidSquadsMultisig,
+
+    #[msg("An execution signed by the Squads vault must be invoked by the Squads program.")]
+    NotInvokedBySquads,
+}
+
+#[error_code]
+pub enum RealmsErrorCode {
+    #[msg("The account is not a governance owned by the SPL Governance program.")]
+    InvalidRealmsGovernance,
+
+    #[msg("An execution signed by the Realms governance must be invoked by the SPL Governance program.")]
+    NotInvokedByRealms,
+}
+
+#[error_code]
+pub enum BubblegumErrorCode {
+    #[msg("The tree is not a Bubblegum tree delegated to the deposit receipt authority.")]
+    InvalidReceiptTree,
+
+    #[msg("No compressed receipt tree is registered in the program configuration.")]
+    ReceiptTreeNotConfigured,
+}
+
+#[error_code]
+pub enum HubRegistryErrorCode {
+    #[msg("The destination chain is not a supported route in the hub registry.")]
+    UnsupportedDestinationChain,
+
+    #[msg("The teleport exceeds the limit of its destination chain's route.")]
+    TeleportAboveRouteLimit,
+}
+
+#[error_code]
+pub enum LightClientErrorCode {
+    #[msg("The state root's height is not above the latest recorded height.")]
+    StaleStateRoot,
+
+    #[msg("No hub state root is recorded at the given height.")]
+    UnknownStateRoot,
+
+    #[msg("The merkle proof exceeds the maximum proof depth.")]
+    ProofTooLong,
+
+    #[msg("The merkle proof does not prove the leaf against the hub state root.")]
+    InvalidProof,
+
+    #[msg("The signers do not reach the council's threshold of distinct members.")]
+    QuorumNotReached,
+}
+
+#[error_code]
+pub enum MathErrorCode {
+    #[msg("An arithmetic operation overflowed or underflowed.")]
+    ArithmeticError,
 }
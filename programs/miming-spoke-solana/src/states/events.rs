@@ -1,9 +1,677 @@
 use anchor_lang::prelude::*;
 
+use crate::access_control::Role;
+use crate::hub_registry::HubRoute;
+use crate::multisig::Multisig;
+use crate::staking::{StakingLedger, StakingProposalAction, StakingRewardScheduleStep};
+use crate::registry::ProgramRegistryKind;
 use crate::vault::VaultLedger;
+use crate::{messaging::MessagingTransport, wormhole::InboundAsset};
+use crate::states::constants::STATE_MUTATION_EVENT_VERSION;
+use crate::states::pda::IDENTIFIER_NAMESPACE_LEN;
+
+/// The program module an instruction belongs to, as reported by `StateMutationEvent`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EventModule {
+    Multisig,
+    ProgramConfig,
+    Vault,
+    Staking,
+    Registry,
+    Nonce,
+    Genesis,
+    LightClient,
+}
+
+/// Common envelope emitted by every state-mutating instruction, giving indexers one uniform stream across modules.
+///
+/// `action` is the instruction name as exposed by the program, `actor` the signing wallet (the default key for
+/// instructions without a signer), and `version` the envelope layout, bumped whenever its fields change.
+#[event]
+pub struct StateMutationEvent {
+    pub module: EventModule,
+    pub action: String,
+    pub version: u8,
+    pub actor: Pubkey,
+    pub slot: u64,
+}
+
+impl StateMutationEvent {
+    /// Emits the envelope for `action` of `module`, performed by `actor` in the current slot.
+    pub fn emit(module: EventModule, action: &str, actor: Pubkey) -> Result<()> {
+        emit!(StateMutationEvent {
+            module,
+            action: action.to_string(),
+            version: STATE_MUTATION_EVENT_VERSION,
+            actor,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
+
+/// The values behind a failed check, emitted right before the instruction fails with `error_code` when the program is
+/// built with the `error-telemetry` feature; see [`with_context`](crate::states::error_context::with_context).
+#[event]
+pub struct ErrorContextEvent {
+    pub error_code: u32,
+    pub account: Pubkey,
+    pub expected: u64,
+    pub actual: u64,
+}
 
 #[event]
 pub struct VaultLedgerLogEvent {
     pub id: u64,
     pub data: VaultLedger,
-}
\ No newline at end of file
+}
+
+/// A teleport posted to the hub through the Wormhole core bridge, with the sequence its message was assigned.
+#[event]
+pub struct TeleportMessagePostedEvent {
+    pub ledger_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub destination_chain: u16,
+    pub destination: [u8; 32],
+    pub sequence: u64,
+    pub chain_sequence: u64,
+    pub relayer: Pubkey,
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
+
+/// A message from another chain consumed at its sequence, with the chain's inbound counters, so monitoring can alert while
+/// sequences below the highest consumed one are missing.
+#[event]
+pub struct InboundMessageSequencedEvent {
+    pub chain: u16,
+    pub sequence: u64,
+    pub inbound_count: u64,
+    pub highest_sequence: u64,
+    pub missing_sequences: u64,
+    pub timestamp: i64,
+}
+
+/// A receipt NFT minted to the depositor of a teleport at or above the receipt threshold.
+#[event]
+pub struct DepositReceiptMintedEvent {
+    pub ledger_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub receipt_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// A compressed receipt NFT minted into the receipt tree for a teleport at or above the receipt threshold.
+#[event]
+pub struct CompressedDepositReceiptMintedEvent {
+    pub ledger_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub merkle_tree: Pubkey,
+    pub receipt_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// A hub state root recorded by the light client at a new height.
+#[event]
+pub struct HubStateRootSubmittedEvent {
+    pub height: u64,
+    pub root: [u8; 32],
+    pub submitter: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A teleport from the hub minted as MIMING from a VAA verified by the Wormhole core bridge.
+#[event]
+pub struct InboundTeleportMintedEvent {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub source_ledger_id: u64,
+    pub sender: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub ledger_id: u64,
+    pub bridged_supply: u64,
+    pub relayer: Pubkey,
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
+
+/// A release from the hub paid out from a VAA verified by the Wormhole core bridge.
+#[event]
+pub struct InboundReleaseCompletedEvent {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub asset: InboundAsset,
+    pub ledger_id: u64,
+    pub relayer: Pubkey,
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingLedgerLogEvent {
+    pub sequence: u64,
+    pub data: StakingLedger,
+}
+
+/// The genesis parameters a deployment was bootstrapped with by `initialize_program`.
+#[event]
+pub struct ProgramInitializedEvent {
+    pub upgrade_authority: Pubkey,
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub teleport_fee: u64,
+    pub guardian: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub reward_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigUpdatedEvent {
+    pub proposal_id: u64,
+    pub vault_enabled: bool,
+    pub staking_enabled: bool,
+    pub teleport_paused: bool,
+    pub teleport_fee: u64,
+    pub guardian: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigCpiGuardConfiguredEvent {
+    pub proposal_id: u64,
+    pub enabled: bool,
+    pub allowed_callers: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigRoleConfiguredEvent {
+    pub proposal_id: u64,
+    pub role: Role,
+    pub previous_holder: Pubkey,
+    pub holder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigHubEmitterConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_chain: u16,
+    pub previous_emitter: [u8; 32],
+    pub chain: u16,
+    pub emitter: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigHubRegistryConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_chain: u16,
+    pub previous_emitter: [u8; 32],
+    pub chain: u16,
+    pub emitter: [u8; 32],
+    pub routes: Vec<HubRoute>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigMessagingConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_transport: MessagingTransport,
+    pub previous_endpoint: Pubkey,
+    pub transport: MessagingTransport,
+    pub endpoint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigUsdFeeConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_usd_cents: u64,
+    pub usd_cents: u64,
+    pub feed_id: [u8; 32],
+    pub max_staleness: i64,
+    pub max_confidence_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigOracleFallbackConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_feed: Pubkey,
+    pub feed: Pubkey,
+    pub max_divergence_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigSquadsAuthorityConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_authority: Pubkey,
+    pub multisig: Pubkey,
+    pub vault_index: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigRealmsGovernanceConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_governance: Pubkey,
+    pub governance: Pubkey,
+    pub realm: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigCompressedReceiptsConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_tree: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigRelayerFeeConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_fee_bps: u16,
+    pub fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigDepositReceiptsConfiguredEvent {
+    pub proposal_id: u64,
+    pub previous_threshold: u64,
+    pub threshold: u64,
+    pub uri_prefix: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramEmergencyPausedEvent {
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramResumedEvent {
+    pub proposal_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeFrozenEvent {
+    pub staker: Pubkey,
+    pub mint: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub reference_id: String,
+    pub unlocks_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakedOnBehalfEvent {
+    pub custodian: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub reference_id: String,
+    pub unlocks_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeThawedEvent {
+    pub staker: Pubkey,
+    pub mint: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub reference_id: String,
+    pub early_exit: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequestedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub cooldown_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PairStakedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub lamports: u64,
+    pub staked_amount: u64,
+    pub paired_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PairUnstakedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeQueuedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub mint: Pubkey,
+    pub sequence: u64,
+    pub position: u64,
+    pub amount: u64,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeQueueProcessedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub mint: Pubkey,
+    pub sequence: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountClosedEvent {
+    pub account: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+impl AccountClosedEvent {
+    /// Emits the event for `account`, whose rent is refunded to `rent_recipient` by its `close` constraint.
+    pub fn emit(account: &AccountInfo, rent_recipient: Pubkey) -> Result<()> {
+        emit!(AccountClosedEvent {
+            account: account.key(),
+            rent_recipient,
+            lamports: account.lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ProgramRegistryEntryAddedEvent {
+    pub kind: ProgramRegistryKind,
+    pub address: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IdentifierInitializedEvent {
+    pub identifier: Pubkey,
+    pub namespace: [u8; IDENTIFIER_NAMESPACE_LEN],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub previous_version: u8,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingConfigMigratedEvent {
+    pub previous_version: u8,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRegistryMigratedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakePositionClosedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimedEvent {
+    pub staker: Pubkey,
+    pub reward_mint: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub reference_id: String,
+    pub compounded: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingSnapshotRecordedEvent {
+    pub epoch: u64,
+    pub total_staked: u64,
+    pub staker_count: u64,
+    pub tier_staked: Vec<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingReportedEvent {
+    pub sequence: u64,
+    pub total_staked: u64,
+    pub staker_count: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsFundedEvent {
+    pub funder: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub reward_budget: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeSlashedEvent {
+    pub proposal_id: u64,
+    pub staker: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlashDisputedEvent {
+    pub proposal_id: u64,
+    pub staker: Pubkey,
+    pub counter_claim_hash: [u8; 32],
+    pub dispute_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeLockExtendedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub previous_unlocks_at: i64,
+    pub unlocks_at: i64,
+    pub multiplier_bps: u16,
+    pub timestamp: i64,
+}
+
+/// A proposed change to the program or staking configuration, emitted when its proposal is created so monitoring can
+/// alert on it before it can be activated at `activates_at`.
+#[event]
+pub struct ConfigChangeProposedEvent {
+    pub proposal_id: u64,
+    pub action: StakingProposalAction,
+    pub activates_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingConfigUpdatedEvent {
+    pub proposal_id: u64,
+    pub previous_min_staking_amount: u64,
+    pub min_staking_amount: u64,
+    pub previous_unstake_cooldown: i64,
+    pub unstake_cooldown: i64,
+    pub previous_mint_allowlist_enabled: bool,
+    pub mint_allowlist_enabled: bool,
+    pub previous_min_inclusive: bool,
+    pub min_inclusive: bool,
+    pub previous_max_total_staked: u64,
+    pub max_total_staked: u64,
+    pub previous_pause_grace_period: i64,
+    pub pause_grace_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardScheduleUpdatedEvent {
+    pub proposal_id: u64,
+    pub steps: Vec<StakingRewardScheduleStep>,
+    pub reward_rate_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardRateAppliedEvent {
+    pub epoch: u64,
+    pub previous_rate_bps: u16,
+    pub reward_rate_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingPairConfigUpdatedEvent {
+    pub proposal_id: u64,
+    pub previous_sol_ratio_bps: u64,
+    pub sol_ratio_bps: u64,
+    pub previous_boost_bps: u16,
+    pub boost_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingPauseUpdatedEvent {
+    pub proposal_id: u64,
+    pub paused: bool,
+    pub paused_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingOperationBlockedEvent {
+    pub staker: Pubkey,
+    pub operation: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingAttestorUpdatedEvent {
+    pub proposal_id: u64,
+    pub previous_attestor: Pubkey,
+    pub attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultStakeDelegatedEvent {
+    pub proposal_id: u64,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultStakeDeactivatedEvent {
+    pub proposal_id: u64,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultStakeWithdrawnEvent {
+    pub proposal_id: u64,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub amount: u64,
+    pub rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolRewardsDistributedEvent {
+    pub proposal_id: u64,
+    pub epoch: u64,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolRewardsClaimedEvent {
+    pub staker: Pubkey,
+    pub position_index: u64,
+    pub epoch: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// What executing a vault transfer proposal would do, emitted by a dry run right before it fails with
+/// `SimulationErrorCode::DryRunCompleted`.
+#[event]
+pub struct VaultTransferPreviewEvent {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+/// The multisig an approved proposal would install, emitted by a dry run right before it fails with
+/// `SimulationErrorCode::DryRunCompleted`.
+#[event]
+pub struct MultisigApprovalPreviewEvent {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    pub multisig: Multisig,
+    pub timestamp: i64,
+}
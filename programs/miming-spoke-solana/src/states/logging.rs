@@ -0,0 +1,56 @@
+//! Leveled, structured program logs.
+//!
+//! Each line is written with `msg!` as space-separated `key=value` pairs, led by the level and the event it describes:
+//!
+//! ```text
+//! level=info event=vault_teleport signer=<pubkey> amount=1000000 miming_fee=5000
+//! ```
+//!
+//! - [`log_info!`](crate::log_info) records the outcome of important handlers.
+//! - [`log_warn!`](crate::log_warn) records operations that need an operator's attention, such as an emergency stop.
+//! - [`log_debug!`](crate::log_debug) traces intermediate values, and is compiled out unless the program is built with the
+//!   `debug-logs` feature, so mainnet builds neither log nor pay compute for it.
+//!
+//! Values are formatted with `Display`.
+
+/// Returns `true` if the program is built with the `debug-logs` feature, so `log_debug!` lines are emitted.
+pub const DEBUG_LOGS_ENABLED: bool = cfg!(feature = "debug-logs");
+
+/// Writes a `key=value` log line at `level` for `event`; use [`log_debug!`](crate::log_debug),
+/// [`log_info!`](crate::log_info) or [`log_warn!`](crate::log_warn) instead.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! log_at {
+    ($level:literal, $event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        anchor_lang::prelude::msg!(
+            concat!("level=", $level, " event=", $event $(, " ", stringify!($key), "={}")*)
+            $(, $value)*
+        )
+    };
+}
+
+/// Logs `event` with its `key = value` pairs when the program is built with the `debug-logs` feature.
+#[macro_export]
+macro_rules! log_debug {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::states::logging::DEBUG_LOGS_ENABLED {
+            $crate::log_at!("debug", $event $(, $key = $value)*);
+        }
+    };
+}
+
+/// Logs `event` with its `key = value` pairs.
+#[macro_export]
+macro_rules! log_info {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::log_at!("info", $event $(, $key = $value)*)
+    };
+}
+
+/// Logs `event` with its `key = value` pairs as needing an operator's attention.
+#[macro_export]
+macro_rules! log_warn {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::log_at!("warn", $event $(, $key = $value)*)
+    };
+}
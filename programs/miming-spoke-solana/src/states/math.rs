@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::states::errors::MathErrorCode;
+
+/// Returns `amount` negated as it is recorded for outflows in the vault ledger, the two's complement of its `i64` value.
+///
+/// Fails with `ArithmeticError` if `amount` does not fit an `i64`, so an outflow is never recorded as an inflow.
+pub fn negate_amount(amount: u64) -> Result<u64> {
+    i64::try_from(amount)
+        .ok()
+        .and_then(i64::checked_neg)
+        .map(|negated| negated as u64)
+        .ok_or_else(|| error!(MathErrorCode::ArithmeticError))
+}
@@ -1,7 +1,23 @@
+pub mod audit;
 pub mod constants;
+pub mod error_context;
 pub mod events;
 pub mod errors;
+pub mod logging;
+pub mod math;
+pub mod pda;
+pub mod realloc;
+pub mod signer_set;
+pub mod versioning;
 
+pub use audit::*;
 pub use constants::*;
+pub use error_context::*;
 pub use events::*;
-pub use errors::*;
\ No newline at end of file
+pub use errors::*;
+pub use logging::*;
+pub use math::*;
+pub use pda::*;
+pub use realloc::*;
+pub use signer_set::*;
+pub use versioning::*;
\ No newline at end of file
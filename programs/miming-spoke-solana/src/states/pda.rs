@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
+pub const PROGRAM_REGISTRY_SEED: &[u8] = b"program_registry";
+pub const WALLET_NONCE_SEED: &[u8] = b"wallet_nonce";
+
+/// Seed of namespaced identifier counters, derived from `[IDENTIFIER_SEED, namespace]`.
+pub const IDENTIFIER_SEED: &[u8] = b"identifier";
+/// Length of an identifier counter's namespace, zero-padded by [`identifier_namespace`].
+pub const IDENTIFIER_NAMESPACE_LEN: usize = 16;
+
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const PROPOSAL_IDENTIFIER_SEED: &[u8] = b"proposal_identifier";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const LEDGER_IDENTIFIER_SEED: &[u8] = b"ledger_identifier";
+pub const LEDGER_SEED: &[u8] = b"ledger";
+pub const TRANSFER_PROPOSAL_IDENTIFIER_SEED: &[u8] = b"transfer_proposal_identifier";
+pub const TRANSFER_PROPOSAL_SEED: &[u8] = b"transfer_proposal";
+pub const VAULT_DELEGATION_SEED: &[u8] = b"vault_delegation";
+pub const VAULT_STAKE_SEED: &[u8] = b"vault_stake";
+pub const WORMHOLE_EMITTER_SEED: &[u8] = b"emitter";
+pub const TELEPORT_MESSAGE_SEED: &[u8] = b"teleport_message";
+pub const INBOUND_RECEIPT_SEED: &[u8] = b"inbound_receipt";
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+pub const DEPOSIT_RECEIPT_MINT_SEED: &[u8] = b"deposit_receipt_mint";
+pub const DEPOSIT_RECEIPT_AUTHORITY_SEED: &[u8] = b"deposit_receipt_authority";
+pub const DEPOSIT_RECEIPT_PAGE_SEED: &[u8] = b"deposit_receipt_page";
+pub const HUB_REGISTRY_SEED: &[u8] = b"hub_registry";
+pub const BRIDGE_STATS_SEED: &[u8] = b"bridge_stats";
+pub const HUB_STATE_ROOTS_SEED: &[u8] = b"hub_state_roots";
+pub const BRIDGE_SEQUENCE_SEED: &[u8] = b"bridge_sequence";
+pub const CONSUMED_MESSAGE_PAGE_SEED: &[u8] = b"consumed_message_page";
+
+pub const STAKING_CONFIG_SEED: &[u8] = b"staking_config";
+pub const STAKING_AUTHORITY_SEED: &[u8] = b"staking_authority";
+pub const STAKING_POOL_SEED: &[u8] = b"staking_pool";
+pub const STAKING_ACTIVITY_SEED: &[u8] = b"staking_activity";
+pub const STAKING_PAIR_VAULT_SEED: &[u8] = b"staking_pair_vault";
+pub const STAKING_REWARD_SCHEDULE_SEED: &[u8] = b"staking_reward_schedule";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const STAKING_USER_SEED: &[u8] = b"staking_user";
+pub const STAKING_REGISTRY_SEED: &[u8] = b"staking_registry";
+pub const STAKING_REFERRER_SEED: &[u8] = b"staking_referrer";
+pub const STAKING_MINT_CONFIG_SEED: &[u8] = b"staking_mint_config";
+pub const STAKING_UNSTAKE_QUEUE_SEED: &[u8] = b"staking_unstake_queue";
+pub const STAKING_CUSTODIAN_SEED: &[u8] = b"staking_custodian";
+pub const STAKING_VALIDATOR_SEED: &[u8] = b"staking_validator";
+pub const STAKING_SNAPSHOT_SEED: &[u8] = b"staking_snapshot";
+pub const STAKING_SOL_DISTRIBUTION_SEED: &[u8] = b"staking_sol_distribution";
+pub const STAKING_SOL_CLAIM_SEED: &[u8] = b"staking_sol_claim";
+pub const STAKING_PROPOSAL_IDENTIFIER_SEED: &[u8] = b"staking_proposal_identifier";
+pub const STAKING_PROPOSAL_SEED: &[u8] = b"staking_proposal";
+pub const STAKING_REPORT_IDENTIFIER_SEED: &[u8] = b"staking_report_identifier";
+pub const STAKING_REPORT_SEED: &[u8] = b"staking_report";
+pub const STAKING_SLASH_DISPUTE_SEED: &[u8] = b"staking_slash_dispute";
+
+fn find_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// Returns the address and bump of the `ProgramConfigAccount`.
+pub fn find_program_config_address() -> (Pubkey, u8) {
+    find_address(&[PROGRAM_CONFIG_SEED])
+}
+
+/// Pads `name` into the fixed-size namespace of an identifier counter, failing to compile a constant whose name does
+/// not fit.
+pub const fn identifier_namespace(name: &str) -> [u8; IDENTIFIER_NAMESPACE_LEN] {
+    let bytes = name.as_bytes();
+    assert!(!bytes.is_empty() && bytes.len() <= IDENTIFIER_NAMESPACE_LEN);
+
+    let mut namespace = [0u8; IDENTIFIER_NAMESPACE_LEN];
+    let mut index = 0;
+    while index < bytes.len() {
+        namespace[index] = bytes[index];
+        index += 1;
+    }
+    namespace
+}
+
+/// Returns the address and bump of the `IdentifierAccount` counting ids in `namespace`.
+pub fn find_identifier_address(namespace: &[u8; IDENTIFIER_NAMESPACE_LEN]) -> (Pubkey, u8) {
+    find_address(&[IDENTIFIER_SEED, namespace.as_ref()])
+}
+
+/// Returns the address and bump of the `ProgramRegistryAccount`.
+pub fn find_program_registry_address() -> (Pubkey, u8) {
+    find_address(&[PROGRAM_REGISTRY_SEED])
+}
+
+/// Returns the address and bump of the `WalletNonceAccount` of `wallet`.
+pub fn find_wallet_nonce_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[WALLET_NONCE_SEED, wallet.as_ref()])
+}
+
+/// Returns the address and bump of the `MultisigAccount`.
+pub fn find_multisig_address() -> (Pubkey, u8) {
+    find_address(&[MULTISIG_SEED])
+}
+
+/// Returns the address and bump of the `IdentifierAccount` numbering multisig proposals.
+pub fn find_proposal_identifier_address() -> (Pubkey, u8) {
+    find_address(&[PROPOSAL_IDENTIFIER_SEED])
+}
+
+/// Returns the address and bump of the `MultisigProposalAccount` with identifier `id`.
+pub fn find_proposal_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[PROPOSAL_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the vault holding the program's SOL.
+pub fn find_vault_address() -> (Pubkey, u8) {
+    find_address(&[VAULT_SEED])
+}
+
+/// Returns the address and bump of the `IdentifierAccount` numbering vault ledger entries.
+pub fn find_ledger_identifier_address() -> (Pubkey, u8) {
+    find_address(&[LEDGER_IDENTIFIER_SEED])
+}
+
+/// Returns the address and bump of the `VaultLedgerAccount` with identifier `id`.
+pub fn find_ledger_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[LEDGER_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the emitter PDA that signs the program's Wormhole messages.
+pub fn find_wormhole_emitter_address() -> (Pubkey, u8) {
+    find_address(&[WORMHOLE_EMITTER_SEED])
+}
+
+/// Returns the address and bump of the Wormhole message account carrying the teleport recorded as ledger entry `id`.
+pub fn find_teleport_message_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[TELEPORT_MESSAGE_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `VaultInboundReceiptAccount` marking the hub emitter's message `sequence` as consumed.
+pub fn find_inbound_receipt_address(hub_emitter: &[u8; 32], sequence: u64) -> (Pubkey, u8) {
+    find_address(&[INBOUND_RECEIPT_SEED, hub_emitter.as_ref(), sequence.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the PDA holding the MIMING mint authority.
+pub fn find_mint_authority_address() -> (Pubkey, u8) {
+    find_address(&[MINT_AUTHORITY_SEED])
+}
+
+/// Returns the address and bump of the receipt NFT's mint for the deposit recorded as ledger entry `id`.
+pub fn find_deposit_receipt_mint_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[DEPOSIT_RECEIPT_MINT_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the PDA that mints receipt NFTs and holds their metadata's update authority.
+pub fn find_deposit_receipt_authority_address() -> (Pubkey, u8) {
+    find_address(&[DEPOSIT_RECEIPT_AUTHORITY_SEED])
+}
+
+/// Returns the address and bump of the `VaultDepositReceiptPageAccount` marking the receipts minted for the ledger entries
+/// of `page`.
+pub fn find_deposit_receipt_page_address(page: u64) -> (Pubkey, u8) {
+    find_address(&[DEPOSIT_RECEIPT_PAGE_SEED, page.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `HubRegistryAccount`.
+pub fn find_hub_registry_address() -> (Pubkey, u8) {
+    find_address(&[HUB_REGISTRY_SEED])
+}
+
+/// Returns the address and bump of the `VaultBridgeStatsAccount`.
+pub fn find_bridge_stats_address() -> (Pubkey, u8) {
+    find_address(&[BRIDGE_STATS_SEED])
+}
+
+/// Returns the address and bump of the `VaultBridgeSequenceAccount` counting the messages exchanged with `chain`.
+pub fn find_bridge_sequence_address(chain: u16) -> (Pubkey, u8) {
+    find_address(&[BRIDGE_SEQUENCE_SEED, chain.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `VaultConsumedMessagePageAccount` marking the consumed messages of `chain` in
+/// `page`.
+pub fn find_consumed_message_page_address(chain: u16, page: u64) -> (Pubkey, u8) {
+    find_address(&[CONSUMED_MESSAGE_PAGE_SEED, chain.to_le_bytes().as_ref(), page.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `HubStateRootsAccount`.
+pub fn find_hub_state_roots_address() -> (Pubkey, u8) {
+    find_address(&[HUB_STATE_ROOTS_SEED])
+}
+
+/// Returns the address and bump of the `IdentifierAccount` numbering vault transfer proposals.
+pub fn find_transfer_proposal_identifier_address() -> (Pubkey, u8) {
+    find_address(&[TRANSFER_PROPOSAL_IDENTIFIER_SEED])
+}
+
+/// Returns the address and bump of the `VaultTransferProposalAccount` with identifier `id`.
+pub fn find_transfer_proposal_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[TRANSFER_PROPOSAL_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingVaultDelegationAccount` of the vault's `stake_account`.
+pub fn find_vault_delegation_address(stake_account: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[VAULT_DELEGATION_SEED, stake_account.as_ref()])
+}
+
+/// Returns the address and bump of the vault stake account created by the staking proposal `proposal_id`.
+pub fn find_vault_stake_address(proposal_id: u64) -> (Pubkey, u8) {
+    find_address(&[VAULT_STAKE_SEED, proposal_id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingConfigAccount`.
+pub fn find_staking_config_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_CONFIG_SEED])
+}
+
+/// Returns the address and bump of the authority that freezes, thaws, and signs for staked tokens.
+pub fn find_staking_authority_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_AUTHORITY_SEED])
+}
+
+/// Returns the address and bump of the `StakingPoolAccount`.
+pub fn find_staking_pool_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_POOL_SEED])
+}
+
+/// Returns the address and bump of the `StakingActivityAccount`.
+pub fn find_staking_activity_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_ACTIVITY_SEED])
+}
+
+/// Returns the address and bump of the `StakingPairVaultAccount`.
+pub fn find_staking_pair_vault_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_PAIR_VAULT_SEED])
+}
+
+/// Returns the address and bump of the `StakingRewardScheduleAccount`.
+pub fn find_staking_reward_schedule_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_REWARD_SCHEDULE_SEED])
+}
+
+/// Returns the address and bump of the insurance fund.
+pub fn find_insurance_fund_address() -> (Pubkey, u8) {
+    find_address(&[INSURANCE_FUND_SEED])
+}
+
+/// Returns the address and bump of the `StakingUserAccount` of `staker`.
+pub fn find_staking_user_address(staker: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_USER_SEED, staker.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingRegistryAccount` of `staker`'s position `position_index`.
+pub fn find_staking_registry_address(staker: &Pubkey, position_index: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_REGISTRY_SEED, staker.as_ref(), position_index.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingReferrerAccount` of `referrer`.
+pub fn find_staking_referrer_address(referrer: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_REFERRER_SEED, referrer.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingMintConfigAccount` of `mint`.
+pub fn find_staking_mint_config_address(mint: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_MINT_CONFIG_SEED, mint.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingUnstakeQueueAccount` of `mint`.
+pub fn find_staking_unstake_queue_address(mint: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_UNSTAKE_QUEUE_SEED, mint.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingCustodianAccount` allowlisting `custodian`.
+pub fn find_staking_custodian_address(custodian: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_CUSTODIAN_SEED, custodian.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingValidatorAccount` allowlisting `vote_account`.
+pub fn find_staking_validator_address(vote_account: &Pubkey) -> (Pubkey, u8) {
+    find_address(&[STAKING_VALIDATOR_SEED, vote_account.as_ref()])
+}
+
+/// Returns the address and bump of the `StakingEpochSnapshotAccount` of `epoch`.
+pub fn find_staking_snapshot_address(epoch: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_SNAPSHOT_SEED, epoch.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingSolDistributionAccount` of `epoch`.
+pub fn find_staking_sol_distribution_address(epoch: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_SOL_DISTRIBUTION_SEED, epoch.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingSolClaimAccount` of `staker`'s position `position_index` in `epoch`.
+pub fn find_staking_sol_claim_address(epoch: u64, staker: &Pubkey, position_index: u64) -> (Pubkey, u8) {
+    find_address(&[
+        STAKING_SOL_CLAIM_SEED,
+        epoch.to_le_bytes().as_ref(),
+        staker.as_ref(),
+        position_index.to_le_bytes().as_ref(),
+    ])
+}
+
+/// Returns the address and bump of the `IdentifierAccount` numbering staking proposals.
+pub fn find_staking_proposal_identifier_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_PROPOSAL_IDENTIFIER_SEED])
+}
+
+/// Returns the address and bump of the `StakingProposalAccount` with identifier `id`.
+pub fn find_staking_proposal_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_PROPOSAL_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `IdentifierAccount` numbering staking reports.
+pub fn find_staking_report_identifier_address() -> (Pubkey, u8) {
+    find_address(&[STAKING_REPORT_IDENTIFIER_SEED])
+}
+
+/// Returns the address and bump of the `StakingReportAccount` with identifier `id`.
+pub fn find_staking_report_address(id: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_REPORT_SEED, id.to_le_bytes().as_ref()])
+}
+
+/// Returns the address and bump of the `StakingSlashDisputeAccount` of the slash proposal `proposal_id`.
+pub fn find_staking_slash_dispute_address(proposal_id: u64) -> (Pubkey, u8) {
+    find_address(&[STAKING_SLASH_DISPUTE_SEED, proposal_id.to_le_bytes().as_ref()])
+}
@@ -0,0 +1,48 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+/// Returns the space of an account whose `InitSpace` size, `init_space`, holds up to `max_len` items of `item_space`
+/// bytes in a `Vec`, when it is allocated for `len` of them instead.
+pub const fn space_for_len(init_space: usize, max_len: usize, item_space: usize, len: usize) -> usize {
+    init_space - max_len.saturating_sub(len) * item_space
+}
+
+/// Grows `account` to at least `space` bytes, charging `payer` for the additional rent.
+///
+/// Accounts holding a `Vec` are allocated for the items they hold rather than for the maximum they could hold, and
+/// grown by the instruction that appends to them, so the caller pushing an item pays for its space. Accounts that are
+/// already large enough, such as those allocated to the maximum before, are left untouched.
+///
+/// ## Errors
+///
+/// Returns an error if the rent transfer or the reallocation fails.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_top_up = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    account.realloc(space, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::multisig::MAX_SIGNERS;
+
+const _: () = assert!(MAX_SIGNERS <= u16::BITS as usize);
+
+/// Returns the bit standing for `signer` in a signature bitmap over `required_signers`, or `None` if `signer` is not
+/// one of them.
+///
+/// Bit `i` of the bitmap is set once the `i`-th required signer has signed, so duplicate checks and the final
+/// all-signed check are single mask operations instead of scans over the collected signatures.
+pub fn signer_bit(required_signers: &[Pubkey], signer: &Pubkey) -> Option<u16> {
+    required_signers
+        .iter()
+        .position(|required_signer| required_signer == signer)
+        .map(|index| 1 << index)
+}
+
+/// Returns the bitmap in which all `required_count` required signers have signed.
+pub fn full_signer_mask(required_count: usize) -> u16 {
+    ((1u32 << required_count) - 1) as u16
+}
+
+/// Builds the bitmap of the `required_signers` found among `signers`, for proposals recorded before they kept one.
+pub fn signer_mask_of(required_signers: &[Pubkey], signers: &[Pubkey]) -> u16 {
+    signers
+        .iter()
+        .filter_map(|signer| signer_bit(required_signers, signer))
+        .fold(0, |mask, bit| mask | bit)
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    program_config::ProgramConfigAccount,
+    states::{
+        errors::VersioningErrorCode,
+        events::{AccountMigratedEvent, EventModule, StateMutationEvent},
+        pda::PROGRAM_CONFIG_SEED,
+        realloc::grow_account,
+    },
+};
+
+/// An account whose layout carries a trailing `version`, upgraded in place by its `migrate_*` instruction.
+///
+/// Accounts created before the version was added read it as zero from their zero-filled tail, so they are picked up
+/// by the migration like any other older layout.
+pub trait VersionedAccount: AccountSerialize + AccountDeserialize {
+    /// The current layout version, stamped on new accounts and by migrations.
+    const VERSION: u8;
+
+    /// The allocated size of an account in the current layout.
+    const SPACE: usize;
+
+    /// Returns the layout version the account was last written with.
+    fn version(&self) -> u8;
+
+    /// Backfills the fields added since `version()` with their defaults, caches the canonical `bump` of the account's
+    /// address, and stamps `VERSION`.
+    fn upgrade(&mut self, bump: u8);
+}
+
+#[derive(Accounts)]
+pub struct AccountMigration<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Reallocated before it is decoded, since an account awaiting migration may be too small to load; decoding checks its discriminator
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates an account of type `T` created with an older layout to the current one.
+///
+/// This function performs the following actions:
+/// - Tops up the account's rent from the signer and reallocates it to `T::SPACE`, zero-filling the new bytes.
+/// - Decodes it in the current layout, checking its discriminator, where fields past the old allocation read as zero.
+/// - Checks that it does not already use `T::VERSION`.
+/// - Derives the account's address from `seeds`, checking that it matches, and upgrades it in place with the canonical
+///   bump found for them.
+/// - Emits an `AccountMigratedEvent` and the instruction's `StateMutationEvent` as `action` of `module`.
+///
+/// Anyone can crank a migration, since it only backfills defaults.
+///
+/// ## Errors
+///
+/// Returns an error if the program is halted, the account is not a `T`, it already uses the current layout, or `seeds`
+/// do not derive its address.
+pub fn migrate_account<T: VersionedAccount>(
+    ctx: Context<AccountMigration>,
+    seeds: Vec<Vec<u8>>,
+    module: EventModule,
+    action: &str,
+) -> Result<()> {
+    ctx.accounts.program_config.check_not_globally_paused()?;
+
+    let account = ctx.accounts.account.to_account_info();
+    grow_account(
+        &account,
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        T::SPACE,
+    )?;
+
+    let mut data = T::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let previous_version = data.version();
+    require!(
+        previous_version != T::VERSION,
+        VersioningErrorCode::AccountAlreadyMigrated
+    );
+
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let (address, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+    require_keys_eq!(address, account.key(), VersioningErrorCode::InvalidAccountSeeds);
+
+    data.upgrade(bump);
+    data.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    emit!(AccountMigratedEvent {
+        account: account.key(),
+        previous_version,
+        version: data.version(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    StateMutationEvent::emit(module, action, ctx.accounts.signer.key())
+}
@@ -9,22 +9,76 @@
 //! - **Vault Custody:** Securely holds SOL in a program-derived address (PDA) vault account.
 //! - **Teleport (Deposit):** Allows users to deposit SOL into the vault, recording each deposit in a ledger with an associated fee.
 //! - **Multisig Transfer Proposals:** Enables creation, signing, and execution of transfer proposals, requiring approval from a configurable set of multisig signers.
+//! - **Squads Approval:** A Squads vault registered in the program configuration can execute transfer proposals through an
+//!   executed Squads vault transaction, its Squads approval standing in for the multisig signers' signatures.
+//! - **Transfer Memos:** Executions passing the SPL Memo program attach a memo naming the proposal id to the transfer, so
+//!   recipients' wallets and exchanges display it as the payment reference for payroll and vendor payments.
+//! - **Realms Approval:** A Realms governance registered in the program configuration can execute transfer proposals from
+//!   an executed Realms proposal, its voters' approval standing in for the multisig signers' signatures.
 //! - **Ledger Tracking:** Maintains a detailed ledger of all vault transactions, including deposits and transfers, for auditability.
 //! - **Event Emission:** Emits events for all ledger updates to facilitate off-chain tracking and analytics.
+//! - **Staker Fee Discounts:** Teleports from active stakers pay a teleport fee discounted by the staking configuration's fee discount table,
+//!   keyed by the live staked amount of the staking registry passed with the teleport, as long as it is a reward-earning
+//!   stake of the reward mint.
+//! - **Insurance Fund:** Early-exit penalties and slashes collected by the staking module are moved into the insurance fund PDA and
+//!   recorded as `InsuranceDeposit` ledger entries, so staking proceeds share the vault's accounting.
+//! - **SOL Reward Distributions:** Staking governance proposals can fund an epoch's SOL staking rewards from the vault, recorded as
+//!   `RewardDistribution` ledger entries; stakers then claim pro-rata shares through the staking module.
+//! - **Program Configuration:** Teleports and transfer executions require the vault to be enabled in the program configuration,
+//!   which also sets the teleport fee and can pause teleports on their own.
+//! - **Hub Teleports:** A recorded teleport can be posted to the hub chain by its sender as a Wormhole message carrying the
+//!   ledger id, user, amount, and destination; the message's sequence is stored on the ledger entry, which is posted once.
+//!   The destination chain must be a route of the [`hub_registry`](crate::hub_registry), within its teleport limit.
+//! - **Hub Releases:** Releases sent by the hub are completed from VAAs the Wormhole core bridge verified, once per hub sequence,
+//!   releasing SOL from the vault or minting MIMING to the recipient, with an `InboundRelease` ledger entry.
+//! - **Mint on Arrival:** Teleports sent from the hub are completed from VAAs the core bridge verified, sharing the releases'
+//!   receipts so each hub sequence is consumed once, by minting MIMING to the recipient with a `Mint` ledger entry; the
+//!   bridge stats track the supply bridged onto Solana.
+//! - **Message Sequencing:** Each chain's sequence counters number the teleports posted to it and count the messages
+//!   consumed from it with the highest sequence among them, so monitoring spots gaps; consumed message pages mark each
+//!   consumed `(chain, sequence)` pair, so a message is processed once even if the chain's hub emitter changes.
+//! - **Relayer Fees:** While the program configuration sets a relayer fee, the wallet paying for a teleport's post or an
+//!   inbound completion receives that share of the amount, from the vault in SOL or minted in MIMING, and the user or
+//!   recipient the rest, so third-party relayers are paid to keep the spoke in sync with the hub.
+//! - **Deposit Receipts:** Depositors of teleports at or above the threshold set in the program configuration can mint a
+//!   Metaplex receipt NFT whose metadata names the ledger id and carries the deposit's receipt hash, as a portable proof of
+//!   deposit other protocols verify against the receipt mint's address and the hash.
+//! - **Compressed Receipts:** Once governance registers a [`bubblegum`](crate::bubblegum) tree, depositors can instead mint
+//!   the same receipt as a compressed NFT, a leaf of the tree, at a fraction of the cost. Receipt pages mark the deposits
+//!   receipted either way, so each deposit gets one receipt.
+//! - **Validator Delegation:** Vault SOL can be delegated to allowlisted validators through staking governance proposals (see the staking module).
 //!
 //! ## Main Data Structures
 //!
-//! - [`VaultTransaction`]: Enum representing supported vault transactions (Teleport/Deposit, Transfer).
+//! - [`VaultTransaction`]: Enum representing supported vault transactions (Teleport/Deposit, Transfer, Insurance Deposit, Reward Distribution, Inbound Release, Mint).
 //! - [`VaultLedger`]: Struct capturing the details of a single vault transaction, including user, type, amount, and fee.
 //! - [`VaultLedgerAccount`]: On-chain account storing a vault ledger entry.
+//! - [`VaultInboundReceiptAccount`]: Marks a hub message as consumed, with the release it paid out.
+//! - [`VaultBridgeStatsAccount`]: Tracks the MIMING minted on arrival of teleports and MIMING releases from the hub.
+//! - [`VaultDepositReceiptPageAccount`]: Marks the deposits of a page of ledger ids whose receipt was minted.
+//! - [`VaultBridgeSequenceAccount`]: Counts the messages posted to and consumed from a chain.
+//! - [`VaultConsumedMessagePageAccount`]: Marks the consumed messages of a page of a chain's sequences.
 //! - [`VaultTransferProposalAccount`]: Stores a multisig transfer proposal, including required signers, collected signatures, and status.
 //!
 //! ## Instructions
 //!
 //! - [`VaultTeleportInstructions::teleport`]: Deposits SOL into the vault, records the transaction in the ledger, and charges a fee.
+//! - [`VaultTeleportInstructions::post_teleport`]: Posts a recorded teleport to the hub chain through the Wormhole core bridge.
+//! - [`VaultTeleportInstructions::complete_inbound`]: Completes a release from the hub chain verified by the Wormhole core bridge.
+//! - [`VaultTeleportInstructions::complete_inbound_mint`]: Mints MIMING for a teleport from the hub chain verified by the
+//!   Wormhole core bridge.
+//! - [`VaultTeleportInstructions::mint_deposit_receipt`]: Mints a Metaplex receipt NFT for a large teleport to its depositor.
+//! - [`VaultTeleportInstructions::create_receipt_tree`]: Creates a Bubblegum tree delegated to the deposit receipt authority.
+//! - [`VaultTeleportInstructions::mint_compressed_deposit_receipt`]: Mints a compressed receipt NFT for a large teleport to
+//!   its depositor.
 //! - [`VaultTransferProposalInstructions::create_transfer_proposal`]: Creates a new transfer proposal requiring multisig approval.
 //! - [`VaultTransferProposalInstructions::sign_transfer_proposal`]: Allows an authorized signer to sign a pending transfer proposal.
+//! - [`VaultTransferProposalInstructions::create_and_sign_transfer_proposal`]: Creates a transfer proposal and records the
+//!   proposer's signature in the same instruction.
 //! - [`VaultTransferProposalInstructions::execute_transfer_proposal`]: Executes a transfer from the vault if all required signatures are collected, and records the transaction in the ledger.
+//! - [`VaultTransferProposalInstructions::close_transfer_proposal`]: Closes an approved transfer proposal, refunding its rent to the configured recipient.
+//! - [`VaultLedgerInstructions::close_ledger`]: Closes a ledger entry older than the `LEDGER_RETENTION` most recent ones, refunding its rent to the configured recipient.
+//! - [`VaultLedgerInstructions::get_status`]: Returns the vault's balance, switches, teleport fee, and next ledger and transfer proposal ids as return data.
 //!
 //! ## Error Handling
 //!
@@ -32,13 +86,18 @@
 //!
 //! ## Constants
 //!
-//! - `MIMING_FEE`: Fee charged for teleport (deposit) operations.
+//! - `MIMING_FEE`: Reference genesis teleport fee; the fee in force is read from the program configuration.
 //! - `MAX_SIGNERS`: Maximum number of allowed multisig signers (from the multisig module).
-//! - Size constants for account serialization (e.g., `DISCRIMINATOR`, `U64_SIZE`, `PUBKEY_SIZE`, etc.).
+//! - `LEDGER_RETENTION`: Number of most recent ledger entries kept on-chain.
+//! - `DEPOSIT_RECEIPT_SYMBOL`: Metaplex symbol of deposit receipt NFTs.
+//! - `DEPOSIT_RECEIPTS_PER_PAGE`: Number of ledger ids whose receipts one receipt page marks.
+//! - `CONSUMED_MESSAGES_PER_PAGE`: Number of a chain's sequences one consumed message page marks.
+//! - `TRANSFER_MEMO_PREFIX`: Text of the memo attached to executed transfers, before the proposal id.
+//! - Account sizes are derived with `InitSpace`, e.g. `VaultLedgerAccount::INIT_SPACE`.
 //!
 //! ## Usage
 //!
-//! 1. **Initialize** the vault and ledger identifier accounts.
+//! 1. **Initialize** the vault's ledger and transfer proposal identifiers through `initialize_program`, in the genesis module.
 //! 2. **Teleport (Deposit)** SOL into the vault using the `teleport` instruction; each deposit is recorded in the ledger.
 //! 3. **Create a transfer proposal** specifying the recipient and amount, requiring multisig approval.
 //! 4. **Sign the proposal** by collecting signatures from authorized multisig signers.
@@ -48,8 +107,12 @@
 //!
 //! - Only authorized signers can create, sign, or execute transfer proposals.
 //! - All SOL transfers from the vault require multisig approval, preventing unauthorized withdrawals.
-//! - Teleport (deposit) operations require sufficient user balance and charge a fixed fee.
-//! - All ledger entries are immutable and auditable for transparency.
+//! - Teleport (deposit) operations require sufficient user balance and charge the configured fee.
+//! - Teleports are rejected while the program is halted by the guardian's emergency stop.
+//! - Once governance enables the CPI guard, transfer executions invoked through CPI are rejected unless the calling program is
+//!   allowlisted.
+//! - All ledger entries are immutable and auditable for transparency; entries past the retention window can only be closed by
+//!   a multisig signer, and their content remains in the emitted ledger events.
 //!
 //! ## Integration
 //!
@@ -58,47 +121,94 @@
 //! ## Extensibility
 //!
 //! - The module includes a placeholder for Raydium proxy instructions, allowing future integration with DeFi protocols or additional vault operations.
-use anchor_lang::prelude::*;
-use solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hashv, sysvar::instructions as instructions_sysvar},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    memo::{build_memo, BuildMemo, Memo},
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::{
+            accounts::{MasterEdition, Metadata as MetadataAccount},
+            types::DataV2,
+        },
+        CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+    },
+    token::Token,
+    token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface},
+};
 use crate::{
+    require_with_context,
+    nonce::{consume_nonce, WalletNonceAccount},
     states::{
-        constants::{
-            DISCRIMINATOR, U64_SIZE, 
-            ENUM_SIZE, VEC_SIZE, 
-            PUBKEY_SIZE,
-            MIMING_FEE
+        constants::{BASIS_POINTS, PUBKEY_SIZE},
+        events::{
+            AccountClosedEvent, CompressedDepositReceiptMintedEvent, DepositReceiptMintedEvent, EventModule, InboundMessageSequencedEvent, InboundReleaseCompletedEvent, InboundTeleportMintedEvent, StateMutationEvent, TeleportMessagePostedEvent,
+            VaultLedgerLogEvent, VaultTransferPreviewEvent,
         },
-        events::VaultLedgerLogEvent,
-        errors::VaultErrorCode,
+        error_context::with_context,
+        errors::{
+            BubblegumErrorCode, MathErrorCode, MultisigErrorCode, ProgramConfigErrorCode, SimulationErrorCode, VaultErrorCode, WormholeErrorCode,
+        },
+        math::negate_amount,
+        pda::{
+            BRIDGE_SEQUENCE_SEED, BRIDGE_STATS_SEED, CONSUMED_MESSAGE_PAGE_SEED, DEPOSIT_RECEIPT_AUTHORITY_SEED, DEPOSIT_RECEIPT_MINT_SEED, DEPOSIT_RECEIPT_PAGE_SEED, HUB_REGISTRY_SEED, HUB_STATE_ROOTS_SEED, INBOUND_RECEIPT_SEED, LEDGER_IDENTIFIER_SEED,
+            LEDGER_SEED, MINT_AUTHORITY_SEED, MULTISIG_SEED, PROGRAM_CONFIG_SEED, STAKING_CONFIG_SEED, STAKING_REGISTRY_SEED, TELEPORT_MESSAGE_SEED, TRANSFER_PROPOSAL_IDENTIFIER_SEED, TRANSFER_PROPOSAL_SEED,
+            VAULT_SEED, WALLET_NONCE_SEED, WORMHOLE_EMITTER_SEED,
+        },
+        audit::Audited,
+        realloc::{grow_account, space_for_len},
+        signer_set::{full_signer_mask, signer_bit, signer_mask_of},
+        versioning::VersionedAccount,
+    },
+    access_control::{has_role, is_member, Role},
+    hub_registry::HubRegistryAccount,
+    light_client::HubStateRootsAccount,
+    bubblegum::{
+        self, find_tree_config_address, BubblegumAccounts, ReceiptMetadata, BUBBLEGUM_PROGRAM_ID,
+        SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID,
     },
     multisig::{MAX_SIGNERS, MultisigAccount},
+    messaging::{messaging_adapter, OutboundAccounts},
+    program_config::ProgramConfigAccount,
+    realms::check_invoked_by_realms,
+    squads::check_invoked_by_squads,
+    staking::{StakingConfigAccount, StakingRegistryAccount},
+    wormhole::{
+        InboundAsset, InboundRelease, InboundTeleport, TeleportMessage, WORMHOLE_BRIDGE_SEED, WORMHOLE_FEE_COLLECTOR_SEED,
+        WORMHOLE_POSTED_VAA_SEED, WORMHOLE_SEQUENCE_SEED,
+    },
     IdentifierAccount
 };
 
-pub const TRANSACTION_SIZE: usize = DISCRIMINATOR + 
-    PUBKEY_SIZE + 
-    PUBKEY_SIZE + 
-    U64_SIZE;
-
-pub const LEDGER_SIZE: usize = DISCRIMINATOR + 
-    // id
-    U64_SIZE +
-    // user
-    PUBKEY_SIZE + 
-    // token_address
-    PUBKEY_SIZE + 
-    // transaction
-    ENUM_SIZE + TRANSACTION_SIZE + 
-    // amount
-    U64_SIZE; 
+/// Number of most recent ledger entries that are kept on-chain; older entries can be closed to reclaim their rent.
+pub const LEDGER_RETENTION: u64 = 1_000;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Metaplex symbol of deposit receipt NFTs.
+pub const DEPOSIT_RECEIPT_SYMBOL: &str = "MIMRCPT";
+
+/// Number of ledger ids whose receipts one `VaultDepositReceiptPageAccount` marks, one bit each.
+pub const DEPOSIT_RECEIPTS_PER_PAGE: u64 = 2_048;
+
+/// Number of a chain's sequences whose messages one `VaultConsumedMessagePageAccount` marks, one bit each.
+pub const CONSUMED_MESSAGES_PER_PAGE: u64 = 2_048;
+
+/// Text of the memo attached to an executed transfer, followed by the proposal id.
+pub const TRANSFER_MEMO_PREFIX: &str = "Miming vault transfer proposal";
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
 pub enum VaultTransaction {
     Teleport { from: Pubkey, amount: u64  },
     Transfer { to: Pubkey, amount: u64  },
+    InsuranceDeposit { from: Pubkey, mint: Pubkey, amount: u64 },
+    RewardDistribution { epoch: u64, amount: u64 },
+    InboundRelease { sequence: u64, to: Pubkey, amount: u64, asset: InboundAsset },
+    Mint { sequence: u64, to: Pubkey, amount: u64 },
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
 pub struct VaultLedger {
     pub id: u64,
     pub user: Pubkey,
@@ -107,176 +217,2056 @@ pub struct VaultLedger {
     pub miming_fee: u64
 }
 
+impl VaultLedger {
+    /// Returns the hash a deposit receipt carries: the SHA-256 of `"miming_deposit" | id | user | amount`, with integers in
+    /// little-endian order, so anyone holding the ledger entry's values can check a receipt against them.
+    pub fn receipt_hash(&self) -> [u8; 32] {
+        hashv(&[
+            b"miming_deposit",
+            &self.id.to_le_bytes(),
+            self.user.as_ref(),
+            &self.amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Returns the metadata of the deposit's receipt: named after the ledger id, with a URI made of `uri_prefix`, the ledger
+    /// id, and the hex-encoded receipt hash.
+    pub fn receipt_metadata(&self, uri_prefix: &str) -> ReceiptMetadata {
+        let receipt_hash_hex: String = self.receipt_hash().iter().map(|byte| format!("{byte:02x}")).collect();
+
+        ReceiptMetadata {
+            name: format!("Deposit #{}", self.id),
+            symbol: DEPOSIT_RECEIPT_SYMBOL.to_string(),
+            uri: format!("{}{}/{}", uri_prefix, self.id, receipt_hash_hex),
+        }
+    }
+
+    /// Checks that receipts are enabled in `program_config` and that the deposit reaches the receipt threshold.
+    fn check_receipt_eligible(&self, program_config: &ProgramConfigAccount) -> Result<()> {
+        require!(program_config.deposit_receipt_threshold > 0, VaultErrorCode::DepositReceiptsDisabled);
+        require!(
+            self.amount >= program_config.deposit_receipt_threshold,
+            VaultErrorCode::DepositBelowReceiptThreshold
+        );
+
+        Ok(())
+    }
+}
+
 #[account]
+#[derive(InitSpace)]
 pub struct VaultLedgerAccount {
     pub id: u64,
-    pub ledger: VaultLedger
+    pub ledger: VaultLedger,
+    pub version: u8,
+    pub bump: u8,
+    /// Sequence of the Wormhole message carrying a teleport to the hub, once it has been posted.
+    pub wormhole_sequence: Option<u64>,
+    /// Relayer fee the teleport pays whoever posts it, fixed when the teleport is recorded.
+    pub relayer_fee: u64,
+    /// Wormhole chain the teleport is bound for, committed by its sender; zero leaves it to the sender when posting.
+    pub destination_chain: u16,
+    /// Recipient on the destination chain, as a 32-byte Wormhole address, committed with `destination_chain`.
+    pub destination: [u8; 32],
 }
 
 impl VaultLedgerAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // ledger
-        LEDGER_SIZE;
+    /// Writes `transaction` as the ledger entry numbered by `ledger_identifier`, caching the canonical `bump` of its address,
+    /// advances the identifier, and emits a `VaultLedgerLogEvent`.
+    pub fn record(
+        &mut self,
+        ledger_identifier: &mut IdentifierAccount,
+        user: Pubkey,
+        transaction: VaultTransaction,
+        amount: u64,
+        miming_fee: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let id = ledger_identifier.next_id()?;
+        self.id = id;
+        self.ledger = VaultLedger {
+            id,
+            user,
+            transaction,
+            amount,
+            miming_fee,
+        };
+        self.version = Self::VERSION;
+        self.bump = bump;
+        self.wormhole_sequence = None;
+        self.relayer_fee = 0;
+        self.destination_chain = 0;
+        self.destination = [0; 32];
+
+        emit!(VaultLedgerLogEvent {
+            id: ledger_identifier.id,
+            data: self.ledger.clone()
+        });
+
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct VaultInitialization<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
+impl VersionedAccount for VaultLedgerAccount {
+    const VERSION: u8 = 4;
+    const SPACE: usize = 8 + VaultLedgerAccount::INIT_SPACE;
 
-    #[account(init, payer = signer, space = 8 + IdentifierAccount::LEN, seeds = [b"ledger_identifier"], bump)]
-    pub ledger_identifier: Account<'info, IdentifierAccount>,
+    fn version(&self) -> u8 {
+        self.version
+    }
 
-    pub system_program: Program<'info, System>,
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
 }
 
-pub struct VaultInitializationInstructions;
+/// Marks a hub message as consumed, so each release is paid out once.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultInboundReceiptAccount {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub asset: InboundAsset,
+    pub ledger_id: u64,
+    pub completed_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
 
-impl VaultInitializationInstructions {
-    /// Initializes the vault by setting up its initial state.
-    ///
-    /// This function performs the following actions:
-    /// - Sets the `id` field of the `ledger_identifier` account to 0.
-    ///
-    /// ## Arguments
-    ///
-    /// * `ctx` - The context containing the accounts required for vault initialization.
-    ///
-    /// ## Returns
-    ///
-    /// Returns `Ok(())` if the initialization is successful, otherwise returns an error.
-    pub fn initialize(ctx: Context<VaultInitialization>) -> Result<()> {
-        ctx.accounts.ledger_identifier.id = 0;
+impl VersionedAccount for VaultInboundReceiptAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + VaultInboundReceiptAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// Tracks the MIMING minted on arrival of teleports and MIMING releases from the hub, the supply bridged onto Solana.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultBridgeStatsAccount {
+    pub bridged_supply: u64,
+    pub mint_count: u64,
+    pub last_minted_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VaultBridgeStatsAccount {
+    /// Adds a mint of `amount` at `now` to the stats, stamping the version and canonical `bump` of stats created by the
+    /// first mint, and returns the bridged supply.
+    pub fn record_mint(&mut self, amount: u64, now: i64, bump: u8) -> Result<u64> {
+        if self.version == 0 {
+            self.upgrade(bump);
+        }
+        self.bridged_supply = self.bridged_supply.checked_add(amount).ok_or(MathErrorCode::ArithmeticError)?;
+        self.mint_count = self.mint_count.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+        self.last_minted_at = now;
+
+        Ok(self.bridged_supply)
+    }
+}
+
+impl VersionedAccount for VaultBridgeStatsAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + VaultBridgeStatsAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// Marks the deposits of a page of `DEPOSIT_RECEIPTS_PER_PAGE` ledger ids whose receipt was minted, full or compressed, so
+/// each deposit gets one receipt. Compressed receipts have no mint account whose creation would fail on a second mint.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultDepositReceiptPageAccount {
+    pub page: u64,
+    pub minted: [u8; 256],
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VaultDepositReceiptPageAccount {
+    /// Returns the page marking the receipt of ledger entry `id`.
+    pub fn page_of(id: u64) -> u64 {
+        id / DEPOSIT_RECEIPTS_PER_PAGE
+    }
+
+    /// Marks the receipt of ledger entry `id` as minted, initializing a page just created with its canonical `bump`, and
+    /// fails if it already was.
+    pub fn mark_minted(&mut self, id: u64, bump: u8) -> Result<()> {
+        if self.version == 0 {
+            self.page = Self::page_of(id);
+            self.version = Self::VERSION;
+            self.bump = bump;
+        }
+
+        let bit = (id % DEPOSIT_RECEIPTS_PER_PAGE) as usize;
+        let mask = 1u8 << (bit % 8);
+        require!(self.minted[bit / 8] & mask == 0, VaultErrorCode::DepositReceiptAlreadyMinted);
+        self.minted[bit / 8] |= mask;
+
+        Ok(())
+    }
+}
+
+impl VersionedAccount for VaultDepositReceiptPageAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + VaultDepositReceiptPageAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// Counts the messages exchanged with a chain: the sequence of the next teleport posted to it, and the messages consumed
+/// from it with the highest sequence among them, so monitoring can spot sequences skipped or still in flight.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultBridgeSequenceAccount {
+    pub chain: u16,
+    pub next_outbound_sequence: u64,
+    pub inbound_count: u64,
+    pub highest_inbound_sequence: u64,
+    pub last_inbound_at: i64,
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VaultBridgeSequenceAccount {
+    /// Stamps the chain, version, and canonical `bump` of counters created by the message being processed.
+    fn initialize_if_needed(&mut self, chain: u16, bump: u8) {
+        if self.version == 0 {
+            self.chain = chain;
+            self.upgrade(bump);
+        }
+    }
+
+    /// Assigns the next outbound sequence of `chain` to a posted message, and returns it.
+    pub fn next_outbound(&mut self, chain: u16, bump: u8) -> Result<u64> {
+        self.initialize_if_needed(chain, bump);
+        let sequence = self.next_outbound_sequence;
+        self.next_outbound_sequence = sequence.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+
+        Ok(sequence)
+    }
+
+    /// Counts a message of `chain` consumed at `sequence` and `now`, and returns the number of sequences below the highest
+    /// consumed one that were not consumed yet.
+    pub fn record_inbound(&mut self, chain: u16, sequence: u64, now: i64, bump: u8) -> Result<u64> {
+        self.initialize_if_needed(chain, bump);
+        if self.inbound_count == 0 || sequence > self.highest_inbound_sequence {
+            self.highest_inbound_sequence = sequence;
+        }
+        self.inbound_count = self.inbound_count.checked_add(1).ok_or(MathErrorCode::ArithmeticError)?;
+        self.last_inbound_at = now;
+
+        Ok(self.missing_inbound())
+    }
+
+    /// Returns the number of sequences below the highest consumed one that were not consumed yet: messages still in
+    /// flight, skipped by relayers, or sent by the chain's emitter to other chains.
+    pub fn missing_inbound(&self) -> u64 {
+        if self.inbound_count == 0 {
+            return 0;
+        }
+
+        self.highest_inbound_sequence.saturating_sub(self.inbound_count - 1)
+    }
+}
+
+impl VersionedAccount for VaultBridgeSequenceAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + VaultBridgeSequenceAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
+/// Marks the consumed messages of a page of `CONSUMED_MESSAGES_PER_PAGE` sequences of a chain, so each message is
+/// processed once whatever emitter the hub registry names for the chain.
+#[account]
+#[derive(InitSpace)]
+pub struct VaultConsumedMessagePageAccount {
+    pub chain: u16,
+    pub page: u64,
+    pub consumed: [u8; 256],
+    pub version: u8,
+    pub bump: u8,
+}
+
+impl VaultConsumedMessagePageAccount {
+    /// Returns the page marking the message at `sequence`.
+    pub fn page_of(sequence: u64) -> u64 {
+        sequence / CONSUMED_MESSAGES_PER_PAGE
+    }
+
+    /// Returns `true` if the message at `sequence`, which must fall in the page, was consumed.
+    pub fn is_consumed(&self, sequence: u64) -> bool {
+        let bit = (sequence % CONSUMED_MESSAGES_PER_PAGE) as usize;
+        self.consumed[bit / 8] & (1u8 << (bit % 8)) != 0
+    }
+
+    /// Marks the message of `chain` at `sequence` as consumed, initializing a page just created with its canonical `bump`,
+    /// and fails if it already was.
+    pub fn mark_consumed(&mut self, chain: u16, sequence: u64, bump: u8) -> Result<()> {
+        if self.version == 0 {
+            self.chain = chain;
+            self.page = Self::page_of(sequence);
+            self.upgrade(bump);
+        }
+
+        require!(!self.is_consumed(sequence), WormholeErrorCode::MessageAlreadyConsumed);
+        let bit = (sequence % CONSUMED_MESSAGES_PER_PAGE) as usize;
+        self.consumed[bit / 8] |= 1u8 << (bit % 8);
 
         Ok(())
     }
 }
 
+impl VersionedAccount for VaultConsumedMessagePageAccount {
+    const VERSION: u8 = 1;
+    const SPACE: usize = 8 + VaultConsumedMessagePageAccount::INIT_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
+}
+
 #[derive(Accounts)]
 pub struct VaultTeleport<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// CHECK: This is the PDA authority for the vault, no need to deserialize
     #[account(
         mut,
-        seeds = [b"vault"],
+        seeds = [VAULT_SEED],
         bump
     )]
     pub vault: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
     pub ledger_identifier: Account<'info, IdentifierAccount>,
 
     #[account(
         init_if_needed,
-        payer = signer,
-        space = 8 + VaultLedgerAccount::LEN,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
         seeds = [
-            b"ledger", 
+            LEDGER_SEED, 
             ledger_identifier.id.to_le_bytes().as_ref()
         ],
         bump
     )]
     pub ledger: Account<'info, VaultLedgerAccount>,
 
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump
+    )]
+    pub staking_config: Option<Account<'info, StakingConfigAccount>>,
+
+    #[account(
+        seeds = [
+            STAKING_REGISTRY_SEED,
+            signer.key().as_ref(),
+            staking_registry.position_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub staking_registry: Option<Account<'info, StakingRegistryAccount>>,
+
+    /// CHECK: The Pyth SOL/USD price update a USD-denominated fee is converted with, decoded by `OraclePrice::load_pyth`
+    pub sol_usd_price_update: Option<AccountInfo<'info>>,
+
+    /// CHECK: The Switchboard SOL/USD pull feed backing the price update, decoded by `OraclePrice::load_switchboard`
+    pub sol_usd_fallback_feed: Option<AccountInfo<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub struct VaultTeleportInstructions;
+#[derive(Accounts)]
+#[instruction(destination_chain: u16)]
+pub struct VaultPostTeleport<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
 
-impl VaultTeleportInstructions {
-    /// Teleports SOL from the signer to the vault, records the transaction in the ledger, and emits an event.
-    ///
-    /// This function performs the following steps:
-    /// - Checks that the signer has enough SOL to cover the requested amount plus the `MIMING_FEE`.
-    /// - Transfers the total amount (requested amount + fee) from the signer to the vault account.
-    /// - Increments the ledger identifier to ensure unique transaction IDs.
-    /// - Records the teleport transaction in the ledger, including the user, amount, and fee.
-    /// - Emits a `VaultLedgerEvent` with the transaction details.
-    ///
-    /// ## Arguments
-    ///
-    /// * `ctx` - The context containing all accounts required for the teleport operation, including the signer, vault, ledger, and ledger identifier.
-    /// * `amount` - The amount of SOL to teleport (excluding the fee).
-    ///
-    /// ## Returns
-    ///
-    /// Returns `Ok(())` if the teleport operation is successful, otherwise returns an error (e.g., if the signer has insufficient balance).
-    pub fn teleport(ctx: Context<VaultTeleport>, amount: u64) -> Result<()> {
-        let signer = &ctx.accounts.signer;
-        let total_amount = amount + MIMING_FEE;
-        let signer_sol_balance = signer.to_account_info().lamports();
-        
-        require!(
-            signer_sol_balance >= total_amount,
-            VaultErrorCode::InsufficientSolBalance
-        );
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
-        let vault = &ctx.accounts.vault;
-        let sol_transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-            &signer.key(),
-            &vault.key(),
-            total_amount,
-        );
+    #[account(
+        mut,
+        seeds = [
+            LEDGER_SEED,
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump = ledger.bump,
+        constraint = matches!(ledger.ledger.transaction, VaultTransaction::Teleport { .. }) @ WormholeErrorCode::NotATeleport,
+        constraint = ledger.wormhole_sequence.is_none() @ WormholeErrorCode::MessageAlreadyPosted
+    )]
+    pub ledger: Account<'info, VaultLedgerAccount>,
 
-        anchor_lang::solana_program::program::invoke(
-            &sol_transfer_instruction,
-            &[signer.to_account_info(), vault.to_account_info()],
-        )?;
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
 
-        let ledger_identifier = &mut ctx.accounts.ledger_identifier;
+    #[account(
+        seeds = [HUB_REGISTRY_SEED],
+        bump = hub_registry.bump
+    )]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
 
-        let ledger = &mut ctx.accounts.ledger;
-        ledger.ledger = VaultLedger {
-            id: ledger_identifier.id,
-            user: signer.key(),
-            transaction: VaultTransaction::Teleport { 
-                from: signer.key(), 
-                amount: amount
-            },
-            amount: amount,
-            miming_fee: MIMING_FEE,
-        };
-        
-        ledger_identifier.id += 1;
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultBridgeSequenceAccount::SPACE,
+        seeds = [
+            BRIDGE_SEQUENCE_SEED,
+            destination_chain.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub outbound_sequence: Box<Account<'info, VaultBridgeSequenceAccount>>,
 
-        emit!(VaultLedgerLogEvent {
-            id: ledger_identifier.id,
-            data: ledger.ledger.clone()
-        });
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: The core bridge's configuration, read for the message fee and written by the core bridge
+    #[account(
+        mut,
+        seeds = [WORMHOLE_BRIDGE_SEED],
+        bump,
+        seeds::program = program_config.messaging_endpoint()
+    )]
+    pub wormhole_bridge: AccountInfo<'info>,
+
+    /// CHECK: The message account created by the core bridge, signed for with the ledger entry's seeds
+    #[account(
+        mut,
+        seeds = [
+            TELEPORT_MESSAGE_SEED,
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub wormhole_message: AccountInfo<'info>,
+
+    /// CHECK: The PDA the program's messages are emitted from, no need to deserialize
+    #[account(
+        seeds = [WORMHOLE_EMITTER_SEED],
+        bump
+    )]
+    pub wormhole_emitter: AccountInfo<'info>,
+
+    /// CHECK: The emitter's sequence tracker, read for the message's sequence and advanced by the core bridge
+    #[account(
+        mut,
+        seeds = [
+            WORMHOLE_SEQUENCE_SEED,
+            wormhole_emitter.key().as_ref()
+        ],
+        bump,
+        seeds::program = program_config.messaging_endpoint()
+    )]
+    pub wormhole_sequence: AccountInfo<'info>,
+
+    /// CHECK: The core bridge's fee collector, only credited with the message fee
+    #[account(
+        mut,
+        seeds = [WORMHOLE_FEE_COLLECTOR_SEED],
+        bump,
+        seeds::program = program_config.messaging_endpoint()
+    )]
+    pub wormhole_fee_collector: AccountInfo<'info>,
+
+    /// CHECK: The messaging endpoint configured in the program configuration, checked by address
+    #[account(address = program_config.messaging_endpoint())]
+    pub wormhole_program: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VaultMintDepositReceipt<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            LEDGER_SEED,
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump = ledger.bump,
+        constraint = matches!(ledger.ledger.transaction, VaultTransaction::Teleport { .. }) @ VaultErrorCode::NotADeposit,
+        constraint = ledger.ledger.user == signer.key() @ VaultErrorCode::NotDepositor
+    )]
+    pub ledger: Box<Account<'info, VaultLedgerAccount>>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfigAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultDepositReceiptPageAccount::INIT_SPACE,
+        seeds = [
+            DEPOSIT_RECEIPT_PAGE_SEED,
+            VaultDepositReceiptPageAccount::page_of(ledger.ledger.id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub receipt_page: Box<Account<'info, VaultDepositReceiptPageAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            DEPOSIT_RECEIPT_MINT_SEED,
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = receipt_authority,
+        mint::freeze_authority = receipt_authority,
+        mint::token_program = token_program
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    pub receipt_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The PDA minting receipts and holding their metadata's update authority, no need to deserialize
+    #[account(
+        seeds = [DEPOSIT_RECEIPT_AUTHORITY_SEED],
+        bump
+    )]
+    pub receipt_authority: AccountInfo<'info>,
+
+    /// CHECK: The receipt's metadata account, created by the token metadata program at its derived address
+    #[account(
+        mut,
+        address = MetadataAccount::find_pda(&receipt_mint.key()).0
+    )]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: The receipt's master edition account, created by the token metadata program at its derived address
+    #[account(
+        mut,
+        address = MasterEdition::find_pda(&receipt_mint.key()).0
+    )]
+    pub master_edition: AccountInfo<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct VaultCreateReceiptTree<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The PDA created as the tree's creator and delegate, no need to deserialize
+    #[account(
+        seeds = [DEPOSIT_RECEIPT_AUTHORITY_SEED],
+        bump
+    )]
+    pub receipt_authority: AccountInfo<'info>,
+
+    /// CHECK: The tree's Bubblegum configuration, created by Bubblegum at its derived address
+    #[account(
+        mut,
+        address = find_tree_config_address(&merkle_tree.key())
+    )]
+    pub tree_config: AccountInfo<'info>,
+
+    /// CHECK: The merkle tree account, allocated to the compression program beforehand and initialized by it
+    #[account(
+        mut,
+        owner = SPL_ACCOUNT_COMPRESSION_ID
+    )]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: The SPL no-op program, checked by address
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: AccountInfo<'info>,
+
+    /// CHECK: The SPL account compression program, checked by address
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: AccountInfo<'info>,
+
+    /// CHECK: The Bubblegum program, checked by address
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VaultMintCompressedDepositReceipt<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            LEDGER_SEED,
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump = ledger.bump,
+        constraint = matches!(ledger.ledger.transaction, VaultTransaction::Teleport { .. }) @ VaultErrorCode::NotADeposit,
+        constraint = ledger.ledger.user == signer.key() @ VaultErrorCode::NotDepositor
+    )]
+    pub ledger: Box<Account<'info, VaultLedgerAccount>>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfigAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultDepositReceiptPageAccount::INIT_SPACE,
+        seeds = [
+            DEPOSIT_RECEIPT_PAGE_SEED,
+            VaultDepositReceiptPageAccount::page_of(ledger.ledger.id).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub receipt_page: Box<Account<'info, VaultDepositReceiptPageAccount>>,
+
+    /// CHECK: The PDA delegated to mint into the receipt tree, no need to deserialize
+    #[account(
+        seeds = [DEPOSIT_RECEIPT_AUTHORITY_SEED],
+        bump
+    )]
+    pub receipt_authority: AccountInfo<'info>,
+
+    /// CHECK: The receipt tree's Bubblegum configuration, checked by address
+    #[account(
+        mut,
+        address = find_tree_config_address(&merkle_tree.key())
+    )]
+    pub tree_config: AccountInfo<'info>,
+
+    /// CHECK: The receipt tree registered in the program configuration, checked in the handler
+    #[account(mut)]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: The SPL no-op program, checked by address
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: AccountInfo<'info>,
+
+    /// CHECK: The SPL account compression program, checked by address
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: AccountInfo<'info>,
+
+    /// CHECK: The Bubblegum program, checked by address
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32], sequence: u64, height: u64, proof: Vec<[u8; 32]>)]
+pub struct VaultCompleteInbound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfigAccount>>,
+
+    #[account(
+        seeds = [HUB_REGISTRY_SEED],
+        bump = hub_registry.bump
+    )]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
+
+    /// CHECK: The VAA posted by the core bridge once it verified the guardians' signatures, decoded by the configured messaging adapter
+    #[account(
+        seeds = [WORMHOLE_POSTED_VAA_SEED, vaa_hash.as_ref()],
+        bump,
+        seeds::program = program_config.messaging_endpoint()
+    )]
+    pub posted_vaa: AccountInfo<'info>,
+
+    #[account(
+        seeds = [HUB_STATE_ROOTS_SEED],
+        bump = hub_state_roots.bump
+    )]
+    pub hub_state_roots: Box<Account<'info, HubStateRootsAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VaultInboundReceiptAccount::INIT_SPACE,
+        seeds = [
+            INBOUND_RECEIPT_SEED,
+            hub_registry.hub_emitter.as_ref(),
+            sequence.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub inbound_receipt: Box<Account<'info, VaultInboundReceiptAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultConsumedMessagePageAccount::SPACE,
+        seeds = [
+            CONSUMED_MESSAGE_PAGE_SEED,
+            hub_registry.hub_chain.to_le_bytes().as_ref(),
+            VaultConsumedMessagePageAccount::page_of(sequence).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub consumed_page: Box<Account<'info, VaultConsumedMessagePageAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultBridgeSequenceAccount::SPACE,
+        seeds = [
+            BRIDGE_SEQUENCE_SEED,
+            hub_registry.hub_chain.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub inbound_sequence: Box<Account<'info, VaultBridgeSequenceAccount>>,
+
+    /// CHECK: The release's recipient, checked against the VAA's payload and only credited
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// CHECK: This is the PDA authority for the vault, no need to deserialize
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED,
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ledger: Box<Account<'info, VaultLedgerAccount>>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Option<Box<Account<'info, StakingConfigAccount>>>,
+
+    #[account(mut)]
+    pub miming_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub recipient_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub payer_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The PDA holding the MIMING mint authority, no need to deserialize
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: Option<AccountInfo<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultBridgeStatsAccount::SPACE,
+        seeds = [BRIDGE_STATS_SEED],
+        bump
+    )]
+    pub bridge_stats: Option<Box<Account<'info, VaultBridgeStatsAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32], sequence: u64, height: u64, proof: Vec<[u8; 32]>)]
+pub struct VaultCompleteInboundMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfigAccount>>,
+
+    #[account(
+        seeds = [HUB_REGISTRY_SEED],
+        bump = hub_registry.bump
+    )]
+    pub hub_registry: Box<Account<'info, HubRegistryAccount>>,
+
+    /// CHECK: The VAA posted by the core bridge once it verified the guardians' signatures, decoded by the configured messaging adapter
+    #[account(
+        seeds = [WORMHOLE_POSTED_VAA_SEED, vaa_hash.as_ref()],
+        bump,
+        seeds::program = program_config.messaging_endpoint()
+    )]
+    pub posted_vaa: AccountInfo<'info>,
+
+    #[account(
+        seeds = [HUB_STATE_ROOTS_SEED],
+        bump = hub_state_roots.bump
+    )]
+    pub hub_state_roots: Box<Account<'info, HubStateRootsAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VaultInboundReceiptAccount::INIT_SPACE,
+        seeds = [
+            INBOUND_RECEIPT_SEED,
+            hub_registry.hub_emitter.as_ref(),
+            sequence.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub inbound_receipt: Box<Account<'info, VaultInboundReceiptAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultConsumedMessagePageAccount::SPACE,
+        seeds = [
+            CONSUMED_MESSAGE_PAGE_SEED,
+            hub_registry.hub_chain.to_le_bytes().as_ref(),
+            VaultConsumedMessagePageAccount::page_of(sequence).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub consumed_page: Box<Account<'info, VaultConsumedMessagePageAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultBridgeSequenceAccount::SPACE,
+        seeds = [
+            BRIDGE_SEQUENCE_SEED,
+            hub_registry.hub_chain.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub inbound_sequence: Box<Account<'info, VaultBridgeSequenceAccount>>,
+
+    /// CHECK: The teleport's recipient, checked against the VAA's payload and only credited
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [STAKING_CONFIG_SEED],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Box<Account<'info, StakingConfigAccount>>,
+
+    #[account(
+        mut,
+        address = staking_config.reward_mint @ WormholeErrorCode::MintAccountsRequired
+    )]
+    pub miming_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = miming_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The PDA holding the MIMING mint authority, no need to deserialize
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Box<Account<'info, IdentifierAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED,
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ledger: Box<Account<'info, VaultLedgerAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultBridgeStatsAccount::SPACE,
+        seeds = [BRIDGE_STATS_SEED],
+        bump
+    )]
+    pub bridge_stats: Box<Account<'info, VaultBridgeStatsAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VaultCloseLedger<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            LEDGER_SEED, 
+            ledger.ledger.id.to_le_bytes().as_ref()
+        ],
+        bump = ledger.bump
+    )]
+    pub ledger: Account<'info, VaultLedgerAccount>,
+
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) || has_role(&program_config, Role::Relayer, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
+pub struct VaultTeleportInstructions;
+
+impl VaultTeleportInstructions {
+    /// Teleports SOL from the signer to the vault, records the transaction in the ledger, and emits an event.
+    ///
+    /// This function performs the following steps:
+    /// - Checks that the vault is enabled and teleports are not paused in the program configuration.
+    /// - Computes the fee: the program configuration's teleport fee, or its USD fee converted at the SOL/USD price, discounted for active stakers who pass the staking configuration and one of
+    ///   their staking registries, according to the configuration's fee discount table and the registry's live staked amount;
+    ///   only reward-earning stakes of the reward mint are discounted, so a self-frozen stake of another mint is not.
+    /// - Checks that the signer has enough SOL to cover the requested amount plus the fee.
+    /// - Transfers the total amount (requested amount + fee) from the signer to the vault account.
+    /// - Increments the ledger identifier to ensure unique transaction IDs.
+    /// - Records the teleport transaction in the ledger, including the user, amount, and fee, along with the relayer fee
+    ///   the configuration sets for the amount and the destination, so any relayer can post it for that fee.
+    /// - Emits a `VaultLedgerEvent` with the transaction details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing all accounts required for the teleport operation, including the signer, vault, ledger, and ledger identifier,
+    ///   and optionally the staking configuration and the signer's staking registry for the staker fee discount, and the
+    ///   SOL/USD price update and Switchboard fallback feed, one of which is required while the fee is set in USD.
+    /// * `amount` - The amount of SOL to teleport (excluding the fee).
+    /// * `destination_chain` - The Wormhole chain id the teleport is bound for, or zero to pick it when posting the
+    ///   teleport, which then only the signer can do.
+    /// * `destination` - The recipient on the destination chain, as a 32-byte Wormhole address.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the teleport operation is successful, otherwise returns an error (e.g., if the signer has insufficient balance).
+    pub fn teleport(ctx: Context<VaultTeleport>, amount: u64, destination_chain: u16, destination: [u8; 32]) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+        require!(!program_config.teleport_paused, VaultErrorCode::TeleportPaused);
+
+        let signer = &ctx.accounts.signer;
+        let discount_bps = match (&ctx.accounts.staking_config, &ctx.accounts.staking_registry) {
+            (Some(staking_config), Some(staking_registry)) => {
+                staking_config.fee_discount_bps(staking_registry.pooled_amount(staking_config, staking_registry.staked_amount))
+            }
+            _ => 0,
+        };
+        let teleport_fee = program_config.teleport_fee_lamports(
+            ctx.accounts.sol_usd_price_update.as_ref(),
+            ctx.accounts.sol_usd_fallback_feed.as_ref(),
+        )?;
+        let fee_discount = (teleport_fee as u128) * (discount_bps as u128) / (BASIS_POINTS as u128);
+        let miming_fee = teleport_fee
+            .checked_sub(u64::try_from(fee_discount).map_err(|_| error!(MathErrorCode::ArithmeticError))?)
+            .ok_or(MathErrorCode::ArithmeticError)?;
+        let total_amount = amount.checked_add(miming_fee).ok_or(MathErrorCode::ArithmeticError)?;
+        crate::log_debug!("vault_teleport_fee", teleport_fee = teleport_fee, discount_bps = discount_bps, miming_fee = miming_fee);
+        let signer_sol_balance = signer.to_account_info().lamports();
+        
+        require_with_context!(
+            signer_sol_balance >= total_amount,
+            VaultErrorCode::InsufficientSolBalance,
+            signer.key(),
+            total_amount,
+            signer_sol_balance
+        );
+
+        let vault = &ctx.accounts.vault;
+        let sol_transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+            &signer.key(),
+            &vault.key(),
+            total_amount,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &sol_transfer_instruction,
+            &[signer.to_account_info(), vault.to_account_info()],
+        )?;
+
+        let ledger_identifier = &mut ctx.accounts.ledger_identifier;
+
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.ledger = VaultLedger {
+            id: ledger_identifier.next_id()?,
+            user: signer.key(),
+            transaction: VaultTransaction::Teleport { 
+                from: signer.key(), 
+                amount
+            },
+            amount,
+            miming_fee,
+        };
+        ledger.version = VaultLedgerAccount::VERSION;
+        ledger.bump = ctx.bumps.ledger;
+        ledger.wormhole_sequence = None;
+        ledger.relayer_fee = program_config.relayer_fee(amount)?;
+        ledger.destination_chain = destination_chain;
+        ledger.destination = destination;
+
+        emit!(VaultLedgerLogEvent {
+            id: ledger_identifier.id,
+            data: ledger.ledger.clone()
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_teleport", ctx.accounts.signer.key())?;
+        crate::log_info!(
+            "vault_teleport",
+            signer = signer.key(),
+            amount = amount,
+            miming_fee = miming_fee,
+            ledger_id = ledger_identifier.id
+        );
+
+        Ok(())
+    }
+
+    /// Posts a recorded teleport to the hub chain as a Wormhole message, and stores the message's sequence on its ledger entry.
+    ///
+    /// This function performs the following steps:
+    /// - Checks, through the account constraints, that the ledger entry is a teleport that has not been posted yet.
+    /// - Checks that the destination matches the one the teleport committed to, or, for teleports that left it to be
+    ///   picked when posting, that the signer is the teleport's sender.
+    /// - Checks that the vault is enabled and teleports are not paused in the program configuration.
+    /// - Checks that the destination chain is a route of the hub registry and that the teleported amount is within its
+    ///   limit.
+    /// - Pays the core bridge's message fee from the payer, and pays the payer the relayer fee recorded on the ledger
+    ///   entry, carved out of the teleported amount, from the vault.
+    /// - Posts a `TeleportMessage` with the ledger id, user, amount net of the relayer fee, and destination through the
+    ///   messaging adapter
+    ///   configured in the program configuration, signed by the program's emitter PDA, into a message account derived from
+    ///   the ledger id.
+    /// - Stores the sequence the core bridge assigned to the message on the ledger entry, so each teleport is posted once.
+    /// - Assigns the teleport the next outbound sequence of its destination chain, from the chain's sequence counters
+    ///   created, at the payer's expense, by its first message.
+    /// - Emits a `TeleportMessagePostedEvent`.
+    ///
+    /// Anyone can relay a teleport that committed its destination, since the ledger entry decides where it goes and the
+    /// fee its relayer earns; the ledger entry's sequence keeps it from being posted twice.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the payer, the ledger entry, the hub registry, the destination chain's
+    ///   sequence counters, the vault, and the core bridge accounts.
+    /// * `destination_chain` - The Wormhole chain id the teleport is bound for, as committed by the teleport if it did.
+    /// * `destination` - The recipient on the destination chain, as a 32-byte Wormhole address, as committed by the
+    ///   teleport if it did.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the message is posted, otherwise returns an error.
+    pub fn post_teleport(ctx: Context<VaultPostTeleport>, destination_chain: u16, destination: [u8; 32]) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+        require!(!program_config.teleport_paused, VaultErrorCode::TeleportPaused);
+
+        let ledger = &ctx.accounts.ledger;
+        if ledger.destination_chain == 0 {
+            require_keys_eq!(ledger.ledger.user, ctx.accounts.signer.key(), WormholeErrorCode::UnauthorizedSender);
+        } else {
+            require!(
+                destination_chain == ledger.destination_chain && destination == ledger.destination,
+                WormholeErrorCode::DestinationMismatch
+            );
+        }
+        ctx.accounts.hub_registry.check_route(destination_chain, ledger.ledger.amount)?;
+
+        let relayer_fee = ledger.relayer_fee;
+        if relayer_fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.payer.to_account_info(),
+                    },
+                    &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+                ),
+                relayer_fee,
+            )?;
+        }
+
+        let ledger_id = ctx.accounts.ledger.ledger.id;
+        let message = TeleportMessage {
+            ledger_id,
+            user: ctx.accounts.ledger.ledger.user,
+            amount: ctx.accounts.ledger.ledger.amount.checked_sub(relayer_fee).ok_or(MathErrorCode::ArithmeticError)?,
+            destination_chain,
+            destination,
+        };
+
+        let ledger_id_bytes = ledger_id.to_le_bytes();
+        let emitter_seeds: &[&[u8]] = &[WORMHOLE_EMITTER_SEED, &[ctx.bumps.wormhole_emitter]];
+        let message_seeds: &[&[u8]] = &[TELEPORT_MESSAGE_SEED, &ledger_id_bytes, &[ctx.bumps.wormhole_message]];
+
+        let sequence = messaging_adapter(program_config).post_message(
+            OutboundAccounts {
+                config: &ctx.accounts.wormhole_bridge,
+                message: &ctx.accounts.wormhole_message,
+                emitter: &ctx.accounts.wormhole_emitter,
+                sequence: &ctx.accounts.wormhole_sequence,
+                payer: &ctx.accounts.payer.to_account_info(),
+                fee_collector: &ctx.accounts.wormhole_fee_collector,
+                clock: &ctx.accounts.clock.to_account_info(),
+                rent: &ctx.accounts.rent.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                endpoint: &ctx.accounts.wormhole_program,
+            },
+            message.to_payload(),
+            &[emitter_seeds, message_seeds],
+        )?;
+
+        ctx.accounts.ledger.wormhole_sequence = Some(sequence);
+        let chain_sequence = ctx.accounts.outbound_sequence.next_outbound(destination_chain, ctx.bumps.outbound_sequence)?;
+
+        emit!(TeleportMessagePostedEvent {
+            ledger_id,
+            user: message.user,
+            amount: message.amount,
+            destination_chain,
+            destination,
+            sequence,
+            chain_sequence,
+            relayer: ctx.accounts.payer.key(),
+            relayer_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_post_teleport", ctx.accounts.signer.key())?;
+        crate::log_info!("vault_post_teleport", ledger_id = ledger_id, destination_chain = destination_chain, sequence = sequence);
+
+        Ok(())
+    }
+
+    /// Mints a Metaplex receipt NFT to the depositor of a teleport at or above the receipt threshold.
+    ///
+    /// This function performs the following steps:
+    /// - Checks, through the account constraints, that the ledger entry is a teleport sent by the signer, and creates the
+    ///   receipt mint derived from the ledger id, which fails if the receipt was already minted.
+    /// - Checks that the vault is enabled, receipts are enabled, and the teleported amount reaches the receipt threshold
+    ///   in the program configuration.
+    /// - Marks the deposit on its receipt page, failing if a compressed receipt was already minted for it.
+    /// - Mints the receipt to the signer's associated token account through the receipt authority PDA.
+    /// - Creates the receipt's immutable metadata, named after the ledger id, with a URI made of the configured prefix, the
+    ///   ledger id, and the hex-encoded receipt hash, and its master edition with a zero supply, so it stays one of a kind.
+    /// - Emits a `DepositReceiptMintedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the depositor, the ledger entry, the receipt page, the receipt mint, token account,
+    ///   metadata, and master edition to create, and the token metadata program.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the receipt is minted, otherwise returns an error.
+    pub fn mint_deposit_receipt(ctx: Context<VaultMintDepositReceipt>) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+
+        let ledger = &ctx.accounts.ledger.ledger;
+        ledger.check_receipt_eligible(program_config)?;
+        ctx.accounts.receipt_page.mark_minted(ledger.id, ctx.bumps.receipt_page)?;
+
+        let receipt_hash = ledger.receipt_hash();
+        let metadata = ledger.receipt_metadata(&program_config.deposit_receipt_uri_prefix);
+        let authority_seeds: &[&[u8]] = &[DEPOSIT_RECEIPT_AUTHORITY_SEED, &[ctx.bumps.receipt_authority]];
+        let receipt_authority = ctx.accounts.receipt_authority.to_account_info();
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    to: ctx.accounts.receipt_token.to_account_info(),
+                    authority: receipt_authority.clone(),
+                },
+                &[authority_seeds],
+            ),
+            1,
+        )?;
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    mint_authority: receipt_authority.clone(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    update_authority: receipt_authority.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[authority_seeds],
+            ),
+            DataV2 {
+                name: metadata.name,
+                symbol: metadata.symbol,
+                uri: metadata.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            false,
+            true,
+            None,
+        )?;
+
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    update_authority: receipt_authority.clone(),
+                    mint_authority: receipt_authority,
+                    payer: ctx.accounts.payer.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[authority_seeds],
+            ),
+            Some(0),
+        )?;
+
+        emit!(DepositReceiptMintedEvent {
+            ledger_id: ledger.id,
+            user: ledger.user,
+            amount: ledger.amount,
+            mint: ctx.accounts.receipt_mint.key(),
+            receipt_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_mint_deposit_receipt", ctx.accounts.signer.key())?;
+        crate::log_info!("vault_mint_deposit_receipt", ledger_id = ledger.id, mint = ctx.accounts.receipt_mint.key());
+
+        Ok(())
+    }
+
+    /// Creates a Bubblegum tree for compressed deposit receipts, with the receipt authority PDA as its creator and delegate.
+    ///
+    /// This function performs the following steps:
+    /// - Checks, through the account constraints, that the merkle tree is owned by the account compression program, as
+    ///   it is once the caller allocated it with the space its depth and buffer size require.
+    /// - Invokes Bubblegum's `create_tree`, signed by the receipt authority, creating a private tree only the program
+    ///   can mint into.
+    ///
+    /// Anyone can create a tree at their own expense; receipts are only minted into the tree governance registers.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer, the receipt authority, the tree and its Bubblegum configuration, and the
+    ///   Bubblegum, compression, and no-op programs.
+    /// * `max_depth` - The tree's depth, which bounds its number of receipts to `2^max_depth`.
+    /// * `max_buffer_size` - The number of concurrent changes the tree accepts per slot.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the tree is created, otherwise returns an error.
+    pub fn create_receipt_tree(ctx: Context<VaultCreateReceiptTree>, max_depth: u32, max_buffer_size: u32) -> Result<()> {
+        let authority_seeds: &[&[u8]] = &[DEPOSIT_RECEIPT_AUTHORITY_SEED, &[ctx.bumps.receipt_authority]];
+
+        bubblegum::create_tree(
+            BubblegumAccounts {
+                tree_config: &ctx.accounts.tree_config,
+                merkle_tree: &ctx.accounts.merkle_tree,
+                payer: &ctx.accounts.payer.to_account_info(),
+                tree_authority: &ctx.accounts.receipt_authority,
+                log_wrapper: &ctx.accounts.log_wrapper,
+                compression_program: &ctx.accounts.compression_program,
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                bubblegum_program: &ctx.accounts.bubblegum_program,
+            },
+            max_depth,
+            max_buffer_size,
+            &[authority_seeds],
+        )?;
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_create_receipt_tree", ctx.accounts.payer.key())?;
+        crate::log_info!("vault_create_receipt_tree", merkle_tree = ctx.accounts.merkle_tree.key(), max_depth = max_depth);
+
+        Ok(())
+    }
+
+    /// Mints a compressed receipt NFT to the depositor of a teleport at or above the receipt threshold.
+    ///
+    /// This function performs the following steps:
+    /// - Checks, through the account constraints, that the ledger entry is a teleport sent by the signer.
+    /// - Checks that the vault is enabled, receipts are enabled, the teleported amount reaches the receipt threshold, and
+    ///   the merkle tree is the receipt tree registered in the program configuration.
+    /// - Marks the deposit on its receipt page, failing if a receipt, full or compressed, was already minted for it.
+    /// - Invokes Bubblegum's `mint_v1`, signed by the receipt authority, minting a leaf to the signer with the metadata of
+    ///   a full receipt.
+    /// - Emits a `CompressedDepositReceiptMintedEvent`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the depositor, the ledger entry, the receipt page, the receipt tree and its
+    ///   Bubblegum configuration, and the Bubblegum, compression, and no-op programs.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the receipt is minted, otherwise returns an error.
+    pub fn mint_compressed_deposit_receipt(ctx: Context<VaultMintCompressedDepositReceipt>) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+        require!(
+            program_config.deposit_receipt_tree != Pubkey::default(),
+            BubblegumErrorCode::ReceiptTreeNotConfigured
+        );
+        require_keys_eq!(
+            ctx.accounts.merkle_tree.key(),
+            program_config.deposit_receipt_tree,
+            BubblegumErrorCode::InvalidReceiptTree
+        );
+
+        let ledger = &ctx.accounts.ledger.ledger;
+        ledger.check_receipt_eligible(program_config)?;
+        ctx.accounts.receipt_page.mark_minted(ledger.id, ctx.bumps.receipt_page)?;
+
+        let authority_seeds: &[&[u8]] = &[DEPOSIT_RECEIPT_AUTHORITY_SEED, &[ctx.bumps.receipt_authority]];
+
+        bubblegum::mint_v1(
+            BubblegumAccounts {
+                tree_config: &ctx.accounts.tree_config,
+                merkle_tree: &ctx.accounts.merkle_tree,
+                payer: &ctx.accounts.payer.to_account_info(),
+                tree_authority: &ctx.accounts.receipt_authority,
+                log_wrapper: &ctx.accounts.log_wrapper,
+                compression_program: &ctx.accounts.compression_program,
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                bubblegum_program: &ctx.accounts.bubblegum_program,
+            },
+            &ctx.accounts.signer.to_account_info(),
+            ledger.receipt_metadata(&program_config.deposit_receipt_uri_prefix),
+            &[authority_seeds],
+        )?;
+
+        emit!(CompressedDepositReceiptMintedEvent {
+            ledger_id: ledger.id,
+            user: ledger.user,
+            amount: ledger.amount,
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            receipt_hash: ledger.receipt_hash(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_mint_compressed_deposit_receipt", ctx.accounts.signer.key())?;
+        crate::log_info!(
+            "vault_mint_compressed_deposit_receipt",
+            ledger_id = ledger.id,
+            merkle_tree = ctx.accounts.merkle_tree.key()
+        );
+
+        Ok(())
+    }
+
+    /// Completes a release sent by the hub chain, paying it out to its recipient and recording it in the ledger.
+    ///
+    /// This function performs the following steps:
+    /// - Checks that the vault is enabled and teleports are not paused in the program configuration.
+    /// - Decodes, through the configured messaging adapter, the VAA the core bridge posted after verifying the guardians'
+    ///   signatures, and checks that it was emitted by the hub emitter configured in the hub registry and carries the given
+    ///   sequence.
+    /// - Creates the inbound receipt derived from the hub emitter and the sequence, which fails if the sequence was already
+    ///   consumed, so each release is paid out once.
+    /// - Marks the sequence on the hub chain's consumed message page, which fails if it was already consumed under another
+    ///   hub emitter, and counts it in the chain's inbound counters, both created at the payer's expense when needed.
+    /// - Decodes the release and checks that the recipient account matches it.
+    /// - Checks the merkle proof of the release's leaf, built from the sequence and the decoded release, against the hub
+    ///   state root recorded at the given height, so the release must also be part of the hub's committed state.
+    /// - Releases SOL from the vault, as long as it keeps its rent-exempt minimum, or mints MIMING, the staking
+    ///   configuration's reward mint, to the recipient's token account through the program's mint authority, less the
+    ///   configured relayer fee, which is paid to the payer, in MIMING to its token account.
+    /// - Adds MIMING mints to the bridged supply of the bridge stats, created by the first mint, like teleports minted by
+    ///   `complete_inbound_mint`.
+    /// - Records an `InboundRelease` ledger entry: SOL releases as outflows, like transfers, and MIMING mints with a zero
+    ///   amount since they leave the vault's SOL untouched.
+    /// - Emits an `InboundMessageSequencedEvent` and an `InboundReleaseCompletedEvent`.
+    ///
+    /// Anyone can relay a release, since the guardians' signatures and the receipt decide what is paid out and to whom.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer, the hub registry, the posted VAA, the hub state roots, the receipt and
+    ///   ledger entry to create, the consumed message page and inbound counters, the recipient, and, for MIMING releases, the mint, the recipient's and, while a relayer fee is set, the payer's
+    ///   token accounts, the mint authority, the bridge stats, and the token program.
+    /// * `vaa_hash` - The hash of the VAA's body, which the posted VAA's address is derived from.
+    /// * `sequence` - The VAA's sequence, which the receipt's address is derived from.
+    /// * `height` - The hub height of the state root the proof was built against.
+    /// * `proof` - The sibling hashes from the release's leaf up to the root.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the release is paid out, otherwise returns an error.
+    pub fn complete_inbound(
+        ctx: Context<VaultCompleteInbound>,
+        _vaa_hash: [u8; 32],
+        sequence: u64,
+        height: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+        require!(!program_config.teleport_paused, VaultErrorCode::TeleportPaused);
+        let hub_registry = &ctx.accounts.hub_registry;
+        require!(hub_registry.hub_chain != 0, WormholeErrorCode::HubEmitterNotConfigured);
+
+        let message = messaging_adapter(program_config).read_message(&ctx.accounts.posted_vaa)?;
+        hub_registry.check_emitter(message.source_chain, &message.sender)?;
+        require!(message.sequence == sequence, WormholeErrorCode::SequenceMismatch);
+
+        let release = InboundRelease::from_payload(&message.payload)?;
+        require_keys_eq!(ctx.accounts.recipient.key(), release.recipient, WormholeErrorCode::RecipientMismatch);
+        ctx.accounts.hub_state_roots.verify(height, &release.leaf(sequence), &proof)?;
+
+        let relayer_fee = program_config.relayer_fee(release.amount)?;
+        let recipient_amount = release.amount.checked_sub(relayer_fee).ok_or(MathErrorCode::ArithmeticError)?;
+        let completed_at = Clock::get()?.unix_timestamp;
+
+        let ledger_amount = match release.asset {
+            InboundAsset::Sol => {
+                // The vault must stay rent exempt, so only the lamports above its rent-exempt minimum can be released.
+                let rent_minimum = Rent::get()?.minimum_balance(ctx.accounts.vault.data_len());
+                let vault_sol_balance = ctx.accounts.vault.lamports().saturating_sub(rent_minimum);
+                require_with_context!(
+                    vault_sol_balance >= release.amount,
+                    VaultErrorCode::InsufficientSolBalance,
+                    ctx.accounts.vault.key(),
+                    release.amount,
+                    vault_sol_balance
+                );
+
+                anchor_lang::system_program::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.vault.to_account_info(),
+                            to: ctx.accounts.recipient.to_account_info(),
+                        },
+                        &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+                    ),
+                    recipient_amount,
+                )?;
+
+                if relayer_fee > 0 {
+                    anchor_lang::system_program::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.system_program.to_account_info(),
+                            anchor_lang::system_program::Transfer {
+                                from: ctx.accounts.vault.to_account_info(),
+                                to: ctx.accounts.payer.to_account_info(),
+                            },
+                            &[&[VAULT_SEED, &[ctx.bumps.vault]]],
+                        ),
+                        relayer_fee,
+                    )?;
+                }
+
+                negate_amount(release.amount)?
+            }
+            InboundAsset::Miming => {
+                let (
+                    Some(staking_config),
+                    Some(miming_mint),
+                    Some(recipient_token),
+                    Some(mint_authority),
+                    Some(bridge_stats),
+                    Some(token_program),
+                ) = (
+                    &ctx.accounts.staking_config,
+                    &ctx.accounts.miming_mint,
+                    &ctx.accounts.recipient_token,
+                    &ctx.accounts.mint_authority,
+                    &mut ctx.accounts.bridge_stats,
+                    &ctx.accounts.token_program,
+                )
+                else {
+                    return err!(WormholeErrorCode::MintAccountsRequired);
+                };
+                require_keys_eq!(miming_mint.key(), staking_config.reward_mint, WormholeErrorCode::MintAccountsRequired);
+                require_keys_eq!(recipient_token.mint, miming_mint.key(), WormholeErrorCode::MintAccountsRequired);
+                require_keys_eq!(recipient_token.owner, release.recipient, WormholeErrorCode::RecipientMismatch);
+
+                let mint_authority_bump = ctx.bumps.mint_authority.ok_or(WormholeErrorCode::MintAccountsRequired)?;
+                mint_to(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        MintTo {
+                            mint: miming_mint.to_account_info(),
+                            to: recipient_token.to_account_info(),
+                            authority: mint_authority.to_account_info(),
+                        },
+                        &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
+                    ),
+                    recipient_amount,
+                )?;
+
+                if relayer_fee > 0 {
+                    let payer_token = ctx.accounts.payer_token.as_ref().ok_or(WormholeErrorCode::MintAccountsRequired)?;
+                    require_keys_eq!(payer_token.mint, miming_mint.key(), WormholeErrorCode::MintAccountsRequired);
+                    require_keys_eq!(payer_token.owner, ctx.accounts.payer.key(), WormholeErrorCode::MintAccountsRequired);
+
+                    mint_to(
+                        CpiContext::new_with_signer(
+                            token_program.to_account_info(),
+                            MintTo {
+                                mint: miming_mint.to_account_info(),
+                                to: payer_token.to_account_info(),
+                                authority: mint_authority.to_account_info(),
+                            },
+                            &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
+                        ),
+                        relayer_fee,
+                    )?;
+                }
+
+                let bridge_stats_bump = ctx.bumps.bridge_stats.ok_or(WormholeErrorCode::MintAccountsRequired)?;
+                bridge_stats.record_mint(release.amount, completed_at, bridge_stats_bump)?;
+
+                0
+            }
+        };
+
+        let ledger_identifier = &mut ctx.accounts.ledger_identifier;
+        let ledger_id = ledger_identifier.id;
+        ctx.accounts.ledger.record(
+            ledger_identifier,
+            release.recipient,
+            VaultTransaction::InboundRelease {
+                sequence,
+                to: release.recipient,
+                amount: release.amount,
+                asset: release.asset,
+            },
+            ledger_amount,
+            0,
+            ctx.bumps.ledger,
+        )?;
+
+        ctx.accounts.inbound_receipt.set_inner(VaultInboundReceiptAccount {
+            emitter_chain: message.source_chain,
+            sequence,
+            recipient: release.recipient,
+            amount: release.amount,
+            asset: release.asset,
+            ledger_id,
+            completed_at,
+            version: VaultInboundReceiptAccount::VERSION,
+            bump: ctx.bumps.inbound_receipt,
+        });
+
+        ctx.accounts.consumed_page.mark_consumed(message.source_chain, sequence, ctx.bumps.consumed_page)?;
+        let inbound_sequence = &mut ctx.accounts.inbound_sequence;
+        let missing_sequences =
+            inbound_sequence.record_inbound(message.source_chain, sequence, completed_at, ctx.bumps.inbound_sequence)?;
+
+        emit!(InboundMessageSequencedEvent {
+            chain: message.source_chain,
+            sequence,
+            inbound_count: inbound_sequence.inbound_count,
+            highest_sequence: inbound_sequence.highest_inbound_sequence,
+            missing_sequences,
+            timestamp: completed_at,
+        });
+
+        emit!(InboundReleaseCompletedEvent {
+            emitter_chain: message.source_chain,
+            sequence,
+            recipient: release.recipient,
+            amount: release.amount,
+            asset: release.asset,
+            ledger_id,
+            relayer: ctx.accounts.payer.key(),
+            relayer_fee,
+            timestamp: completed_at,
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_complete_inbound", ctx.accounts.payer.key())?;
+        crate::log_info!(
+            "vault_complete_inbound",
+            sequence = sequence,
+            recipient = release.recipient,
+            amount = release.amount,
+            ledger_id = ledger_id
+        );
+
+        Ok(())
+    }
+
+    /// Completes a teleport sent from the hub chain by minting MIMING to its recipient, the counterpart of the hub burning
+    /// it, and records it in the ledger.
+    ///
+    /// This function performs the following steps:
+    /// - Checks that the vault is enabled and teleports are not paused in the program configuration.
+    /// - Decodes, through the configured messaging adapter, the VAA the core bridge posted after verifying the guardians'
+    ///   signatures, and checks that it was emitted by the hub emitter configured in the hub registry and carries the given
+    ///   sequence.
+    /// - Creates the inbound receipt derived from the hub emitter and the sequence, shared with releases, which fails if the
+    ///   sequence was already consumed, so each teleport is minted once.
+    /// - Marks the sequence on the hub chain's consumed message page and counts it in the chain's inbound counters, like
+    ///   releases.
+    /// - Decodes the inbound teleport and checks that the recipient account matches it.
+    /// - Checks the merkle proof of the teleport's leaf, built from the sequence and the decoded teleport, against the hub
+    ///   state root recorded at the given height, so the teleport must also be part of the hub's committed state.
+    /// - Mints the amount of MIMING, the staking configuration's reward mint, to the recipient's associated token account,
+    ///   created at the payer's expense if needed, through the program's mint authority, less the configured relayer fee,
+    ///   which is minted to the payer's MIMING token account, required only while the fee is not zero.
+    /// - Records a `Mint` ledger entry with a zero amount, since it leaves the vault's SOL untouched.
+    /// - Adds the amount to the bridged supply of the bridge stats, created by the first mint.
+    /// - Emits an `InboundMessageSequencedEvent` and an `InboundTeleportMintedEvent`.
+    ///
+    /// Anyone can relay a teleport, since the guardians' signatures and the receipt decide what is minted and to whom.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the payer, the hub registry, the posted VAA, the hub state roots, the receipt and
+    ///   ledger entry to create, the consumed message page and inbound counters, the recipient's token account and optionally the payer's, the
+    ///   mint and its authority, and the bridge stats.
+    /// * `vaa_hash` - The hash of the VAA's body, which the posted VAA's address is derived from.
+    /// * `sequence` - The VAA's sequence, which the receipt's address is derived from.
+    /// * `height` - The hub height of the state root the proof was built against.
+    /// * `proof` - The sibling hashes from the teleport's leaf up to the root.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the teleport is minted, otherwise returns an error.
+    pub fn complete_inbound_mint(
+        ctx: Context<VaultCompleteInboundMint>,
+        _vaa_hash: [u8; 32],
+        sequence: u64,
+        height: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let program_config = &ctx.accounts.program_config;
+        program_config.check_not_globally_paused()?;
+        program_config.check_vault_enabled()?;
+        require!(!program_config.teleport_paused, VaultErrorCode::TeleportPaused);
+        let hub_registry = &ctx.accounts.hub_registry;
+        require!(hub_registry.hub_chain != 0, WormholeErrorCode::HubEmitterNotConfigured);
+
+        let message = messaging_adapter(program_config).read_message(&ctx.accounts.posted_vaa)?;
+        hub_registry.check_emitter(message.source_chain, &message.sender)?;
+        require!(message.sequence == sequence, WormholeErrorCode::SequenceMismatch);
+
+        let teleport = InboundTeleport::from_payload(&message.payload)?;
+        require_keys_eq!(ctx.accounts.recipient.key(), teleport.recipient, WormholeErrorCode::RecipientMismatch);
+        ctx.accounts.hub_state_roots.verify(height, &teleport.leaf(sequence), &proof)?;
+
+        let relayer_fee = program_config.relayer_fee(teleport.amount)?;
+        let recipient_amount = teleport.amount.checked_sub(relayer_fee).ok_or(MathErrorCode::ArithmeticError)?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.miming_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[MINT_AUTHORITY_SEED, &[ctx.bumps.mint_authority]]],
+            ),
+            recipient_amount,
+        )?;
+
+        if relayer_fee > 0 {
+            let payer_token = ctx.accounts.payer_token.as_ref().ok_or(WormholeErrorCode::MintAccountsRequired)?;
+            require_keys_eq!(payer_token.mint, ctx.accounts.miming_mint.key(), WormholeErrorCode::MintAccountsRequired);
+            require_keys_eq!(payer_token.owner, ctx.accounts.payer.key(), WormholeErrorCode::MintAccountsRequired);
+
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.miming_mint.to_account_info(),
+                        to: payer_token.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[&[MINT_AUTHORITY_SEED, &[ctx.bumps.mint_authority]]],
+                ),
+                relayer_fee,
+            )?;
+        }
+
+        let ledger_identifier = &mut ctx.accounts.ledger_identifier;
+        let ledger_id = ledger_identifier.id;
+        ctx.accounts.ledger.record(
+            ledger_identifier,
+            teleport.recipient,
+            VaultTransaction::Mint {
+                sequence,
+                to: teleport.recipient,
+                amount: teleport.amount,
+            },
+            0,
+            0,
+            ctx.bumps.ledger,
+        )?;
+
+        let completed_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.inbound_receipt.set_inner(VaultInboundReceiptAccount {
+            emitter_chain: message.source_chain,
+            sequence,
+            recipient: teleport.recipient,
+            amount: teleport.amount,
+            asset: InboundAsset::Miming,
+            ledger_id,
+            completed_at,
+            version: VaultInboundReceiptAccount::VERSION,
+            bump: ctx.bumps.inbound_receipt,
+        });
+
+        ctx.accounts.consumed_page.mark_consumed(message.source_chain, sequence, ctx.bumps.consumed_page)?;
+        let inbound_sequence = &mut ctx.accounts.inbound_sequence;
+        let missing_sequences =
+            inbound_sequence.record_inbound(message.source_chain, sequence, completed_at, ctx.bumps.inbound_sequence)?;
+
+        emit!(InboundMessageSequencedEvent {
+            chain: message.source_chain,
+            sequence,
+            inbound_count: inbound_sequence.inbound_count,
+            highest_sequence: inbound_sequence.highest_inbound_sequence,
+            missing_sequences,
+            timestamp: completed_at,
+        });
+
+        let bridged_supply = ctx.accounts.bridge_stats.record_mint(teleport.amount, completed_at, ctx.bumps.bridge_stats)?;
+
+        emit!(InboundTeleportMintedEvent {
+            emitter_chain: message.source_chain,
+            sequence,
+            source_ledger_id: teleport.source_ledger_id,
+            sender: teleport.sender,
+            recipient: teleport.recipient,
+            amount: teleport.amount,
+            ledger_id,
+            bridged_supply,
+            relayer: ctx.accounts.payer.key(),
+            relayer_fee,
+            timestamp: completed_at,
+        });
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_complete_inbound_mint", ctx.accounts.payer.key())?;
+        crate::log_info!(
+            "vault_complete_inbound_mint",
+            sequence = sequence,
+            recipient = teleport.recipient,
+            amount = teleport.amount,
+            ledger_id = ledger_id
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq)]
+pub enum VaultTransferProposalStatus {
+    Pending,
+    Approved,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultTransferProposalAccount {
+    pub id: u64,
+    pub transaction: VaultTransaction,
+    #[max_len(MAX_SIGNERS)]
+    pub multisig_required_signers: Vec<Pubkey>,
+    #[max_len(MAX_SIGNERS)]
+    pub multisig_signers: Vec<Pubkey>,
+    pub status: VaultTransferProposalStatus,
+    pub version: u8,
+    pub bump: u8,
+    pub signed_mask: u16,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_actor: Pubkey,
+}
+
+impl VaultTransferProposalAccount {
+    /// Returns the size of a proposal with room for `signer_count` required signers and signatures.
+    pub const fn len_for(signer_count: usize) -> usize {
+        // multisig_required_signers and multisig_signers each hold up to `MAX_SIGNERS` keys
+        space_for_len(Self::INIT_SPACE, 2 * MAX_SIGNERS, PUBKEY_SIZE, 2 * signer_count)
+    }
+
+    /// Rebuilds `signed_mask` from the collected signatures of a proposal recorded before it kept one.
+    pub fn sync_signed_mask(&mut self) {
+        if self.version < Self::VERSION {
+            self.signed_mask = signer_mask_of(&self.multisig_required_signers, &self.multisig_signers);
+        }
+    }
+
+    /// Checks that `signer` may sign the pending proposal: one of its required signers that has not signed yet, or, for
+    /// a proposal without required signers, any key that has not signed yet.
+    pub fn check_can_sign(&self, signer: &Pubkey) -> Result<()> {
+        require!(
+            self.status == VaultTransferProposalStatus::Pending,
+            VaultErrorCode::AlreadyResolved
+        );
+
+        if self.multisig_required_signers.is_empty() {
+            require!(
+                !self.multisig_signers.contains(signer),
+                VaultErrorCode::DuplicateSignature
+            );
+        } else {
+            let signer_bit = signer_bit(&self.multisig_required_signers, signer)
+                .ok_or_else(|| with_context(VaultErrorCode::UnauthorizedSigner, *signer, 0, 0))?;
+            require!(
+                self.signed_mask & signer_bit == 0,
+                VaultErrorCode::DuplicateSignature
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Records `signer`'s signature, setting its bit in the signature bitmap when it is a required signer.
+    pub fn add_signature(&mut self, signer: Pubkey) {
+        if let Some(signer_bit) = signer_bit(&self.multisig_required_signers, &signer) {
+            self.signed_mask |= signer_bit;
+        }
+        self.multisig_signers.push(signer);
+    }
+
+    /// Writes a new pending proposal transferring `amount` lamports to `recipient`, requiring the signatures of
+    /// `required_signers`.
+    pub fn open(&mut self, id: u64, recipient: Pubkey, amount: u64, required_signers: Vec<Pubkey>, bump: u8) {
+        self.id = id;
+        self.transaction = VaultTransaction::Transfer {
+            to: recipient,
+            amount,
+        };
+        self.multisig_required_signers = required_signers;
+        self.multisig_signers = Vec::new();
+        self.signed_mask = 0;
+        self.status = VaultTransferProposalStatus::Pending;
+        self.version = Self::VERSION;
+        self.bump = bump;
+    }
+
+    /// Resolves the pending proposal for execution by `signer`, once every required signer has signed it.
+    ///
+    /// The proposal is marked approved, so its transfer can only be executed once.
+    pub fn resolve(&mut self, signer: &Pubkey) -> Result<()> {
+        require!(
+            self.status == VaultTransferProposalStatus::Pending,
+            VaultErrorCode::AlreadyResolved
+        );
+
+        if !self.multisig_required_signers.is_empty() {
+            require_with_context!(
+                self.multisig_required_signers.contains(signer),
+                VaultErrorCode::UnauthorizedSigner,
+                *signer,
+                0,
+                0
+            );
+        }
+
+        self.sync_signed_mask();
+        require_with_context!(
+            self.signed_mask == full_signer_mask(self.multisig_required_signers.len()),
+            VaultErrorCode::InsufficientSignatures,
+            *signer,
+            self.multisig_required_signers.len() as u64,
+            self.signed_mask.count_ones() as u64
+        );
+
+        self.status = VaultTransferProposalStatus::Approved;
+        Ok(())
+    }
+
+    /// Resolves the pending proposal for execution by an approving authority registered in the program configuration,
+    /// whose own approval stands in for the required signers' signatures.
+    pub fn resolve_by_authority(&mut self) -> Result<()> {
+        require!(
+            self.status == VaultTransferProposalStatus::Pending,
+            VaultErrorCode::AlreadyResolved
+        );
 
+        self.status = VaultTransferProposalStatus::Approved;
         Ok(())
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum VaultTransferProposalStatus {
-    Pending,
-    Approved,
-}
+/// Version 4 appended the audit metadata, which proposals migrated from version 3 leave zeroed until their next write.
+impl VersionedAccount for VaultTransferProposalAccount {
+    const VERSION: u8 = 4;
+    const SPACE: usize = 8 + VaultTransferProposalAccount::INIT_SPACE;
 
-#[account]
-pub struct VaultTransferProposalAccount {
-    pub id: u64,
-    pub transaction: VaultTransaction,
-    pub multisig_required_signers: Vec<Pubkey>,
-    pub multisig_signers: Vec<Pubkey>,
-    pub status: VaultTransferProposalStatus,
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self, bump: u8) {
+        self.sync_signed_mask();
+        self.bump = bump;
+        self.version = Self::VERSION;
+    }
 }
 
-impl VaultTransferProposalAccount {
-    pub const LEN: usize = DISCRIMINATOR + 
-        // id
-        U64_SIZE + 
-        // transaction
-        ENUM_SIZE + (PUBKEY_SIZE + PUBKEY_SIZE + U64_SIZE) + 
-        // multisig_required_signers
-        VEC_SIZE + (MAX_SIGNERS * PUBKEY_SIZE) +  
-        // multisig_signers
-        VEC_SIZE + (MAX_SIGNERS * PUBKEY_SIZE) +  
-        // status
-        ENUM_SIZE; 
+impl Audited for VaultTransferProposalAccount {
+    fn audit_fields(&mut self) -> (&mut i64, &mut i64, &mut Pubkey) {
+        (&mut self.created_at, &mut self.updated_at, &mut self.last_actor)
+    }
 }
 
 #[derive(Accounts)]
@@ -285,17 +2275,28 @@ pub struct VaultCreateTransferProposal<'info> {
     pub signer: Signer<'info>,
 
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
     pub current_multisig: Account<'info, MultisigAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [TRANSFER_PROPOSAL_IDENTIFIER_SEED],
+        bump = transfer_proposal_identifier.bump
+    )]
     pub transfer_proposal_identifier: Account<'info, IdentifierAccount>,
 
     #[account(
         init_if_needed,
-        payer = signer,
-        space = 8 + VaultTransferProposalAccount::LEN,
+        payer = payer,
+        space = 8 + VaultTransferProposalAccount::len_for(current_multisig.signers.len()),
         seeds = [
-            b"transfer_proposal", 
+            TRANSFER_PROPOSAL_SEED, 
             transfer_proposal_identifier.id.to_le_bytes().as_ref()
         ],
         bump
@@ -306,16 +2307,78 @@ pub struct VaultCreateTransferProposal<'info> {
 }
 
 #[derive(Accounts)]
-pub struct VaultSignTransferProposal<'info> {
+pub struct VaultCreateAndSignTransferProposal<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
     pub current_multisig: Account<'info, MultisigAccount>,
 
+    #[account(
+        mut,
+        seeds = [TRANSFER_PROPOSAL_IDENTIFIER_SEED],
+        bump = transfer_proposal_identifier.bump
+    )]
+    pub transfer_proposal_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VaultTransferProposalAccount::len_for(current_multisig.signers.len().max(1)),
+        seeds = [
+            TRANSFER_PROPOSAL_SEED,
+            transfer_proposal_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub transfer_proposal: Account<'info, VaultTransferProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VaultSignTransferProposal<'info> {
     #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            TRANSFER_PROPOSAL_SEED,
+            current_transfer_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_transfer_proposal.bump
+    )]
     pub current_transfer_proposal: Account<'info, VaultTransferProposalAccount>,
 
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -325,28 +2388,111 @@ pub struct VaultExecuteTransferProposal<'info> {
     pub signer: Signer<'info>,
 
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
     pub current_multisig: Account<'info, MultisigAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [
+            TRANSFER_PROPOSAL_SEED,
+            current_transfer_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_transfer_proposal.bump
+    )]
     pub current_transfer_proposal: Account<'info, VaultTransferProposalAccount>,
 
     /// CHECK: This is the PDA authority for the vault, no need to deserialize
     #[account(
         mut,
-        seeds = [b"vault"],
+        seeds = [VAULT_SEED],
         bump
     )]
     pub vault: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
     pub ledger_identifier: Account<'info, IdentifierAccount>,
 
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VaultLedgerAccount::INIT_SPACE,
+        seeds = [
+            LEDGER_SEED, 
+            ledger_identifier.id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
     pub ledger: Account<'info, VaultLedgerAccount>,
 
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    /// CHECK: This is the instructions sysvar, read to identify the program calling this instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_NONCE_SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub wallet_nonce: Option<Account<'info, WalletNonceAccount>>,
+
+    pub memo_program: Option<Program<'info, Memo>>,
+
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct VaultCloseTransferProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = current_multisig.bump
+    )]
+    pub current_multisig: Account<'info, MultisigAccount>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            TRANSFER_PROPOSAL_SEED,
+            current_transfer_proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = current_transfer_proposal.bump
+    )]
+    pub current_transfer_proposal: Account<'info, VaultTransferProposalAccount>,
+
+    /// CHECK: The rent recipient configured in the program configuration, only credited with the closed account's rent
+    #[account(
+        mut,
+        address = program_config.rent_recipient
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.rent_recipient != Pubkey::default() @ ProgramConfigErrorCode::RentRecipientNotConfigured,
+        constraint = is_member(&current_multisig, signer.key) @ MultisigErrorCode::UnauthorizedSigner
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+}
+
 pub struct VaultTransferProposalInstructions;
 
 impl VaultTransferProposalInstructions {
@@ -378,16 +2524,69 @@ impl VaultTransferProposalInstructions {
         let multisig_required_signers: Vec<Pubkey> = current_multisig.signers.iter().map(|d| d.pubkey).collect();
 
         let transfer_proposal = &mut ctx.accounts.transfer_proposal;
-        transfer_proposal.id = transfer_proposal_identifier.id;
-        transfer_proposal.transaction = VaultTransaction::Transfer { 
-            to: recipient, 
-            amount: amount 
-        };
-        transfer_proposal.multisig_required_signers = multisig_required_signers;
-        transfer_proposal.multisig_signers = Vec::new();
-        transfer_proposal.status = VaultTransferProposalStatus::Pending;
+        transfer_proposal.open(
+            transfer_proposal_identifier.next_id()?,
+            recipient,
+            amount,
+            multisig_required_signers,
+            ctx.bumps.transfer_proposal,
+        );
+        transfer_proposal.record_creation(ctx.accounts.signer.key())?;
 
-        transfer_proposal_identifier.id += 1;
+        StateMutationEvent::emit(EventModule::Vault, "vault_create_transfer_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Creates a new transfer proposal and records the proposer's signature on it, saving the proposer a separate
+    /// `sign_transfer_proposal` transaction.
+    ///
+    /// This function performs the following actions:
+    /// - Checks and advances the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
+    /// - Creates the proposal as [`create_transfer_proposal`](VaultTransferProposalInstructions::create_transfer_proposal)
+    ///   does, with room for at least the proposer's signature.
+    /// - Ensures the signer is among the required multisig signers (if any are specified).
+    /// - Sets the signer's bit in the bitmap and appends their public key to the proposal's signers.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required to create the transfer proposal and the signer's nonce.
+    /// * `recipient` - The public key of the recipient to receive the transfer.
+    /// * `amount` - The amount of lamports to be transferred.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is created and signed successfully, otherwise returns an error.
+    pub fn create_and_sign_transfer_proposal(
+        ctx: Context<VaultCreateAndSignTransferProposal>,
+        recipient: Pubkey,
+        amount: u64,
+        expected_nonce: Option<u64>,
+    ) -> Result<()> {
+        let signer_key = ctx.accounts.signer.key();
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
+        let transfer_proposal_identifier = &mut ctx.accounts.transfer_proposal_identifier;
+
+        let current_multisig = &ctx.accounts.current_multisig;
+        let multisig_required_signers: Vec<Pubkey> = current_multisig.signers.iter().map(|d| d.pubkey).collect();
+
+        let transfer_proposal = &mut ctx.accounts.transfer_proposal;
+        transfer_proposal.open(
+            transfer_proposal_identifier.next_id()?,
+            recipient,
+            amount,
+            multisig_required_signers,
+            ctx.bumps.transfer_proposal,
+        );
+        transfer_proposal.record_creation(signer_key)?;
+
+        transfer_proposal.check_can_sign(&signer_key)?;
+        transfer_proposal.add_signature(signer_key);
+        transfer_proposal.record_update(signer_key)?;
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_create_and_sign_transfer_proposal", signer_key)?;
 
         Ok(())
     }
@@ -395,42 +2594,46 @@ impl VaultTransferProposalInstructions {
     /// Signs a transfer proposal within the vault multisig system.
     /// 
     /// This function allows an authorized multisig signer to sign a pending transfer proposal by:
+    /// - Checking and advancing the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
     /// - Verifying the proposal is still in the `Pending` status.
     /// - Ensuring the signer is among the required multisig signers (if any are specified).
-    /// - Preventing duplicate signatures from the same signer.
-    /// - Appending the signer's public key to the list of actual signers for the proposal.
+    /// - Preventing duplicate signatures from the same signer, using the proposal's signature bitmap when it has required signers.
+    /// - Growing the proposal, at the signer's expense, when it has no room left for another signature.
+    /// - Setting the signer's bit in the bitmap and appending their public key to the list of actual signers for the proposal.
     ///
     /// ## Arguments
     ///
     /// * `ctx` - The context containing the accounts required to sign the transfer proposal.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
     ///
     /// ## Returns
     ///
     /// Returns `Ok(())` if the proposal is signed successfully, otherwise returns an error.
-    pub fn sign_transfer_proposal(ctx: Context<VaultSignTransferProposal>) -> Result<()> {
+    pub fn sign_transfer_proposal(ctx: Context<VaultSignTransferProposal>, expected_nonce: Option<u64>) -> Result<()> {
         let signer_key = ctx.accounts.signer.key();
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
+
         let current_transfer_proposal = &mut ctx.accounts.current_transfer_proposal;
 
-        require!(
-            current_transfer_proposal.status == VaultTransferProposalStatus::Pending,
-            VaultErrorCode::AlreadyResolved
-        );
+        current_transfer_proposal.sync_signed_mask();
+        current_transfer_proposal.check_can_sign(&signer_key)?;
 
-        if current_transfer_proposal.multisig_required_signers.len() > 0 {
-            require!(
-                current_transfer_proposal.multisig_required_signers.contains(&signer_key),
-                VaultErrorCode::UnauthorizedSigner
-            );
-        }
+        let signer_count = current_transfer_proposal
+            .multisig_required_signers
+            .len()
+            .max(current_transfer_proposal.multisig_signers.len() + 1)
+            .min(MAX_SIGNERS);
+        grow_account(
+            &current_transfer_proposal.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + VaultTransferProposalAccount::len_for(signer_count),
+        )?;
 
-        if current_transfer_proposal.multisig_signers.len() > 0 {
-            require!(
-                !current_transfer_proposal.multisig_signers.contains(&signer_key),
-                VaultErrorCode::DuplicateSignature
-            );
-        }
+        current_transfer_proposal.add_signature(signer_key);
+        current_transfer_proposal.record_update(signer_key)?;
 
-        current_transfer_proposal.multisig_signers.push(signer_key);
+        StateMutationEvent::emit(EventModule::Vault, "vault_sign_transfer_proposal", ctx.accounts.signer.key())?;
 
         Ok(())
     }
@@ -438,53 +2641,85 @@ impl VaultTransferProposalInstructions {
     /// Executes a transfer proposal within the vault multisig system.
     /// 
     /// This function performs the following actions:
+    /// - Checks that the vault is enabled in the program configuration.
+    /// - When the signer is the registered Squads vault, checks that the execution is invoked by the Squads program, whose
+    ///   vault transaction carries the Squads members' approval, and when it is the registered Realms governance, that it
+    ///   is invoked by the SPL Governance program executing an approved proposal; otherwise checks, while the CPI guard is
+    ///   enabled, that the execution is not invoked through CPI by a program outside its allowlist.
+    /// - Checks and advances the signer's nonce when `expected_nonce` is given, so a replayed transaction fails.
     /// - Verifies that the transfer proposal is still in the `Pending` status.
-    /// - Ensures the executing signer is among the required multisig signers (if any are specified).
-    /// - Checks that all required multisig signers have signed the proposal.
+    /// - Unless the signer is the registered Squads vault or Realms governance, ensures the executing signer is among the
+    ///   required multisig signers (if any are specified) and checks that all of them have signed the proposal, by comparing
+    ///   its signature bitmap to the full mask.
+    /// - Marks the proposal as approved, so it cannot be executed twice.
     /// - Validates that the vault has sufficient SOL balance for the transfer.
+    /// - On a dry run, emits a `VaultTransferPreviewEvent` and fails with `DryRunCompleted`, so a simulation shows the
+    ///   transfer without committing it.
     /// - Executes the SOL transfer from the vault to the specified recipient.
+    /// - When the memo program is passed, attaches a memo naming the proposal id to the transfer, so the recipient's wallet
+    ///   or exchange shows it as the payment reference.
     /// - Increments the ledger identifier and records the transaction in the vault ledger.
     /// - Emits a `VaultLedgerEvent` with the details of the executed transaction.
     ///
     /// ## Arguments
     ///
     /// * `ctx` - The context containing the accounts required to execute the transfer proposal.
+    /// * `expected_nonce` - The signer's current nonce, required to match when given.
+    /// * `dry_run` - Whether to only preview the transfer, failing once every check has passed.
     ///
     /// ## Returns
     ///
     /// Returns `Ok(())` if the transfer is executed successfully, otherwise returns an error.
-    pub fn execute_transfer_proposal(ctx: Context<VaultExecuteTransferProposal>) -> Result<()> {
-        let signer_key = ctx.accounts.signer.key();
-        let current_transfer_proposal = &mut ctx.accounts.current_transfer_proposal;
-
-        require!(
-            current_transfer_proposal.status == VaultTransferProposalStatus::Pending,
-            VaultErrorCode::AlreadyResolved
-        );
-
-        if current_transfer_proposal.multisig_required_signers.len() > 0 {
-            require!(
-                current_transfer_proposal.multisig_required_signers.contains(&signer_key),
-                VaultErrorCode::UnauthorizedSigner
-            );
+    pub fn execute_transfer_proposal(
+        ctx: Context<VaultExecuteTransferProposal>,
+        expected_nonce: Option<u64>,
+        dry_run: bool,
+    ) -> Result<()> {
+        ctx.accounts.program_config.check_vault_enabled()?;
+        let squads_approved = ctx.accounts.program_config.is_squads_authority(&ctx.accounts.signer.key());
+        let realms_approved = ctx.accounts.program_config.is_realms_governance(&ctx.accounts.signer.key());
+        if squads_approved {
+            check_invoked_by_squads(&ctx.accounts.instructions_sysvar)?;
+        } else if realms_approved {
+            check_invoked_by_realms(&ctx.accounts.instructions_sysvar)?;
+        } else {
+            ctx.accounts.program_config.check_cpi_caller(&ctx.accounts.instructions_sysvar)?;
         }
+        consume_nonce(&mut ctx.accounts.wallet_nonce, expected_nonce)?;
 
-        let all_signed = current_transfer_proposal
-            .multisig_required_signers
-            .iter()
-            .all(|req| current_transfer_proposal.multisig_signers.contains(req));
-
-        require!(all_signed, VaultErrorCode::InsufficientSignatures);
+        let current_transfer_proposal = &mut ctx.accounts.current_transfer_proposal;
+        if squads_approved || realms_approved {
+            current_transfer_proposal.resolve_by_authority()?;
+        } else {
+            current_transfer_proposal.resolve(&ctx.accounts.signer.key())?;
+        }
+        current_transfer_proposal.record_update(ctx.accounts.signer.key())?;
 
         if let VaultTransaction::Transfer { to, amount } = current_transfer_proposal.transaction {
             let vault = &ctx.accounts.vault;
             let vault_sol_balance = vault.to_account_info().lamports();
 
-            require!(
+            require_with_context!(
                 vault_sol_balance >= amount,
-                VaultErrorCode::InsufficientSolBalance
+                VaultErrorCode::InsufficientSolBalance,
+                vault.key(),
+                amount,
+                vault_sol_balance
             );
 
+            if dry_run {
+                emit!(VaultTransferPreviewEvent {
+                    proposal_id: current_transfer_proposal.id,
+                    signer: ctx.accounts.signer.key(),
+                    to,
+                    amount,
+                    vault_balance: vault_sol_balance,
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+
+                return err!(SimulationErrorCode::DryRunCompleted);
+            }
+
             let sol_transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
                 &vault.key(),
                 &to,
@@ -496,30 +2731,189 @@ impl VaultTransferProposalInstructions {
                 &[vault.to_account_info()],
             )?;
 
+            if let Some(memo_program) = &ctx.accounts.memo_program {
+                let memo = format!("{} #{}", TRANSFER_MEMO_PREFIX, current_transfer_proposal.id);
+                build_memo(CpiContext::new(memo_program.to_account_info(), BuildMemo {}), memo.as_bytes())?;
+            }
+
             let ledger_identifier = &mut ctx.accounts.ledger_identifier;
 
             let ledger = &mut ctx.accounts.ledger;
             ledger.ledger = VaultLedger {
-                id: ledger_identifier.id,
+                id: ledger_identifier.next_id()?,
                 user: vault.key(),
                 transaction: VaultTransaction::Transfer { 
-                    to: to, 
-                    amount: amount
+                    to, 
+                    amount
                 },
-                amount: -(amount as i64) as u64,
+                amount: negate_amount(amount)?,
                 miming_fee: 0, 
             };
-            
-            ledger_identifier.id += 1;
+            ledger.version = VaultLedgerAccount::VERSION;
+            ledger.bump = ctx.bumps.ledger;
+            ledger.wormhole_sequence = None;
+            ledger.relayer_fee = 0;
+            ledger.destination_chain = 0;
+            ledger.destination = [0; 32];
 
             emit!(VaultLedgerLogEvent {
                 id: ledger_identifier.id,
                 data: ledger.ledger.clone()
             });
+
+            crate::log_info!(
+                "vault_execute_transfer_proposal",
+                proposal_id = current_transfer_proposal.id,
+                to = to,
+                amount = amount,
+                ledger_id = ledger_identifier.id,
+                squads_approved = squads_approved,
+                realms_approved = realms_approved
+            );
         }
 
+        StateMutationEvent::emit(EventModule::Vault, "vault_execute_transfer_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+
+    /// Closes an approved transfer proposal, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following checks and actions:
+    /// - Ensures a rent recipient is configured and the signer is one of the multisig's signers.
+    /// - Ensures the proposal has been approved; pending proposals are kept.
+    /// - Emits an `AccountClosedEvent`; the account is closed on exit.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the proposal, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the proposal is
+    /// still pending.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the proposal is closed, otherwise returns an error.
+    pub fn close_transfer_proposal(ctx: Context<VaultCloseTransferProposal>) -> Result<()> {
+        require!(
+            ctx.accounts.current_transfer_proposal.status == VaultTransferProposalStatus::Approved,
+            VaultErrorCode::ProposalNotResolved
+        );
+
+        AccountClosedEvent::emit(
+            &ctx.accounts.current_transfer_proposal.to_account_info(),
+            ctx.accounts.rent_recipient.key(),
+        )?;
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_close_transfer_proposal", ctx.accounts.signer.key())?;
+
+        Ok(())
+    }
+}
+
+/// A view of the vault's state, returned by [`VaultLedgerInstructions::get_status`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VaultStatusView {
+    pub balance: u64,
+    pub vault_enabled: bool,
+    pub teleport_paused: bool,
+    pub teleport_fee: u64,
+    pub next_ledger_id: u64,
+    pub next_transfer_proposal_id: u64,
+}
+
+#[derive(Accounts)]
+pub struct VaultGetStatus<'info> {
+    /// CHECK: This is the PDA authority for the vault, only its balance is read
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfigAccount>,
+
+    #[account(
+        seeds = [LEDGER_IDENTIFIER_SEED],
+        bump = ledger_identifier.bump
+    )]
+    pub ledger_identifier: Account<'info, IdentifierAccount>,
+
+    #[account(
+        seeds = [TRANSFER_PROPOSAL_IDENTIFIER_SEED],
+        bump = transfer_proposal_identifier.bump
+    )]
+    pub transfer_proposal_identifier: Account<'info, IdentifierAccount>,
+}
+
+pub struct VaultLedgerInstructions;
+
+impl VaultLedgerInstructions {
+    /// Closes a ledger entry that has fallen out of the retention window, refunding its rent to the configured rent recipient.
+    ///
+    /// This function performs the following checks and actions:
+    /// - Ensures a rent recipient is configured and the signer is one of the multisig's signers.
+    /// - Ensures at least `LEDGER_RETENTION` newer entries have been recorded since the entry.
+    /// - Emits an `AccountClosedEvent`; the account is closed on exit.
+    ///
+    /// The entry's content stays available in the `VaultLedgerLogEvent` emitted when it was recorded.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the signer, the multisig, the ledger identifier, the entry, and the rent recipient.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no rent recipient is configured, the signer is not a multisig signer, or the entry is still
+    /// within the retention window.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `Ok(())` if the entry is closed, otherwise returns an error.
+    pub fn close_ledger(ctx: Context<VaultCloseLedger>) -> Result<()> {
+        require!(
+            ctx.accounts.ledger.ledger.id.saturating_add(LEDGER_RETENTION) <= ctx.accounts.ledger_identifier.id,
+            VaultErrorCode::LedgerEntryRetained
+        );
+
+        AccountClosedEvent::emit(&ctx.accounts.ledger.to_account_info(), ctx.accounts.rent_recipient.key())?;
+
+        StateMutationEvent::emit(EventModule::Vault, "vault_close_ledger", ctx.accounts.signer.key())?;
+
         Ok(())
     }
+
+    /// Returns a view of the vault: its SOL balance, whether it is enabled and teleports are paused, the teleport fee, and
+    /// the ids the next ledger entry and transfer proposal will take.
+    ///
+    /// The result is exposed as the instruction's return data, so clients can read it by simulating the transaction and
+    /// other programs through CPI, independently of the account layouts.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ctx` - The context containing the vault, the program configuration, and the vault's identifiers.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the vault's [`VaultStatusView`].
+    pub fn get_status(ctx: Context<VaultGetStatus>) -> Result<VaultStatusView> {
+        let program_config = &ctx.accounts.program_config;
+
+        Ok(VaultStatusView {
+            balance: ctx.accounts.vault.lamports(),
+            vault_enabled: program_config.vault_enabled,
+            teleport_paused: program_config.teleport_paused,
+            teleport_fee: program_config.teleport_fee,
+            next_ledger_id: ctx.accounts.ledger_identifier.id,
+            next_transfer_proposal_id: ctx.accounts.transfer_proposal_identifier.id,
+        })
+    }
 }
 
 /// # Raydium Proxy Modules
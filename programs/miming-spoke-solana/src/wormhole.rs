@@ -0,0 +1,339 @@
+//! # Wormhole Module
+//!
+//! This module implements the Wormhole transport of the [`messaging`](crate::messaging) layer, through which teleports reach
+//! the hub chain and releases from the hub reach the spoke.
+//!
+//! ## Features
+//!
+//! - **Message Payloads:** Teleports are encoded as fixed-layout, big-endian payloads the hub decodes without a Borsh
+//!   implementation.
+//! - **Core Bridge CPI:** Messages are posted through the core bridge's `post_message` instruction, signed by the
+//!   program's emitter PDA, with the bridge fee paid from the caller.
+//! - **Sequence Tracking:** The sequence the core bridge assigns to a message is read from the emitter's sequence tracker
+//!   before posting, so it can be stored with the teleport it carries.
+//! - **VAA Decoding:** Inbound messages are read from the posted VAA accounts the core bridge writes once it has verified
+//!   the guardians' signatures, so the spoke only accepts what the guardians signed.
+//!
+//! ## Main Data Structures
+//!
+//! - [`TeleportMessage`]: The ledger id, user, amount, and destination of an outbound teleport.
+//! - [`WormholeAdapter`]: The [`MessagingAdapter`] posting to and reading from a core bridge.
+//! - [`PostedVaa`]: The emitter, sequence, and payload of a VAA verified by the core bridge.
+//! - [`InboundRelease`]: The recipient, amount, and asset of a release sent by the hub.
+//! - [`InboundTeleport`]: The source ledger id, sender, amount, and recipient of a teleport from the hub, minted on arrival.
+//!
+//! ## Constants
+//!
+//! - `WORMHOLE_CORE_BRIDGE_ID`: The mainnet core bridge, used unless governance configures another endpoint.
+//! - `SOLANA_CHAIN_ID`: Solana's Wormhole chain id, the source chain of every message the spoke posts.
+//! - `WORMHOLE_BRIDGE_SEED`, `WORMHOLE_FEE_COLLECTOR_SEED`, `WORMHOLE_SEQUENCE_SEED`, `WORMHOLE_POSTED_VAA_SEED`: Seeds of
+//!   the core bridge's own PDAs.
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::{invoke, invoke_signed},
+        system_instruction, sysvar,
+    },
+};
+use crate::{
+    messaging::{InboundMessage, MessagingAdapter, OutboundAccounts},
+    states::errors::WormholeErrorCode,
+};
+
+/// The Wormhole core bridge program on Solana mainnet.
+pub const WORMHOLE_CORE_BRIDGE_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Solana's Wormhole chain id.
+pub const SOLANA_CHAIN_ID: u16 = 1;
+
+/// Seed of the core bridge's configuration account, which holds the message fee.
+pub const WORMHOLE_BRIDGE_SEED: &[u8] = b"Bridge";
+/// Seed of the core bridge's fee collector, which the message fee is paid to.
+pub const WORMHOLE_FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+/// Seed of an emitter's sequence tracker, derived from `[WORMHOLE_SEQUENCE_SEED, emitter]` under the core bridge.
+pub const WORMHOLE_SEQUENCE_SEED: &[u8] = b"Sequence";
+/// Seed of a posted VAA, derived from `[WORMHOLE_POSTED_VAA_SEED, vaa hash]` under the core bridge.
+pub const WORMHOLE_POSTED_VAA_SEED: &[u8] = b"PostedVAA";
+
+/// Index of the core bridge's `post_message` instruction.
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+/// Consistency level asking guardians to sign only once the posting slot is finalized.
+const FINALIZED: u8 = 1;
+/// Offset of the message fee in the core bridge's configuration: guardian set index, last lamports, and guardian set
+/// expiration time come first.
+const BRIDGE_FEE_OFFSET: usize = 4 + 8 + 4;
+/// Prefix of a posted VAA account: the `vaa` magic followed by the VAA version.
+const POSTED_VAA_PREFIX: &[u8] = b"vaa\x01";
+/// Offset of the sequence in a posted VAA account: the prefix, consistency level, VAA time, signature set, submission time,
+/// and nonce come first.
+const POSTED_VAA_SEQUENCE_OFFSET: usize = 4 + 1 + 4 + 32 + 4 + 4;
+
+/// An outbound teleport, as the hub decodes it from a message's payload.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TeleportMessage {
+    pub ledger_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub destination_chain: u16,
+    pub destination: [u8; 32],
+}
+
+impl TeleportMessage {
+    /// Leading byte of a teleport payload, telling it apart from other payloads the spoke may post later.
+    pub const PAYLOAD_ID: u8 = 1;
+
+    /// Encodes the message as `payload id | ledger id | user | amount | destination chain | destination`, with integers in
+    /// big-endian order.
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(1 + 8 + 32 + 8 + 2 + 32);
+        payload.push(Self::PAYLOAD_ID);
+        payload.extend_from_slice(&self.ledger_id.to_be_bytes());
+        payload.extend_from_slice(self.user.as_ref());
+        payload.extend_from_slice(&self.amount.to_be_bytes());
+        payload.extend_from_slice(&self.destination_chain.to_be_bytes());
+        payload.extend_from_slice(&self.destination);
+        payload
+    }
+}
+
+/// A VAA the core bridge has verified and posted, reduced to the fields the spoke checks.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PostedVaa {
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    /// Decodes a posted VAA account, checking that `core_bridge` owns it and that it holds a VAA rather than an outbound
+    /// message.
+    pub fn load(posted_vaa: &AccountInfo, core_bridge: &Pubkey) -> Result<Self> {
+        require_keys_eq!(*posted_vaa.owner, *core_bridge, WormholeErrorCode::InvalidVaa);
+
+        let data = posted_vaa.try_borrow_data()?;
+        require!(data.starts_with(POSTED_VAA_PREFIX), WormholeErrorCode::InvalidVaa);
+
+        let mut fields = data.get(POSTED_VAA_SEQUENCE_OFFSET..).ok_or(WormholeErrorCode::InvalidVaa)?;
+        let sequence = u64::deserialize(&mut fields).map_err(|_| WormholeErrorCode::InvalidVaa)?;
+        let emitter_chain = u16::deserialize(&mut fields).map_err(|_| WormholeErrorCode::InvalidVaa)?;
+        let emitter_address = <[u8; 32]>::deserialize(&mut fields).map_err(|_| WormholeErrorCode::InvalidVaa)?;
+        let payload = Vec::<u8>::deserialize(&mut fields).map_err(|_| WormholeErrorCode::InvalidVaa)?;
+
+        Ok(Self { sequence, emitter_chain, emitter_address, payload })
+    }
+}
+
+/// The asset an inbound release pays out.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InboundAsset {
+    /// SOL released from the vault.
+    Sol,
+    /// MIMING minted by the program's mint authority.
+    Miming,
+}
+
+/// A release sent by the hub, as the spoke decodes it from a VAA's payload.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InboundRelease {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub asset: InboundAsset,
+}
+
+impl InboundRelease {
+    /// Leading byte of a release payload.
+    pub const PAYLOAD_ID: u8 = 2;
+    /// Size of a release payload.
+    pub const PAYLOAD_LEN: usize = 1 + 32 + 8 + 1;
+
+    /// Decodes a payload laid out as `payload id | recipient | amount | asset`, with the amount in big-endian order and the
+    /// asset as `0` for SOL and `1` for MIMING.
+    pub fn from_payload(payload: &[u8]) -> Result<Self> {
+        require!(
+            payload.len() == Self::PAYLOAD_LEN && payload[0] == Self::PAYLOAD_ID,
+            WormholeErrorCode::InvalidPayload
+        );
+
+        let recipient = Pubkey::try_from(&payload[1..33]).map_err(|_| WormholeErrorCode::InvalidPayload)?;
+        let amount = u64::from_be_bytes(payload[33..41].try_into().map_err(|_| WormholeErrorCode::InvalidPayload)?);
+        let asset = match payload[41] {
+            0 => InboundAsset::Sol,
+            1 => InboundAsset::Miming,
+            _ => return err!(WormholeErrorCode::InvalidPayload),
+        };
+        require!(amount > 0, WormholeErrorCode::InvalidPayload);
+
+        Ok(Self { recipient, amount, asset })
+    }
+
+    /// Returns the light client leaf the hub commits for the release carried at `sequence`, laid out as
+    /// `sequence | payload id | recipient | amount | asset`, with integers in big-endian order.
+    pub fn leaf(&self, sequence: u64) -> Vec<u8> {
+        let mut leaf = Vec::with_capacity(8 + Self::PAYLOAD_LEN);
+        leaf.extend_from_slice(&sequence.to_be_bytes());
+        leaf.push(Self::PAYLOAD_ID);
+        leaf.extend_from_slice(self.recipient.as_ref());
+        leaf.extend_from_slice(&self.amount.to_be_bytes());
+        leaf.push(match self.asset {
+            InboundAsset::Sol => 0,
+            InboundAsset::Miming => 1,
+        });
+
+        leaf
+    }
+}
+
+/// A teleport sent from the hub to the spoke, minted on arrival, as the spoke decodes it from a VAA's payload.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InboundTeleport {
+    pub source_ledger_id: u64,
+    pub sender: [u8; 32],
+    pub amount: u64,
+    pub recipient: Pubkey,
+}
+
+impl InboundTeleport {
+    /// Leading byte of an inbound teleport payload.
+    pub const PAYLOAD_ID: u8 = 3;
+    /// Size of an inbound teleport payload.
+    pub const PAYLOAD_LEN: usize = 1 + 8 + 32 + 8 + 32;
+
+    /// Decodes the 81-byte payload the hub sends, laid out as `payload id (u8, 3) | source ledger id (u64) | sender ([u8; 32])
+    /// | amount (u64) | recipient (32 bytes)`, with integers in big-endian order.
+    pub fn from_payload(payload: &[u8]) -> Result<Self> {
+        require!(
+            payload.len() == Self::PAYLOAD_LEN && payload[0] == Self::PAYLOAD_ID,
+            WormholeErrorCode::InvalidPayload
+        );
+
+        let source_ledger_id = u64::from_be_bytes(payload[1..9].try_into().map_err(|_| WormholeErrorCode::InvalidPayload)?);
+        let sender = payload[9..41].try_into().map_err(|_| WormholeErrorCode::InvalidPayload)?;
+        let amount = u64::from_be_bytes(payload[41..49].try_into().map_err(|_| WormholeErrorCode::InvalidPayload)?);
+        let recipient = Pubkey::try_from(&payload[49..81]).map_err(|_| WormholeErrorCode::InvalidPayload)?;
+        require!(amount > 0, WormholeErrorCode::InvalidPayload);
+
+        Ok(Self { source_ledger_id, sender, amount, recipient })
+    }
+
+    /// Returns the light client leaf the hub commits for the teleport carried at `sequence`, laid out as
+    /// `sequence | payload id | source ledger id | sender | amount | recipient`, with integers in big-endian order.
+    pub fn leaf(&self, sequence: u64) -> Vec<u8> {
+        let mut leaf = Vec::with_capacity(8 + Self::PAYLOAD_LEN);
+        leaf.extend_from_slice(&sequence.to_be_bytes());
+        leaf.push(Self::PAYLOAD_ID);
+        leaf.extend_from_slice(&self.source_ledger_id.to_be_bytes());
+        leaf.extend_from_slice(&self.sender);
+        leaf.extend_from_slice(&self.amount.to_be_bytes());
+        leaf.extend_from_slice(self.recipient.as_ref());
+
+        leaf
+    }
+}
+
+/// Returns the message fee set in the core bridge's configuration.
+pub fn bridge_fee(bridge: &AccountInfo) -> Result<u64> {
+    let data = bridge.try_borrow_data()?;
+    let fee = data
+        .get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+        .and_then(|fee| fee.try_into().ok())
+        .ok_or(WormholeErrorCode::InvalidBridgeAccount)?;
+
+    Ok(u64::from_le_bytes(fee))
+}
+
+/// Returns the sequence the core bridge will assign to the emitter's next message: the value of its sequence tracker, or
+/// zero before the emitter's first message creates it.
+pub fn next_sequence(sequence: &AccountInfo) -> Result<u64> {
+    let data = sequence.try_borrow_data()?;
+    if data.is_empty() {
+        return Ok(0);
+    }
+    let value = data
+        .get(..8)
+        .and_then(|value| value.try_into().ok())
+        .ok_or(WormholeErrorCode::InvalidBridgeAccount)?;
+
+    Ok(u64::from_le_bytes(value))
+}
+
+/// The Wormhole transport, posting to and reading from the core bridge deployed at `core_bridge`.
+pub struct WormholeAdapter {
+    pub core_bridge: Pubkey,
+}
+
+impl MessagingAdapter for WormholeAdapter {
+    fn endpoint(&self) -> Pubkey {
+        self.core_bridge
+    }
+
+    /// Pays the message fee from the payer and posts `payload` through the core bridge's `post_message`, with the
+    /// configuration as the bridge account and the sequence as the emitter's sequence tracker.
+    ///
+    /// The emitter and the message account are PDAs of this program; `signer_seeds` carries the seeds of both, so the core
+    /// bridge can create the message account and record the emitter as its sender.
+    fn post_message(&self, accounts: OutboundAccounts, payload: Vec<u8>, signer_seeds: &[&[&[u8]]]) -> Result<u64> {
+        let sequence = next_sequence(accounts.sequence)?;
+
+        let fee = bridge_fee(accounts.config)?;
+        if fee > 0 {
+            invoke(
+                &system_instruction::transfer(accounts.payer.key, accounts.fee_collector.key, fee),
+                &[accounts.payer.clone(), accounts.fee_collector.clone(), accounts.system_program.clone()],
+            )?;
+        }
+
+        let mut data = vec![POST_MESSAGE_INSTRUCTION];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        payload.serialize(&mut data)?;
+        data.push(FINALIZED);
+
+        let instruction = Instruction {
+            program_id: self.core_bridge,
+            accounts: vec![
+                AccountMeta::new(accounts.config.key(), false),
+                AccountMeta::new(accounts.message.key(), true),
+                AccountMeta::new_readonly(accounts.emitter.key(), true),
+                AccountMeta::new(accounts.sequence.key(), false),
+                AccountMeta::new(accounts.payer.key(), true),
+                AccountMeta::new(accounts.fee_collector.key(), false),
+                AccountMeta::new_readonly(sysvar::clock::ID, false),
+                AccountMeta::new_readonly(accounts.system_program.key(), false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                accounts.config.clone(),
+                accounts.message.clone(),
+                accounts.emitter.clone(),
+                accounts.sequence.clone(),
+                accounts.payer.clone(),
+                accounts.fee_collector.clone(),
+                accounts.clock.clone(),
+                accounts.system_program.clone(),
+                accounts.rent.clone(),
+                accounts.endpoint.clone(),
+            ],
+            signer_seeds,
+        )?;
+
+        Ok(sequence)
+    }
+
+    /// Decodes a VAA the core bridge posted once it verified the guardians' signatures.
+    fn read_message(&self, message: &AccountInfo) -> Result<InboundMessage> {
+        let vaa = PostedVaa::load(message, &self.core_bridge)?;
+
+        Ok(InboundMessage {
+            source_chain: vaa.emitter_chain,
+            sender: vaa.emitter_address,
+            sequence: vaa.sequence,
+            payload: vaa.payload,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! Tests for the vault's cross-chain message sequencing.
+//!
+//! The cases consume and post messages through the sequence counters and consumed message pages the teleport handlers
+//! update, and check that each `(chain, sequence)` pair is consumed once and that skipped sequences are counted.
+use anchor_lang::error::Error;
+use miming_spoke_solana::{
+    states::errors::WormholeErrorCode,
+    vault::{VaultBridgeSequenceAccount, VaultConsumedMessagePageAccount, CONSUMED_MESSAGES_PER_PAGE},
+};
+
+const HUB_CHAIN: u16 = 2;
+const NOW: i64 = 1_700_000_000;
+
+fn counters() -> VaultBridgeSequenceAccount {
+    VaultBridgeSequenceAccount {
+        chain: 0,
+        next_outbound_sequence: 0,
+        inbound_count: 0,
+        highest_inbound_sequence: 0,
+        last_inbound_at: 0,
+        version: 0,
+        bump: 0,
+    }
+}
+
+fn page() -> VaultConsumedMessagePageAccount {
+    VaultConsumedMessagePageAccount { chain: 0, page: 0, consumed: [0; 256], version: 0, bump: 0 }
+}
+
+#[test]
+fn messages_are_consumed_once() {
+    let sequence = 3 * CONSUMED_MESSAGES_PER_PAGE + 17;
+    let mut page = page();
+
+    page.mark_consumed(HUB_CHAIN, sequence, 254).unwrap();
+    assert_eq!((page.chain, page.page, page.bump), (HUB_CHAIN, 3, 254));
+    assert!(page.is_consumed(sequence));
+    assert!(!page.is_consumed(sequence + 1));
+
+    assert_eq!(
+        page.mark_consumed(HUB_CHAIN, sequence, 254).unwrap_err(),
+        Error::from(WormholeErrorCode::MessageAlreadyConsumed)
+    );
+    page.mark_consumed(HUB_CHAIN, sequence + 1, 254).unwrap();
+}
+
+#[test]
+fn outbound_sequences_are_contiguous() {
+    let mut counters = counters();
+
+    for expected in 0..5 {
+        assert_eq!(counters.next_outbound(HUB_CHAIN, 253).unwrap(), expected);
+    }
+    assert_eq!((counters.chain, counters.next_outbound_sequence, counters.bump), (HUB_CHAIN, 5, 253));
+}
+
+#[test]
+fn skipped_inbound_sequences_are_counted() {
+    let mut counters = counters();
+    assert_eq!(counters.missing_inbound(), 0);
+
+    assert_eq!(counters.record_inbound(HUB_CHAIN, 0, NOW, 253).unwrap(), 0);
+    assert_eq!(counters.record_inbound(HUB_CHAIN, 1, NOW, 253).unwrap(), 0);
+    assert_eq!(counters.record_inbound(HUB_CHAIN, 4, NOW, 253).unwrap(), 2);
+
+    // Relaying the skipped sequences late closes the gap.
+    assert_eq!(counters.record_inbound(HUB_CHAIN, 3, NOW + 1, 253).unwrap(), 1);
+    assert_eq!(counters.record_inbound(HUB_CHAIN, 2, NOW + 2, 253).unwrap(), 0);
+    assert_eq!((counters.inbound_count, counters.highest_inbound_sequence), (5, 4));
+    assert_eq!(counters.last_inbound_at, NOW + 2);
+}
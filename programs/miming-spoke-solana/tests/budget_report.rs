@@ -0,0 +1,146 @@
+//! Account space budget report, built with the `bench` feature.
+//!
+//! Prints one JSON object per account type, with the space it is allocated with at its smallest and at its largest,
+//! and fails if any of them outgrows what a single instruction can allocate, so a field or a vector limit added by a new
+//! feature is caught before deployment. The compute units each instruction consumes are measured against a validator by
+//! `tests/05-budget-report.ts`.
+//!
+//! ```text
+//! cargo test -p miming-spoke-solana --features bench --test budget_report -- --nocapture
+//! ```
+#![cfg(feature = "bench")]
+
+use anchor_lang::{prelude::*, solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE};
+use miming_spoke_solana::{
+    hub_registry::HubRegistryAccount,
+    light_client::HubStateRootsAccount,
+    multisig::{MultisigAccount, MultisigProposalAccount},
+    nonce::WalletNonceAccount,
+    program_config::ProgramConfigAccount,
+    registry::ProgramRegistryAccount,
+    staking::{
+        StakingActivityAccount, StakingConfigAccount, StakingCustodianAccount, StakingEpochSnapshotAccount,
+        StakingMintConfigAccount, StakingPairVaultAccount, StakingPoolAccount, StakingProposalAccount,
+        StakingReferrerAccount, StakingRegistryAccount, StakingReportAccount, StakingRewardScheduleAccount,
+        StakingSlashDisputeAccount, StakingSolClaimAccount, StakingSolDistributionAccount, StakingUnstakeQueueAccount,
+        StakingUserAccount, StakingValidatorAccount, StakingVaultDelegationAccount,
+    },
+    states::versioning::VersionedAccount,
+    vault::{
+        VaultBridgeSequenceAccount, VaultBridgeStatsAccount, VaultConsumedMessagePageAccount, VaultDepositReceiptPageAccount,
+        VaultInboundReceiptAccount, VaultLedgerAccount, VaultTransferProposalAccount,
+    },
+    IdentifierAccount,
+};
+
+/// Size of an account's discriminator.
+const DISCRIMINATOR: usize = 8;
+
+/// The most an instruction can allocate for one account, whether it creates the account or grows it.
+const SPACE_BUDGET: usize = MAX_PERMITTED_DATA_INCREASE;
+
+struct AccountSpace {
+    account: &'static str,
+    /// Space of a new account, which is smaller than `max_space` for accounts grown as their vectors fill.
+    min_space: usize,
+    max_space: usize,
+}
+
+impl AccountSpace {
+    const fn fixed(account: &'static str, space: usize) -> Self {
+        Self { account, min_space: space, max_space: space }
+    }
+
+    const fn growable(account: &'static str, min_space: usize, max_space: usize) -> Self {
+        Self { account, min_space, max_space }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            r#"{{"kind":"account_space","account":"{}","min_space":{},"max_space":{},"budget":{},"rent_exempt_lamports":{}}}"#,
+            self.account,
+            self.min_space,
+            self.max_space,
+            SPACE_BUDGET,
+            Rent::default().minimum_balance(self.max_space)
+        )
+    }
+}
+
+fn account_spaces() -> Vec<AccountSpace> {
+    vec![
+        AccountSpace::growable(
+            "MultisigAccount",
+            DISCRIMINATOR + MultisigAccount::len_for(0),
+            MultisigAccount::SPACE,
+        ),
+        AccountSpace::fixed("MultisigProposalAccount", DISCRIMINATOR + MultisigProposalAccount::INIT_SPACE),
+        AccountSpace::fixed("ProgramConfigAccount", ProgramConfigAccount::SPACE),
+        AccountSpace::growable(
+            "ProgramRegistryAccount",
+            DISCRIMINATOR + ProgramRegistryAccount::len_for(0),
+            ProgramRegistryAccount::SPACE,
+        ),
+        AccountSpace::fixed("HubRegistryAccount", HubRegistryAccount::SPACE),
+        AccountSpace::fixed("HubStateRootsAccount", HubStateRootsAccount::SPACE),
+        AccountSpace::fixed("IdentifierAccount", IdentifierAccount::SPACE),
+        AccountSpace::fixed("WalletNonceAccount", WalletNonceAccount::SPACE),
+        AccountSpace::fixed("VaultLedgerAccount", VaultLedgerAccount::SPACE),
+        AccountSpace::fixed("VaultInboundReceiptAccount", VaultInboundReceiptAccount::SPACE),
+        AccountSpace::fixed("VaultDepositReceiptPageAccount", VaultDepositReceiptPageAccount::SPACE),
+        AccountSpace::fixed("VaultBridgeStatsAccount", VaultBridgeStatsAccount::SPACE),
+        AccountSpace::fixed("VaultBridgeSequenceAccount", VaultBridgeSequenceAccount::SPACE),
+        AccountSpace::fixed("VaultConsumedMessagePageAccount", VaultConsumedMessagePageAccount::SPACE),
+        AccountSpace::growable(
+            "VaultTransferProposalAccount",
+            DISCRIMINATOR + VaultTransferProposalAccount::len_for(0),
+            VaultTransferProposalAccount::SPACE,
+        ),
+        AccountSpace::fixed("StakingConfigAccount", DISCRIMINATOR + StakingConfigAccount::INIT_SPACE),
+        AccountSpace::fixed("StakingPoolAccount", DISCRIMINATOR + StakingPoolAccount::INIT_SPACE),
+        AccountSpace::fixed("StakingActivityAccount", DISCRIMINATOR + StakingActivityAccount::INIT_SPACE),
+        AccountSpace::fixed("StakingRegistryAccount", StakingRegistryAccount::SPACE),
+        AccountSpace::fixed("StakingUserAccount", StakingUserAccount::SPACE),
+        AccountSpace::fixed("StakingReferrerAccount", StakingReferrerAccount::SPACE),
+        AccountSpace::fixed("StakingEpochSnapshotAccount", StakingEpochSnapshotAccount::SPACE),
+        AccountSpace::fixed("StakingReportAccount", StakingReportAccount::SPACE),
+        AccountSpace::fixed("StakingSolDistributionAccount", StakingSolDistributionAccount::SPACE),
+        AccountSpace::fixed("StakingSolClaimAccount", StakingSolClaimAccount::SPACE),
+        AccountSpace::growable(
+            "StakingUnstakeQueueAccount",
+            DISCRIMINATOR + StakingUnstakeQueueAccount::len_for(0),
+            StakingUnstakeQueueAccount::SPACE,
+        ),
+        AccountSpace::fixed("StakingPairVaultAccount", StakingPairVaultAccount::SPACE),
+        AccountSpace::fixed("StakingRewardScheduleAccount", StakingRewardScheduleAccount::SPACE),
+        AccountSpace::fixed("StakingMintConfigAccount", StakingMintConfigAccount::SPACE),
+        AccountSpace::fixed("StakingValidatorAccount", StakingValidatorAccount::SPACE),
+        AccountSpace::fixed("StakingCustodianAccount", StakingCustodianAccount::SPACE),
+        AccountSpace::fixed("StakingVaultDelegationAccount", StakingVaultDelegationAccount::SPACE),
+        AccountSpace::growable(
+            "StakingProposalAccount",
+            DISCRIMINATOR + StakingProposalAccount::len_for(0),
+            StakingProposalAccount::SPACE,
+        ),
+        AccountSpace::fixed("StakingSlashDisputeAccount", StakingSlashDisputeAccount::SPACE),
+    ]
+}
+
+#[test]
+fn account_spaces_fit_within_budget() {
+    let spaces = account_spaces();
+    for space in &spaces {
+        println!("{}", space.to_json());
+    }
+
+    let over_budget: Vec<&str> =
+        spaces.iter().filter(|space| space.max_space > SPACE_BUDGET).map(|space| space.account).collect();
+    assert!(over_budget.is_empty(), "accounts over the {SPACE_BUDGET} byte budget: {over_budget:?}");
+}
+
+#[test]
+fn growable_accounts_start_below_their_maximum() {
+    for space in account_spaces() {
+        assert!(space.min_space <= space.max_space, "{} starts above its maximum", space.account);
+    }
+}
@@ -0,0 +1,55 @@
+//! Tests for validating the genesis parameters.
+//!
+//! Each case starts from a valid two-of-three council and breaks one rule, checking that `GenesisParams::validate`
+//! rejects it before any singleton is created.
+use anchor_lang::{error::Error, prelude::Pubkey};
+use miming_spoke_solana::{genesis::GenesisParams, multisig::MultisigSigners, states::errors::GenesisErrorCode};
+
+fn signer(name: &str) -> MultisigSigners {
+    MultisigSigners { name: name.to_string(), pubkey: Pubkey::new_unique() }
+}
+
+fn params() -> GenesisParams {
+    GenesisParams {
+        signers: vec![signer("alice"), signer("bob"), signer("carol")],
+        threshold: 2,
+        teleport_fee: 0,
+        guardian: Pubkey::new_unique(),
+        rent_recipient: Pubkey::new_unique(),
+    }
+}
+
+fn assert_error(params: GenesisParams, expected: GenesisErrorCode) {
+    assert_eq!(params.validate().unwrap_err(), Error::from(expected));
+}
+
+#[test]
+fn a_valid_council_is_accepted() {
+    params().validate().unwrap();
+}
+
+#[test]
+fn an_empty_council_is_rejected() {
+    let params = GenesisParams { signers: Vec::new(), threshold: 1, ..params() };
+    assert_error(params, GenesisErrorCode::EmptyCouncil);
+}
+
+#[test]
+fn a_zero_threshold_is_rejected() {
+    let params = GenesisParams { threshold: 0, ..params() };
+    assert_error(params, GenesisErrorCode::InvalidThreshold);
+}
+
+#[test]
+fn a_threshold_above_the_council_size_is_rejected() {
+    let params = GenesisParams { threshold: 4, ..params() };
+    assert_error(params, GenesisErrorCode::InvalidThreshold);
+}
+
+#[test]
+fn a_duplicate_signer_is_rejected() {
+    let mut params = params();
+    let duplicate = MultisigSigners { name: "alice-again".to_string(), pubkey: params.signers[0].pubkey };
+    params.signers.push(duplicate);
+    assert_error(params, GenesisErrorCode::DuplicateSigner);
+}
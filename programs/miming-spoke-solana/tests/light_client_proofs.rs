@@ -0,0 +1,130 @@
+//! Tests for verifying merkle proofs against recorded hub state roots.
+//!
+//! Each case builds a small tree with the light client's own leaf and node hashes, records its root, and checks that the
+//! proof of every leaf verifies while tampered leaves, proofs, and heights are rejected. Roots are submitted only by a
+//! quorum of the council.
+use anchor_lang::{error::Error, prelude::Pubkey};
+use miming_spoke_solana::{
+    light_client::{check_quorum, leaf_hash, node_hash, HubStateRootsAccount, MAX_HUB_STATE_ROOTS},
+    multisig::{MultisigAccount, MultisigMode, MultisigSigners},
+    states::errors::LightClientErrorCode,
+    wormhole::{InboundAsset, InboundRelease, InboundTeleport},
+};
+
+const NOW: i64 = 1_700_000_000;
+
+const LEAVES: [&[u8]; 4] = [b"release:0", b"release:1", b"release:2", b"release:3"];
+
+/// Returns the root of the tree over `LEAVES`, and the proof of each leaf.
+fn tree() -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+    let hashes: Vec<[u8; 32]> = LEAVES.iter().map(|leaf| leaf_hash(leaf)).collect();
+    let (left, right) = (node_hash(&hashes[0], &hashes[1]), node_hash(&hashes[2], &hashes[3]));
+    let proofs = vec![
+        vec![hashes[1], right],
+        vec![hashes[0], right],
+        vec![hashes[3], left],
+        vec![hashes[2], left],
+    ];
+
+    (node_hash(&left, &right), proofs)
+}
+
+fn roots() -> HubStateRootsAccount {
+    HubStateRootsAccount { roots: Vec::new(), latest_height: 0, version: 1, bump: 255 }
+}
+
+fn assert_error(result: anchor_lang::Result<()>, expected: LightClientErrorCode) {
+    assert_eq!(result.unwrap_err(), Error::from(expected));
+}
+
+#[test]
+fn proofs_verify_against_the_recorded_root() {
+    let (root, proofs) = tree();
+    let mut roots = roots();
+    roots.push(10, root, NOW).unwrap();
+
+    for (leaf, proof) in LEAVES.iter().zip(&proofs) {
+        roots.verify(10, leaf, proof).unwrap();
+    }
+}
+
+#[test]
+fn tampered_claims_are_rejected() {
+    let (root, proofs) = tree();
+    let mut roots = roots();
+    roots.push(10, root, NOW).unwrap();
+
+    assert_error(roots.verify(10, b"release:4", &proofs[0]), LightClientErrorCode::InvalidProof);
+    assert_error(roots.verify(10, LEAVES[0], &proofs[1]), LightClientErrorCode::InvalidProof);
+    assert_error(roots.verify(11, LEAVES[0], &proofs[0]), LightClientErrorCode::UnknownStateRoot);
+
+    // An inner node is not accepted as a leaf.
+    let inner = node_hash(&leaf_hash(LEAVES[0]), &leaf_hash(LEAVES[1]));
+    assert_error(roots.verify(10, &inner, &proofs[0][1..]), LightClientErrorCode::InvalidProof);
+}
+
+#[test]
+fn inbound_claims_verify_only_for_their_sequence_and_contents() {
+    let release = InboundRelease { recipient: Pubkey::new_unique(), amount: 5_000, asset: InboundAsset::Sol };
+    let teleport = InboundTeleport { source_ledger_id: 7, sender: [9; 32], amount: 5_000, recipient: release.recipient };
+    let (release_leaf, teleport_leaf) = (release.leaf(42), teleport.leaf(43));
+    let root = node_hash(&leaf_hash(&release_leaf), &leaf_hash(&teleport_leaf));
+    let mut roots = roots();
+    roots.push(10, root, NOW).unwrap();
+
+    roots.verify(10, &release_leaf, &[leaf_hash(&teleport_leaf)]).unwrap();
+    roots.verify(10, &teleport_leaf, &[leaf_hash(&release_leaf)]).unwrap();
+
+    let inflated = InboundRelease { amount: 5_001, ..release.clone() };
+    let minted = InboundRelease { asset: InboundAsset::Miming, ..release.clone() };
+    for leaf in [release.leaf(43), inflated.leaf(42), minted.leaf(42)] {
+        assert_error(roots.verify(10, &leaf, &[leaf_hash(&teleport_leaf)]), LightClientErrorCode::InvalidProof);
+    }
+}
+
+#[test]
+fn roots_advance_and_keep_the_most_recent() {
+    let (root, proofs) = tree();
+    let mut roots = roots();
+    roots.push(10, root, NOW).unwrap();
+
+    assert_error(roots.push(10, [1; 32], NOW), LightClientErrorCode::StaleStateRoot);
+    assert_error(roots.push(9, [1; 32], NOW), LightClientErrorCode::StaleStateRoot);
+
+    for height in 11..11 + MAX_HUB_STATE_ROOTS as u64 - 1 {
+        roots.push(height, [height as u8; 32], NOW).unwrap();
+    }
+    roots.verify(10, LEAVES[0], &proofs[0]).unwrap();
+
+    roots.push(100, [100; 32], NOW).unwrap();
+    assert_eq!(roots.roots.len(), MAX_HUB_STATE_ROOTS);
+    assert_eq!(roots.latest_height, 100);
+    assert_error(roots.verify(10, LEAVES[0], &proofs[0]), LightClientErrorCode::UnknownStateRoot);
+}
+
+#[test]
+fn roots_need_a_quorum_of_the_council() {
+    let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let council = MultisigAccount {
+        name: "System".to_string(),
+        threshold: 2,
+        signers: members.iter().map(|&pubkey| MultisigSigners { name: "member".to_string(), pubkey }).collect(),
+        mode: MultisigMode::Unanimous,
+        min_approval_weight: 0,
+        version: 1,
+        bump: 255,
+        created_at: NOW,
+        updated_at: NOW,
+        last_actor: Pubkey::default(),
+    };
+    let outsider = Pubkey::new_unique();
+
+    check_quorum(&council, &[members[0], members[2]]).unwrap();
+    check_quorum(&council, &members).unwrap();
+    for signers in [vec![members[0]], vec![members[0], members[0]], vec![members[1], outsider], vec![]] {
+        assert_error(check_quorum(&council, &signers), LightClientErrorCode::QuorumNotReached);
+    }
+
+    let empty = MultisigAccount { signers: Vec::new(), threshold: 0, ..council };
+    assert_error(check_quorum(&empty, &[outsider]), LightClientErrorCode::QuorumNotReached);
+}
@@ -0,0 +1,343 @@
+//! Property tests for the multisig and vault transfer proposal state machines.
+//!
+//! Each case draws a council, a set of outside keys, and a random sequence of proposal creations, signatures, approvals
+//! and executions, applies it through the same proposal methods the instruction handlers call, and checks the sequence
+//! against a plain model after every step:
+//!
+//! - A proposal is only approved or executed once every required signer has signed it, or, for stake-weighted multisig
+//!   proposals, once the collected voting weight reaches its minimum.
+//! - A resolved proposal rejects further signatures, approvals and executions, so each transfer moves lamports once.
+//! - Lamports moved by executed transfers leave the vault and reach the recipients, and a transfer the vault cannot
+//!   cover fails without resolving its proposal.
+//! - The signature bitmap always matches the collected signatures.
+//!
+//! A separate case checks that a disputed staking slash needs every required signer, where an undisputed one only needs
+//! the multisig's threshold.
+use anchor_lang::{error::Error, prelude::*};
+use bytemuck::Zeroable;
+use miming_spoke_solana::{
+    multisig::{Multisig, MultisigMode, MultisigProposalAccount, MultisigSigners},
+    staking::{StakingProposalAccount, StakingProposalAction, StakingProposalStatus},
+    states::{
+        errors::{MultisigErrorCode, StakingErrorCode, VaultErrorCode},
+        signer_set::{full_signer_mask, signer_mask_of},
+        versioning::VersionedAccount,
+    },
+    vault::{VaultTransaction, VaultTransferProposalAccount, VaultTransferProposalStatus},
+};
+use proptest::prelude::*;
+
+/// Number of keys drawn from in a case: council members first, then outside keys.
+const KEY_POOL: usize = 8;
+
+#[derive(Clone, Debug)]
+enum MultisigOp {
+    Create { mode_stake_weighted: bool, min_approval_weight: u64 },
+    Sign { proposal: usize, key: usize },
+    Approve { proposal: usize, key: usize },
+}
+
+#[derive(Clone, Debug)]
+enum VaultOp {
+    Create { recipient: usize, amount: u64 },
+    Sign { proposal: usize, key: usize },
+    Execute { proposal: usize, key: usize },
+}
+
+fn multisig_op() -> impl Strategy<Value = MultisigOp> {
+    prop_oneof![
+        (any::<bool>(), 0u64..400).prop_map(|(mode_stake_weighted, min_approval_weight)| MultisigOp::Create {
+            mode_stake_weighted,
+            min_approval_weight,
+        }),
+        (0usize..4, 0..KEY_POOL).prop_map(|(proposal, key)| MultisigOp::Sign { proposal, key }),
+        (0usize..4, 0..KEY_POOL).prop_map(|(proposal, key)| MultisigOp::Approve { proposal, key }),
+    ]
+}
+
+fn vault_op() -> impl Strategy<Value = VaultOp> {
+    prop_oneof![
+        (0..KEY_POOL, 0u64..2_000).prop_map(|(recipient, amount)| VaultOp::Create { recipient, amount }),
+        (0usize..4, 0..KEY_POOL).prop_map(|(proposal, key)| VaultOp::Sign { proposal, key }),
+        (0usize..4, 0..KEY_POOL).prop_map(|(proposal, key)| VaultOp::Execute { proposal, key }),
+    ]
+}
+
+/// Returns the error code of a failed call, or `None` if it succeeded.
+fn error_code<T>(result: Result<T>) -> Option<u32> {
+    match result {
+        Ok(_) => None,
+        Err(Error::AnchorError(error)) => Some(error.error_code_number),
+        Err(Error::ProgramError(_)) => Some(u32::MAX),
+    }
+}
+
+fn keys() -> Vec<Pubkey> {
+    (0..KEY_POOL).map(|_| Pubkey::new_unique()).collect()
+}
+
+/// A multisig proposal alongside the model of the signatures it should hold.
+struct MultisigCase {
+    account: MultisigProposalAccount,
+    signed: Vec<Pubkey>,
+    approvals: u32,
+}
+
+/// A vault transfer proposal alongside the model of the signatures it should hold.
+struct VaultCase {
+    account: VaultTransferProposalAccount,
+    signed: Vec<Pubkey>,
+    recipient: usize,
+    amount: u64,
+    executions: u32,
+}
+
+proptest! {
+    #[test]
+    fn multisig_proposals_resolve_once_and_only_with_quorum(
+        council_size in 0usize..=5,
+        weights in prop::collection::vec(0u64..150, KEY_POOL),
+        ops in prop::collection::vec(multisig_op(), 0..48),
+    ) {
+        let keys = keys();
+        let council = &keys[..council_size];
+        let mut proposals: Vec<MultisigCase> = Vec::new();
+
+        for op in ops {
+            match op {
+                MultisigOp::Create { mode_stake_weighted, min_approval_weight } => {
+                    let mode = if mode_stake_weighted { MultisigMode::StakeWeighted } else { MultisigMode::Unanimous };
+                    let data = Multisig {
+                        name: "Council".to_string(),
+                        threshold: 1,
+                        signers: vec![MultisigSigners { name: "member".to_string(), pubkey: keys[0] }],
+                        mode,
+                        min_approval_weight,
+                    };
+                    let mut account = MultisigProposalAccount::zeroed();
+                    account.write(proposals.len() as u64, &data, council, mode, min_approval_weight).unwrap();
+                    proposals.push(MultisigCase { account, signed: Vec::new(), approvals: 0 });
+                }
+                MultisigOp::Sign { proposal, key } => {
+                    let Some(case) = proposals.get_mut(proposal) else { continue };
+                    let signer = keys[key];
+
+                    let expected = if !case.account.is_pending() {
+                        Some(u32::from(MultisigErrorCode::AlreadyResolved))
+                    } else if !council.is_empty() && !council.contains(&signer) {
+                        Some(u32::from(MultisigErrorCode::UnauthorizedSigner))
+                    } else if case.signed.contains(&signer) {
+                        Some(u32::from(MultisigErrorCode::DuplicateSignature))
+                    } else {
+                        None
+                    };
+
+                    case.account.sync_signed_mask();
+                    let result = case.account.check_can_sign(&signer);
+                    prop_assert_eq!(error_code(result), expected);
+                    if expected.is_none() {
+                        let weight = match case.account.mode() {
+                            MultisigMode::Unanimous => 0,
+                            MultisigMode::StakeWeighted => weights[key],
+                        };
+                        case.account.add_signature(signer, weight).unwrap();
+                        case.signed.push(signer);
+                    }
+                }
+                MultisigOp::Approve { proposal, key } => {
+                    let Some(case) = proposals.get_mut(proposal) else { continue };
+                    let signer = keys[key];
+
+                    let quorum = match case.account.mode() {
+                        MultisigMode::Unanimous => council.iter().all(|member| case.signed.contains(member)),
+                        MultisigMode::StakeWeighted => {
+                            case.signed.iter().map(|signed| weights[keys.iter().position(|key| key == signed).unwrap()]).sum::<u64>()
+                                >= case.account.min_approval_weight
+                        }
+                    };
+                    let expected = if !case.account.is_pending() {
+                        Some(u32::from(MultisigErrorCode::AlreadyResolved))
+                    } else if !case.signed.is_empty() && !case.signed.contains(&signer) {
+                        Some(u32::from(MultisigErrorCode::UnauthorizedSigner))
+                    } else if !quorum {
+                        Some(u32::from(match case.account.mode() {
+                            MultisigMode::Unanimous => MultisigErrorCode::InsufficientSignatures,
+                            MultisigMode::StakeWeighted => MultisigErrorCode::InsufficientVotingWeight,
+                        }))
+                    } else {
+                        None
+                    };
+
+                    let result = case.account.approve(&signer);
+                    prop_assert_eq!(error_code(result), expected);
+                    if expected.is_none() {
+                        case.approvals += 1;
+                    }
+                }
+            }
+
+            for case in &proposals {
+                prop_assert!(case.approvals <= 1);
+                prop_assert_eq!(case.account.is_pending(), case.approvals == 0);
+                prop_assert_eq!(case.account.signer_keys(), &case.signed[..]);
+                prop_assert_eq!(
+                    case.account.signed_mask(),
+                    signer_mask_of(case.account.required_signer_keys(), case.account.signer_keys())
+                );
+                if case.approvals == 1 && case.account.mode() == MultisigMode::Unanimous {
+                    prop_assert!(case.account.all_required_signed());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn vault_transfers_execute_once_with_quorum_and_conserve_lamports(
+        council_size in 0usize..=5,
+        vault_balance in 0u64..6_000,
+        ops in prop::collection::vec(vault_op(), 0..48),
+    ) {
+        let keys = keys();
+        let council = &keys[..council_size];
+        let initial_balance = vault_balance;
+        let mut vault_balance = vault_balance;
+        let mut received = [0u64; KEY_POOL];
+        let mut proposals: Vec<VaultCase> = Vec::new();
+
+        for op in ops {
+            match op {
+                VaultOp::Create { recipient, amount } => {
+                    let account = VaultTransferProposalAccount {
+                        id: proposals.len() as u64,
+                        transaction: VaultTransaction::Transfer { to: keys[recipient], amount },
+                        multisig_required_signers: council.to_vec(),
+                        multisig_signers: Vec::new(),
+                        status: VaultTransferProposalStatus::Pending,
+                        version: VaultTransferProposalAccount::VERSION,
+                        bump: 0,
+                        signed_mask: 0,
+                        created_at: 0,
+                        updated_at: 0,
+                        last_actor: Pubkey::default(),
+                    };
+                    proposals.push(VaultCase { account, signed: Vec::new(), recipient, amount, executions: 0 });
+                }
+                VaultOp::Sign { proposal, key } => {
+                    let Some(case) = proposals.get_mut(proposal) else { continue };
+                    let signer = keys[key];
+
+                    let expected = if case.account.status != VaultTransferProposalStatus::Pending {
+                        Some(u32::from(VaultErrorCode::AlreadyResolved))
+                    } else if !council.is_empty() && !council.contains(&signer) {
+                        Some(u32::from(VaultErrorCode::UnauthorizedSigner))
+                    } else if case.signed.contains(&signer) {
+                        Some(u32::from(VaultErrorCode::DuplicateSignature))
+                    } else {
+                        None
+                    };
+
+                    case.account.sync_signed_mask();
+                    let result = case.account.check_can_sign(&signer);
+                    prop_assert_eq!(error_code(result), expected);
+                    if expected.is_none() {
+                        case.account.add_signature(signer);
+                        case.signed.push(signer);
+                    }
+                }
+                VaultOp::Execute { proposal, key } => {
+                    let Some(case) = proposals.get_mut(proposal) else { continue };
+                    let signer = keys[key];
+
+                    let expected = if case.account.status != VaultTransferProposalStatus::Pending {
+                        Some(u32::from(VaultErrorCode::AlreadyResolved))
+                    } else if !council.is_empty() && !council.contains(&signer) {
+                        Some(u32::from(VaultErrorCode::UnauthorizedSigner))
+                    } else if !council.iter().all(|member| case.signed.contains(member)) {
+                        Some(u32::from(VaultErrorCode::InsufficientSignatures))
+                    } else {
+                        None
+                    };
+
+                    // A failed execution reverts the whole transaction, including the proposal's resolution.
+                    let before = case.account.clone();
+                    let result = case.account.resolve(&signer);
+                    prop_assert_eq!(error_code(result), expected);
+                    if expected.is_none() {
+                        if vault_balance >= case.amount {
+                            vault_balance -= case.amount;
+                            received[case.recipient] += case.amount;
+                            case.executions += 1;
+                        } else {
+                            case.account = before;
+                        }
+                    }
+                }
+            }
+
+            prop_assert_eq!(vault_balance + received.iter().sum::<u64>(), initial_balance);
+            for case in &proposals {
+                prop_assert!(case.executions <= 1);
+                prop_assert_eq!(
+                    case.account.status == VaultTransferProposalStatus::Approved,
+                    case.executions == 1
+                );
+                prop_assert_eq!(&case.account.multisig_signers, &case.signed);
+                prop_assert_eq!(
+                    case.account.signed_mask,
+                    signer_mask_of(&case.account.multisig_required_signers, &case.account.multisig_signers)
+                );
+                if case.executions == 1 {
+                    prop_assert_eq!(
+                        case.account.signed_mask,
+                        full_signer_mask(case.account.multisig_required_signers.len())
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn disputed_slashes_need_more_signatures_than_undisputed_ones() {
+    let council: Vec<Pubkey> = keys()[..5].to_vec();
+    let threshold = 3;
+    let slash_proposal = |disputed_at: i64, signers: &[Pubkey]| StakingProposalAccount {
+        id: 0,
+        action: StakingProposalAction::Slash {
+            staker: Pubkey::new_unique(),
+            position_index: 0,
+            amount: 100,
+            evidence_hash: [1; 32],
+            reason: String::from("double signing"),
+        },
+        multisig_required_signers: council.clone(),
+        multisig_signers: signers.to_vec(),
+        status: StakingProposalStatus::Pending,
+        created_at: 0,
+        disputed_at,
+        version: StakingProposalAccount::VERSION,
+        bump: 0,
+        signed_mask: signer_mask_of(&council, signers),
+        updated_at: 0,
+        last_actor: Pubkey::default(),
+    };
+
+    let mut undisputed = slash_proposal(0, &council[..3]);
+    let approvals_required = undisputed.slash_approvals_required(threshold);
+    assert_eq!(approvals_required, 3);
+    undisputed.approve_with(&council[0], approvals_required).unwrap();
+    assert!(undisputed.status == StakingProposalStatus::Approved);
+
+    let mut disputed = slash_proposal(1, &council[..3]);
+    let approvals_required = disputed.slash_approvals_required(threshold);
+    assert_eq!(approvals_required, council.len());
+    assert_eq!(
+        disputed.approve_with(&council[0], approvals_required).unwrap_err(),
+        Error::from(StakingErrorCode::InsufficientSignatures)
+    );
+    assert!(disputed.status == StakingProposalStatus::Pending);
+
+    let mut disputed = slash_proposal(1, &council);
+    disputed.approve_with(&council[0], disputed.slash_approvals_required(threshold)).unwrap();
+    assert!(disputed.status == StakingProposalStatus::Approved);
+}
@@ -0,0 +1,120 @@
+//! Tests for decoding staking registries written with older layouts through `StakingRegistryData`.
+//!
+//! Each case writes a registry in the current layout, cuts it back to the fields an older layout serialized, and zero-pads
+//! it to the size that layout was allocated with, as a registry created before an upgrade looks on chain. Decoding must
+//! pick the layout by size, keep the fields that layout stored, and backfill the rest with their defaults.
+use anchor_lang::prelude::*;
+use miming_spoke_solana::staking::{
+    StakingMode, StakingRegistryAccount, StakingRegistryData, StakingRegistryV2Fields, StakingRegistryV3Fields,
+    StakingRegistryV4Fields, REGISTRY_VERSION,
+};
+
+/// Size of the fields appended after the legacy layout: `version`, `started_at`, `loyalty_boost_bps`, `custodian`,
+/// `paired_lamports`, `bump`, and the audit metadata.
+const APPENDED_SPACE: usize = 1 + 8 + 2 + 32 + 8 + 1 + 2 * 8 + 32;
+
+const NOW: i64 = 1_700_000_000;
+
+fn registry() -> StakingRegistryAccount {
+    StakingRegistryAccount {
+        position_index: 3,
+        reference_id: "REF-42".to_string(),
+        mode: StakingMode::Escrow,
+        mint: Pubkey::new_unique(),
+        staked_amount: 250_000,
+        multiplier_bps: 12_500,
+        accrued_rewards: 77,
+        last_accrued_at: NOW - 100,
+        slashed_amount: 0,
+        unlocks_at: NOW + 1_000,
+        emergency_thawed_at: 0,
+        unstake_requested_at: 0,
+        cooldown_ends_at: 0,
+        referrer: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        version: REGISTRY_VERSION,
+        started_at: NOW - 5_000,
+        loyalty_boost_bps: 500,
+        custodian: Pubkey::new_unique(),
+        paired_lamports: 9_000,
+        bump: 254,
+        created_at: NOW - 5_000,
+        updated_at: NOW - 100,
+        last_actor: Pubkey::new_unique(),
+    }
+}
+
+/// Returns `registry` as an older layout stores it: its first `serialized_len` bytes, zero-padded to `account_space`.
+fn legacy_data(registry: &StakingRegistryAccount, serialized_len: usize, account_space: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    registry.try_serialize(&mut data).unwrap();
+    data.truncate(serialized_len);
+    data.resize(account_space, 0);
+    data
+}
+
+fn legacy_len(registry: &StakingRegistryAccount) -> usize {
+    let mut data = Vec::new();
+    registry.try_serialize(&mut data).unwrap();
+    data.len() - APPENDED_SPACE
+}
+
+#[test]
+fn current_registries_decode_unchanged() {
+    let registry = registry();
+    let mut data = Vec::new();
+    registry.try_serialize(&mut data).unwrap();
+    data.resize(StakingRegistryAccount::SPACE, 0);
+
+    let decoded = StakingRegistryData::decode(&data).unwrap();
+    assert!(decoded.is_current());
+    assert_eq!(decoded.position_index(), 3);
+
+    let upgraded = decoded.upgrade(NOW);
+    assert_eq!(upgraded.bump, 254);
+    assert_eq!(upgraded.last_actor, registry.last_actor);
+}
+
+#[test]
+fn legacy_registries_decode_by_size_and_backfill_missing_fields() {
+    let registry = registry();
+    let legacy_len = legacy_len(&registry);
+
+    let v0 = StakingRegistryData::decode(&legacy_data(&registry, legacy_len, StakingRegistryV2Fields::ACCOUNT_SPACE - 1)).unwrap();
+    assert!(matches!(v0, StakingRegistryData::V0(_)));
+    assert_eq!(v0.position_index(), 3);
+    let v0 = v0.upgrade(NOW);
+    assert_eq!(v0.staked_amount, 250_000);
+    assert_eq!(v0.version, REGISTRY_VERSION);
+    assert_eq!(v0.started_at, NOW, "an active stake without a recorded start starts its holding time now");
+    assert_eq!(v0.loyalty_boost_bps, 0);
+    assert_eq!(v0.custodian, Pubkey::default());
+
+    let v2 = StakingRegistryData::decode(&legacy_data(&registry, legacy_len + 11, StakingRegistryV2Fields::ACCOUNT_SPACE)).unwrap();
+    assert!(matches!(v2, StakingRegistryData::V2(..)));
+    let v2 = v2.upgrade(NOW);
+    assert_eq!(v2.started_at, NOW - 5_000);
+    assert_eq!(v2.loyalty_boost_bps, 500);
+    assert_eq!(v2.custodian, Pubkey::default());
+
+    let v3 = StakingRegistryData::decode(&legacy_data(&registry, legacy_len + 43, StakingRegistryV3Fields::ACCOUNT_SPACE)).unwrap();
+    assert!(matches!(v3, StakingRegistryData::V3(..)));
+    let v3 = v3.upgrade(NOW);
+    assert_eq!(v3.custodian, registry.custodian);
+    assert_eq!(v3.paired_lamports, 0);
+
+    let v4 = StakingRegistryData::decode(&legacy_data(&registry, legacy_len + 51, StakingRegistryV4Fields::ACCOUNT_SPACE)).unwrap();
+    assert!(matches!(v4, StakingRegistryData::V4(..)));
+    let v4 = v4.upgrade(NOW);
+    assert_eq!(v4.paired_lamports, 9_000);
+    assert_eq!(v4.bump, 0, "legacy layouts leave the bump for the caller to record");
+    assert_eq!(v4.created_at, 0);
+}
+
+#[test]
+fn data_of_another_account_type_is_rejected() {
+    let mut data = legacy_data(&registry(), legacy_len(&registry()), StakingRegistryV2Fields::ACCOUNT_SPACE);
+    data[0] ^= 0xff;
+
+    assert!(StakingRegistryData::decode(&data).is_err());
+}
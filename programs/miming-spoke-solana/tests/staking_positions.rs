@@ -0,0 +1,178 @@
+//! Tests for which stake positions count towards the staking pool.
+//!
+//! Each case builds positions in the reward mint and in other mints, frozen with and without custody, and checks that only
+//! reward-earning positions in the reward mint move the pool's totals, fill its staking cap, discount teleport fees, carry
+//! voting power, and share in SOL distributions.
+use anchor_lang::{error::Error, prelude::Pubkey};
+use bytemuck::Zeroable;
+use miming_spoke_solana::{
+    staking::{
+        StakingActivityAccount, StakingConfigAccount, StakingMode, StakingPoolAccount, StakingRegistryAccount,
+        StakingSolDistributionAccount, StakingUserAccount,
+    },
+    states::errors::StakingErrorCode,
+};
+
+const EPOCH: u64 = 100;
+
+fn staking_config() -> StakingConfigAccount {
+    StakingConfigAccount { reward_mint: Pubkey::new_unique(), max_total_staked: 1_000_000, ..Default::default() }
+}
+
+fn position(mode: StakingMode, mint: Pubkey, custodian: Pubkey, staked_amount: u64) -> StakingRegistryAccount {
+    StakingRegistryAccount {
+        position_index: 0,
+        reference_id: String::new(),
+        mode,
+        mint,
+        staked_amount,
+        multiplier_bps: 10_000,
+        accrued_rewards: 0,
+        last_accrued_at: 0,
+        slashed_amount: 0,
+        unlocks_at: 0,
+        emergency_thawed_at: 0,
+        unstake_requested_at: 0,
+        cooldown_ends_at: 0,
+        referrer: Pubkey::default(),
+        token_account: Pubkey::default(),
+        version: 0,
+        started_at: 0,
+        loyalty_boost_bps: 0,
+        custodian,
+        paired_lamports: 0,
+        bump: 0,
+        created_at: 0,
+        updated_at: 0,
+        last_actor: Pubkey::default(),
+    }
+}
+
+fn staking_user() -> StakingUserAccount {
+    StakingUserAccount { position_count: 0, active_position_count: 0, ledger_sequence: 0, version: 0, bump: 0 }
+}
+
+#[test]
+fn only_reward_stakes_are_pooled() {
+    let staking_config = staking_config();
+    let reward_mint = staking_config.reward_mint;
+    let custodian = Pubkey::new_unique();
+
+    let pooled = [
+        position(StakingMode::Escrow, reward_mint, Pubkey::default(), 500),
+        position(StakingMode::Pair, reward_mint, Pubkey::default(), 500),
+        position(StakingMode::Freeze, reward_mint, custodian, 500),
+    ];
+    for position in &pooled {
+        assert_eq!(position.pooled_amount(&staking_config, 500), 500);
+    }
+
+    let unpooled = [
+        position(StakingMode::Freeze, reward_mint, Pubkey::default(), 500),
+        position(StakingMode::Escrow, Pubkey::new_unique(), Pubkey::default(), 500),
+        position(StakingMode::Freeze, Pubkey::new_unique(), custodian, 500),
+    ];
+    for position in &unpooled {
+        assert_eq!(position.pooled_amount(&staking_config, 500), 0);
+    }
+}
+
+#[test]
+fn foreign_mints_neither_fill_the_cap_nor_count_as_stakers() {
+    let staking_config = staking_config();
+    let mut pool = StakingPoolAccount::zeroed();
+    let mut activity = StakingActivityAccount::zeroed();
+    let mut staking_user = staking_user();
+
+    // A self-frozen position in a mint the staker created, far above the cap.
+    let junk = position(StakingMode::Freeze, Pubkey::new_unique(), Pubkey::default(), 0);
+    let junk_amount = junk.pooled_amount(&staking_config, u64::MAX / 2);
+    pool.check_stake_cap(staking_config.max_total_staked, 0, junk_amount).unwrap();
+    pool.record_stake_change(&mut staking_user, &mut activity, &staking_config, 0, junk_amount, EPOCH);
+    assert_eq!((pool.total_staked, pool.staker_count), (0, 0));
+
+    // Real stakers can still use the whole cap, and no more.
+    let escrow = position(StakingMode::Escrow, staking_config.reward_mint, Pubkey::default(), 0);
+    let amount = escrow.pooled_amount(&staking_config, staking_config.max_total_staked);
+    pool.check_stake_cap(staking_config.max_total_staked, 0, amount).unwrap();
+    pool.record_stake_change(&mut staking_user, &mut activity, &staking_config, 0, amount, EPOCH);
+    assert_eq!((pool.total_staked, pool.staker_count), (staking_config.max_total_staked, 1));
+    assert_eq!(
+        pool.check_stake_cap(staking_config.max_total_staked, amount, amount + 1).unwrap_err(),
+        Error::from(StakingErrorCode::StakingCapReached)
+    );
+}
+
+#[test]
+fn only_reward_stakes_discount_the_teleport_fee() {
+    let staking_config = staking_config();
+    let top_discount = staking_config.fee_discount_bps(u64::MAX);
+
+    let escrow = position(StakingMode::Escrow, staking_config.reward_mint, Pubkey::default(), 1_000_000);
+    let junk = position(StakingMode::Freeze, Pubkey::new_unique(), Pubkey::default(), 1_000_000);
+    let discount_bps = |position: &StakingRegistryAccount| {
+        staking_config.fee_discount_bps(position.pooled_amount(&staking_config, position.staked_amount))
+    };
+
+    assert_eq!(discount_bps(&escrow), top_discount);
+    assert_eq!(discount_bps(&junk), 0);
+}
+
+#[test]
+fn voting_power_only_counts_reward_stakes_unchanged_since_the_vote_opened() {
+    let staking_config = staking_config();
+    let opened_at = 1_000;
+
+    let mut escrow = position(StakingMode::Escrow, staking_config.reward_mint, Pubkey::default(), 500);
+    escrow.updated_at = opened_at - 1;
+    assert_eq!(escrow.voting_power_at(&staking_config, opened_at), 500);
+    assert_eq!(escrow.voting_power_at(&staking_config, i64::MAX), escrow.voting_power());
+
+    let mut junk = position(StakingMode::Freeze, Pubkey::new_unique(), Pubkey::default(), 500);
+    junk.updated_at = opened_at - 1;
+    assert_eq!(junk.voting_power_at(&staking_config, opened_at), 0);
+
+    // Releasing the stake and staking it into another member's position after the vote opened counts it in neither.
+    escrow.staked_amount = 0;
+    escrow.updated_at = opened_at + 1;
+    let mut moved = position(StakingMode::Escrow, staking_config.reward_mint, Pubkey::default(), 500);
+    moved.created_at = opened_at + 1;
+    moved.updated_at = opened_at + 1;
+    assert_eq!(escrow.voting_power_at(&staking_config, opened_at), 0);
+    assert_eq!(moved.voting_power_at(&staking_config, opened_at), 0);
+    assert_eq!(moved.voting_power_at(&staking_config, i64::MAX), 500);
+}
+
+#[test]
+fn a_top_up_after_the_snapshot_does_not_increase_the_sol_share() {
+    let staking_config = staking_config();
+    let recorded_at = 1_000;
+    let distribution = StakingSolDistributionAccount {
+        epoch: EPOCH,
+        proposal_id: 0,
+        amount: 1_000,
+        total_staked: 1_000,
+        claimed_amount: 0,
+        distributed_at: recorded_at,
+        version: 0,
+        bump: 0,
+    };
+
+    let mut escrow = position(StakingMode::Escrow, staking_config.reward_mint, Pubkey::default(), 500);
+    escrow.started_at = recorded_at - 10;
+    escrow.updated_at = recorded_at - 10;
+    let payout = distribution.share(escrow.snapshot_stake(&staking_config, recorded_at).unwrap());
+    assert_eq!(payout, 500);
+
+    escrow.staked_amount = 900;
+    escrow.updated_at = recorded_at + 1;
+    assert_eq!(
+        escrow.snapshot_stake(&staking_config, recorded_at).unwrap_err(),
+        Error::from(StakingErrorCode::StakedAfterSnapshot)
+    );
+
+    let mut junk = position(StakingMode::Freeze, Pubkey::new_unique(), Pubkey::default(), 500);
+    junk.started_at = recorded_at - 10;
+    junk.updated_at = recorded_at - 10;
+    assert_eq!(distribution.share(junk.snapshot_stake(&staking_config, recorded_at).unwrap()), 0);
+}
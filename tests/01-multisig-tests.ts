@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { MimingSpokeSolana } from "../target/types/miming_spoke_solana";
 import { SystemProgram, Keypair, PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
+import { genesisParams, initializedProgram } from "./fixtures";
 
 const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
 
@@ -12,33 +13,67 @@ const connection = program.provider.connection;
 
 const [proposalIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("proposal_identifier")], program.programId);
 const [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from("multisig")], program.programId);
+const [programConfigPda] = PublicKey.findProgramAddressSync([Buffer.from("program_config")], program.programId);
+const [hubStateRootsPda] = PublicKey.findProgramAddressSync([Buffer.from("hub_state_roots")], program.programId);
+
+const decodeName = (bytes: number[], length: number) => Buffer.from(bytes.slice(0, length)).toString("utf8");
+
+// Proposals are zero-copy accounts holding fixed-size arrays; this reads one back in its logical shape.
+const fetchProposal = async (proposalPda: PublicKey) => {
+    const proposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+    return {
+        data: {
+            name: decodeName(proposal.data.name, proposal.data.nameLen),
+            threshold: proposal.data.threshold,
+            signers: proposal.data.signers.slice(0, proposal.data.signerCount).map((signer) => ({
+                name: decodeName(signer.name, signer.nameLen),
+                pubkey: signer.pubkey,
+            })),
+        },
+        requiredSigners: proposal.requiredSigners.slice(0, proposal.requiredSignerCount),
+        signers: proposal.signers.slice(0, proposal.signerCount),
+        status: proposal.status == 0 ? { pending: {} } : { approved: {} },
+    };
+};
 
 describe("01-multisig-tests", () => {
     const signer = Keypair.generate();
     const target = Keypair.generate();
+    const genesisSigner = provider.wallet.publicKey;
 
     let firstSigners: { name: string; pubkey: PublicKey; keypair: Keypair; }[] = [];
     let secondSigners: { name: string; pubkey: PublicKey; keypair: Keypair; }[] = [];
     let thirdSigners: { name: string; pubkey: PublicKey; keypair: Keypair; }[] = [];
     let fourthSigners: { name: string; pubkey: PublicKey; keypair: Keypair; }[] = [];
 
-    it("should initialize multisig.", async () => {
-        const signer = Keypair.generate();
-
-        await connection.requestAirdrop(signer.publicKey, 10e9);
+    it("should initialize multisig at genesis.", async () => {
         await connection.requestAirdrop(target.publicKey, 10e9);
 
-        await sleep(2000);
+        await initializedProgram();
+
+        const multisig = await program.account.multisigAccount.fetch(multisigPda);
+        expect(multisig.name).to.equal("System");
+        expect(multisig.threshold).equal(genesisParams.threshold);
+        expect(multisig.signers).to.deep.equal(genesisParams.signers);
+    });
+
+    it("submitting a hub state root should record it for a quorum of the genesis council.", async () => {
+        const root = Array(32).fill(1);
 
-        await program.methods.multisigInitialize()
+        await program.methods.lightClientSubmitRoot(new anchor.BN(1), root)
             .accounts({
-                signer: signer.publicKey,
-                proposalIdentifier: proposalIdentifierPda,
-                multisig: multisigPda,
+                signer: genesisSigner,
+                payer: genesisSigner,
+                currentMultisig: multisigPda,
+                programConfig: programConfigPda,
+                hubStateRoots: hubStateRootsPda,
                 systemProgram: SystemProgram.programId
             } as any)
-            .signers([signer])
             .rpc();
+
+        const hubStateRoots = await program.account.hubStateRootsAccount.fetch(hubStateRootsPda);
+        expect(hubStateRoots.latestHeight.toNumber()).to.equal(1);
+        expect(hubStateRoots.roots.map((stateRoot) => stateRoot.root)).to.deep.equal([root]);
     });
 
     it("creating a proposal should succeed with valid signers and threshold.", async () => {
@@ -70,58 +105,77 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
-                signer: signer.publicKey,
+                signer: genesisSigner,
+                payer: genesisSigner,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
                 systemProgram: SystemProgram.programId
             } as any)
-            .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(5);
         expect(newProposal.data.signers).to.deep.equal(signers);
-        expect(newProposal.requiredSigners).to.deep.equal([]);
+        expect(newProposal.requiredSigners).to.deep.equal([genesisSigner]);
         expect(newProposal.signers).to.deep.equal([]);
         expect(newProposal.status).to.have.property("pending");
 
-        await program.methods.multisigSignProposal()
+        await program.methods.multisigSignProposal(null)
             .accounts({
-                signer: signer.publicKey,
+                signer: genesisSigner,
                 currentProposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
                 systemProgram: SystemProgram.programId
             } as any)
-            .signers([signer])
             .rpc();
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(5);
         expect(signedProposal.data.signers).to.deep.equal(signers);
-        expect(signedProposal.requiredSigners).to.deep.equal([]);
-        expect(signedProposal.signers).to.deep.equal([signer.publicKey]);
+        expect(signedProposal.requiredSigners).to.deep.equal([genesisSigner]);
+        expect(signedProposal.signers).to.deep.equal([genesisSigner]);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, true)
             .accounts({
-                signer: signer.publicKey,
+                signer: genesisSigner,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .rpc()
+            .then(() => expect.fail("a dry run should not commit"))
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("DryRunCompleted");
+            });
+
+        const previewedProposal = await fetchProposal(proposalPda);
+        expect(previewedProposal.status).to.have.property("pending");
+
+        await program.methods.multisigApproveProposal(null, false)
+            .accounts({
+                signer: genesisSigner,
+                currentProposal: proposalPda,
+                currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
-            .signers([signer])
             .rpc();
 
-        const approvedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const approvedProposal = await fetchProposal(proposalPda);
         expect(approvedProposal.data.name).to.equal(name);
         expect(approvedProposal.data.threshold).equal(5);
         expect(approvedProposal.data.signers).to.deep.equal(signers);
-        expect(approvedProposal.requiredSigners).to.deep.equal([]);
-        expect(approvedProposal.signers).to.deep.equal([signer.publicKey]);
+        expect(approvedProposal.requiredSigners).to.deep.equal([genesisSigner]);
+        expect(approvedProposal.signers).to.deep.equal([genesisSigner]);
         expect(approvedProposal.status).to.have.property("approved");
 
         const multisig = await program.account.multisigAccount.fetch(multisigPda);
@@ -166,9 +220,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -205,9 +260,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -222,6 +278,41 @@ describe("01-multisig-tests", () => {
             });
     });
 
+    it("creating a proposal should fail if the name does not fit the proposal (NameTooLong).", async () => {
+        await connection.requestAirdrop(signer.publicKey, 10e9);
+
+        await sleep(2000);
+
+        const name = "A multisig name longer than thirty-two bytes";
+        const threshold = 1;
+        const signers: { name: string; pubkey: PublicKey; }[] = [
+            { name: "signer1", pubkey: Keypair.generate().publicKey },
+        ];
+
+        const proposalIdentifier = await program.account.identifierAccount.fetch(proposalIdentifierPda);
+        const [proposalPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("proposal"),
+            new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                currentMultisig: multisigPda,
+                proposalIdentifier: proposalIdentifierPda,
+                proposal: proposalPda,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .signers([signer])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("NameTooLong");
+                expect(err.error.errorMessage).to.equal("The name is longer than the maximum allowed length.");
+            });
+    });
+
     it("should sign a proposal if signer is valid and has not signed yet.", async () => {
         await connection.requestAirdrop(signer.publicKey, 10e9);
         await connection.requestAirdrop(target.publicKey, 10e9);
@@ -248,9 +339,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -259,7 +351,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(5);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -269,10 +361,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of firstSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -281,7 +375,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(5);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -290,6 +384,37 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.status).to.have.property("pending");
     });
 
+    it("creating and signing a proposal in one instruction should record the proposer's signature.", async () => {
+        const proposer = firstSigners[0];
+        const signers: { name: string; pubkey: PublicKey; }[] = [{ name: "signer1", pubkey: Keypair.generate().publicKey }];
+
+        const proposalIdentifier = await program.account.identifierAccount.fetch(proposalIdentifierPda);
+        const [proposalPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("proposal"),
+            new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods.multisigCreateAndSignProposal("Test", 1, signers, { unanimous: {} }, new anchor.BN(0), null)
+            .accounts({
+                signer: proposer.pubkey,
+                payer: proposer.pubkey,
+                currentMultisig: multisigPda,
+                proposalIdentifier: proposalIdentifierPda,
+                proposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .signers([proposer.keypair])
+            .rpc();
+
+        const proposal = await fetchProposal(proposalPda);
+        expect(proposal.data.signers).to.deep.equal(signers);
+        expect(proposal.requiredSigners).to.deep.equal(firstSigners.map(signer => signer.pubkey));
+        expect(proposal.signers).to.deep.equal([proposer.pubkey]);
+        expect(proposal.status).to.have.property("pending");
+    });
+
     it("signing a proposal should fail if the proposal is already resolved (AlreadyResolved).", async () => {
         await connection.requestAirdrop(signer.publicKey, 10e9);
         await connection.requestAirdrop(target.publicKey, 10e9);
@@ -323,9 +448,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -334,7 +460,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(5);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -344,10 +470,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of firstSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -356,7 +484,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(5);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -364,17 +492,18 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal(signersArray);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: firstSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([firstSigners[0].keypair])
             .rpc();
 
-        const approvedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const approvedProposal = await fetchProposal(proposalPda);
         expect(approvedProposal.data.name).to.equal(name);
         expect(approvedProposal.data.threshold).equal(5);
         expect(approvedProposal.data.signers).to.deep.equal(signers);
@@ -394,11 +523,12 @@ describe("01-multisig-tests", () => {
             keypair: signerKeypairs[index]
         }));
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: firstSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([firstSigners[0].keypair])
@@ -436,9 +566,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -447,7 +578,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(5);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -455,10 +586,12 @@ describe("01-multisig-tests", () => {
         expect(newProposal.signers).to.deep.equal([]);
         expect(newProposal.status).to.have.property("pending");
 
-        await program.methods.multisigSignProposal()
+        await program.methods.multisigSignProposal(null)
             .accounts({
                 signer: signer.publicKey,
                 currentProposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([signer])
@@ -496,9 +629,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -507,7 +641,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(5);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -517,10 +651,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of secondSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -529,7 +665,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(5);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -537,10 +673,12 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal(signersArray);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigSignProposal()
+        await program.methods.multisigSignProposal(null)
             .accounts({
                 signer: secondSigners[0].pubkey,
                 currentProposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([secondSigners[0].keypair])
@@ -589,9 +727,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -600,7 +739,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(7);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -610,10 +749,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of secondSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -622,7 +763,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(7);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -630,17 +771,18 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal(signersArray);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: secondSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([secondSigners[0].keypair])
             .rpc();
 
-        const approvedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const approvedProposal = await fetchProposal(proposalPda);
         expect(approvedProposal.data.name).to.equal(name);
         expect(approvedProposal.data.threshold).equal(7);
         expect(approvedProposal.data.signers).to.deep.equal(signers);
@@ -692,9 +834,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -703,7 +846,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(4);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -713,10 +856,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of thirdSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -725,7 +870,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(4);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -733,17 +878,18 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal(signersArray);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: thirdSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([thirdSigners[0].keypair])
             .rpc();
 
-        const approvedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const approvedProposal = await fetchProposal(proposalPda);
         expect(approvedProposal.data.name).to.equal(name);
         expect(approvedProposal.data.threshold).equal(4);
         expect(approvedProposal.data.signers).to.deep.equal(signers);
@@ -763,11 +909,12 @@ describe("01-multisig-tests", () => {
             keypair: signerKeypairs[index]
         }));
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: fourthSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([fourthSigners[0].keypair])
@@ -810,9 +957,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -821,7 +969,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(4);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -831,10 +979,12 @@ describe("01-multisig-tests", () => {
 
         const signersArray: PublicKey[] = [];
         for (const signer of fourthSigners) {
-            await program.methods.multisigSignProposal()
+            await program.methods.multisigSignProposal(null)
                 .accounts({
                     signer: signer.pubkey,
                     currentProposal: proposalPda,
+                    walletNonce: null,
+                    stakingConfig: null,
                     systemProgram: SystemProgram.programId
                 } as any)
                 .signers([signer.keypair])
@@ -843,7 +993,7 @@ describe("01-multisig-tests", () => {
             signersArray.push(signer.pubkey);
         };
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(4);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -851,11 +1001,12 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal(signersArray);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: signer.publicKey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([signer])
@@ -893,9 +1044,10 @@ describe("01-multisig-tests", () => {
             new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
         ], program.programId);
 
-        await program.methods.multisigCreateProposal(name, threshold, signers)
+        await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 currentMultisig: multisigPda,
                 proposalIdentifier: proposalIdentifierPda,
                 proposal: proposalPda,
@@ -904,7 +1056,7 @@ describe("01-multisig-tests", () => {
             .signers([signer])
             .rpc();
 
-        const newProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const newProposal = await fetchProposal(proposalPda);
         expect(newProposal.data.name).to.equal(name);
         expect(newProposal.data.threshold).equal(3);
         expect(newProposal.data.signers).to.deep.equal(signers);
@@ -912,16 +1064,18 @@ describe("01-multisig-tests", () => {
         expect(newProposal.signers).to.deep.equal([]);
         expect(newProposal.status).to.have.property("pending");
 
-        await program.methods.multisigSignProposal()
+        await program.methods.multisigSignProposal(null)
             .accounts({
                 signer: fourthSigners[0].pubkey,
                 currentProposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([fourthSigners[0].keypair])
             .rpc();
 
-        const signedProposal = await program.account.multisigProposalAccount.fetch(proposalPda);
+        const signedProposal = await fetchProposal(proposalPda);
         expect(signedProposal.data.name).to.equal(name);
         expect(signedProposal.data.threshold).equal(3);
         expect(signedProposal.data.signers).to.deep.equal(signers);
@@ -929,11 +1083,12 @@ describe("01-multisig-tests", () => {
         expect(signedProposal.signers).to.deep.equal([fourthSigners[0].pubkey]);
         expect(signedProposal.status).to.have.property("pending");
 
-        await program.methods.multisigApproveProposal()
+        await program.methods.multisigApproveProposal(null, false)
             .accounts({
                 signer: fourthSigners[0].pubkey,
                 currentProposal: proposalPda,
                 currentMultisig: multisigPda,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([fourthSigners[0].keypair])
@@ -944,4 +1099,96 @@ describe("01-multisig-tests", () => {
                 expect(err.error.errorMessage).to.equal("Not enough signatures have been collected to proceed.");
             });
     });
+
+    it("signing and approving a proposal should stay within the compute budget for a 10-signer council.", async () => {
+        await connection.requestAirdrop(signer.publicKey, 10e9);
+
+        await sleep(2000);
+
+        const council = Array.from({ length: 10 }, () => Keypair.generate());
+        const councilSigners = council.map((keypair, i) => ({ name: `council${i + 1}`, pubkey: keypair.publicKey }));
+
+        const computeUnits = async (signature: string) => {
+            const transaction = await connection.getTransaction(signature, {
+                commitment: "confirmed",
+                maxSupportedTransactionVersion: 0,
+            });
+            return transaction!.meta!.computeUnitsConsumed!;
+        };
+
+        const createProposal = async () => {
+            const proposalIdentifier = await program.account.identifierAccount.fetch(proposalIdentifierPda);
+            const [proposalPda] = PublicKey.findProgramAddressSync([
+                Buffer.from("proposal"),
+                new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+            ], program.programId);
+
+            await program.methods.multisigCreateProposal("Council", 10, councilSigners, { unanimous: {} }, new anchor.BN(0))
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    proposalIdentifier: proposalIdentifierPda,
+                    proposal: proposalPda,
+                    systemProgram: SystemProgram.programId
+                } as any)
+                .signers([signer])
+                .rpc();
+
+            return proposalPda;
+        };
+
+        const signProposal = (proposalPda: PublicKey, keypair: Keypair) => program.methods.multisigSignProposal(null)
+            .accounts({
+                signer: keypair.publicKey,
+                currentProposal: proposalPda,
+                walletNonce: null,
+                stakingConfig: null,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .signers([keypair])
+            .rpc({ commitment: "confirmed" });
+
+        const approveProposal = (proposalPda: PublicKey, keypair: Keypair) => program.methods.multisigApproveProposal(null, false)
+            .accounts({
+                signer: keypair.publicKey,
+                currentProposal: proposalPda,
+                currentMultisig: multisigPda,
+                walletNonce: null,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .signers([keypair])
+            .rpc({ commitment: "confirmed" });
+
+        // Seat the 10-signer council with the current one.
+        const seatingProposalPda = await createProposal();
+        for (const signer of fourthSigners) {
+            await signProposal(seatingProposalPda, signer.keypair);
+        }
+        await approveProposal(seatingProposalPda, fourthSigners[0].keypair);
+
+        for (const keypair of council) {
+            await connection.requestAirdrop(keypair.publicKey, 1e9);
+        }
+        await sleep(2000);
+
+        const benchmarkProposalPda = await createProposal();
+
+        const signUnits: number[] = [];
+        for (const keypair of council) {
+            signUnits.push(await computeUnits(await signProposal(benchmarkProposalPda, keypair)));
+        }
+        const approveUnits = await computeUnits(await approveProposal(benchmarkProposalPda, council[9]));
+
+        console.log(`        sign: ${signUnits.join(", ")} CU; approve: ${approveUnits} CU`);
+
+        // Signature checks are bitmap operations, so the 10th signature costs about as much as the first.
+        expect(Math.max(...signUnits)).to.be.lessThan(25000);
+        expect(Math.max(...signUnits) - Math.min(...signUnits)).to.be.lessThan(1500);
+        expect(approveUnits).to.be.lessThan(50000);
+
+        const approvedProposal = await fetchProposal(benchmarkProposalPda);
+        expect(approvedProposal.signers).to.deep.equal(council.map(keypair => keypair.publicKey));
+        expect(approvedProposal.status).to.have.property("approved");
+    });
 });
\ No newline at end of file
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { MimingSpokeSolana } from "../target/types/miming_spoke_solana";
 import { SystemProgram, Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { expect } from "chai";
+import { initializedProgram } from "./fixtures";
 
 const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
 
@@ -12,22 +13,78 @@ const connection = program.provider.connection;
 
 const [vaultPda] = PublicKey.findProgramAddressSync([Buffer.from("vault")], program.programId);
 const [ledgerIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("ledger_identifier")], program.programId);
+const [transferProposalIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("transfer_proposal_identifier")], program.programId);
+const [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from("multisig")], program.programId);
+const [proposalIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("proposal_identifier")], program.programId);
+const [programConfigPda, programConfigBump] = PublicKey.findProgramAddressSync([Buffer.from("program_config")], program.programId);
 
 describe("02-vault-tests", () => {
-    it("should initialize vault.", async () => {
+    it("should initialize vault at genesis.", async () => {
+        await initializedProgram();
+
+        const transferProposalIdentifier = await program.account.identifierAccount.fetch(transferProposalIdentifierPda);
+        expect(transferProposalIdentifier.id.toNumber()).to.equal(0);
+    });
+
+    it("should initialize the program configuration at genesis.", async () => {
+        await initializedProgram();
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        expect(programConfig.authority.toBase58()).to.equals(multisigPda.toBase58());
+        expect(programConfig.vaultEnabled).to.be.true;
+        expect(programConfig.stakingEnabled).to.be.true;
+        expect(programConfig.teleportPaused).to.be.false;
+        expect(programConfig.teleportFee.toNumber()).to.equals(10000000);
+        expect(programConfig.globalPaused).to.be.false;
+        expect(programConfig.rentRecipient.toBase58()).to.equals(PublicKey.default.toBase58());
+        expect(programConfig.cpiGuardEnabled).to.be.false;
+        expect(programConfig.cpiAllowedCallers).to.deep.equal([]);
+        expect(programConfig.operator.toBase58()).to.equals(PublicKey.default.toBase58());
+        expect(programConfig.relayer.toBase58()).to.equals(PublicKey.default.toBase58());
+    });
+
+    it("should reject migrating an account that already uses the current layout.", async () => {
         const signer = Keypair.generate();
 
         await connection.requestAirdrop(signer.publicKey, 5e9);
         await sleep(2000);
 
-        await program.methods.vaultInitialize()
+        await program.methods.programConfigMigrate([Buffer.from("program_config")])
             .accounts({
                 signer: signer.publicKey,
-                ledgerIdentifier: ledgerIdentifierPda,
+                account: programConfigPda,
+                programConfig: programConfigPda,
                 systemProgram: SystemProgram.programId
             } as any)
             .signers([signer])
-            .rpc();
+            .rpc().catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("AccountAlreadyMigrated");
+                expect(err.error.errorMessage).to.equal("The account already uses the current layout.");
+            });
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        expect(programConfig.version).to.equals(4);
+        expect(programConfig.bump).to.equals(programConfigBump);
+    });
+
+    it("should reject an emergency stop from a key other than the guardian.", async () => {
+        const signer = Keypair.generate();
+
+        await program.methods.programConfigEmergencyPause()
+            .accounts({
+                guardian: signer.publicKey,
+                programConfig: programConfigPda
+            } as any)
+            .signers([signer])
+            .rpc().catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("UnauthorizedGuardian");
+                expect(err.error.errorMessage).to.equal("Only the guardian can trigger an emergency stop.");
+            });
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        expect(programConfig.globalPaused).to.be.false;
     });
 
     it("should teleport tokens and update the ledger if the user has sufficient SOL balance.", async () => {
@@ -49,12 +106,18 @@ describe("02-vault-tests", () => {
         ], program.programId);
 
         await program.methods
-            .vaultTeleport(amount) // 2 * LAMPORTS_PER_SOL
+            .vaultTeleport(amount, 0, Array(32).fill(0)) // 2 * LAMPORTS_PER_SOL
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 vault: vaultPda,
                 ledgerIdentifier: ledgerIdentifierPda,
                 ledger: ledgerPda,
+                programConfig: programConfigPda,
+                stakingConfig: null,
+                stakingRegistry: null,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([signer])
@@ -71,6 +134,53 @@ describe("02-vault-tests", () => {
         expect(solBalanceBefore - solBalanceAfter).to.be.lessThan(3 * LAMPORTS_PER_SOL);
     });
 
+    it("should let a separate payer fund the rent of the teleport's ledger entry.", async () => {
+        const signer = Keypair.generate();
+        const relayer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await connection.requestAirdrop(relayer.publicKey, 5e9);
+
+        await sleep(2000);
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        const solBalanceBefore = await connection.getBalance(signer.publicKey);
+        const relayerBalanceBefore = await connection.getBalance(relayer.publicKey);
+
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        const [ledgerPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("ledger"),
+            new anchor.BN(ledgerIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods
+            .vaultTeleport(amount, 0, Array(32).fill(0))
+            .accounts({
+                signer: signer.publicKey,
+                payer: relayer.publicKey,
+                vault: vaultPda,
+                ledgerIdentifier: ledgerIdentifierPda,
+                ledger: ledgerPda,
+                programConfig: programConfigPda,
+                stakingConfig: null,
+                stakingRegistry: null,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer, relayer])
+            .rpc();
+
+        const solBalanceAfter = await connection.getBalance(signer.publicKey);
+        const relayerBalanceAfter = await connection.getBalance(relayer.publicKey);
+        const ledgerRent = await connection.getBalance(ledgerPda);
+
+        expect(solBalanceBefore - solBalanceAfter).to.equals(amount.toNumber() + programConfig.teleportFee.toNumber());
+        expect(relayerBalanceBefore - relayerBalanceAfter).to.equals(ledgerRent);
+    });
+
     it("should fail if the teleporter has insufficient SOL balance (InsufficientSolBalance).", async () => {
         const signer = Keypair.generate();
 
@@ -90,12 +200,18 @@ describe("02-vault-tests", () => {
         ], program.programId);
 
         await program.methods
-            .vaultTeleport(amount)
+            .vaultTeleport(amount, 0, Array(32).fill(0))
             .accounts({
                 signer: signer.publicKey,
+                payer: signer.publicKey,
                 vault: vaultPda,
                 ledgerIdentifier: ledgerIdentifierPda,
                 ledger: ledgerPda,
+                programConfig: programConfigPda,
+                stakingConfig: null,
+                stakingRegistry: null,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([signer])
@@ -111,4 +227,232 @@ describe("02-vault-tests", () => {
         const solBalanceAfter = await connection.getBalance(signer.publicKey);
         expect(solBalanceAfter).to.lessThanOrEqual(solBalanceBefore);
     });
+
+    it("teleporting u64::MAX lamports should fail when the fee overflows the total (ArithmeticError).", async () => {
+        const signer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await sleep(2000);
+
+        const amount = new anchor.BN("18446744073709551615");
+
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        const [ledgerPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("ledger"),
+            new anchor.BN(ledgerIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods
+            .vaultTeleport(amount, 0, Array(32).fill(0))
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                vault: vaultPda,
+                ledgerIdentifier: ledgerIdentifierPda,
+                ledger: ledgerPda,
+                programConfig: programConfigPda,
+                stakingConfig: null,
+                stakingRegistry: null,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("ArithmeticError");
+                expect(err.error.errorMessage).to.equal("An arithmetic operation overflowed or underflowed.");
+            });
+
+        const ledgerIdentifierAfter = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        expect(ledgerIdentifierAfter.id.toNumber()).to.equal(ledgerIdentifier.id.toNumber());
+    });
+
+    it("teleporting should reject an identifier other than the ledger identifier (ConstraintSeeds).", async () => {
+        const signer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await sleep(2000);
+
+        const proposalIdentifier = await program.account.identifierAccount.fetch(proposalIdentifierPda);
+        const [ledgerPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("ledger"),
+            new anchor.BN(proposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods
+            .vaultTeleport(new anchor.BN(LAMPORTS_PER_SOL), 0, Array(32).fill(0))
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                vault: vaultPda,
+                ledgerIdentifier: proposalIdentifierPda,
+                ledger: ledgerPda,
+                programConfig: programConfigPda,
+                stakingConfig: null,
+                stakingRegistry: null,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("ConstraintSeeds");
+            });
+    });
+
+    it("creating a multisig proposal should reject an identifier other than the proposal identifier (ConstraintSeeds).", async () => {
+        const signer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await sleep(2000);
+
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        const [proposalPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("proposal"),
+            new anchor.BN(ledgerIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        const signers = [{ name: "signer1", pubkey: Keypair.generate().publicKey }];
+
+        await program.methods.multisigCreateProposal("Substituted", 1, signers, { unanimous: {} }, new anchor.BN(0))
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                currentMultisig: multisigPda,
+                proposalIdentifier: ledgerIdentifierPda,
+                proposal: proposalPda,
+                systemProgram: SystemProgram.programId
+            } as any)
+            .signers([signer])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("ConstraintSeeds");
+            });
+    });
+
+    it("should create a namespaced identifier counter once.", async () => {
+        const signer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await sleep(2000);
+
+        const namespace = Buffer.alloc(16);
+        namespace.write("receipts");
+        const [identifierPda] = PublicKey.findProgramAddressSync([Buffer.from("identifier"), namespace], program.programId);
+
+        await program.methods.identifierInitialize([...namespace])
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                identifier: identifierPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc();
+
+        const identifier = await program.account.identifierAccount.fetch(identifierPda);
+        expect(identifier.id.toNumber()).to.equal(0);
+        expect(identifier.version).to.equal(2);
+
+        let reinitialized = true;
+        await program.methods.identifierInitialize([...namespace])
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                identifier: identifierPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc()
+            .catch(() => {
+                reinitialized = false;
+            });
+        expect(reinitialized).to.be.false;
+    });
+
+    it("should list singleton PDAs in the program registry once, at their canonical address.", async () => {
+        const signer = Keypair.generate();
+
+        await connection.requestAirdrop(signer.publicKey, 5e9);
+        await sleep(2000);
+
+        const [programRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from("program_registry")], program.programId);
+
+        await program.methods.registryInitialize()
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                programRegistry: programRegistryPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc();
+
+        const register = (kind: any, account: PublicKey) => program.methods.registryRegister(kind)
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                programRegistry: programRegistryPda,
+                account,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc();
+
+        await register({ programConfig: {} }, programConfigPda);
+        await register({ vault: {} }, vaultPda);
+
+        const programRegistry = await program.account.programRegistryAccount.fetch(programRegistryPda);
+        expect(programRegistry.entries.map(entry => entry.address.toBase58())).to.deep.equal([
+            programConfigPda.toBase58(),
+            vaultPda.toBase58(),
+        ]);
+        expect(programRegistry.entries[0].kind).to.have.property("programConfig");
+
+        await register({ programConfig: {} }, programConfigPda)
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("AlreadyRegistered");
+            });
+
+        await register({ multisig: {} }, programConfigPda)
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidRegistryAddress");
+            });
+    });
+
+    it("should expose the vault status, multisig state, and program config as views.", async () => {
+        const vaultStatus = await program.methods.vaultGetStatus()
+            .accounts({
+                vault: vaultPda,
+                programConfig: programConfigPda,
+                ledgerIdentifier: ledgerIdentifierPda,
+                transferProposalIdentifier: transferProposalIdentifierPda,
+            } as any)
+            .view();
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        expect(vaultStatus.balance.toNumber()).to.equal(await connection.getBalance(vaultPda));
+        expect(vaultStatus.nextLedgerId.toNumber()).to.equal(ledgerIdentifier.id.toNumber());
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        const settings = await program.methods.programConfigGetSettings()
+            .accounts({ programConfig: programConfigPda } as any)
+            .view();
+        expect(settings.authority.toBase58()).to.equal(programConfig.authority.toBase58());
+        expect(settings.teleportFee.toNumber()).to.equal(vaultStatus.teleportFee.toNumber());
+        expect(settings.vaultEnabled).to.equal(vaultStatus.vaultEnabled);
+
+        const multisig = await program.account.multisigAccount.fetch(multisigPda);
+        const multisigState = await program.methods.multisigGetState()
+            .accounts({ multisig: multisigPda, proposalIdentifier: proposalIdentifierPda } as any)
+            .view();
+        expect(multisigState.threshold).to.equal(multisig.threshold);
+        expect(multisigState.signers.length).to.equal(multisig.signers.length);
+    });
 });
@@ -1,8 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { MimingSpokeSolana } from "../target/types/miming_spoke_solana";
-import { SystemProgram, Keypair, PublicKey } from "@solana/web3.js";
-import { createMint, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { SystemProgram, Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
+import { createMint, getOrCreateAssociatedTokenAccount, getAssociatedTokenAddressSync, mintTo, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount, approve } from '@solana/spl-token';
 import { expect } from "chai";
+import { initializedProgram } from "./fixtures";
 
 const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
 
@@ -38,17 +39,55 @@ const setupTestVariables = async () => {
         program.programId
     );
 
+    const [stakingUserPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("staking_user"), staker.publicKey.toBuffer()],
+        program.programId
+    );
+
     const [stakingRegistryPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("staking_registry"), staker.publicKey.toBuffer()],
+        [Buffer.from("staking_registry"), staker.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
         program.programId
     );
 
-    return { staker, token, stakerToken, stakingConfigPda, stakingRegistryPda }
+    return { staker, token, stakerToken, stakingConfigPda, stakingUserPda, stakingRegistryPda }
 }
 
+const [stakingConfigPda] = PublicKey.findProgramAddressSync([Buffer.from("staking_config")], program.programId);
+const [stakingAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from("staking_authority")], program.programId);
+const [stakingPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("staking_pool")], program.programId);
+const [stakingActivityPda] = PublicKey.findProgramAddressSync([Buffer.from("staking_activity")], program.programId);
+const [stakingProposalIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("staking_proposal_identifier")], program.programId);
+const [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from("multisig")], program.programId);
+const [programConfigPda] = PublicKey.findProgramAddressSync([Buffer.from("program_config")], program.programId);
+const [insuranceFundPda] = PublicKey.findProgramAddressSync([Buffer.from("insurance_fund")], program.programId);
+const [ledgerIdentifierPda] = PublicKey.findProgramAddressSync([Buffer.from("ledger_identifier")], program.programId);
+
 describe("03-staking-tests", () => {
+    it("should initialize staking with the default configuration at genesis", async () => {
+        await initializedProgram();
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        expect(stakingConfig.minStakingAmount.toNumber()).to.equals(10000);
+        expect(stakingConfig.minInclusive).to.be.true;
+        expect(stakingConfig.rewardMint.toBase58()).to.not.equal(PublicKey.default.toBase58());
+        expect(await connection.getAccountInfo(getAssociatedTokenAddressSync(stakingConfig.rewardMint, stakingAuthorityPda, true))).to.not.be.null;
+        expect(stakingConfig.rewardRateBps).to.equals(1000);
+        expect(stakingConfig.loyaltyMilestones.map((milestone: any) => milestone.boostBps)).to.deep.equal([500, 500]);
+        expect(stakingConfig.rewardBudget.toNumber()).to.equals(0);
+        expect(stakingConfig.stakingPaused).to.be.false;
+        expect(stakingConfig.maxTotalStaked.toNumber()).to.equals(0);
+        expect(stakingConfig.pausedAt.toNumber()).to.equals(0);
+        expect(stakingConfig.pauseGracePeriod.toNumber()).to.equals(30 * 24 * 60 * 60);
+        expect(stakingConfig.tiers.map(tier => tier.multiplierBps)).to.deep.equal([10000, 12500, 15000]);
+
+        const stakingPool = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+        expect(stakingPool.totalStaked.toNumber()).to.equals(0);
+        expect(stakingPool.stakerCount.toNumber()).to.equals(0);
+    });
+
     it("should freeze tokens with sufficient balance", async () => {
         const variables = await setupTestVariables();
+        const stakingPoolBefore = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
 
         await mintTo(
             connection,
@@ -56,20 +95,27 @@ describe("03-staking-tests", () => {
             variables.token,
             variables.stakerToken,
             variables.staker,
-            1000
+            200000
         );
 
         const stakerTokenBalanceBefore = await connection.getTokenAccountBalance(variables.stakerToken);
-        expect(stakerTokenBalanceBefore.value.amount).to.equals("1000")
+        expect(stakerTokenBalanceBefore.value.amount).to.equals("200000")
 
         await program.methods
-            .stakingFreeze("12345")
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
             .accounts({
                 staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -78,7 +124,7 @@ describe("03-staking-tests", () => {
             .rpc()
 
         const stakerTokenBalanceAfter = await connection.getTokenAccountBalance(variables.stakerToken);
-        expect(stakerTokenBalanceAfter.value.amount).to.equals("1000")
+        expect(stakerTokenBalanceAfter.value.amount).to.equals("200000")
 
         const stakerTokenInfo = await getAccount(connection, variables.stakerToken);
         const isFrozen = stakerTokenInfo.isFrozen;
@@ -87,6 +133,26 @@ describe("03-staking-tests", () => {
 
         const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
         expect(stakingRegistry.referenceId).to.equals("12345")
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(200000)
+        expect(stakingRegistry.multiplierBps).to.equals(12500)
+        expect(stakingRegistry.startedAt.toNumber()).to.be.greaterThan(0)
+        expect(stakingRegistry.loyaltyBoostBps).to.equals(0)
+
+        // The staker holds the freeze authority and can thaw without the program, so the freeze earns no rewards.
+        expect(stakingRegistry.custodian).to.deep.equal(PublicKey.default)
+        const position = await program.methods
+            .stakingGetPosition(variables.staker.publicKey, new anchor.BN(0))
+            .accounts({
+                stakingConfig: stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .view();
+        expect(position.aprBps.toNumber()).to.equals(0);
+
+        // Only reward-earning positions in the reward mint are pooled, so the self-frozen test mint leaves the pool as is.
+        const stakingPoolAfter = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+        expect(stakingPoolAfter.totalStaked.sub(stakingPoolBefore.totalStaked).toNumber()).to.equals(0);
+        expect(stakingPoolAfter.stakerCount.sub(stakingPoolBefore.stakerCount).toNumber()).to.equals(0);
     });
 
     it("should fail if the staker has an insufficient balance (InsufficientStakingBalance)", async () => {
@@ -105,14 +171,21 @@ describe("03-staking-tests", () => {
         expect(stakerTokenBalanceBefore.value.amount).to.equals("0")
 
         await program.methods
-            .stakingFreeze("12345")
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
             .accounts({
                 staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 freezeAuthority: variables.staker.publicKey,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -151,14 +224,21 @@ describe("03-staking-tests", () => {
         expect(stakerTokenBalanceBefore.value.amount).to.equals("1000")
 
         await program.methods
-            .stakingFreeze("12345")
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
             .accounts({
                 staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 freezeAuthority: variables.staker.publicKey,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -173,16 +253,23 @@ describe("03-staking-tests", () => {
         expect(stakerTokenInfoAfterFreezing.isFrozen).to.be.true;
 
         await program.methods
-            .stakingThaw()
+            .stakingThaw(false, null, null)
             .accounts({
                 staker: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 freezeAuthority: variables.staker.publicKey,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([variables.staker])
@@ -214,14 +301,21 @@ describe("03-staking-tests", () => {
         expect(stakerTokenBalanceBefore.value.amount).to.equals("1000")
 
         await program.methods
-            .stakingFreeze("12345")
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
             .accounts({
                 staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 freezeAuthority: variables.staker.publicKey,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -236,16 +330,23 @@ describe("03-staking-tests", () => {
         expect(stakerTokenInfoAfterFreezing.isFrozen).to.be.true;
 
         await program.methods
-            .stakingThaw()
+            .stakingThaw(false, null, null)
             .accounts({
                 staker: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 freezeAuthority: variables.staker.publicKey,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                walletNonce: null,
                 systemProgram: SystemProgram.programId,
             } as any)
             .signers([variables.staker])
@@ -264,13 +365,20 @@ describe("03-staking-tests", () => {
         expect(stakerTokenBalanceAfterThawed.value.amount).to.equals("1000")
 
         await program.methods
-            .stakingFreeze("12345")
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
             .accounts({
                 staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
                 token: variables.token,
                 stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
                 stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
                 stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -289,4 +397,2151 @@ describe("03-staking-tests", () => {
         const updatedStakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
         expect(updatedStakingRegistry.referenceId).to.equals("12345")
     });
+
+    it("should fail to claim rewards when none have accrued (NoRewardsToClaim)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        const rewardVault = getAssociatedTokenAddressSync(stakingConfig.rewardMint, stakingAuthorityPda, true);
+        const stakerRewardToken = getAssociatedTokenAddressSync(stakingConfig.rewardMint, variables.staker.publicKey);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingClaimRewards(false)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                rewardMint: stakingConfig.rewardMint,
+                stakingAuthority: stakingAuthorityPda,
+                rewardVault: rewardVault,
+                stakerRewardToken: stakerRewardToken,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("NoRewardsToClaim");
+                expect(err.error.errorMessage).to.equal("There are no accrued rewards to claim.");
+            });
+    });
+
+    it("configuring a mint should fail if not all required signatures are collected (InsufficientSignatures)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("staking_proposal"),
+            new anchor.BN(stakingProposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        const [stakingMintConfigPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_mint_config"), variables.token.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ configureMint: { mint: variables.token, custodial: true, allowed: true } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetch(stakingProposalPda);
+        expect(stakingProposal.action).to.have.property("configureMint");
+        expect(stakingProposal.action.configureMint.allowed).to.be.true;
+        expect(stakingProposal.multisigSigners).to.deep.equal([]);
+        expect(stakingProposal.status).to.have.property("pending");
+
+        await program.methods
+            .stakingConfigureMint()
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentStakingProposal: stakingProposalPda,
+                token: variables.token,
+                stakingMintConfig: stakingMintConfigPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.be.oneOf(["UnauthorizedSigner", "InsufficientSignatures"]);
+            });
+    });
+
+    it("should stake tokens in escrow and allow partial unstakes", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakerTokenBalanceAfterStaking = await connection.getTokenAccountBalance(variables.stakerToken);
+        expect(stakerTokenBalanceAfterStaking.value.amount).to.equals("0")
+
+        const stakingRegistryAfterStaking = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
+        expect(stakingRegistryAfterStaking.mode).to.have.property("escrow");
+        expect(stakingRegistryAfterStaking.mint).to.deep.equal(variables.token);
+        expect(stakingRegistryAfterStaking.stakedAmount.toNumber()).to.equals(50000)
+
+        await program.methods
+            .stakingUnstake(new anchor.BN(20000), false)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                insuranceFund: insuranceFundPda,
+                insuranceFundToken: null,
+                ledgerIdentifier: ledgerIdentifierPda,
+                ledger: null,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakerTokenBalanceAfterUnstaking = await connection.getTokenAccountBalance(variables.stakerToken);
+        expect(stakerTokenBalanceAfterUnstaking.value.amount).to.equals("20000")
+
+        const stakingRegistryAfterUnstaking = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
+        expect(stakingRegistryAfterUnstaking.stakedAmount.toNumber()).to.equals(30000)
+        expect(stakingRegistryAfterUnstaking.referenceId).to.equals("12345")
+    });
+
+    it("should reject unstaking a locked stake unless the early-exit penalty is paid", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        const escrowAccounts = {
+            staker: variables.staker.publicKey,
+            payer: variables.staker.publicKey,
+            token: variables.token,
+            stakingMintConfig: null,
+            stakerToken: variables.stakerToken,
+            stakingConfig: variables.stakingConfigPda,
+            stakingUser: variables.stakingUserPda,
+            stakingRegistry: variables.stakingRegistryPda,
+            stakingReferrer: null,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            stakingAuthority: stakingAuthorityPda,
+            stakeVault: stakeVault,
+            insuranceFund: insuranceFundPda,
+            insuranceFundToken: null,
+            ledgerIdentifier: ledgerIdentifierPda,
+            ledger: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        };
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(86400), null)
+            .accounts(escrowAccounts as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingUnstake(new anchor.BN(20000), false)
+            .accounts(escrowAccounts as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("StakeStillLocked");
+            });
+
+        const insuranceFundToken = getAssociatedTokenAddressSync(variables.token, insuranceFundPda, true);
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        const [ledgerPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("ledger"),
+            new anchor.BN(ledgerIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods
+            .stakingUnstake(new anchor.BN(20000), true)
+            .accounts({ ...escrowAccounts, insuranceFundToken: insuranceFundToken, ledger: ledgerPda } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakerTokenBalance = await connection.getTokenAccountBalance(variables.stakerToken);
+        expect(stakerTokenBalance.value.amount).to.equals("18000")
+
+        const insuranceFundBalance = await connection.getTokenAccountBalance(insuranceFundToken);
+        expect(insuranceFundBalance.value.amount).to.equals("2000")
+
+        const ledger = await program.account.vaultLedgerAccount.fetch(ledgerPda);
+        expect(ledger.ledger.amount.toNumber()).to.equals(2000);
+        expect(ledger.ledger.transaction).to.have.property("insuranceDeposit");
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(30000)
+    });
+
+    it("should hold several stake positions for the same staker", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        const [secondStakingRegistryPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_registry"), variables.staker.publicKey.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+        const [skippedStakingRegistryPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_registry"), variables.staker.publicKey.toBuffer(), new anchor.BN(5).toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            100000
+        );
+
+        const escrowAccounts = (stakingRegistry: PublicKey) => ({
+            staker: variables.staker.publicKey,
+            payer: variables.staker.publicKey,
+            token: variables.token,
+            stakingMintConfig: null,
+            stakerToken: variables.stakerToken,
+            stakingConfig: variables.stakingConfigPda,
+            stakingUser: variables.stakingUserPda,
+            stakingRegistry: stakingRegistry,
+            stakingReferrer: null,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            stakingAuthority: stakingAuthorityPda,
+            stakeVault: stakeVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        });
+
+        const stakingPoolBefore = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(40000), new anchor.BN(0), null)
+            .accounts(escrowAccounts(variables.stakingRegistryPda) as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingStake(new anchor.BN(1), "67890", new anchor.BN(60000), new anchor.BN(86400), null)
+            .accounts(escrowAccounts(secondStakingRegistryPda) as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingStake(new anchor.BN(5), "13579", new anchor.BN(20000), new anchor.BN(0), null)
+            .accounts(escrowAccounts(skippedStakingRegistryPda) as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidPositionIndex");
+            });
+
+        // The test mint is not the reward mint, so its positions are opened but not pooled.
+        const stakingUser = await program.account.stakingUserAccount.fetch(variables.stakingUserPda);
+        expect(stakingUser.positionCount.toNumber()).to.equals(2);
+        expect(stakingUser.activePositionCount.toNumber()).to.equals(0);
+
+        const firstStakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        const secondStakingRegistry = await program.account.stakingRegistryAccount.fetch(secondStakingRegistryPda);
+        expect(firstStakingRegistry.stakedAmount.toNumber()).to.equals(40000);
+        expect(secondStakingRegistry.positionIndex.toNumber()).to.equals(1);
+        expect(secondStakingRegistry.stakedAmount.toNumber()).to.equals(60000);
+        expect(firstStakingRegistry.unlocksAt.toNumber()).to.be.lessThan(secondStakingRegistry.unlocksAt.toNumber());
+
+        const stakingPoolAfter = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+        expect(stakingPoolAfter.stakerCount.toNumber() - stakingPoolBefore.stakerCount.toNumber()).to.equals(0);
+    });
+
+    it("should extend a stake lock for a higher multiplier", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistryBefore = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistryBefore.multiplierBps).to.equals(10000);
+
+        const newUnlockTs = new anchor.BN(Math.floor(Date.now() / 1000) + 180 * 86400);
+
+        await program.methods
+            .stakingExtendLock(newUnlockTs)
+            .accounts({
+                staker: variables.staker.publicKey,
+                stakingConfig: variables.stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistryAfter = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistryAfter.unlocksAt.toNumber()).to.equals(newUnlockTs.toNumber());
+        expect(stakingRegistryAfter.multiplierBps).to.be.greaterThan(stakingRegistryBefore.multiplierBps);
+
+        await program.methods
+            .stakingExtendLock(newUnlockTs)
+            .accounts({
+                staker: variables.staker.publicKey,
+                stakingConfig: variables.stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("LockNotExtended");
+            });
+    });
+
+    it("creating a config update proposal should fail for an out-of-bounds minimum staking amount (InvalidConfigValue)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ updateConfig: { minStakingAmount: new anchor.BN(0), unstakeCooldown: new anchor.BN(0), mintAllowlistEnabled: false, minInclusive: true, maxTotalStaked: new anchor.BN(0), pauseGracePeriod: new anchor.BN(0) } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidConfigValue");
+            });
+    });
+
+    it("creating a CPI guard proposal should fail for more allowlisted callers than allowed (InvalidConfigValue)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        const allowedCallers = Array.from({ length: 5 }, () => Keypair.generate().publicKey);
+
+        await program.methods
+            .stakingCreateProposal({ configureCpiGuard: { enabled: true, allowedCallers } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidConfigValue");
+            });
+
+        const programConfig = await program.account.programConfigAccount.fetch(programConfigPda);
+        expect(programConfig.cpiGuardEnabled).to.be.false;
+    });
+
+    it("should stop accruing rewards once an unstake is requested", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        const escrowAccounts = {
+            staker: variables.staker.publicKey,
+            payer: variables.staker.publicKey,
+            token: variables.token,
+            stakingMintConfig: null,
+            stakerToken: variables.stakerToken,
+            stakingConfig: variables.stakingConfigPda,
+            stakingUser: variables.stakingUserPda,
+            stakingRegistry: variables.stakingRegistryPda,
+            stakingReferrer: null,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            stakingAuthority: stakingAuthorityPda,
+            stakeVault: stakeVault,
+            insuranceFund: insuranceFundPda,
+            insuranceFundToken: null,
+            ledgerIdentifier: ledgerIdentifierPda,
+            ledger: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        };
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts(escrowAccounts as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const requestUnstakeAccounts = {
+            staker: variables.staker.publicKey,
+            payer: variables.staker.publicKey,
+            stakingConfig: variables.stakingConfigPda,
+            stakingRegistry: variables.stakingRegistryPda,
+        };
+
+        await program.methods
+            .stakingRequestUnstake()
+            .accounts(requestUnstakeAccounts as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.unstakeRequestedAt.toNumber()).to.be.greaterThan(0);
+        expect(stakingRegistry.cooldownEndsAt.toNumber()).to.be.at.least(stakingRegistry.unstakeRequestedAt.toNumber());
+
+        await program.methods
+            .stakingRequestUnstake()
+            .accounts(requestUnstakeAccounts as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("UnstakeAlreadyRequested");
+            });
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        if (stakingConfig.unstakeCooldown.toNumber() == 0) {
+            await program.methods
+                .stakingUnstake(new anchor.BN(50000), false)
+                .accounts(escrowAccounts as any)
+                .signers([variables.staker])
+                .rpc()
+
+            const stakingRegistryAfterUnstaking = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+            expect(stakingRegistryAfterUnstaking.stakedAmount.toNumber()).to.equals(0);
+            expect(stakingRegistryAfterUnstaking.unstakeRequestedAt.toNumber()).to.equals(0);
+        }
+    });
+
+    it("should report no voting power for positions outside the reward mint", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const votingPower = await program.methods
+            .stakingGetVotingPower(variables.staker.publicKey)
+            .remainingAccounts([{ pubkey: variables.stakingRegistryPda, isWritable: false, isSigner: false }])
+            .view();
+        // Only reward-earning positions in the reward mint carry voting power, and the test mint is not the reward mint.
+        expect(votingPower.toNumber()).to.equals(0);
+
+        const stakingConfig = await program.methods
+            .stakingGetConfig()
+            .accounts({ stakingConfig: variables.stakingConfigPda } as any)
+            .view();
+        expect(stakingConfig.stakingPaused).to.equal(false);
+
+        await program.methods
+            .stakingGetVotingPower(Keypair.generate().publicKey)
+            .remainingAccounts([{ pubkey: variables.stakingRegistryPda, isWritable: false, isSigner: false }])
+            .view()
+            .catch((err: any) => {
+                expect(err.toString()).to.contain("InvalidStakingRegistry");
+            });
+    });
+
+    it("should freeze Token-2022 tokens with sufficient balance", async () => {
+        const variables = await setupTestVariables();
+
+        const token = await createMint(
+            connection,
+            variables.staker,
+            variables.staker.publicKey,
+            variables.staker.publicKey,
+            0,
+            undefined,
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+        );
+
+        const stakerTokenAccount = await getOrCreateAssociatedTokenAccount(
+            connection,
+            variables.staker,
+            token,
+            variables.staker.publicKey,
+            false,
+            undefined,
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+        );
+
+        await mintTo(
+            connection,
+            variables.staker,
+            token,
+            stakerTokenAccount.address,
+            variables.staker,
+            20000,
+            [],
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: token,
+                stakerToken: stakerTokenAccount.address,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_2022_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakerTokenInfo = await getAccount(connection, stakerTokenAccount.address, undefined, TOKEN_2022_PROGRAM_ID);
+        expect(stakerTokenInfo.isFrozen).to.be.true;
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda)
+        expect(stakingRegistry.mint).to.deep.equal(token);
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(20000)
+    });
+
+    it("should attribute a stake to its referrer", async () => {
+        const variables = await setupTestVariables();
+        const referrer = Keypair.generate();
+
+        const [stakingReferrerPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_referrer"), referrer.publicKey.toBuffer()],
+            program.programId
+        );
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), referrer.publicKey)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: stakingReferrerPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.referrer).to.deep.equal(referrer.publicKey);
+
+        const stakingReferrer = await program.account.stakingReferrerAccount.fetch(stakingReferrerPda);
+        expect(stakingReferrer.referrer).to.deep.equal(referrer.publicKey);
+        expect(stakingReferrer.referralCount.toNumber()).to.equals(1);
+        expect(stakingReferrer.referredAmount.toNumber()).to.equals(20000);
+    });
+
+    it("should not freeze tokens with an invalid reference ID", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "ref 12345!", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidReferenceId");
+            });
+
+        const stakerTokenInfo = await getAccount(connection, variables.stakerToken);
+        expect(stakerTokenInfo.isFrozen).to.be.false;
+    });
+
+    it("configuring a thaw attestor should fail if not all required signatures are collected (InsufficientSignatures)", async () => {
+        const variables = await setupTestVariables();
+        const attestor = Keypair.generate();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ configureAttestor: { attestor: attestor.publicKey } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetch(stakingProposalPda);
+        expect(stakingProposal.action.configureAttestor.attestor).to.deep.equal(attestor.publicKey);
+
+        await program.methods
+            .stakingConfigureAttestor()
+            .accounts({
+                signer: variables.staker.publicKey,
+                currentStakingProposal: stakingProposalPda,
+                stakingConfig: variables.stakingConfigPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.be.oneOf(["UnauthorizedSigner", "InsufficientSignatures"]);
+            });
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        expect(stakingConfig.attestor).to.deep.equal(PublicKey.default);
+    });
+
+    it("closing a stake position should fail while it still holds a stake (PositionNotClosable)", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingClosePosition()
+            .accounts({
+                staker: variables.staker.publicKey,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("PositionNotClosable");
+            });
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(20000);
+    });
+
+    it("should freeze tokens when the balance equals the minimum staking amount", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            10000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakerTokenInfo = await getAccount(connection, variables.stakerToken);
+        expect(stakerTokenInfo.isFrozen).to.be.true;
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(10000);
+    });
+
+    it("allowlisting a validator should fail if not all required signatures are collected (InsufficientSignatures)", async () => {
+        const variables = await setupTestVariables();
+        const voteAccount = Keypair.generate().publicKey;
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+        const [stakingValidatorPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_validator"), voteAccount.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ configureValidator: { voteAccount: voteAccount, allowed: true } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingConfigureValidator()
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentStakingProposal: stakingProposalPda,
+                voteAccount: voteAccount,
+                stakingValidator: stakingValidatorPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.be.oneOf(["UnauthorizedSigner", "InsufficientSignatures"]);
+            });
+
+        let stakingValidator;
+        try {
+            stakingValidator = await program.account.stakingValidatorAccount.fetch(stakingValidatorPda);
+        } catch (err) {
+            stakingValidator = null;
+        }
+        expect(stakingValidator).to.be.null;
+    });
+
+    it("should fail to fund rewards with a zero amount (InvalidAmount)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        const rewardVault = getAssociatedTokenAddressSync(stakingConfig.rewardMint, stakingAuthorityPda, true);
+        const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+            connection,
+            variables.staker,
+            stakingConfig.rewardMint,
+            variables.staker.publicKey
+        );
+
+        await program.methods
+            .stakingFundRewards(new anchor.BN(0))
+            .accounts({
+                funder: variables.staker.publicKey,
+                stakingConfig: stakingConfigPda,
+                rewardMint: stakingConfig.rewardMint,
+                funderToken: funderTokenAccount.address,
+                stakingAuthority: stakingAuthorityPda,
+                rewardVault: rewardVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidAmount");
+            });
+
+        const updatedStakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        expect(updatedStakingConfig.rewardBudget.toNumber()).to.equals(stakingConfig.rewardBudget.toNumber());
+    });
+
+    it("should return a live view of a stake position", async () => {
+        const variables = await setupTestVariables();
+
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+
+        const position = await program.methods
+            .stakingGetPosition(variables.staker.publicKey, new anchor.BN(0))
+            .accounts({
+                stakingConfig: stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .view();
+        expect(position.stakedAmount.toNumber()).to.equals(50000);
+        expect(position.accruedRewards.toNumber()).to.be.at.least(stakingRegistry.accruedRewards.toNumber());
+        expect(position.aprBps.toNumber()).to.equals(stakingConfig.rewardRateBps * stakingRegistry.multiplierBps / 10000);
+        expect(position.unlocksAt.toNumber()).to.equals(stakingRegistry.unlocksAt.toNumber());
+    });
+
+    it("should record a snapshot of the current epoch's stake totals", async () => {
+        const variables = await setupTestVariables();
+
+        const { epoch } = await connection.getEpochInfo();
+        const [stakingSnapshotPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_snapshot"), new anchor.BN(epoch).toArrayLike(Buffer, 'le', 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingSnapshotEpoch(new anchor.BN(epoch + 1))
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingConfig: stakingConfigPda,
+                stakingPool: stakingPoolPda,
+                stakingSnapshot: PublicKey.findProgramAddressSync(
+                    [Buffer.from("staking_snapshot"), new anchor.BN(epoch + 1).toArrayLike(Buffer, 'le', 8)],
+                    program.programId
+                )[0],
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidSnapshotEpoch");
+            });
+
+        await program.methods
+            .stakingSnapshotEpoch(new anchor.BN(epoch))
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingConfig: stakingConfigPda,
+                stakingPool: stakingPoolPda,
+                stakingSnapshot: stakingSnapshotPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingPool = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+        const stakingSnapshot = await program.account.stakingEpochSnapshotAccount.fetch(stakingSnapshotPda);
+        expect(stakingSnapshot.epoch.toNumber()).to.equals(epoch);
+        expect(stakingSnapshot.totalStaked.toNumber()).to.equals(stakingPool.totalStaked.toNumber());
+        expect(stakingSnapshot.tierStaked.map((amount: anchor.BN) => amount.toNumber()))
+            .to.deep.equal(stakingPool.tierStaked.map((amount: anchor.BN) => amount.toNumber()));
+    });
+
+    it("should leave the epoch's snapshot unchanged when the crank runs again", async () => {
+        const variables = await setupTestVariables();
+
+        const { epoch } = await connection.getEpochInfo();
+        const [stakingSnapshotPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_snapshot"), new anchor.BN(epoch).toArrayLike(Buffer, 'le', 8)],
+            program.programId
+        );
+        const snapshotEpoch = () => program.methods
+            .stakingSnapshotEpoch(new anchor.BN(epoch))
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingConfig: stakingConfigPda,
+                stakingPool: stakingPoolPda,
+                stakingSnapshot: stakingSnapshotPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc();
+
+        await snapshotEpoch();
+        const stakingSnapshot = await program.account.stakingEpochSnapshotAccount.fetch(stakingSnapshotPda);
+
+        await snapshotEpoch();
+        const stakingSnapshotAfter = await program.account.stakingEpochSnapshotAccount.fetch(stakingSnapshotPda);
+        expect(stakingSnapshotAfter.recordedAt.toNumber()).to.equals(stakingSnapshot.recordedAt.toNumber());
+        expect(stakingSnapshotAfter.totalStaked.toNumber()).to.equals(stakingSnapshot.totalStaked.toNumber());
+    });
+
+    it("should freeze and thaw several positions in one transaction", async () => {
+        const variables = await setupTestVariables();
+
+        const secondToken = await createMint(
+            connection,
+            variables.staker,
+            variables.staker.publicKey,
+            variables.staker.publicKey,
+            0
+        );
+        const secondStakerTokenAccount = await getOrCreateAssociatedTokenAccount(
+            connection,
+            variables.staker,
+            secondToken,
+            variables.staker.publicKey
+        );
+        const secondStakerToken = secondStakerTokenAccount.address;
+        const [secondStakingRegistryPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_registry"), variables.staker.publicKey.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await mintTo(connection, variables.staker, variables.token, variables.stakerToken, variables.staker, 20000);
+        await mintTo(connection, variables.staker, secondToken, secondStakerToken, variables.staker, 30000);
+
+        const positionAccounts = [
+            { pubkey: variables.token, isWritable: false, isSigner: false },
+            { pubkey: variables.stakerToken, isWritable: true, isSigner: false },
+            { pubkey: program.programId, isWritable: false, isSigner: false },
+            { pubkey: variables.stakingRegistryPda, isWritable: true, isSigner: false },
+            { pubkey: secondToken, isWritable: false, isSigner: false },
+            { pubkey: secondStakerToken, isWritable: true, isSigner: false },
+            { pubkey: program.programId, isWritable: false, isSigner: false },
+            { pubkey: secondStakingRegistryPda, isWritable: true, isSigner: false },
+        ];
+
+        await program.methods
+            .stakingBatchFreeze([
+                { positionIndex: new anchor.BN(0), referenceNumber: "12345", lockDuration: new anchor.BN(0) },
+                { positionIndex: new anchor.BN(1), referenceNumber: "67890", lockDuration: new anchor.BN(0) },
+            ])
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .remainingAccounts(positionAccounts)
+            .signers([variables.staker])
+            .rpc()
+
+        expect((await getAccount(connection, variables.stakerToken)).isFrozen).to.be.true;
+        expect((await getAccount(connection, secondStakerToken)).isFrozen).to.be.true;
+
+        const secondStakingRegistry = await program.account.stakingRegistryAccount.fetch(secondStakingRegistryPda);
+        expect(secondStakingRegistry.mint).to.deep.equal(secondToken);
+        expect(secondStakingRegistry.stakedAmount.toNumber()).to.equals(30000);
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        if (stakingConfig.unstakeCooldown.toNumber() == 0 && stakingConfig.attestor.equals(PublicKey.default)) {
+            await program.methods
+                .stakingBatchThaw(false)
+                .accounts({
+                    staker: variables.staker.publicKey,
+                    stakingAuthority: stakingAuthorityPda,
+                    stakingConfig: variables.stakingConfigPda,
+                    stakingUser: variables.stakingUserPda,
+                    stakingPool: stakingPoolPda,
+                    stakingActivity: stakingActivityPda,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                } as any)
+                .remainingAccounts(positionAccounts)
+                .signers([variables.staker])
+                .rpc()
+
+            expect((await getAccount(connection, variables.stakerToken)).isFrozen).to.be.false;
+            expect((await getAccount(connection, secondStakerToken)).isFrozen).to.be.false;
+
+            const stakingUser = await program.account.stakingUserAccount.fetch(variables.stakingUserPda);
+            expect(stakingUser.activePositionCount.toNumber()).to.equals(0);
+        }
+    });
+
+    it("should fail to thaw against a mint other than the one that was frozen (MintMismatch)", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.mint).to.deep.equal(variables.token);
+        expect(stakingRegistry.tokenAccount).to.deep.equal(variables.stakerToken);
+
+        const otherToken = await createMint(
+            connection,
+            variables.staker,
+            variables.staker.publicKey,
+            variables.staker.publicKey,
+            0
+        );
+        const otherStakerTokenAccount = await getOrCreateAssociatedTokenAccount(
+            connection,
+            variables.staker,
+            otherToken,
+            variables.staker.publicKey
+        );
+
+        await program.methods
+            .stakingThaw(false, null, null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                token: otherToken,
+                stakerToken: otherStakerTokenAccount.address,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                walletNonce: null,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("MintMismatch");
+            });
+
+        const stakerTokenInfo = await getAccount(connection, variables.stakerToken);
+        expect(stakerTokenInfo.isFrozen).to.be.true;
+    });
+
+    it("should not migrate a registry that already uses the current layout (RegistryAlreadyMigrated)", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.version).to.equals(5);
+
+        await program.methods
+            .stakingMigrateRegistry(new anchor.BN(0))
+            .accounts({
+                staker: variables.staker.publicKey,
+                stakingRegistry: variables.stakingRegistryPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("RegistryAlreadyMigrated");
+            });
+    });
+
+    it("pausing staking should fail if not all required signatures are collected (InsufficientSignatures)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync([
+            Buffer.from("staking_proposal"),
+            new anchor.BN(stakingProposalIdentifier.id).toArrayLike(Buffer, 'le', 8)
+        ], program.programId);
+
+        await program.methods
+            .stakingCreateProposal({ setPaused: { paused: true } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingSetPaused()
+            .accounts({
+                signer: variables.staker.publicKey,
+                currentStakingProposal: stakingProposalPda,
+                stakingConfig: stakingConfigPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.be.oneOf(["UnauthorizedSigner", "InsufficientSignatures"]);
+            });
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        expect(stakingConfig.stakingPaused).to.be.false;
+        expect(stakingConfig.maxTotalStaked.toNumber()).to.equals(0);
+    });
+
+    it("should count a freeze in the current epoch's activity metrics", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        const { epoch } = await connection.getEpochInfo();
+        const activityBefore = await program.account.stakingActivityAccount.fetch(stakingActivityPda);
+        const entryBefore = activityBefore.epochs.find((entry: any) => entry.epoch.toNumber() === epoch);
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const activityAfter = await program.account.stakingActivityAccount.fetch(stakingActivityPda);
+        const entryAfter = activityAfter.epochs.find((entry: any) => entry.epoch.toNumber() === epoch);
+        const stakeCountBefore = entryBefore ? entryBefore.stakeCount.toNumber() : 0;
+        const stakedAmountBefore = entryBefore ? entryBefore.stakedAmount.toNumber() : 0;
+        const netFlowBefore = entryBefore ? entryBefore.netFlow.toNumber() : 0;
+        expect(entryAfter.stakeCount.toNumber()).to.equals(stakeCountBefore + 1);
+        expect(entryAfter.stakedAmount.toNumber()).to.equals(stakedAmountBefore + 20000);
+        expect(entryAfter.netFlow.toNumber()).to.equals(netFlowBefore + 20000);
+    });
+
+    it("creating a config update proposal should fail for a zero pause grace period (InvalidConfigValue)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ updateConfig: { minStakingAmount: new anchor.BN(10000), unstakeCooldown: new anchor.BN(0), mintAllowlistEnabled: false, minInclusive: true, maxTotalStaked: new anchor.BN(0), pauseGracePeriod: new anchor.BN(0) } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidConfigValue");
+            });
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+        expect(stakingConfig.stakingPaused).to.be.false;
+        expect(stakingConfig.pauseGracePeriod.toNumber()).to.equals(30 * 24 * 60 * 60);
+    });
+
+    it("should fail to stake on behalf of a beneficiary from an unauthorized custodian (AccountNotInitialized)", async () => {
+        const variables = await setupTestVariables();
+        const beneficiary = Keypair.generate();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        const [stakingCustodianPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_custodian"), variables.staker.publicKey.toBuffer()],
+            program.programId
+        );
+        const [beneficiaryUserPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_user"), beneficiary.publicKey.toBuffer()],
+            program.programId
+        );
+        const [beneficiaryRegistryPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_registry"), beneficiary.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingStakeFor(beneficiary.publicKey, new anchor.BN(0), "12345", new anchor.BN(20000), new anchor.BN(0))
+            .accounts({
+                custodian: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingCustodian: stakingCustodianPda,
+                beneficiaryWallet: beneficiary.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                custodianToken: variables.stakerToken,
+                beneficiaryToken: getAssociatedTokenAddressSync(variables.token, beneficiary.publicKey),
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: beneficiaryUserPda,
+                stakingRegistry: beneficiaryRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("AccountNotInitialized");
+            });
+
+        const stakerTokenBalance = await connection.getTokenAccountBalance(variables.stakerToken);
+        expect(stakerTokenBalance.value.amount).to.equals("20000");
+    });
+
+    it("should stake through a delegate approval on a relayer's transaction", async () => {
+        const variables = await setupTestVariables();
+        const relayer = Keypair.generate();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+
+        await connection.requestAirdrop(relayer.publicKey, 2e9);
+        await sleep(2000);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        const delegatedAccounts = {
+            relayer: relayer.publicKey,
+            stakerWallet: variables.staker.publicKey,
+            token: variables.token,
+            stakingMintConfig: null,
+            stakerToken: variables.stakerToken,
+            stakingConfig: variables.stakingConfigPda,
+            stakingUser: variables.stakingUserPda,
+            stakingRegistry: variables.stakingRegistryPda,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            stakingAuthority: stakingAuthorityPda,
+            stakeVault: stakeVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        };
+
+        await program.methods
+            .stakingStakeDelegated(variables.staker.publicKey, new anchor.BN(0), "12345", new anchor.BN(20000), new anchor.BN(0))
+            .accounts(delegatedAccounts as any)
+            .signers([relayer])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("DelegationMissing");
+            });
+
+        await approve(
+            connection,
+            variables.staker,
+            variables.stakerToken,
+            stakingAuthorityPda,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingStakeDelegated(variables.staker.publicKey, new anchor.BN(0), "12345", new anchor.BN(20000), new anchor.BN(0))
+            .accounts(delegatedAccounts as any)
+            .signers([relayer])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(20000);
+        expect(stakingRegistry.tokenAccount).to.deep.equal(variables.stakerToken);
+
+        const stakerTokenBalance = await connection.getTokenAccountBalance(variables.stakerToken);
+        expect(stakerTokenBalance.value.amount).to.equals("0");
+    });
+
+    it("should record the pool's totals in the next sequenced hub report", async () => {
+        const variables = await setupTestVariables();
+
+        const [stakingReportIdentifierPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_report_identifier")],
+            program.programId
+        );
+        const stakingReportIdentifier = await program.account.identifierAccount.fetchNullable(stakingReportIdentifierPda);
+        const sequence = stakingReportIdentifier ? stakingReportIdentifier.id.toNumber() : 0;
+        const [stakingReportPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_report"), new anchor.BN(sequence).toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingReport()
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                stakingPool: stakingPoolPda,
+                stakingReportIdentifier: stakingReportIdentifierPda,
+                stakingReport: stakingReportPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingReport = await program.account.stakingReportAccount.fetch(stakingReportPda);
+        const stakingPool = await program.account.stakingPoolAccount.fetch(stakingPoolPda);
+        expect(stakingReport.sequence.toNumber()).to.equals(sequence);
+        expect(stakingReport.totalStaked.toNumber()).to.equals(stakingPool.totalStaked.toNumber());
+        expect(stakingReport.stakerCount.toNumber()).to.equals(stakingPool.stakerCount.toNumber());
+
+        const stakingReportIdentifierAfter = await program.account.identifierAccount.fetch(stakingReportIdentifierPda);
+        expect(stakingReportIdentifierAfter.id.toNumber()).to.equals(sequence + 1);
+    });
+
+    it("should pay out queued unstakes in FIFO order through the crank", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+        const [stakingUnstakeQueuePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_unstake_queue"), variables.token.toBuffer()],
+            program.programId
+        );
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            50000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(50000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingQueueUnstake(new anchor.BN(20000))
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingConfig: variables.stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingUnstakeQueue: stakingUnstakeQueuePda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingUnstakeQueue = await program.account.stakingUnstakeQueueAccount.fetch(stakingUnstakeQueuePda);
+        expect(stakingUnstakeQueue.entries.length).to.equals(1);
+        expect(stakingUnstakeQueue.entries[0].staker).to.deep.equal(variables.staker.publicKey);
+        expect(stakingUnstakeQueue.entries[0].amount.toNumber()).to.equals(20000);
+        expect(stakingUnstakeQueue.queuedAmount.toNumber()).to.equals(20000);
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        if (stakingConfig.unstakeCooldown.toNumber() == 0) {
+            await program.methods
+                .stakingProcessUnstakeQueue()
+                .accounts({
+                    signer: variables.staker.publicKey,
+                    staker: variables.staker.publicKey,
+                    token: variables.token,
+                    stakerToken: variables.stakerToken,
+                    stakingConfig: variables.stakingConfigPda,
+                    stakingUser: variables.stakingUserPda,
+                    stakingRegistry: variables.stakingRegistryPda,
+                    stakingUnstakeQueue: stakingUnstakeQueuePda,
+                    stakingPool: stakingPoolPda,
+                    stakingActivity: stakingActivityPda,
+                    stakingAuthority: stakingAuthorityPda,
+                    stakeVault: stakeVault,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                } as any)
+                .signers([variables.staker])
+                .rpc()
+
+            const stakerTokenBalance = await connection.getTokenAccountBalance(variables.stakerToken);
+            expect(stakerTokenBalance.value.amount).to.equals("20000");
+
+            const stakingUnstakeQueueAfter = await program.account.stakingUnstakeQueueAccount.fetch(stakingUnstakeQueuePda);
+            expect(stakingUnstakeQueueAfter.entries.length).to.equals(0);
+            expect(stakingUnstakeQueueAfter.processedSequence.toNumber()).to.equals(1);
+        }
+    });
+
+    it("should stake a token and SOL pair at the configured ratio and release both", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+        const [stakingPairVaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_pair_vault")],
+            program.programId
+        );
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            20000
+        );
+
+        await program.methods
+            .stakingStakePair(new anchor.BN(0), "12345", new anchor.BN(20000), new anchor.BN(0))
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingPairVault: stakingPairVaultPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        const pairedLamports = 20000 * stakingConfig.pairSolRatioBps.toNumber() / 10000;
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+        expect(stakingRegistry.mode).to.have.property("pair");
+        expect(stakingRegistry.stakedAmount.toNumber()).to.equals(20000);
+        expect(stakingRegistry.pairedLamports.toNumber()).to.equals(pairedLamports);
+
+        if (stakingConfig.unstakeCooldown.toNumber() == 0) {
+            await program.methods
+                .stakingUnstakePair()
+                .accounts({
+                    staker: variables.staker.publicKey,
+                    token: variables.token,
+                    stakerToken: variables.stakerToken,
+                    stakingConfig: variables.stakingConfigPda,
+                    stakingUser: variables.stakingUserPda,
+                    stakingRegistry: variables.stakingRegistryPda,
+                    stakingPool: stakingPoolPda,
+                    stakingActivity: stakingActivityPda,
+                    stakingPairVault: stakingPairVaultPda,
+                    stakingAuthority: stakingAuthorityPda,
+                    stakeVault: stakeVault,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                } as any)
+                .signers([variables.staker])
+                .rpc()
+
+            const stakerTokenBalance = await connection.getTokenAccountBalance(variables.stakerToken);
+            expect(stakerTokenBalance.value.amount).to.equals("20000");
+
+            const stakingRegistryAfter = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+            expect(stakingRegistryAfter.stakedAmount.toNumber()).to.equals(0);
+            expect(stakingRegistryAfter.pairedLamports.toNumber()).to.equals(0);
+        }
+    });
+
+    it("creating a SOL reward distribution proposal should fail for a zero amount (InvalidAmount)", async () => {
+        const variables = await setupTestVariables();
+
+        const { epoch } = await connection.getEpochInfo();
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({ distributeSolRewards: { epoch: new anchor.BN(epoch), amount: new anchor.BN(0) } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidAmount");
+            });
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetchNullable(stakingProposalPda);
+        expect(stakingProposal).to.be.null;
+    });
+
+    it("should not discount the teleport fee for a stake of a mint other than the reward mint", async () => {
+        const variables = await setupTestVariables();
+        const stakeVault = getAssociatedTokenAddressSync(variables.token, stakingAuthorityPda, true);
+        const [vaultPda] = PublicKey.findProgramAddressSync([Buffer.from("vault")], program.programId);
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            1000000
+        );
+
+        await program.methods
+            .stakingStake(new anchor.BN(0), "12345", new anchor.BN(1000000), new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakingMintConfig: null,
+                stakerToken: variables.stakerToken,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                stakingAuthority: stakingAuthorityPda,
+                stakeVault: stakeVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const ledgerIdentifier = await program.account.identifierAccount.fetch(ledgerIdentifierPda);
+        const [ledgerPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("ledger"), ledgerIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .vaultTeleport(new anchor.BN(1000000), 0, Array(32).fill(0))
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                vault: vaultPda,
+                ledgerIdentifier: ledgerIdentifierPda,
+                ledger: ledgerPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                solUsdPriceUpdate: null,
+                solUsdFallbackFeed: null,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        // The stake reaches the top discount entry, but the test mint is not the reward mint, so the full fee is charged.
+        const ledger = await program.account.vaultLedgerAccount.fetch(ledgerPda);
+        expect(ledger.ledger.mimingFee.toNumber()).to.equals(10000000);
+    });
+
+    it("should number a staker's ledger entries in sequence", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            200000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingUser = await program.account.stakingUserAccount.fetch(variables.stakingUserPda);
+        expect(stakingUser.ledgerSequence.toNumber()).to.equals(1);
+    });
+
+    it("creating a reward schedule proposal should fail for a rising rate (InvalidRewardSchedule)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        const steps = [
+            { startEpoch: new anchor.BN(0), rewardRateBps: 500 },
+            { startEpoch: new anchor.BN(100), rewardRateBps: 1000 },
+        ];
+
+        await program.methods
+            .stakingCreateProposal({ setRewardSchedule: { steps } })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("InvalidRewardSchedule");
+            });
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetchNullable(stakingProposalPda);
+        expect(stakingProposal).to.be.null;
+    });
+
+    it("creating a slash proposal should fail without an evidence hash (MissingSlashEvidence)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({
+                slash: {
+                    staker: variables.staker.publicKey,
+                    positionIndex: new anchor.BN(0),
+                    amount: new anchor.BN(1000),
+                    evidenceHash: Array(32).fill(0),
+                    reason: "double signing",
+                },
+            })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("MissingSlashEvidence");
+            });
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetchNullable(stakingProposalPda);
+        expect(stakingProposal).to.be.null;
+    });
+
+    it("should let the slashed staker dispute a slash proposal", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingProposalIdentifier = await program.account.identifierAccount.fetch(stakingProposalIdentifierPda);
+        const [stakingProposalPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_proposal"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+        const [stakingSlashDisputePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("staking_slash_dispute"), stakingProposalIdentifier.id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+
+        await program.methods
+            .stakingCreateProposal({
+                slash: {
+                    staker: variables.staker.publicKey,
+                    positionIndex: new anchor.BN(0),
+                    amount: new anchor.BN(1000),
+                    evidenceHash: Array(32).fill(1),
+                    reason: "double signing",
+                },
+            })
+            .accounts({
+                signer: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal: stakingProposalPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        await program.methods
+            .stakingDisputeSlash(Array(32).fill(2))
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                currentStakingProposal: stakingProposalPda,
+                stakingSlashDispute: stakingSlashDisputePda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingProposal = await program.account.stakingProposalAccount.fetch(stakingProposalPda);
+        expect(stakingProposal.disputedAt.toNumber()).to.be.greaterThan(0);
+        expect(stakingProposal.multisigSigners.length).to.equals(0);
+
+        const stakingSlashDispute = await program.account.stakingSlashDisputeAccount.fetch(stakingSlashDisputePda);
+        expect(stakingSlashDispute.staker.toBase58()).to.equals(variables.staker.publicKey.toBase58());
+        expect(stakingSlashDispute.counterClaimHash).to.deep.equals(Array(32).fill(2));
+    });
+
+    it("should not migrate a staking configuration that already uses the current layout (ConfigAlreadyMigrated)", async () => {
+        const variables = await setupTestVariables();
+
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(variables.stakingConfigPda);
+        expect(stakingConfig.version).to.equals(2);
+
+        await program.methods
+            .stakingMigrateConfig()
+            .accounts({
+                signer: variables.staker.publicKey,
+                stakingConfig: variables.stakingConfigPda,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+            .catch((err: any) => {
+                expect(err).to.have.property("error");
+                expect(err.error.errorCode?.code).to.equal("ConfigAlreadyMigrated");
+            });
+    });
+
+    it("should return a compact view of a stake position for CPI consumers", async () => {
+        const variables = await setupTestVariables();
+
+        await mintTo(
+            connection,
+            variables.staker,
+            variables.token,
+            variables.stakerToken,
+            variables.staker,
+            200000
+        );
+
+        await program.methods
+            .stakingFreeze(new anchor.BN(0), "12345", new anchor.BN(0), null)
+            .accounts({
+                staker: variables.staker.publicKey,
+                payer: variables.staker.publicKey,
+                token: variables.token,
+                stakerToken: variables.stakerToken,
+                stakingMintConfig: null,
+                stakingAuthority: stakingAuthorityPda,
+                stakingConfig: variables.stakingConfigPda,
+                stakingUser: variables.stakingUserPda,
+                stakingRegistry: variables.stakingRegistryPda,
+                stakingReferrer: null,
+                stakingPool: stakingPoolPda,
+                stakingActivity: stakingActivityPda,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([variables.staker])
+            .rpc()
+
+        const stakingRegistry = await program.account.stakingRegistryAccount.fetch(variables.stakingRegistryPda);
+
+        const stakeInfo = await program.methods
+            .stakingGetStakeInfo(variables.staker.publicKey, new anchor.BN(0))
+            .accounts({
+                stakingConfig: variables.stakingConfigPda,
+                stakingRegistry: variables.stakingRegistryPda,
+            } as any)
+            .view();
+        expect(stakeInfo.stakedAmount.toNumber()).to.equals(200000);
+        expect(stakeInfo.unlocksAt.toNumber()).to.equals(stakingRegistry.unlocksAt.toNumber());
+        expect(stakeInfo.multiplierBps).to.equals(stakingRegistry.multiplierBps);
+        expect(stakeInfo.slashed).to.be.false;
+    });
 });
@@ -0,0 +1,634 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SystemProgram, Keypair, PublicKey, LAMPORTS_PER_SOL, SYSVAR_CLOCK_PUBKEY, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+    program,
+    connection,
+    multisigPda,
+    programConfigPda,
+    programRegistryPda,
+    hubRegistryPda,
+    hubStateRootsPda,
+    vaultPda,
+    ledgerIdentifierPda,
+    transferProposalIdentifierPda,
+    stakingConfigPda,
+    stakingProposalIdentifierPda,
+    transferProposalPda,
+    walletNoncePda,
+    ledgerPda,
+    pda,
+    u64Seed,
+    chainSeed,
+    nextId,
+    expectError,
+    fundedKeypair,
+    initializedVault,
+    initializedStaking,
+    initializeProgram,
+    teleport,
+    createMultisigProposal,
+    stakedUser,
+} from "./fixtures";
+
+// Regression coverage built on the shared fixtures: a happy path per module, and each error code reachable without the
+// multisig council's keys.
+describe("04-regression-tests", () => {
+    before(async () => {
+        await initializedVault();
+        await initializedStaking();
+    });
+
+    it("initializing the program should fail for a signer other than the upgrade authority (NotUpgradeAuthority).", async () => {
+        const signer = await fundedKeypair();
+        const stakingConfig = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+
+        await expectError(
+            initializeProgram(stakingConfig.rewardMint, signer.publicKey).signers([signer]).rpc(),
+            "NotUpgradeAuthority"
+        );
+    });
+
+    it("teleporting should record the deposit in the next ledger entry.", async () => {
+        const signer = await fundedKeypair();
+        const ledgerId = await nextId(ledgerIdentifierPda);
+
+        await (await teleport(signer, new anchor.BN(LAMPORTS_PER_SOL))).rpc();
+
+        const ledger = await program.account.vaultLedgerAccount.fetch(ledgerPda(ledgerId));
+        expect(ledger.ledger.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+        const vaultStatus = await program.methods.vaultGetStatus()
+            .accounts({
+                vault: vaultPda,
+                programConfig: programConfigPda,
+                ledgerIdentifier: ledgerIdentifierPda,
+                transferProposalIdentifier: transferProposalIdentifierPda,
+            } as any)
+            .view();
+        expect(vaultStatus.nextLedgerId.toNumber()).to.equal(ledgerId.toNumber() + 1);
+    });
+
+    it("posting a teleport to the hub should fail for a signer other than its sender when it left its destination to be picked (UnauthorizedSender).", async () => {
+        const sender = await fundedKeypair();
+        const other = await fundedKeypair();
+        const ledgerId = await nextId(ledgerIdentifierPda);
+        await (await teleport(sender, new anchor.BN(LAMPORTS_PER_SOL))).rpc();
+
+        const wormholeProgram = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+        const wormholePda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, wormholeProgram)[0];
+        const emitter = pda(Buffer.from("emitter"));
+
+        await expectError(
+            program.methods.vaultPostTeleport(2, Array.from(other.publicKey.toBuffer()))
+                .accounts({
+                    signer: other.publicKey,
+                    payer: other.publicKey,
+                    ledger: ledgerPda(ledgerId),
+                    programConfig: programConfigPda,
+                    hubRegistry: hubRegistryPda,
+                    outboundSequence: pda(Buffer.from("bridge_sequence"), chainSeed(2)),
+                    vault: vaultPda,
+                    wormholeBridge: wormholePda(Buffer.from("Bridge")),
+                    wormholeMessage: pda(Buffer.from("teleport_message"), u64Seed(ledgerId)),
+                    wormholeEmitter: emitter,
+                    wormholeSequence: wormholePda(Buffer.from("Sequence"), emitter.toBuffer()),
+                    wormholeFeeCollector: wormholePda(Buffer.from("fee_collector")),
+                    wormholeProgram,
+                    clock: SYSVAR_CLOCK_PUBKEY,
+                    rent: SYSVAR_RENT_PUBKEY,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([other])
+                .rpc(),
+            "UnauthorizedSender"
+        );
+    });
+
+    it("posting a teleport to the hub should fail for a destination other than the one it committed to (DestinationMismatch).", async () => {
+        const sender = await fundedKeypair();
+        const other = await fundedKeypair();
+        const ledgerId = await nextId(ledgerIdentifierPda);
+        await (await teleport(sender, new anchor.BN(LAMPORTS_PER_SOL), 2, Array.from(sender.publicKey.toBuffer()))).rpc();
+
+        const wormholeProgram = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+        const wormholePda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, wormholeProgram)[0];
+        const emitter = pda(Buffer.from("emitter"));
+
+        await expectError(
+            program.methods.vaultPostTeleport(2, Array.from(other.publicKey.toBuffer()))
+                .accounts({
+                    signer: other.publicKey,
+                    payer: other.publicKey,
+                    ledger: ledgerPda(ledgerId),
+                    programConfig: programConfigPda,
+                    hubRegistry: hubRegistryPda,
+                    outboundSequence: pda(Buffer.from("bridge_sequence"), chainSeed(2)),
+                    vault: vaultPda,
+                    wormholeBridge: wormholePda(Buffer.from("Bridge")),
+                    wormholeMessage: pda(Buffer.from("teleport_message"), u64Seed(ledgerId)),
+                    wormholeEmitter: emitter,
+                    wormholeSequence: wormholePda(Buffer.from("Sequence"), emitter.toBuffer()),
+                    wormholeFeeCollector: wormholePda(Buffer.from("fee_collector")),
+                    wormholeProgram,
+                    clock: SYSVAR_CLOCK_PUBKEY,
+                    rent: SYSVAR_RENT_PUBKEY,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([other])
+                .rpc(),
+            "DestinationMismatch"
+        );
+    });
+
+    it("completing a release from the hub should fail while no hub emitter is configured (HubEmitterNotConfigured).", async () => {
+        const relayer = await fundedKeypair();
+        const wormholeProgram = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+        const vaaHash = Buffer.alloc(32, 7);
+        const sequence = new anchor.BN(0);
+
+        await expectError(
+            program.methods.vaultCompleteInbound(Array.from(vaaHash), sequence, new anchor.BN(1), [])
+                .accounts({
+                    payer: relayer.publicKey,
+                    programConfig: programConfigPda,
+                    hubRegistry: hubRegistryPda,
+                    postedVaa: PublicKey.findProgramAddressSync([Buffer.from("PostedVAA"), vaaHash], wormholeProgram)[0],
+                    hubStateRoots: hubStateRootsPda,
+                    inboundReceipt: pda(Buffer.from("inbound_receipt"), Buffer.alloc(32), u64Seed(sequence)),
+                    consumedPage: pda(Buffer.from("consumed_message_page"), chainSeed(0), u64Seed(0)),
+                    inboundSequence: pda(Buffer.from("bridge_sequence"), chainSeed(0)),
+                    recipient: relayer.publicKey,
+                    vault: vaultPda,
+                    ledgerIdentifier: ledgerIdentifierPda,
+                    ledger: ledgerPda(await nextId(ledgerIdentifierPda)),
+                    stakingConfig: null,
+                    mimingMint: null,
+                    recipientToken: null,
+                    payerToken: null,
+                    mintAuthority: null,
+                    bridgeStats: null,
+                    tokenProgram: null,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([relayer])
+                .rpc(),
+            "HubEmitterNotConfigured"
+        );
+    });
+
+    it("minting a teleport from the hub should fail while no hub emitter is configured (HubEmitterNotConfigured).", async () => {
+        const relayer = await fundedKeypair();
+        const wormholeProgram = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+        const vaaHash = Buffer.alloc(32, 8);
+        const sequence = new anchor.BN(1);
+        const { rewardMint } = await program.account.stakingConfigAccount.fetch(stakingConfigPda);
+
+        await expectError(
+            program.methods.vaultCompleteInboundMint(Array.from(vaaHash), sequence, new anchor.BN(1), [])
+                .accounts({
+                    payer: relayer.publicKey,
+                    programConfig: programConfigPda,
+                    hubRegistry: hubRegistryPda,
+                    postedVaa: PublicKey.findProgramAddressSync([Buffer.from("PostedVAA"), vaaHash], wormholeProgram)[0],
+                    hubStateRoots: hubStateRootsPda,
+                    inboundReceipt: pda(Buffer.from("inbound_receipt"), Buffer.alloc(32), u64Seed(sequence)),
+                    consumedPage: pda(Buffer.from("consumed_message_page"), chainSeed(0), u64Seed(0)),
+                    inboundSequence: pda(Buffer.from("bridge_sequence"), chainSeed(0)),
+                    recipient: relayer.publicKey,
+                    stakingConfig: stakingConfigPda,
+                    mimingMint: rewardMint,
+                    recipientToken: getAssociatedTokenAddressSync(rewardMint, relayer.publicKey),
+                    payerToken: null,
+                    mintAuthority: pda(Buffer.from("mint_authority")),
+                    ledgerIdentifier: ledgerIdentifierPda,
+                    ledger: ledgerPda(await nextId(ledgerIdentifierPda)),
+                    bridgeStats: pda(Buffer.from("bridge_stats")),
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([relayer])
+                .rpc(),
+            "HubEmitterNotConfigured"
+        );
+    });
+
+    it("posting a teleport to the hub should fail for a chain the hub registry does not route (UnsupportedDestinationChain).", async () => {
+        const sender = await fundedKeypair();
+        const ledgerId = await nextId(ledgerIdentifierPda);
+        await (await teleport(sender, new anchor.BN(LAMPORTS_PER_SOL))).rpc();
+
+        const wormholeProgram = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+        const wormholePda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, wormholeProgram)[0];
+        const emitter = pda(Buffer.from("emitter"));
+
+        await expectError(
+            program.methods.vaultPostTeleport(2, Array.from(sender.publicKey.toBuffer()))
+                .accounts({
+                    signer: sender.publicKey,
+                    payer: sender.publicKey,
+                    ledger: ledgerPda(ledgerId),
+                    programConfig: programConfigPda,
+                    hubRegistry: hubRegistryPda,
+                    outboundSequence: pda(Buffer.from("bridge_sequence"), chainSeed(2)),
+                    vault: vaultPda,
+                    wormholeBridge: wormholePda(Buffer.from("Bridge")),
+                    wormholeMessage: pda(Buffer.from("teleport_message"), u64Seed(ledgerId)),
+                    wormholeEmitter: emitter,
+                    wormholeSequence: wormholePda(Buffer.from("Sequence"), emitter.toBuffer()),
+                    wormholeFeeCollector: wormholePda(Buffer.from("fee_collector")),
+                    wormholeProgram,
+                    clock: SYSVAR_CLOCK_PUBKEY,
+                    rent: SYSVAR_RENT_PUBKEY,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([sender])
+                .rpc(),
+            "UnsupportedDestinationChain"
+        );
+    });
+
+    it("proposing a messaging transport switch should fail without an endpoint program (InvalidConfigValue).", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+
+        await expectError(
+            program.methods
+                .stakingCreateProposal({ configureMessaging: { transport: { wormhole: {} }, endpoint: PublicKey.default } })
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    stakingProposalIdentifier: stakingProposalIdentifierPda,
+                    stakingProposal,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "InvalidConfigValue"
+        );
+    });
+
+    it("proposing a relayer fee above the maximum should fail (InvalidConfigValue).", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+
+        await expectError(
+            program.methods
+                .stakingCreateProposal({ configureRelayerFee: { feeBps: 501 } })
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    stakingProposalIdentifier: stakingProposalIdentifierPda,
+                    stakingProposal,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "InvalidConfigValue"
+        );
+    });
+
+    it("proposing a USD teleport fee should fail without a price feed (InvalidConfigValue).", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+        const action = {
+            configureUsdFee: {
+                usdCents: new anchor.BN(50),
+                feedId: Array(32).fill(0),
+                maxStaleness: new anchor.BN(60),
+                maxConfidenceBps: 100,
+            },
+        };
+
+        await expectError(
+            program.methods
+                .stakingCreateProposal(action)
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    stakingProposalIdentifier: stakingProposalIdentifierPda,
+                    stakingProposal,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "InvalidConfigValue"
+        );
+    });
+
+    it("proposing an oracle fallback should fail without a divergence bound (InvalidConfigValue).", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+
+        await expectError(
+            program.methods
+                .stakingCreateProposal({ configureOracleFallback: { feed: Keypair.generate().publicKey, maxDivergenceBps: 0 } })
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    stakingProposalIdentifier: stakingProposalIdentifierPda,
+                    stakingProposal,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "InvalidConfigValue"
+        );
+    });
+
+    it("proposing deposit receipts should fail with an overlong metadata URI prefix (InvalidConfigValue).", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+
+        await expectError(
+            program.methods
+                .stakingCreateProposal({
+                    configureDepositReceipts: { threshold: new anchor.BN(LAMPORTS_PER_SOL), uriPrefix: "x".repeat(65) },
+                })
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    stakingProposalIdentifier: stakingProposalIdentifierPda,
+                    stakingProposal,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "InvalidConfigValue"
+        );
+    });
+
+    it("creating a multisig proposal should snapshot the council as its required signers.", async () => {
+        const signer = await fundedKeypair();
+        const multisig = await program.account.multisigAccount.fetch(multisigPda);
+
+        const proposal = await createMultisigProposal(signer, "Regression", 1, [
+            { name: "member", pubkey: Keypair.generate().publicKey },
+        ]);
+
+        const proposalAccount = await program.account.multisigProposalAccount.fetch(proposal);
+        expect(proposalAccount.requiredSignerCount).to.equal(multisig.signers.length);
+        expect(proposalAccount.status).to.equal(0);
+    });
+
+    it("freezing should open a position readable through the position view.", async () => {
+        const { staker, stakingRegistry } = await stakedUser(200000);
+
+        const position = await program.methods.stakingGetPosition(staker.publicKey, new anchor.BN(0))
+            .accounts({ stakingConfig: stakingConfigPda, stakingRegistry } as any)
+            .view();
+        expect(position.stakedAmount.toNumber()).to.equal(200000);
+    });
+
+    it("creating a multisig proposal should fail past the signer and threshold limits and name length.", async () => {
+        const signer = await fundedKeypair();
+        const members = Array.from({ length: 11 }, (_, index) => ({
+            name: `member${index}`,
+            pubkey: Keypair.generate().publicKey,
+        }));
+
+        await expectError(createMultisigProposal(signer, "Regression", 11, members.slice(0, 5)), "ThresholdLimitReached");
+        await expectError(createMultisigProposal(signer, "Regression", 1, members), "SignerLimitReached");
+        await expectError(createMultisigProposal(signer, "x".repeat(33), 1, members.slice(0, 1)), "NameTooLong");
+    });
+
+    it("signing a multisig proposal should fail for a key outside the council (UnauthorizedSigner).", async () => {
+        const signer = await fundedKeypair();
+        const proposal = await createMultisigProposal(signer, "Regression", 1, [
+            { name: "member", pubkey: signer.publicKey },
+        ]);
+
+        await expectError(
+            program.methods.multisigSignProposal(null)
+                .accounts({
+                    signer: signer.publicKey,
+                    currentProposal: proposal,
+                    walletNonce: null,
+                    stakingConfig: null,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "UnauthorizedSigner",
+        );
+    });
+
+    it("submitting a hub state root should fail without a quorum of the council (QuorumNotReached).", async () => {
+        const signer = await fundedKeypair();
+
+        await expectError(
+            program.methods.lightClientSubmitRoot(new anchor.BN(2), Array(32).fill(2))
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    programConfig: programConfigPda,
+                    hubStateRoots: hubStateRootsPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "QuorumNotReached",
+        );
+    });
+
+    it("signing a vault transfer proposal should fail for a key outside the council (UnauthorizedSigner).", async () => {
+        const signer = await fundedKeypair();
+        const transferProposal = transferProposalPda(await nextId(transferProposalIdentifierPda));
+
+        await program.methods.vaultCreateTransferProposal(signer.publicKey, new anchor.BN(1000))
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                currentMultisig: multisigPda,
+                transferProposalIdentifier: transferProposalIdentifierPda,
+                transferProposal,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc();
+
+        await expectError(
+            program.methods.vaultSignTransferProposal(null)
+                .accounts({
+                    signer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    currentTransferProposal: transferProposal,
+                    walletNonce: null,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "UnauthorizedSigner",
+        );
+    });
+
+    it("teleporting should fail beyond the signer's balance or when the fee overflows.", async () => {
+        const signer = await fundedKeypair(LAMPORTS_PER_SOL);
+
+        await expectError((await teleport(signer, new anchor.BN(2 * LAMPORTS_PER_SOL))).rpc(), "InsufficientSolBalance");
+        await expectError((await teleport(signer, new anchor.BN("18446744073709551615"))).rpc(), "ArithmeticError");
+    });
+
+    it("closing a ledger entry should fail while no rent recipient is configured (RentRecipientNotConfigured).", async () => {
+        const signer = await fundedKeypair();
+        const ledgerId = await nextId(ledgerIdentifierPda);
+        await (await teleport(signer, new anchor.BN(LAMPORTS_PER_SOL))).rpc();
+
+        await expectError(
+            program.methods.vaultCloseLedger()
+                .accounts({
+                    signer: signer.publicKey,
+                    currentMultisig: multisigPda,
+                    ledgerIdentifier: ledgerIdentifierPda,
+                    ledger: ledgerPda(ledgerId),
+                    rentRecipient: PublicKey.default,
+                    programConfig: programConfigPda,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "RentRecipientNotConfigured",
+        );
+    });
+
+    it("the emergency stop should fail for a key other than the guardian (UnauthorizedGuardian).", async () => {
+        const signer = await fundedKeypair();
+
+        await expectError(
+            program.methods.programConfigEmergencyPause()
+                .accounts({ guardian: signer.publicKey, programConfig: programConfigPda } as any)
+                .signers([signer])
+                .rpc(),
+            "UnauthorizedGuardian",
+        );
+    });
+
+    it("migrating an account already on its current layout should fail (AccountAlreadyMigrated).", async () => {
+        const signer = await fundedKeypair();
+
+        await expectError(
+            program.methods.programConfigMigrate([Buffer.from("program_config")])
+                .accounts({
+                    signer: signer.publicKey,
+                    account: programConfigPda,
+                    programConfig: programConfigPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "AccountAlreadyMigrated",
+        );
+    });
+
+    it("registering an identifier that has not been created should fail (AccountNotCreated).", async () => {
+        const signer = await fundedKeypair();
+
+        if ((await connection.getAccountInfo(programRegistryPda)) === null) {
+            await program.methods.registryInitialize()
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    programRegistry: programRegistryPda,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc();
+        }
+
+        const namespace = Array.from(Buffer.alloc(16, "unregistered"));
+
+        await expectError(
+            program.methods.registryRegister({ identifier: { namespace } } as any)
+                .accounts({
+                    signer: signer.publicKey,
+                    payer: signer.publicKey,
+                    programRegistry: programRegistryPda,
+                    account: pda(Buffer.from("identifier"), Buffer.from(namespace)),
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc(),
+            "AccountNotCreated",
+        );
+    });
+
+    it("signing with an expected nonce should fail without the nonce account or on a stale nonce, and a failed operation should not consume it.", async () => {
+        const signer = await fundedKeypair();
+        const walletNonce = walletNoncePda(signer.publicKey);
+        const proposal = await createMultisigProposal(signer, "Regression", 1, [
+            { name: "member", pubkey: signer.publicKey },
+        ]);
+
+        await program.methods.nonceInitialize()
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                walletNonce,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc();
+
+        const sign = (expectedNonce: number, walletNonceAccount: PublicKey | null) =>
+            program.methods.multisigSignProposal(new anchor.BN(expectedNonce))
+                .accounts({
+                    signer: signer.publicKey,
+                    currentProposal: proposal,
+                    walletNonce: walletNonceAccount,
+                    stakingConfig: null,
+                    systemProgram: SystemProgram.programId,
+                } as any)
+                .signers([signer])
+                .rpc();
+
+        await expectError(sign(0, null), "NonceAccountRequired");
+        await expectError(sign(1, walletNonce), "NonceMismatch");
+        await expectError(sign(0, walletNonce), "UnauthorizedSigner");
+
+        const nonce = await program.account.walletNonceAccount.fetch(walletNonce);
+        expect(nonce.wallet.toBase58()).to.equal(signer.publicKey.toBase58());
+        expect(nonce.nonce.toNumber()).to.equal(0);
+    });
+
+    it("proposing a configuration change should announce when it can be activated.", async () => {
+        const signer = await fundedKeypair();
+        const stakingProposal = pda(Buffer.from("staking_proposal"), u64Seed(await nextId(stakingProposalIdentifierPda)));
+
+        const signature = await program.methods
+            .stakingCreateProposal({ configureAttestor: { attestor: Keypair.generate().publicKey } })
+            .accounts({
+                signer: signer.publicKey,
+                payer: signer.publicKey,
+                currentMultisig: multisigPda,
+                stakingProposalIdentifier: stakingProposalIdentifierPda,
+                stakingProposal,
+                systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([signer])
+            .rpc({ commitment: "confirmed" });
+
+        const transaction = await connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 0,
+        });
+        const events = [...new anchor.EventParser(program.programId, program.coder).parseLogs(transaction!.meta!.logMessages!)];
+        const proposed = events.find((event) => event.name === "configChangeProposedEvent");
+        const proposal = await program.account.stakingProposalAccount.fetch(stakingProposal);
+
+        expect(proposed, "a ConfigChangeProposedEvent is emitted").to.not.be.undefined;
+        expect(proposed!.data.activatesAt.toNumber()).to.equal(proposal.createdAt.toNumber() + 24 * 60 * 60);
+    });
+
+    it("freezing should fail below the minimum staking amount (InsufficientStakingBalance).", async () => {
+        const { freeze } = await stakedUser(0, false);
+
+        await expectError(freeze.rpc(), "InsufficientStakingBalance");
+    });
+});
@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+    program,
+    connection,
+    multisigPda,
+    proposalIdentifierPda,
+    ledgerIdentifierPda,
+    proposalPda,
+    ledgerPda,
+    stakingRegistryPda,
+    nextId,
+    fundedKeypair,
+    initializedVault,
+    initializedStaking,
+    teleport,
+    stakedUser,
+} from "./fixtures";
+
+// Compute unit budget report, run with `BENCH=1 anchor test`. Each measured instruction is sent, and read back for the
+// compute units it consumed and the space of the accounts it created. The suite prints one JSON object per instruction
+// and fails if any of them goes over its budget. The space of every account type at its largest is reported by
+// `cargo test --features bench --test budget_report`.
+
+/** The compute units an instruction may consume by default, without requesting a higher limit. */
+const COMPUTE_UNIT_BUDGET = 200_000;
+
+type Measurement = {
+    instruction: string;
+    builder: any;
+    created: PublicKey[];
+};
+
+/** Sends `builder` and returns the report line for it, with the compute units it consumed and the space it created. */
+async function measure({ instruction, builder, created }: Measurement) {
+    const signature = await builder.rpc({ commitment: "confirmed" });
+    const transaction = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+    });
+
+    const createdSpace = await Promise.all(
+        created.map(async (address) => (await connection.getAccountInfo(address))?.data.length ?? 0)
+    );
+
+    return {
+        kind: "compute_units",
+        instruction,
+        units: transaction!.meta!.computeUnitsConsumed!,
+        budget: COMPUTE_UNIT_BUDGET,
+        created_space: createdSpace.reduce((total, space) => total + space, 0),
+    };
+}
+
+(process.env.BENCH === "1" ? describe : describe.skip)("05-budget-report", () => {
+    before(async () => {
+        await initializedVault();
+        await initializedStaking();
+    });
+
+    it("every measured instruction should stay within its compute unit budget.", async () => {
+        const teleporter = await fundedKeypair();
+        const proposer = await fundedKeypair();
+        const { staker, freeze } = await stakedUser(200000, false);
+
+        const measurements: (() => Promise<Measurement>)[] = [
+            async () => ({
+                instruction: "vault_teleport",
+                builder: await teleport(teleporter, new anchor.BN(LAMPORTS_PER_SOL)),
+                created: [ledgerPda(await nextId(ledgerIdentifierPda))],
+            }),
+            async () => {
+                const proposal = proposalPda(await nextId(proposalIdentifierPda));
+                const signers = [{ name: "Bench", pubkey: proposer.publicKey }];
+
+                return {
+                    instruction: "multisig_create_proposal",
+                    builder: program.methods
+                        .multisigCreateProposal("Bench", 1, signers, { unanimous: {} }, new anchor.BN(0))
+                        .accounts({
+                            signer: proposer.publicKey,
+                            payer: proposer.publicKey,
+                            currentMultisig: multisigPda,
+                            proposalIdentifier: proposalIdentifierPda,
+                            proposal,
+                            systemProgram: SystemProgram.programId,
+                        } as any)
+                        .signers([proposer]),
+                    created: [proposal],
+                };
+            },
+            async () => ({
+                instruction: "staking_freeze",
+                builder: freeze,
+                created: [stakingRegistryPda(staker.publicKey, 0)],
+            }),
+        ];
+
+        const report = [];
+        for (const measurement of measurements) {
+            const line = await measure(await measurement());
+            console.log(JSON.stringify(line));
+            report.push(line);
+        }
+
+        const overBudget = report.filter((line) => line.units > line.budget).map((line) => line.instruction);
+        expect(overBudget, "instructions over their compute unit budget").to.be.empty;
+    });
+});
@@ -0,0 +1,236 @@
+import * as anchor from "@coral-xyz/anchor";
+import { MimingSpokeSolana } from "../target/types/miming_spoke_solana";
+import { SystemProgram, Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    createMint,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+    mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// Shared fixtures for the test suites: PDAs, funded wallets, and idempotent setup of the program's singletons, so each
+// suite can run on its own or after the others against the same validator.
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const program = anchor.workspace.mimingSpokeSolana as anchor.Program<MimingSpokeSolana>;
+export const connection = program.provider.connection;
+
+export const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+export const u64Seed = (value: number | anchor.BN) => new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+export const chainSeed = (chain: number) => new anchor.BN(chain).toArrayLike(Buffer, "le", 2);
+
+export const multisigPda = pda(Buffer.from("multisig"));
+export const proposalIdentifierPda = pda(Buffer.from("proposal_identifier"));
+export const programConfigPda = pda(Buffer.from("program_config"));
+export const programRegistryPda = pda(Buffer.from("program_registry"));
+export const hubRegistryPda = pda(Buffer.from("hub_registry"));
+export const hubStateRootsPda = pda(Buffer.from("hub_state_roots"));
+export const vaultPda = pda(Buffer.from("vault"));
+export const ledgerIdentifierPda = pda(Buffer.from("ledger_identifier"));
+export const transferProposalIdentifierPda = pda(Buffer.from("transfer_proposal_identifier"));
+export const stakingConfigPda = pda(Buffer.from("staking_config"));
+export const stakingAuthorityPda = pda(Buffer.from("staking_authority"));
+export const stakingPoolPda = pda(Buffer.from("staking_pool"));
+export const stakingActivityPda = pda(Buffer.from("staking_activity"));
+export const stakingProposalIdentifierPda = pda(Buffer.from("staking_proposal_identifier"));
+
+export const proposalPda = (id: number | anchor.BN) => pda(Buffer.from("proposal"), u64Seed(id));
+export const ledgerPda = (id: number | anchor.BN) => pda(Buffer.from("ledger"), u64Seed(id));
+export const transferProposalPda = (id: number | anchor.BN) => pda(Buffer.from("transfer_proposal"), u64Seed(id));
+export const stakingUserPda = (staker: PublicKey) => pda(Buffer.from("staking_user"), staker.toBuffer());
+export const walletNoncePda = (wallet: PublicKey) => pda(Buffer.from("wallet_nonce"), wallet.toBuffer());
+export const stakingRegistryPda = (staker: PublicKey, positionIndex: number | anchor.BN) =>
+    pda(Buffer.from("staking_registry"), staker.toBuffer(), u64Seed(positionIndex));
+
+const accountExists = async (address: PublicKey) => (await connection.getAccountInfo(address)) !== null;
+
+/** Returns the id the next account numbered by `identifier` will take. */
+export const nextId = async (identifier: PublicKey) => (await program.account.identifierAccount.fetch(identifier)).id;
+
+/** Asserts that `call` fails with the program or Anchor error `code`, and fails the test if it succeeds. */
+export async function expectError(call: Promise<unknown>, code: string) {
+    try {
+        await call;
+    } catch (err: any) {
+        const errorCode = err?.error?.errorCode?.code;
+        if (errorCode) {
+            expect(errorCode).to.equal(code);
+        } else {
+            expect(err.toString()).to.contain(code);
+        }
+        return;
+    }
+    expect.fail(`expected the call to fail with ${code}`);
+}
+
+/** Returns a new wallet holding `lamports`, waiting for the airdrop to confirm. */
+export async function fundedKeypair(lamports = 5 * LAMPORTS_PER_SOL): Promise<Keypair> {
+    const keypair = Keypair.generate();
+    const signature = await connection.requestAirdrop(keypair.publicKey, lamports);
+    await connection.confirmTransaction({ signature, ...(await connection.getLatestBlockhash()) });
+    return keypair;
+}
+
+export const programDataPda = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+)[0];
+
+/** The genesis parameters the suites run with: the provider wallet alone, whose first multisig proposal seats the test signers. */
+export const genesisParams = {
+    signers: [{ name: "genesis", pubkey: provider.wallet.publicKey }],
+    threshold: 1,
+    teleportFee: new anchor.BN(10_000_000),
+    guardian: PublicKey.default,
+    rentRecipient: PublicKey.default,
+};
+
+/** Builds an `initialize_program` signed by the provider wallet, the program's upgrade authority on the test validator. */
+export function initializeProgram(rewardMint: PublicKey, signer: PublicKey = provider.wallet.publicKey) {
+    return program.methods.initializeProgram(genesisParams)
+        .accounts({
+            signer,
+            payer: provider.wallet.publicKey,
+            program: program.programId,
+            programData: programDataPda,
+            proposalIdentifier: proposalIdentifierPda,
+            multisig: multisigPda,
+            programConfig: programConfigPda,
+            hubRegistry: hubRegistryPda,
+            ledgerIdentifier: ledgerIdentifierPda,
+            transferProposalIdentifier: transferProposalIdentifierPda,
+            stakingConfig: stakingConfigPda,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            stakingProposalIdentifier: stakingProposalIdentifierPda,
+            rewardMint,
+            stakingAuthority: stakingAuthorityPda,
+            rewardVault: getAssociatedTokenAddressSync(rewardMint, stakingAuthorityPda, true),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        } as any);
+}
+
+/** Bootstraps the program's singletons through `initialize_program` if genesis has not run yet. */
+export async function initializedProgram() {
+    if (!(await accountExists(multisigPda))) {
+        const mintAuthority = await fundedKeypair();
+        const rewardMint = await createMint(connection, mintAuthority, mintAuthority.publicKey, null, 0);
+
+        await initializeProgram(rewardMint).rpc();
+    }
+}
+
+/** Bootstraps the program if needed, and funds the vault. */
+export async function fundedMultisig(vaultLamports = 5 * LAMPORTS_PER_SOL) {
+    await initializedProgram();
+
+    const signature = await connection.requestAirdrop(vaultPda, vaultLamports);
+    await connection.confirmTransaction({ signature, ...(await connection.getLatestBlockhash()) });
+}
+
+/** Bootstraps the program if needed, with the vault's identifiers, and funds the vault. */
+export async function initializedVault() {
+    await fundedMultisig();
+}
+
+/** Bootstraps the program if needed, with the staking configuration and reward vault, and funds the vault. */
+export async function initializedStaking() {
+    await fundedMultisig();
+}
+
+/**
+ * Builds a `vault_teleport` of `amount` lamports from `signer`, recorded in the next ledger entry. The destination is
+ * left to be picked when posting unless `destinationChain` is given.
+ */
+export async function teleport(
+    signer: Keypair,
+    amount: anchor.BN,
+    destinationChain = 0,
+    destination: number[] = Array(32).fill(0)
+) {
+    const ledger = ledgerPda(await nextId(ledgerIdentifierPda));
+
+    return program.methods.vaultTeleport(amount, destinationChain, destination)
+        .accounts({
+            signer: signer.publicKey,
+            payer: signer.publicKey,
+            vault: vaultPda,
+            ledgerIdentifier: ledgerIdentifierPda,
+            ledger,
+            programConfig: programConfigPda,
+            stakingConfig: null,
+            stakingRegistry: null,
+            solUsdPriceUpdate: null,
+            solUsdFallbackFeed: null,
+            systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([signer]);
+}
+
+/** Creates a multisig proposal from `signer` and returns its address. */
+export async function createMultisigProposal(
+    signer: Keypair,
+    name: string,
+    threshold: number,
+    signers: { name: string; pubkey: PublicKey; }[],
+) {
+    const proposal = proposalPda(await nextId(proposalIdentifierPda));
+
+    await program.methods.multisigCreateProposal(name, threshold, signers, { unanimous: {} }, new anchor.BN(0))
+        .accounts({
+            signer: signer.publicKey,
+            payer: signer.publicKey,
+            currentMultisig: multisigPda,
+            proposalIdentifier: proposalIdentifierPda,
+            proposal,
+            systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([signer])
+        .rpc();
+
+    return proposal;
+}
+
+/** Returns a new staker holding `balance` tokens of a fresh mint, with their first position frozen when `staked` is set. */
+export async function stakedUser(balance = 200000, staked = true) {
+    await initializedStaking();
+
+    const staker = await fundedKeypair();
+    const token = await createMint(connection, staker, staker.publicKey, staker.publicKey, 0);
+    const stakerToken = (await getOrCreateAssociatedTokenAccount(connection, staker, token, staker.publicKey)).address;
+    if (balance > 0) {
+        await mintTo(connection, staker, token, stakerToken, staker, balance);
+    }
+
+    const freeze = program.methods.stakingFreeze(new anchor.BN(0), "fixture", new anchor.BN(0), null)
+        .accounts({
+            staker: staker.publicKey,
+            payer: staker.publicKey,
+            token,
+            stakerToken,
+            stakingMintConfig: null,
+            stakingAuthority: stakingAuthorityPda,
+            stakingConfig: stakingConfigPda,
+            stakingUser: stakingUserPda(staker.publicKey),
+            stakingRegistry: stakingRegistryPda(staker.publicKey, 0),
+            stakingReferrer: null,
+            stakingPool: stakingPoolPda,
+            stakingActivity: stakingActivityPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([staker]);
+
+    if (staked) {
+        await freeze.rpc();
+    }
+
+    return { staker, token, stakerToken, stakingRegistry: stakingRegistryPda(staker.publicKey, 0), freeze };
+}